@@ -4,17 +4,34 @@
 
 use eframe::egui;
 use crate::app_state::TN5250RApp;
+use crate::ui::split_view::SplitLayout;
 
 
 
 
 impl eframe::App for TN5250RApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let tab_used_for_navigation = self.handle_keyboard_input(ctx);
+        if self.fonts_changed {
+            self.fonts_changed = false;
+            if let Err(e) = self.display_settings.install_font(ctx) {
+                self.error_message = Some(e);
+            }
+        }
+
+        // Tab shortcuts first so Ctrl+Tab and Ctrl+1..9 never reach the keymap
+        self.handle_session_shortcuts(ctx);
+        let tab_used_for_navigation = match self.active_session_id.clone() {
+            Some(session_id) => self.handle_keyboard_input(ctx, &session_id),
+            None => false,
+        };
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
                 ui.menu_button("File", |ui| {
+                    if ui.button("New Quick Connect Session").clicked() {
+                        self.open_quick_connect_session(None, None);
+                        ui.close();
+                    }
                     if ui.button("Connect").clicked() {
                         self.do_connect();
                         ui.close();
@@ -23,6 +40,12 @@ impl eframe::App for TN5250RApp {
                         self.do_disconnect();
                         ui.close();
                     }
+                    if ui.button("Close Session").clicked() {
+                        if let Some(session_id) = self.active_session_id.clone() {
+                            self.close_session(&session_id);
+                        }
+                        ui.close();
+                    }
                     if ui.button("Exit").clicked() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                     }
@@ -31,6 +54,28 @@ impl eframe::App for TN5250RApp {
                 ui.menu_button("View", |ui| {
                     ui.checkbox(&mut self.function_keys_visible, "Function Keys");
                     ui.checkbox(&mut self.show_monitoring_dashboard, "Monitoring Dashboard");
+                    if ui.add(egui::Button::new("Find on Screen").shortcut_text("Ctrl+F")).clicked() {
+                        if let Some(session) = self.get_active_session_mut() {
+                            TN5250RApp::open_search(ui.ctx(), session);
+                        }
+                        ui.close();
+                    }
+                    ui.separator();
+                    if ui.add(egui::Button::new("Next Session").shortcut_text("Ctrl+Tab")).clicked() {
+                        self.cycle_session(1);
+                        ui.close();
+                    }
+                    if ui.add(egui::Button::new("Previous Session").shortcut_text("Ctrl+Shift+Tab")).clicked() {
+                        self.cycle_session(-1);
+                        ui.close();
+                    }
+                    ui.menu_button("Layout", |ui| self.show_split_layout_menu(ui));
+                    if ui.button("Detach Session to Window").clicked() {
+                        if let Some(session_id) = self.active_session_id.clone() {
+                            self.detach_session(&session_id);
+                        }
+                        ui.close();
+                    }
                 });
 
                 ui.menu_button("Settings", |ui| {
@@ -42,6 +87,10 @@ impl eframe::App for TN5250RApp {
                         self.show_advanced_settings = true;
                         ui.close();
                     }
+                    if ui.button("Keyboard Map").clicked() {
+                        self.open_keymap_editor();
+                        ui.close();
+                    }
                 });
 
                 ui.menu_button("Profiles", |ui| {
@@ -53,64 +102,6 @@ impl eframe::App for TN5250RApp {
             });
         });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // If we used Tab for field navigation, prevent egui widget focus
-            if tab_used_for_navigation {
-                ui.memory_mut(|mem| {
-                    // Clear focus entirely to prevent widgets from getting Tab focus
-                    mem.surrender_focus(egui::Id::NULL);
-                });
-            }
-
-            // Show error message prominently if present
-            if let Some(ref error) = self.error_message {
-                ui.colored_label(egui::Color32::RED, format!("⚠ Error: {error}"));
-                ui.separator();
-            }
-
-            // Show session tabs if we have multiple sessions
-            if self.sessions.len() > 1 {
-                let session_ids: Vec<String> = self.sessions.keys().cloned().collect();
-                ui.horizontal(|ui| {
-                    for session_id in &session_ids {
-                        if let Some(session) = self.sessions.get(session_id) {
-                            let is_active = Some(session_id.clone()) == self.active_session_id;
-                            let tab_name = if session_id == "legacy" {
-                                "Main Session".to_string()
-                            } else {
-                                session.profile.name.clone()
-                            };
-
-                            if ui.selectable_label(is_active, &tab_name).clicked() {
-                                self.switch_to_session(session_id.clone());
-                            }
-                        }
-                    }
-
-                    // Add close buttons for sessions
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        for session_id in &session_ids {
-                            if session_id != "legacy" {  // Don't allow closing the legacy session
-                                if ui.button("✕").on_hover_text("Close session").clicked() {
-                                    self.close_session(session_id);
-                                    break; // Avoid modifying while iterating
-                                }
-                            }
-                        }
-                    });
-                });
-                ui.separator();
-            }
-
-            // Show session-specific content
-            if let Some(active_session_id) = self.active_session_id.clone() {
-                self.show_session_content(ui, &active_session_id);
-            } else {
-                // Fallback to legacy single-session mode
-                self.show_legacy_session_content(ui);
-            }
-        });
-
         // Show profile sidebar if requested
         if self.show_profile_manager {
             egui::SidePanel::left("profiles_panel")
@@ -121,6 +112,13 @@ impl eframe::App for TN5250RApp {
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
+            // If we used Tab for field navigation, prevent egui widget focus
+            if tab_used_for_navigation {
+                ui.memory_mut(|mem| {
+                    // Clear focus entirely to prevent widgets from getting Tab focus
+                    mem.surrender_focus(egui::Id::NULL);
+                });
+            }
 
             // Show error message prominently if present
             if let Some(ref error) = self.error_message {
@@ -128,54 +126,33 @@ impl eframe::App for TN5250RApp {
                 ui.separator();
             }
 
-            // Show session tabs if we have multiple sessions
-            if self.sessions.len() > 1 {
-                let session_ids: Vec<String> = self.sessions.keys().cloned().collect();
-                ui.horizontal(|ui| {
-                    for session_id in &session_ids {
-                        if let Some(session) = self.sessions.get(session_id) {
-                            let is_active = Some(session_id.clone()) == self.active_session_id;
-                            let tab_name = if session_id == "legacy" {
-                                "Main Session".to_string()
-                            } else {
-                                session.profile.name.clone()
-                            };
-
-                            if ui.selectable_label(is_active, &tab_name).clicked() {
-                                self.switch_to_session(session_id.clone());
-                            }
-                        }
-                    }
-
-                    // Add close buttons for sessions
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        for session_id in &session_ids {
-                            if session_id != "legacy" {  // Don't allow closing the legacy session
-                                if ui.button("✕").on_hover_text("Close session").clicked() {
-                                    self.close_session(session_id);
-                                    break; // Avoid modifying while iterating
-                                }
-                            }
-                        }
-                    });
-                });
+            // Tabs with the status of every open session
+            if !self.sessions.is_empty() {
+                self.show_session_tabs(ui);
                 ui.separator();
             }
 
-            // Show session-specific content
-            if let Some(active_session_id) = self.active_session_id.clone() {
+            // Show session-specific content, tiled when a split layout is chosen
+            if self.split_layout != SplitLayout::Tabs && self.active_session_id.is_some() {
+                self.show_split_view(ui);
+            } else if let Some(active_session_id) = self.active_session_id.clone() {
                 self.show_session_content(ui, &active_session_id);
+            } else if self.sessions.is_empty() {
+                ui.heading("TN5250R - IBM AS/400 Terminal Emulator");
+                ui.label("No session is open. Connect with a profile, or:");
+                if ui.button("New Quick Connect Session").clicked() {
+                    self.open_quick_connect_session(None, None);
+                }
             } else {
-                // Fallback to legacy single-session mode
-                self.show_legacy_session_content(ui);
+                ui.label("Every session is in a window of its own. Click a tab to bring it back.");
             }
         });
 
-        // Process incoming data and update terminal content
-        let content_changed = self.update_terminal_content();
+        // Sessions detached into windows of their own
+        self.show_detached_sessions(ctx);
 
-        // Check if new data arrived for event-driven repaints
-        let data_arrived = self.controller.check_data_arrival().unwrap_or(false);
+        // Process incoming data and update every session's screen and status
+        let content_changed = self.update_sessions();
 
         // Show debug panel if requested
         if self.show_debug_panel {
@@ -192,6 +169,17 @@ impl eframe::App for TN5250RApp {
             self.show_settings_dialog(ctx);
         }
 
+        // Show keymap editor if open
+        if self.keymap_editor.is_some() {
+            self.show_keymap_editor_dialog(ctx);
+        }
+
+        // Ask the user about untrusted or changed server certificates
+        self.poll_certificate_prompts();
+        if self.certificate_prompt.is_some() {
+            self.show_certificate_dialog(ctx);
+        }
+
         // Show profile manager if requested
         if self.show_profile_manager {
             self.show_profile_manager(ctx);
@@ -232,11 +220,13 @@ impl eframe::App for TN5250RApp {
         // - Connecting: Check every 100ms for connection completion
         // - Connected with recent data: Check every 50ms for smooth updates
         // - Connected but idle: Check every 500ms for status/errors
-        if self.connecting {
+        let connecting = self.sessions.values().any(|s| s.connecting || s.reconnect.is_some());
+        let connected = self.sessions.values().any(|s| s.connected);
+        if connecting {
             // Check every 100ms while connecting
             ctx.request_repaint_after(std::time::Duration::from_millis(100));
-        } else if self.connected {
-            if content_changed || data_arrived {
+        } else if connected {
+            if content_changed {
                 // Content just changed or data arrived, check again soon for more data
                 ctx.request_repaint_after(std::time::Duration::from_millis(50));
             } else {
@@ -0,0 +1,157 @@
+//! Screen selection, copy formats and paste options
+//!
+//! A selection is a rectangle of screen cells. Copying turns the selected
+//! rectangle (or the whole screen) into plain text, tab-delimited columns or an
+//! HTML table; columns are the runs of text separated by two or more blanks,
+//! which is how protected labels and input fields are laid out on 5250 screens.
+//! Pasting is done by the field manager, which spreads the text across the
+//! input fields following the cursor.
+
+use serde::{Deserialize, Serialize};
+
+/// Rectangular selection between two screen cells (1-based row, column), inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    /// Cell where the drag started
+    pub anchor: (usize, usize),
+    /// Cell under the pointer
+    pub end: (usize, usize),
+}
+
+impl Selection {
+    pub fn new(anchor: (usize, usize), end: (usize, usize)) -> Self {
+        Self { anchor, end }
+    }
+
+    /// Top-left and bottom-right cells
+    pub fn bounds(&self) -> ((usize, usize), (usize, usize)) {
+        (
+            (self.anchor.0.min(self.end.0), self.anchor.1.min(self.end.1)),
+            (self.anchor.0.max(self.end.0), self.anchor.1.max(self.end.1)),
+        )
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        let ((top, left), (bottom, right)) = self.bounds();
+        (top..=bottom).contains(&row) && (left..=right).contains(&col)
+    }
+}
+
+/// Clipboard representation of copied screen text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// Rows of the rectangle, trailing blanks removed
+    Text,
+    /// Columns separated by tabs, for spreadsheets
+    TabDelimited,
+    /// An HTML table of the columns
+    Html,
+}
+
+/// Where a line break in pasted text continues
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LineHandling {
+    /// Continue in the next input field
+    #[default]
+    NextField,
+    /// Continue on the next screen row, below where the paste started
+    NextLine,
+    /// Replace the line break with a blank
+    Join,
+}
+
+impl LineHandling {
+    pub const ALL: [LineHandling; 3] = [LineHandling::NextField, LineHandling::NextLine, LineHandling::Join];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineHandling::NextField => "Line break moves to the next field",
+            LineHandling::NextLine => "Line break moves to the next row",
+            LineHandling::Join => "Line breaks become blanks",
+        }
+    }
+}
+
+/// Result of a paste
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasteOutcome {
+    /// Characters placed into fields, in order
+    pub typed: String,
+    /// Characters left over when the screen ran out of input fields
+    pub dropped: usize,
+}
+
+/// Rows of `screen` inside `selection` (every row when None), trailing blanks removed
+pub fn selected_rows(screen: &str, selection: Option<&Selection>) -> Vec<String> {
+    let lines: Vec<&str> = screen.lines().collect();
+    let ((top, left), (bottom, right)) = match selection {
+        Some(selection) => selection.bounds(),
+        None => ((1, 1), (lines.len().max(1), usize::MAX)),
+    };
+    (top..=bottom)
+        .map(|row| {
+            let line = lines.get(row - 1).copied().unwrap_or("");
+            let cells: String = line.chars().skip(left - 1).take(right.saturating_sub(left) + 1).collect();
+            cells.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Columns of a row: the runs of text separated by two or more blanks
+pub fn columns(row: &str) -> Vec<String> {
+    let mut columns = Vec::new();
+    let mut current = String::new();
+    let mut blanks = 0;
+    for ch in row.chars() {
+        if ch == ' ' {
+            blanks += 1;
+            continue;
+        }
+        if blanks >= 2 && !current.is_empty() {
+            columns.push(std::mem::take(&mut current));
+        } else if !current.is_empty() {
+            current.extend(std::iter::repeat_n(' ', blanks));
+        }
+        blanks = 0;
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        columns.push(current);
+    }
+    columns
+}
+
+/// Selected screen text in `format`; the whole screen when nothing is selected
+pub fn copy_text(screen: &str, selection: Option<&Selection>, format: CopyFormat) -> String {
+    let rows = selected_rows(screen, selection);
+    match format {
+        CopyFormat::Text => rows.join("\n"),
+        CopyFormat::TabDelimited => rows.iter().map(|row| columns(row).join("\t")).collect::<Vec<_>>().join("\n"),
+        CopyFormat::Html => {
+            let mut html = String::from("<table style=\"font-family: monospace\">\n");
+            for row in &rows {
+                html.push_str("<tr>");
+                for column in columns(row) {
+                    html.push_str("<td>");
+                    html.push_str(&escape_html(&column));
+                    html.push_str("</td>");
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>");
+            html
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            _ => ch.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,225 @@
+//! Color schemes for the terminal screen
+//!
+//! A scheme maps the seven colors shared by 5250 and 3270 color displays, the
+//! screen background, the cursor, reverse image text and the field states the
+//! emulator shades (active, error, highlighted, modified) to RGB values. Schemes
+//! are stored with each profile as JSON, colors written as `#RRGGBB`, so a team
+//! can hand round one profile file to standardise appearance.
+
+use serde::{Deserialize, Serialize};
+
+use crate::screen_grid::ScreenColor;
+
+/// An sRGB color, serialized as `#RRGGBB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rgb(pub [u8; 3]);
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self([r, g, b])
+    }
+}
+
+impl TryFrom<String> for Rgb {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let hex = value.trim().trim_start_matches('#');
+        let channel = |i: usize| hex.get(i..i + 2).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Self([r, g, b])),
+            _ => Err(format!("Invalid color '{value}', expected #RRGGBB")),
+        }
+    }
+}
+
+impl From<Rgb> for String {
+    fn from(rgb: Rgb) -> Self {
+        let [r, g, b] = rgb.0;
+        format!("#{r:02X}{g:02X}{b:02X}")
+    }
+}
+
+/// Text and background of a shaded field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldColors {
+    pub foreground: Rgb,
+    pub background: Rgb,
+}
+
+impl FieldColors {
+    const fn new(foreground: Rgb, background: Rgb) -> Self {
+        Self { foreground, background }
+    }
+}
+
+/// Colors the terminal screen is drawn with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorScheme {
+    /// Name of the built-in scheme this started from, or a name chosen by the user
+    pub name: String,
+    pub background: Rgb,
+    pub cursor: Rgb,
+    /// Text of reverse image cells, drawn on the cell's color
+    pub reverse_text: Rgb,
+    pub green: Rgb,
+    pub white: Rgb,
+    pub red: Rgb,
+    pub turquoise: Rgb,
+    pub yellow: Rgb,
+    pub pink: Rgb,
+    pub blue: Rgb,
+    pub active_field: FieldColors,
+    pub error_field: FieldColors,
+    pub highlighted_field: FieldColors,
+    /// Input fields whose modified data tag is set
+    pub modified_field: FieldColors,
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::classic_green()
+    }
+}
+
+impl ColorScheme {
+    /// Names of the built-in schemes, in menu order
+    pub const BUILT_IN: [&'static str; 4] = ["Classic Green Screen", "IBM ACS Default", "High Contrast", "Light"];
+
+    /// Built-in scheme by name
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "Classic Green Screen" => Some(Self::classic_green()),
+            "IBM ACS Default" => Some(Self::ibm_acs()),
+            "High Contrast" => Some(Self::high_contrast()),
+            "Light" => Some(Self::light()),
+            _ => None,
+        }
+    }
+
+    /// Bright phosphor colors on black, as on a 3477 or 3179
+    pub fn classic_green() -> Self {
+        let black = Rgb::new(0, 0, 0);
+        Self {
+            name: "Classic Green Screen".to_string(),
+            background: black,
+            cursor: Rgb::new(0, 255, 0),
+            reverse_text: black,
+            green: Rgb::new(0, 220, 0),
+            white: Rgb::new(255, 255, 255),
+            red: Rgb::new(255, 64, 64),
+            turquoise: Rgb::new(0, 224, 224),
+            yellow: Rgb::new(255, 255, 0),
+            pink: Rgb::new(255, 128, 255),
+            blue: Rgb::new(96, 160, 255),
+            active_field: FieldColors::new(Rgb::new(255, 255, 255), Rgb::new(0, 0, 255)),
+            error_field: FieldColors::new(Rgb::new(255, 255, 255), Rgb::new(255, 0, 0)),
+            highlighted_field: FieldColors::new(black, Rgb::new(255, 255, 0)),
+            modified_field: FieldColors::new(Rgb::new(255, 255, 255), Rgb::new(0, 64, 0)),
+        }
+    }
+
+    /// The softer palette of IBM i Access Client Solutions
+    pub fn ibm_acs() -> Self {
+        let black = Rgb::new(0, 0, 0);
+        Self {
+            name: "IBM ACS Default".to_string(),
+            background: black,
+            cursor: Rgb::new(255, 255, 255),
+            reverse_text: black,
+            green: Rgb::new(0, 255, 0),
+            white: Rgb::new(255, 255, 255),
+            red: Rgb::new(255, 0, 0),
+            turquoise: Rgb::new(0, 255, 255),
+            yellow: Rgb::new(255, 255, 0),
+            pink: Rgb::new(255, 0, 255),
+            blue: Rgb::new(120, 120, 255),
+            active_field: FieldColors::new(Rgb::new(255, 255, 255), Rgb::new(40, 40, 160)),
+            error_field: FieldColors::new(Rgb::new(255, 255, 255), Rgb::new(192, 0, 0)),
+            highlighted_field: FieldColors::new(black, Rgb::new(224, 224, 0)),
+            modified_field: FieldColors::new(Rgb::new(0, 255, 0), Rgb::new(32, 32, 32)),
+        }
+    }
+
+    /// Fully saturated colors for low vision
+    pub fn high_contrast() -> Self {
+        let black = Rgb::new(0, 0, 0);
+        let white = Rgb::new(255, 255, 255);
+        Self {
+            name: "High Contrast".to_string(),
+            background: black,
+            cursor: white,
+            reverse_text: black,
+            green: white,
+            white,
+            red: Rgb::new(255, 80, 80),
+            turquoise: Rgb::new(0, 255, 255),
+            yellow: Rgb::new(255, 255, 0),
+            pink: Rgb::new(255, 128, 255),
+            blue: Rgb::new(128, 192, 255),
+            active_field: FieldColors::new(black, Rgb::new(0, 255, 255)),
+            error_field: FieldColors::new(white, Rgb::new(192, 0, 0)),
+            highlighted_field: FieldColors::new(black, Rgb::new(255, 255, 0)),
+            modified_field: FieldColors::new(black, white),
+        }
+    }
+
+    /// Dark text on a light background
+    pub fn light() -> Self {
+        let white = Rgb::new(255, 255, 255);
+        Self {
+            name: "Light".to_string(),
+            background: Rgb::new(250, 250, 245),
+            cursor: Rgb::new(0, 0, 0),
+            reverse_text: white,
+            green: Rgb::new(0, 110, 0),
+            white: Rgb::new(0, 0, 0),
+            red: Rgb::new(190, 0, 0),
+            turquoise: Rgb::new(0, 120, 140),
+            yellow: Rgb::new(150, 110, 0),
+            pink: Rgb::new(170, 0, 140),
+            blue: Rgb::new(0, 60, 200),
+            active_field: FieldColors::new(Rgb::new(0, 0, 0), Rgb::new(200, 220, 255)),
+            error_field: FieldColors::new(white, Rgb::new(200, 0, 0)),
+            highlighted_field: FieldColors::new(Rgb::new(0, 0, 0), Rgb::new(255, 240, 120)),
+            modified_field: FieldColors::new(Rgb::new(0, 0, 0), Rgb::new(225, 245, 225)),
+        }
+    }
+
+    /// RGB a display color is drawn in
+    pub fn color(&self, color: ScreenColor) -> Rgb {
+        match color {
+            ScreenColor::Green => self.green,
+            ScreenColor::White => self.white,
+            ScreenColor::Red => self.red,
+            ScreenColor::Turquoise => self.turquoise,
+            ScreenColor::Yellow => self.yellow,
+            ScreenColor::Pink => self.pink,
+            ScreenColor::Blue => self.blue,
+        }
+    }
+
+    /// Mutable color of a display color, for editing
+    pub fn color_mut(&mut self, color: ScreenColor) -> &mut Rgb {
+        match color {
+            ScreenColor::Green => &mut self.green,
+            ScreenColor::White => &mut self.white,
+            ScreenColor::Red => &mut self.red,
+            ScreenColor::Turquoise => &mut self.turquoise,
+            ScreenColor::Yellow => &mut self.yellow,
+            ScreenColor::Pink => &mut self.pink,
+            ScreenColor::Blue => &mut self.blue,
+        }
+    }
+
+    /// Scheme from its JSON form, as stored in the `terminal.colorScheme` setting
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid color scheme: {e}"))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
@@ -162,13 +162,25 @@ impl SessionConfig {
         self.properties.insert("session.autoConnect".to_string(), false.into());
         self.properties.insert("session.keepAlive".to_string(), true.into());
         self.properties.insert("session.timeout".to_string(), 30i64.into());
+        self.properties.insert("session.autoReconnect".to_string(), false.into());
+        self.properties.insert("session.maxReconnectAttempts".to_string(), 3i64.into());
         
         // Terminal settings
         self.properties.insert("terminal.cursorBlink".to_string(), true.into());
+        self.properties.insert("terminal.cursorStyle".to_string(), "Block".into());
+        self.properties.insert("terminal.fontSize".to_string(), 14.0.into());
+        self.properties.insert("terminal.fontFile".to_string(), "".into());
+        self.properties.insert("terminal.autoFit".to_string(), false.into());
+        self.properties.insert("terminal.columnSeparators".to_string(), true.into());
+        self.properties.insert("terminal.underlines".to_string(), true.into());
+        self.properties.insert("terminal.colorScheme".to_string(), "".into());
         self.properties.insert("terminal.insertMode".to_string(), false.into());
         self.properties.insert("terminal.mouseSupport".to_string(), true.into());
         self.properties.insert("terminal.type".to_string(), "IBM-3179-2".into());
         
+        // Workspace settings
+        self.properties.insert("workspace.splitLayout".to_string(), "tabs".into());
+
         // Field settings
         self.properties.insert("fields.validateInput".to_string(), true.into());
         self.properties.insert("fields.mandatoryHighlight".to_string(), true.into());
@@ -3,92 +3,101 @@
 //! This module handles connection establishment, disconnection, and connection string parsing.
 
 use crate::app_state::TN5250RApp;
+use crate::config;
 use crate::monitoring;
-use uuid;
 
-impl TN5250RApp {
-    pub fn parse_connection_string(&self) -> (String, u16) {
-        if let Some((host, port_str)) = self.connection_string.rsplit_once(':') {
-            let host = host.to_string();
-            if let Ok(port) = port_str.parse::<u16>() {
-                (host, port)
-            } else {
-                (host, 23) // Default telnet port
-            }
+/// Split a `host:port` connection string, defaulting to the telnet port
+pub fn parse_connection_string(address: &str) -> (String, u16) {
+    if let Some((host, port_str)) = address.trim().rsplit_once(':') {
+        let host = host.to_string();
+        if let Ok(port) = port_str.parse::<u16>() {
+            (host, port)
         } else {
-            (self.connection_string.clone(), 23) // Default telnet port
+            (host, 23) // Default telnet port
         }
+    } else {
+        (address.trim().to_string(), 23) // Default telnet port
     }
+}
 
+impl TN5250RApp {
+    /// Connect the active session
     pub fn do_connect(&mut self) {
         // Clear any previous error message
         self.error_message = None;
 
-        // Parse host and port from connection string
-        let (host, port) = self.parse_connection_string();
-        self.host = host;
-        self.port = port;
+        if let Some(session_id) = self.active_session_id.clone() {
+            self.connect_session(&session_id);
+        }
+    }
+
+    /// Connect a session: a profile session with its profile's settings, a Quick
+    /// Connect session with the address typed for it and the connection settings
+    pub fn connect_session(&mut self, session_id: &str) {
+        self.connect_session_with_tls(session_id, None);
+    }
 
-        // Configure credentials before connecting (RFC 4777 authentication)
-        if !self.username.is_empty() && !self.password.is_empty() {
-            self.controller.set_credentials(&self.username, &self.password);
-            println!("GUI: Configured credentials for user: {}", self.username);
-        } else {
-            // Clear credentials if fields are empty
-            self.controller.clear_credentials();
+    /// Connect a session, overriding the `connection.ssl` setting of a Quick Connect session
+    pub fn connect_session_with_tls(&mut self, session_id: &str, tls_override: Option<bool>) {
+        let Some(session) = self.sessions.get_mut(session_id) else {
+            return;
+        };
+        if !session.is_quick_connect() {
+            session.connect();
+            return;
         }
 
-        // Use non-blocking connect to avoid UI hang
-        self.connecting = true;
-        self.terminal_content = format!("Connecting to {}:{}...\n", self.host, self.port);
-        // Read TLS settings from config (non-blocking)
+        let (host, port) = parse_connection_string(&session.address);
+        session.profile.host = host;
+        session.profile.port = port;
+
+        // Read TLS and reconnection settings from config (non-blocking)
         let (use_tls, insecure, ca_opt) = {
             if let Ok(cfg) = self.config.try_lock() {
-                let use_tls = cfg.get_boolean_property_or("connection.ssl", self.port == 992);
+                let use_tls = cfg.get_boolean_property_or("connection.ssl", port == 992);
                 let insecure = cfg.get_boolean_property_or("connection.tls.insecure", false);
                 let ca = cfg.get_string_property_or("connection.tls.caBundlePath", "");
                 let ca_opt = if ca.trim().is_empty() { None } else { Some(ca) };
+                session.profile.session_config.auto_reconnect = cfg.get_boolean_property_or("session.autoReconnect", false);
+                session.profile.session_config.max_reconnect_attempts = cfg
+                    .get_int_property_or("session.maxReconnectAttempts", 3)
+                    .clamp(1, 100) as u32;
                 (use_tls, insecure, ca_opt)
             } else {
                 // Config locked, use safe defaults
-                (self.port == 992, false, None)
+                (port == 992, false, None)
             }
         };
+        session.connect_with_tls(tls_override.unwrap_or(use_tls), insecure, ca_opt);
+    }
 
-        if let Err(e) = self.controller.connect_async_with_tls_options(self.host.clone(), self.port, Some(use_tls), Some(insecure), ca_opt) {
-            self.terminal_content = format!("Connection failed to start: {e}\n");
-            self.connecting = false;
-
-            // Record connection failure in monitoring
-            let monitoring = monitoring::MonitoringSystem::global();
-            let alert = monitoring::Alert {
-                id: uuid::Uuid::new_v4().to_string(),
-                timestamp: std::time::Instant::now(),
-                level: monitoring::AlertLevel::Warning,
-                component: "network".to_string(),
-                message: format!("Connection failed to {}:{}", self.host, self.port),
-                details: std::collections::HashMap::new(),
-                acknowledged: false,
-                acknowledged_at: None,
-                resolved: false,
-                resolved_at: None,
-                occurrence_count: 1,
-                last_occurrence: std::time::Instant::now(),
-            };
-            monitoring.alerting_system.trigger_alert(alert);
-        } else {
-            self.connection_time = Some(std::time::Instant::now());
-            self.login_screen_requested = false;
+    /// Remember the address typed for a Quick Connect session as the connection settings
+    pub fn save_quick_connect_address(&self, address: &str) {
+        let (host, port) = parse_connection_string(address);
+        // Do NOT auto-toggle TLS, keep user's persisted choice
+        if let Ok(mut cfg) = self.config.try_lock() {
+            cfg.set_property("connection.host", host.as_str());
+            cfg.set_property("connection.port", port as i64);
         }
+        // Persist change (async to avoid blocking GUI)
+        config::save_shared_config_async(&self.config);
     }
 
+    /// Disconnect the active session
     pub fn do_disconnect(&mut self) {
-        self.controller.disconnect();
-        self.connected = false;
-        self.login_screen_requested = false;
-        self.connection_time = None;
-        self.terminal_content = "Disconnected from AS/400 system\nReady for new connection...\n".to_string();
-        self.error_message = None;  // Clear any error on disconnect
+        if let Some(session_id) = self.active_session_id.clone() {
+            self.disconnect_session(&session_id);
+        }
+    }
+
+    /// Disconnect a session, keeping its tab open
+    pub fn disconnect_session(&mut self, session_id: &str) {
+        let Some(session) = self.sessions.get_mut(session_id) else {
+            return;
+        };
+        session.disconnect();
+        session.error_message = None;  // Clear any error on disconnect
+        self.error_message = None;
 
         // Record disconnection in monitoring
         let monitoring = monitoring::MonitoringSystem::global();
@@ -103,4 +112,4 @@ impl TN5250RApp {
             success: true,
         });
     }
-}
\ No newline at end of file
+}
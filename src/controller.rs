@@ -5,14 +5,30 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
-use std::thread;
 use std::time::Duration;
 
 use crate::ansi_processor::AnsiProcessor;
+use crate::clipboard::{LineHandling, PasteOutcome};
+use crate::screen_grid::CellGrid;
+use crate::screen_history::ScreenHistory;
+use crate::ebcdic;
 use crate::field_manager::FieldManager;
 use crate::keyboard;
 use crate::lib5250::Session;
+use crate::lib5250::field::Field;
 use crate::network;
+use crate::network_actor;
+use crate::password_substitute::PasswordLevel;
+use crate::device_name::{self, DeviceNames};
+use crate::telnet_environment::{EnvironmentSettings, EnvironmentVariable};
+use crate::network_tls::TlsOptions;
+use crate::network_proxy::ProxySettings;
+use crate::network_ssh::SshTunnel;
+use crate::known_hosts::CertificatePrompt;
+use crate::error_handling::{LogSeverity, StructuredLogger};
+
+/// Device names tried after the host reports the requested one in use, before giving up
+const MAX_DEVICE_NAME_RETRIES: u32 = 20;
 
 /// Protocol type for terminal connections
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -57,7 +73,26 @@ impl ProtocolType {
         }
     }
 }
-use crate::terminal::{CharAttribute, TerminalChar};
+use crate::terminal::{CharAttribute, TerminalChar, TextDirection};
+use crate::field_manager::FieldError;
+use crate::lib5250::display::{TN5250_DISPLAY_IND_INSERT, TN5250_DISPLAY_IND_MESSAGE_WAITING};
+
+/// Operator information area (OIA) state shown on the status line
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OiaState {
+    /// Insert mode is active (overstrike otherwise)
+    pub insert_mode: bool,
+    /// Keyboard is locked by the host (X SYSTEM)
+    pub keyboard_locked: bool,
+    /// Message waiting indicator is on
+    pub message_waiting: bool,
+    /// Pending operator error, cleared by the Reset key
+    pub operator_error: Option<FieldError>,
+    /// Push mode is active for bidirectional entry
+    pub push_mode: bool,
+    /// Screen direction (right-to-left when the screen is reversed)
+    pub screen_direction: TextDirection,
+}
 
 /// Core terminal controller responsible for managing the connection and protocol
 #[derive(Debug)]
@@ -70,10 +105,20 @@ pub struct TerminalController {
     ansi_processor: AnsiProcessor,
     use_ansi_mode: bool,
     field_manager: FieldManager,
+    format_table: Vec<Field>,                // Host format table the field manager was last loaded from
     pending_input: Vec<u8>,                  // Buffer for queued input to be transmitted
     username: Option<String>,                // Username for AS/400 authentication (RFC 4777)
     password: Option<String>,                // Password for AS/400 authentication (RFC 4777)
+    password_level: PasswordLevel,           // Password substitute algorithm (RFC 4777)
+    tls_options: TlsOptions,                 // Client certificate, minimum TLS version and pin
+    proxy: Option<ProxySettings>,            // HTTP CONNECT or SOCKS5 proxy for the next connection
+    ssh_tunnel: Option<SshTunnel>,           // SSH server to tunnel the next connection through
+    session_config: network::SessionConfig,  // Keepalive, timeouts and automatic reconnection
+    device_name: Option<String>,             // Display device name or pattern requested from the host (DEVNAME)
+    session_index: u32,                      // Index substituted for %S in the device name pattern
+    environment: EnvironmentSettings,        // NEW-ENVIRON variables (IBMPROGRAM, IBMCURLIB, custom pairs)
     data_arrival_flag: Arc<AtomicBool>,      // Flag to signal GUI when new data arrives
+    history: ScreenHistory,                  // Previous screens from the host, for looking back
 }
 
 impl Default for TerminalController {
@@ -87,10 +132,20 @@ impl Default for TerminalController {
             ansi_processor: AnsiProcessor::new(),
             use_ansi_mode: false,
             field_manager: FieldManager::new(),
+            format_table: Vec::new(),
             pending_input: Vec::new(),
             username: None,
             password: None,
+            password_level: PasswordLevel::default(),
+            tls_options: TlsOptions::default(),
+            proxy: None,
+            ssh_tunnel: None,
+            session_config: network::SessionConfig::default(),
+            device_name: None,
+            session_index: 1,
+            environment: EnvironmentSettings::default(),
             data_arrival_flag: Arc::new(AtomicBool::new(false)),
+            history: ScreenHistory::default(),
         };
 
         // Initialize session display with welcome message
@@ -111,11 +166,7 @@ impl TerminalController {
     ///
     /// # Arguments
     /// * `username` - AS/400 user profile name (will be converted to uppercase)
-    /// * `password` - User password (sent as plain text unless encryption implemented)
-    ///
-    /// # Security Note
-    /// Current implementation uses plain text password transmission (IBMRSEED empty).
-    /// For production use, implement DES or SHA password encryption per RFC 4777.
+    /// * `password` - User password (only its encrypted substitute is sent)
     pub fn set_credentials(&mut self, username: &str, password: &str) {
         self.username = Some(username.to_uppercase());
         self.password = Some(password.to_string());
@@ -131,6 +182,80 @@ impl TerminalController {
         self.password = None;
     }
 
+    /// Set the password substitute algorithm matching the host's QPWDLVL
+    pub fn set_password_level(&mut self, level: PasswordLevel) {
+        self.password_level = level;
+    }
+
+    /// Set TLS client certificate, minimum version and certificate pin for the next connection
+    pub fn set_tls_options(&mut self, options: TlsOptions) {
+        self.tls_options = options;
+    }
+
+    /// Set the proxy used for the next connection (None connects directly)
+    pub fn set_proxy(&mut self, proxy: Option<ProxySettings>) {
+        self.proxy = proxy;
+    }
+
+    /// Set the SSH tunnel used for the next connection (None connects directly)
+    pub fn set_ssh_tunnel(&mut self, tunnel: Option<SshTunnel>) {
+        self.ssh_tunnel = tunnel;
+    }
+
+    /// Set keepalive, timeouts and automatic reconnection for the next connection
+    pub fn set_session_config(&mut self, config: network::SessionConfig) {
+        self.session_config = config;
+    }
+
+    /// Request a display device name or pattern such as `QPADEV%02D` for the next connection (None uses the default)
+    pub fn set_device_name(&mut self, name: Option<String>) {
+        self.device_name = name;
+    }
+
+    /// Set the session index substituted for `%S` in the device name pattern
+    pub fn set_session_index(&mut self, index: u32) {
+        self.session_index = index;
+    }
+
+    /// Set the NEW-ENVIRON variables sent during the next connection's negotiation
+    pub fn set_environment(&mut self, environment: EnvironmentSettings) {
+        self.environment = environment;
+        self.apply_code_page();
+    }
+
+    /// Use the CODEPAGE variable's code page for the screen and keyboard (37 when unset)
+    fn apply_code_page(&mut self) {
+        let code_page = self.environment.host_code_page().unwrap_or(37);
+        self.session.set_code_page(code_page);
+    }
+
+    /// NEW-ENVIRON variables sent to the host on the current connection
+    pub fn negotiated_environment(&self) -> Vec<EnvironmentVariable> {
+        self.network_connection.as_ref().map(|conn| conn.sent_environment().to_vec()).unwrap_or_default()
+    }
+
+    /// Device names to request, expanded from the configured pattern
+    fn device_names(&self) -> Result<Option<DeviceNames>, String> {
+        self.device_name
+            .as_deref()
+            .filter(|pattern| !pattern.trim().is_empty())
+            .map(|pattern| DeviceNames::new(pattern, self.session_index))
+            .transpose()
+    }
+
+    /// Device name of the current connection
+    pub fn device_name(&self) -> Option<String> {
+        self.network_connection.as_ref().map(|conn| conn.device_name().to_string())
+    }
+
+    /// Stored sign-on credentials and password level, for connections made off the controller lock
+    fn sign_on_credentials(&self) -> Option<(String, String, PasswordLevel)> {
+        match (&self.username, &self.password) {
+            (Some(username), Some(password)) => Some((username.clone(), password.clone(), self.password_level)),
+            _ => None,
+        }
+    }
+
     /// Connect with optional TLS override. When `tls_override` is Some, it forces TLS on/off.
     /// SECURITY: Enhanced with secure error handling to prevent information disclosure
     pub fn connect_with_tls(
@@ -148,9 +273,15 @@ impl TerminalController {
         if let Some(tls) = tls_override {
             conn.set_tls(tls);
         }
+        conn.set_tls_options(self.tls_options.clone());
+        conn.set_proxy(self.proxy.clone());
+        conn.set_ssh_tunnel(self.ssh_tunnel.clone());
+        conn.set_session_config(self.session_config.clone());
+        conn.set_device_name(self.device_names()?.map(|names| names.current().to_string()));
+        conn.set_environment(self.environment.clone());
 
         // SECURITY: Handle connection errors securely without exposing internal details
-        conn.connect().map_err(|_e| {
+        conn.connect_with_timeout(self.session_config.connection_timeout()).map_err(|_e| {
             eprintln!("SECURITY: Connection failed - suppressing detailed error information");
             "Connection failed".to_string()
         })?;
@@ -225,9 +356,15 @@ impl TerminalController {
         if let Some(tls) = tls_override {
             conn.set_tls(tls);
         }
+        conn.set_tls_options(self.tls_options.clone());
+        conn.set_proxy(self.proxy.clone());
+        conn.set_ssh_tunnel(self.ssh_tunnel.clone());
+        conn.set_session_config(self.session_config.clone());
+        conn.set_device_name(self.device_names()?.map(|names| names.current().to_string()));
+        conn.set_environment(self.environment.clone());
 
         // SECURITY: Handle connection errors securely without exposing internal details
-        conn.connect().map_err(|_e| {
+        conn.connect_with_timeout(self.session_config.connection_timeout()).map_err(|_e| {
             eprintln!("SECURITY: Connection failed - suppressing detailed error information");
 
             // Record connection failure in monitoring
@@ -274,6 +411,7 @@ impl TerminalController {
                 "Controller: Configuring authentication for user: {username}"
             );
             conn.set_credentials(username, password);
+            conn.set_password_level(self.password_level);
         }
 
         // Initialize session
@@ -385,12 +523,14 @@ impl TerminalController {
         // CRITICAL FIX: Clear sensitive session data with validation
         if !self.session.display_string().is_empty() {
             self.session = Session::new();
+            self.apply_code_page();
         }
 
         // CRITICAL FIX: Clear field manager state with validation
         if !self.field_manager.get_fields().is_empty() {
             self.field_manager = FieldManager::new();
         }
+        self.format_table.clear();
 
         // CRITICAL FIX: Clear screen content that might contain sensitive data
         // Validate screen has content before clearing
@@ -437,6 +577,34 @@ impl TerminalController {
         self.connected
     }
 
+    /// True when the session is still up but its network connection has dropped
+    pub fn is_connection_lost(&self) -> bool {
+        self.connected && self.network_connection.as_ref().is_some_and(|conn| !conn.is_connected())
+    }
+
+    /// Release a dropped connection, keeping the last screen on display; returns that screen's text
+    fn release_lost_connection(&mut self) -> Option<String> {
+        self.network_connection.take()?;
+        self.connected = false;
+        self.pending_input.clear();
+        Some(self.get_terminal_content())
+    }
+
+    /// Continue the session on a new connection after the previous one was lost
+    fn restore_connection(&mut self, conn: network::AS400Connection, status: &str) {
+        // The host starts a fresh 5250 session and redraws the screen
+        self.session = Session::new();
+        self.apply_code_page();
+        self.field_manager = FieldManager::new();
+        self.format_table.clear();
+        self.use_ansi_mode = false;
+        self.network_connection = Some(conn);
+        self.connected = true;
+        self.session.display_mut().screen().clear();
+        self.session.display_mut().screen().write_string(&format!("{status}\nReady...\n"));
+        self.data_arrival_flag.store(true, Ordering::SeqCst);
+    }
+
     /// COMPREHENSIVE VALIDATION: Validate controller state consistency
     /// This method ensures all controller components are in valid states
     pub fn validate_controller_consistency(&self) -> Result<(), String> {
@@ -540,6 +708,9 @@ impl TerminalController {
             return Err("Not connected to AS/400".to_string());
         }
 
+        // The operator leaves this screen; keep it in the history as it is now
+        self.history.seal(self.session.display().screen_ref(), self.ui_cursor_position());
+
         // Send any pending input first, then the function key
         self.flush_pending_input()?;
 
@@ -550,13 +721,48 @@ impl TerminalController {
         Ok(())
     }
 
+    /// Screen content in display order (mirrored when the screen is right-to-left)
     pub fn get_terminal_content(&self) -> String {
-        self.session.display_string()
+        self.session.display().screen_ref().to_visual_string()
+    }
+
+    /// Screen cells with their display styles, for the renderer
+    pub fn cell_grid(&self) -> CellGrid {
+        CellGrid::from_screen(self.session.display().screen_ref(), self.ui_cursor_position())
     }
 
     /// Get the UI cursor position (1-based). In 5250 mode use Session display cursor; in ANSI use screen cursor.
     pub fn ui_cursor_position(&self) -> (usize, usize) {
-        self.session.cursor_position()
+        let (row, col) = self.session.cursor_position();
+        (row, self.session.display().screen_ref().visual_column(col - 1) + 1)
+    }
+
+    /// Keep the screen the host just updated in the history
+    fn record_screen(&mut self) {
+        let cursor = self.ui_cursor_position();
+        self.history.record(self.session.display().screen_ref(), cursor);
+    }
+
+    /// Load the input fields of the host's format table into the field manager,
+    /// with the continued groups and FCW behavior of each field
+    fn load_format_table(&mut self) {
+        self.sync_screen_direction();
+        self.format_table = self.session.fields.clone();
+        let groups = self.session.continued_field_groups();
+        self.field_manager.load_format_table(&self.format_table, &groups, self.session.display().screen_ref());
+        // The field the host placed the cursor in with an IC order is active
+        let (row, col) = match self.session.display().pending_insert_cursor() {
+            Some((row, col)) => (row + 1, col + 1),
+            None => self.session.cursor_position(),
+        };
+        if self.field_manager.set_active_field_at_position(row, col) {
+            self.session.display_mut().set_cursor(row - 1, col - 1);
+        }
+    }
+
+    /// Previous screens of this session
+    pub fn screen_history(&self) -> &ScreenHistory {
+        &self.history
     }
 
     /// Check if screen initialization should be sent
@@ -575,106 +781,120 @@ impl TerminalController {
             return Ok(());
         }
 
-        // Check for incoming data from network
-        if let Some(ref mut conn) = self.network_connection {
-            if let Some(received_data) = conn.receive_data_channel() {
-                println!("DEBUG: Received {} bytes of data", received_data.len());
-                if !received_data.is_empty() {
-                    println!(
-                        "DEBUG: First 50 bytes: {:02x?}",
-                        &received_data[..received_data.len().min(50)]
-                    );
-                }
+        // Take everything the connection has queued; one wakeup can stand for several events
+        loop {
+            if let Some(received_data) = self.network_connection.as_mut().and_then(|conn| conn.receive_data_channel()) {
+                self.process_host_data(&received_data);
+            }
+            if !self.network_connection.as_ref().is_some_and(|conn| conn.has_pending_events()) {
+                break;
+            }
+        }
 
-                // Detect if this looks like ANSI escape sequences
-                // ANSI data starts with ESC [ or ESC ( (matching test_connection.rs logic)
-                let is_ansi = received_data.len() >= 2
-                    && received_data[0] == 0x1B
-                    && (received_data[1] == 0x5B || received_data[1] == 0x28);
-
-                if !self.use_ansi_mode && is_ansi {
-                    self.use_ansi_mode = true;
-                    println!("Controller: Detected ANSI/VT100 data - switching to ANSI mode");
-                    // Clear screen for ANSI mode
-                    self.session.display_mut().screen().clear();
-                }
+        Ok(())
+    }
 
-                if self.use_ansi_mode {
-                    // Process as ANSI terminal data
-                    self.ansi_processor
-                        .process_data(&received_data, self.session.display_mut().screen());
-                    println!("DEBUG: Processed data in ANSI mode");
+    /// Process a block of data received from the host: update the screen and
+    /// fields and answer the host where the data stream asks for it
+    pub fn process_host_data(&mut self, received_data: &[u8]) {
+        println!("DEBUG: Received {} bytes of data", received_data.len());
+        if !received_data.is_empty() {
+            println!(
+                "DEBUG: First 50 bytes: {:02x?}",
+                &received_data[..received_data.len().min(50)]
+            );
+        }
 
-                    // Detect fields after processing ANSI data
-                    self.field_manager.detect_fields(self.session.display().screen_ref());
+        // Detect if this looks like ANSI escape sequences
+        // ANSI data starts with ESC [ or ESC ( (matching test_connection.rs logic)
+        let is_ansi = received_data.len() >= 2
+            && received_data[0] == 0x1B
+            && (received_data[1] == 0x5B || received_data[1] == 0x28);
 
-                    // Signal GUI that new data has arrived for event-driven updates
-                    self.data_arrival_flag.store(true, Ordering::SeqCst);
-                } else {
-                    // Process through the 5250 session processor
-                    println!("DEBUG: Processing data through 5250 session");
-                    let result = self.session.process_integrated_data(&received_data);
-                    println!("DEBUG: Session processing result: {result:?}");
-
-                    // Send any response data back to the server
-                    if let Ok(response_data) = &result {
-                        if !response_data.is_empty() {
-                            println!(
-                                "DEBUG: Sending {} bytes response to server",
-                                response_data.len()
-                            );
-                            if let Err(e) = self.send_input(response_data) {
-                                eprintln!("Failed to send session response: {e}");
-                            }
-                        }
-                    }
+        if !self.use_ansi_mode && is_ansi {
+            self.use_ansi_mode = true;
+            println!("Controller: Detected ANSI/VT100 data - switching to ANSI mode");
+            // Clear screen for ANSI mode
+            self.session.display_mut().screen().clear();
+        }
 
-                    // Debug: show current display content
-                    let display_content = self.session.display_string();
-                    println!(
-                        "DEBUG: Current display content ({} chars): '{}'",
-                        display_content.len(),
-                        display_content.chars().take(100).collect::<String>()
-                    );
+        if self.use_ansi_mode {
+            // Process as ANSI terminal data
+            self.ansi_processor
+                .process_data(received_data, self.session.display_mut().screen());
+            println!("DEBUG: Processed data in ANSI mode");
 
-                // Detect fields after processing 5250 data
-                // Use the session display's screen snapshot for field detection
-                let screen_ref = self.session.display().screen_ref();
-                self.field_manager.detect_fields(screen_ref);
+            // Detect fields after processing ANSI data
+            self.field_manager.detect_fields(self.session.display().screen_ref());
+            self.record_screen();
 
-                // Signal GUI that new data has arrived for event-driven updates
-                self.data_arrival_flag.store(true, Ordering::SeqCst);
-                }
+            // Signal GUI that new data has arrived for event-driven updates
+            self.data_arrival_flag.store(true, Ordering::SeqCst);
+        } else {
+            // Data only arrives once the connection has finished telnet negotiation
+            if !self.session.authenticated {
+                self.session.mark_telnet_negotiation_complete();
+            }
 
-                // Check if we received a Query Reply and send screen initialization
-                if self.session.should_send_screen_initialization() {
-                    println!("DEBUG: Query Reply received, sending screen initialization");
-                    if let Ok(init_data) = self.session.send_screen_initialization() {
-                        if let Some(ref mut conn) = self.network_connection {
-                            if let Err(e) = conn.send_data(&init_data) {
-                                eprintln!("Failed to send screen initialization: {e}");
-                            } else {
-                                println!("DEBUG: Screen initialization sent");
-                                self.session.mark_screen_initialization_sent();
-                            }
-                        }
+            // Process through the 5250 session processor
+            println!("DEBUG: Processing data through 5250 session");
+            let result = self.session.process_integrated_data(received_data);
+            println!("DEBUG: Session processing result: {result:?}");
+
+            // Send any response data back to the server
+            if let Ok(response_data) = &result {
+                if !response_data.is_empty() {
+                    println!(
+                        "DEBUG: Sending {} bytes response to server",
+                        response_data.len()
+                    );
+                    if let Err(e) = self.send_input(response_data) {
+                        eprintln!("Failed to send session response: {e}");
                     }
                 }
+            }
 
-                // SECURITY: Use generic success message without exposing connection details
-                if !self.use_ansi_mode && self.session.display_string().contains("Connecting") {
-                    self.session.display_mut().screen().clear();
-                    self.session.display_mut().screen()
-                        .write_string("Connected to remote system\nReady...\n");
+            // Debug: show current display content
+            let display_content = self.session.display_string();
+            println!(
+                "DEBUG: Current display content ({} chars): '{}'",
+                display_content.len(),
+                display_content.chars().take(100).collect::<String>()
+            );
+
+            // Take over the input fields when the host changed the format table
+            if self.session.fields != self.format_table {
+                self.load_format_table();
+            }
+            self.record_screen();
+
+            // Signal GUI that new data has arrived for event-driven updates
+            self.data_arrival_flag.store(true, Ordering::SeqCst);
+        }
+
+        // Check if we received a Query Reply and send screen initialization
+        if self.session.should_send_screen_initialization() {
+            println!("DEBUG: Query Reply received, sending screen initialization");
+            if let Ok(init_data) = self.session.send_screen_initialization() {
+                if let Some(ref mut conn) = self.network_connection {
+                    if let Err(e) = conn.send_data(&init_data) {
+                        eprintln!("Failed to send screen initialization: {e}");
+                    } else {
+                        println!("DEBUG: Screen initialization sent");
+                        self.session.mark_screen_initialization_sent();
+                    }
                 }
             }
         }
 
-        Ok(())
+        // SECURITY: Use generic success message without exposing connection details
+        if !self.use_ansi_mode && self.session.display_string().contains("Connecting") {
+            self.session.display_mut().screen().clear();
+            self.session.display_mut().screen()
+                .write_string("Connected to remote system\nReady...\n");
+        }
     }
 
-
-
     /// Check if negotiation is complete and request login screen if needed
     pub fn check_and_request_login_screen(&mut self) -> Result<(), String> {
         if let Some(ref conn) = self.network_connection {
@@ -715,39 +935,63 @@ impl TerminalController {
     pub fn type_char(&mut self, ch: char) -> Result<(), String> {
         // In ANSI mode, send characters directly to the terminal (server will echo)
         if self.use_ansi_mode {
-            // Send the character as raw ASCII byte
-            let byte = ch as u8;
-            self.send_input(&[byte])?;
+            // Send the character as UTF-8
+            let mut utf8 = [0u8; 4];
+            self.send_input(ch.encode_utf8(&mut utf8).as_bytes())?;
             return Ok(());
         }
 
-        // 5250 mode: Use field-based input
-        // Get field ID before borrowing
-        let field_id = if let Some(active_field) = self.field_manager.get_active_field() {
-            active_field.id
-        } else {
-            return Err("No active field".to_string());
+        // 5250 mode: the character must exist in the session's code page
+        let Some(ebcdic_byte) = ebcdic::char_to_ebcdic(ch, self.session.code_page()) else {
+            self.field_manager.set_error(FieldError::InvalidCharacter(ch));
+            return Err(format!("'{ch}' is not in code page {}", self.session.code_page()));
         };
 
-        // Update field manager (local echo)
-        if let Some(field) = self.field_manager.get_active_field_mut() {
-            let offset = field.content.len();
-            match field.insert_char(ch, offset) {
-                Ok(_) => {
-                    self.update_field_display(field_id);
+        // Use field-based input at the cursor, honouring insert mode
+        self.sync_insert_mode();
+        let field_id = self.field_manager.get_active_field().map(|f| f.id);
 
-                    // Queue character for network transmission
-                    // Convert character to EBCDIC for 5250 protocol
-                    let ebcdic_byte = self.ascii_to_ebcdic(ch as u8);
-                    self.pending_input.push(ebcdic_byte);
+        // Update field manager (local echo); auto-enter fields may advance to the next field
+        self.field_manager.type_char(ch)?;
+        if let Some(field_id) = field_id {
+            self.update_field_display(field_id);
+        }
+        self.refresh_active_field_display();
 
-                    Ok(())
-                }
-                Err(error) => Err(error.get_user_message().to_string()),
-            }
-        } else {
-            Err("No active field".to_string())
+        // Queue the encoded character for network transmission
+        self.pending_input.push(ebcdic_byte);
+
+        Ok(())
+    }
+
+    /// Paste text at the cursor, spreading it across the following input fields
+    ///
+    /// In ANSI mode the text is sent as typed, with line breaks as CR.
+    pub fn paste_text(&mut self, text: &str, lines: LineHandling) -> Result<PasteOutcome, String> {
+        if self.use_ansi_mode {
+            let text: String = text.replace("\r\n", "\n").chars().map(|ch| if ch == '\n' { '\r' } else { ch }).collect();
+            self.send_input(text.as_bytes())?;
+            return Ok(PasteOutcome { typed: text, dropped: 0 });
         }
+
+        // Every character must exist in the code page before anything is typed
+        let code_page = self.session.code_page();
+        if let Some(ch) = text.chars().find(|&ch| !ch.is_control() && ebcdic::char_to_ebcdic(ch, code_page).is_none()) {
+            self.field_manager.set_error(FieldError::InvalidCharacter(ch));
+            return Err(format!("'{ch}' is not in code page {code_page}"));
+        }
+
+        self.sync_insert_mode();
+        let result = self.field_manager.paste_text(text, lines);
+        let field_ids: Vec<usize> = self.field_manager.get_fields().iter().map(|f| f.id).collect();
+        for field_id in field_ids {
+            self.update_field_display(field_id);
+        }
+        self.refresh_active_field_display();
+
+        let outcome = result?;
+        self.pending_input.extend(outcome.typed.chars().filter_map(|ch| ebcdic::char_to_ebcdic(ch, code_page)));
+        Ok(outcome)
     }
 
     /// Backspace in active field
@@ -761,24 +1005,9 @@ impl TerminalController {
         }
 
         // 5250 mode: Use field-based backspace
-        // First get the field ID to avoid borrowing conflicts
-        let field_id = if let Some(active_field) = self.field_manager.get_active_field() {
-            active_field.id
-        } else {
-            return Err("No active field".to_string());
-        };
-
-        if let Some(field) = self.field_manager.get_active_field_mut() {
-            let offset = field.content.len();
-            if field.backspace(offset) {
-                self.update_field_display(field_id);
-                Ok(())
-            } else {
-                Err("Cannot backspace in this field".to_string())
-            }
-        } else {
-            Err("No active field".to_string())
-        }
+        self.field_manager.backspace()?;
+        self.refresh_active_field_display();
+        Ok(())
     }
 
     /// Delete character in active field
@@ -790,23 +1019,179 @@ impl TerminalController {
         }
 
         // 5250 mode: Use field-based delete
-        // First get the field ID to avoid borrowing conflicts
-        let field_id = if let Some(active_field) = self.field_manager.get_active_field() {
-            active_field.id
+        self.field_manager.delete()?;
+        self.refresh_active_field_display();
+        Ok(())
+    }
+
+    /// Toggle insert/overstrike mode, returning the new insert state
+    pub fn toggle_insert_mode(&mut self) -> bool {
+        self.sync_insert_mode();
+        let insert = self.field_manager.toggle_insert_mode();
+        let display = self.session.display_mut();
+        if insert {
+            display.indicator_set(TN5250_DISPLAY_IND_INSERT);
         } else {
-            return Err("No active field".to_string());
-        };
+            display.indicator_clear(TN5250_DISPLAY_IND_INSERT);
+        }
+        insert
+    }
 
-        if let Some(field) = self.field_manager.get_active_field_mut() {
-            let offset = field.content.len();
-            if field.delete_char(offset) {
-                self.update_field_display(field_id);
+    /// Erase EOF: clear the active field from the cursor to its end
+    pub fn erase_eof(&mut self) -> Result<(), String> {
+        if self.use_ansi_mode {
+            return Ok(());
+        }
+        self.field_manager.erase_eof()?;
+        self.refresh_active_field_display();
+        Ok(())
+    }
+
+    /// Field Exit: clear the active field from the cursor to its end and move to the next input field
+    pub fn field_exit(&mut self) -> Result<(), String> {
+        if self.use_ansi_mode {
+            // ANSI hosts have no fields; Field Exit ends the line
+            self.send_input(&[0x0D])?;
+            return Ok(());
+        }
+        let field_id = self.field_manager.get_active_field().map(|f| f.id);
+        self.field_manager.field_exit()?;
+        if let Some(field_id) = field_id {
+            self.update_field_display(field_id);
+        }
+        self.refresh_active_field_display();
+        Ok(())
+    }
+
+    /// Erase Input: clear all unprotected fields and move to the first input field
+    pub fn erase_input(&mut self) -> Result<(), String> {
+        if self.use_ansi_mode {
+            return Ok(());
+        }
+        self.field_manager.erase_input()?;
+        let field_ids: Vec<usize> = self.field_manager.get_fields().iter().map(|f| f.id).collect();
+        for field_id in field_ids {
+            self.update_field_display(field_id);
+        }
+        self.refresh_active_field_display();
+        Ok(())
+    }
+
+    /// Home: move the cursor to the first input field
+    pub fn home(&mut self) -> Result<(), String> {
+        if self.use_ansi_mode {
+            // ANSI/VT Home key
+            self.send_input(&[0x1B, 0x5B, 0x48])?; // ESC [ H
+            return Ok(());
+        }
+
+        match self.field_manager.home_to_first_input_field() {
+            Ok(()) => {
+                self.refresh_active_field_display();
                 Ok(())
-            } else {
-                Err("Cannot delete in this field".to_string())
             }
-        } else {
-            Err("No active field".to_string())
+            Err(_) => {
+                // No input fields: home is the top-left corner
+                self.session.display_mut().set_cursor(0, 0);
+                Ok(())
+            }
+        }
+    }
+
+    /// Screen reverse: flip the screen between left-to-right and right-to-left
+    pub fn toggle_screen_reverse(&mut self) -> TextDirection {
+        let direction = self.session.display().screen_direction().reversed();
+        self.session.display_mut().set_screen_direction(direction);
+        self.field_manager.set_screen_direction(direction);
+        direction
+    }
+
+    /// Toggle push mode for bidirectional entry, returning the new state
+    pub fn toggle_push_mode(&mut self) -> bool {
+        self.sync_screen_direction();
+        let push = self.field_manager.toggle_push_mode();
+        self.refresh_active_field_display();
+        push
+    }
+
+    /// Field Reverse: flip the cursor direction of the active field, returning whether it is reversed
+    pub fn toggle_field_reverse(&mut self) -> Result<bool, String> {
+        self.sync_screen_direction();
+        let reversed = self.field_manager.toggle_field_reverse().map_err(|e| e.get_user_message().to_string())?;
+        self.refresh_active_field_display();
+        Ok(reversed)
+    }
+
+    /// Auto Push: toggle automatic push mode in the active field, returning the new state
+    pub fn toggle_auto_push(&mut self) -> Result<bool, String> {
+        self.sync_screen_direction();
+        self.field_manager.toggle_auto_push().map_err(|e| e.get_user_message().to_string())
+    }
+
+    /// Close: toggle closed entry in the active field, returning the new state
+    pub fn toggle_closed_field(&mut self) -> Result<bool, String> {
+        self.field_manager.toggle_closed_field().map_err(|e| e.get_user_message().to_string())
+    }
+
+    /// Attention key: ask the host to interrupt the job
+    pub fn attn(&mut self) -> Result<(), String> {
+        self.send_header_flags(keyboard::RECORD_FLAG_ATN, "Attn")
+    }
+
+    /// System Request key: ask the host for the System Request menu
+    pub fn sys_req(&mut self) -> Result<(), String> {
+        self.send_header_flags(keyboard::RECORD_FLAG_SRQ, "SysReq")
+    }
+
+    /// Send an empty 5250 record whose header carries `flags`
+    fn send_header_flags(&mut self, flags: u8, key_name: &str) -> Result<(), String> {
+        if !self.connected {
+            return Err("Not connected to AS/400".to_string());
+        }
+        if self.use_ansi_mode {
+            return Err(format!("{key_name} is not available on ANSI hosts"));
+        }
+        let conn = self.network_connection.as_mut().ok_or("Network connection not available")?;
+        conn.send_data(&keyboard::header_flag_record(flags)).map_err(|_| "Network operation failed".to_string())?;
+        Ok(())
+    }
+
+    /// Reset key: clear the pending operator error
+    pub fn reset_operator_error(&mut self) {
+        self.field_manager.clear_error();
+    }
+
+    /// Snapshot of the operator information area (status line)
+    pub fn oia_state(&self) -> OiaState {
+        let display = self.session.display();
+        let indicators = display.indicators();
+        OiaState {
+            insert_mode: indicators & TN5250_DISPLAY_IND_INSERT != 0,
+            keyboard_locked: display.keyboard_locked(),
+            message_waiting: indicators & TN5250_DISPLAY_IND_MESSAGE_WAITING != 0,
+            operator_error: self.field_manager.get_error().cloned(),
+            push_mode: self.field_manager.is_push_mode(),
+            screen_direction: display.screen_direction(),
+        }
+    }
+
+    /// Pick up insert-mode changes made by the host (e.g. Clear Unit resets insert)
+    fn sync_insert_mode(&mut self) {
+        let insert = self.session.display().indicators() & TN5250_DISPLAY_IND_INSERT != 0;
+        self.field_manager.set_insert_mode(insert);
+        self.sync_screen_direction();
+    }
+
+    /// Pick up screen direction changes made by the host or the code page
+    fn sync_screen_direction(&mut self) {
+        let direction = self.session.display().screen_direction();
+        self.field_manager.set_screen_direction(direction);
+    }
+
+    /// Redraw the active field and place the display cursor on its edit position
+    fn refresh_active_field_display(&mut self) {
+        if let Some(field_id) = self.field_manager.get_active_field().map(|f| f.id) {
+            self.update_field_display(field_id);
         }
     }
 
@@ -827,7 +1212,17 @@ impl TerminalController {
 
     /// Get field information for display
     pub fn get_fields_info(&self) -> Vec<crate::field_manager::FieldDisplayInfo> {
-        self.field_manager.get_fields_display_info()
+        let screen = self.session.display().screen_ref();
+        let mut fields = self.field_manager.get_fields_display_info();
+        // Report field positions as displayed; a reversed screen mirrors each field's span
+        for field in &mut fields {
+            if field.start_col >= 1 && field.length >= 1 {
+                let first = screen.visual_column(field.start_col - 1);
+                let last = screen.visual_column(field.start_col + field.length - 2);
+                field.start_col = first.min(last) + 1;
+            }
+        }
+        fields
     }
 
     /// Update field display on screen
@@ -848,8 +1243,8 @@ impl TerminalController {
             for i in 0..field.length {
                 if field.start_col + i <= 80 {
                     screen_ref.set_char_at(
-                        field.start_row - 1,
                         field.start_col + i - 1,
+                        field.start_row - 1,
                         TerminalChar {
                             character: ' ',
                             attribute: CharAttribute::Normal,
@@ -858,12 +1253,13 @@ impl TerminalController {
                 }
             }
 
-            // Write the field content
+            // Write the field content; reversed fields fill from their far end
             for (i, ch) in display_content.chars().enumerate() {
-                if i < field.length && field.start_col + i <= 80 {
+                let col = field.column_for_offset(i);
+                if i < field.length && col <= 80 {
                     screen_ref.set_char_at(
+                        col - 1,
                         field.start_row - 1,
-                        field.start_col + i - 1,
                         TerminalChar {
                             character: ch,
                             attribute: CharAttribute::Normal,
@@ -872,9 +1268,9 @@ impl TerminalController {
                 }
             }
 
-            // Position the session/display cursor at the insertion point for active field
+            // Position the session/display cursor at the edit position for active field
             if field.active {
-                let col = field.start_col + display_content.len();
+                let col = field.column_for_offset(field.cursor_position);
                 if col >= 1 {
                     // Update the cursor in the lib5250 Display so the UI can render it
                     self.session
@@ -908,8 +1304,10 @@ impl TerminalController {
             .collect()
     }
 
-    /// Click/activate field at position
+    /// Click/activate field at position (1-based, as displayed)
     pub fn activate_field_at_position(&mut self, row: usize, col: usize) -> bool {
+        self.sync_screen_direction();
+        let col = self.session.display().screen_ref().buffer_column(col.saturating_sub(1)) + 1;
         let activated = self.field_manager.set_active_field_at_position(row, col);
         if activated {
             // Reflect cursor move in session display for 5250 mode rendering
@@ -952,12 +1350,6 @@ impl TerminalController {
         Ok(data)
     }
 
-    /// Convert ASCII to EBCDIC (basic conversion)
-    fn ascii_to_ebcdic(&self, ascii: u8) -> u8 {
-        // Use the EBCDIC conversion from protocol_common
-        crate::protocol_common::ebcdic::ascii_to_ebcdic(ascii as char)
-    }
-
     /// Get pending input buffer (for testing)
     pub fn get_pending_input(&self) -> &[u8] {
         &self.pending_input
@@ -1022,16 +1414,86 @@ impl TerminalController {
     }
 }
 
+/// Progress of an automatic reconnection after the connection was lost
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectProgress {
+    /// Attempt being waited for or in progress (1-based)
+    pub attempt: u32,
+    /// Attempts allowed before giving up
+    pub max_attempts: u32,
+    /// When the next attempt starts; None while it is connecting
+    pub next_attempt_at: Option<std::time::Instant>,
+    /// Why the previous attempt failed
+    pub last_error: Option<String>,
+}
+
+impl ReconnectProgress {
+    /// One-line status for the UI
+    pub fn summary(&self) -> String {
+        let attempt = format!("attempt {} of {}", self.attempt, self.max_attempts);
+        match self.next_attempt_at {
+            Some(at) => {
+                let secs = at.saturating_duration_since(std::time::Instant::now()).as_secs_f32().ceil();
+                format!("Connection lost - reconnecting in {secs:.0}s ({attempt})")
+            }
+            None => format!("Connection lost - reconnecting ({attempt})..."),
+        }
+    }
+}
+
+/// Everything needed to open the session's connection again, captured when it was first made
+#[derive(Clone)]
+struct ConnectSettings {
+    host: String,
+    port: u16,
+    tls_override: Option<bool>,
+    sign_on: Option<(String, String, PasswordLevel)>,
+    tls_options: TlsOptions,
+    proxy: Option<ProxySettings>,
+    ssh_tunnel: Option<SshTunnel>,
+    ca_bundle_path: Option<String>,
+    session_config: network::SessionConfig,
+    device_names: Option<DeviceNames>,
+    environment: EnvironmentSettings,
+}
+
+impl ConnectSettings {
+    /// Connect and negotiate (blocking)
+    fn open(&self, timeout: Duration) -> std::io::Result<network::AS400Connection> {
+        let mut conn = network::AS400Connection::new(self.host.clone(), self.port);
+        if let Some(tls) = self.tls_override {
+            conn.set_tls(tls);
+        }
+        if let Some((ref username, ref password, level)) = self.sign_on {
+            conn.set_credentials(username, password);
+            conn.set_password_level(level);
+        }
+        conn.set_tls_options(self.tls_options.clone());
+        conn.set_proxy(self.proxy.clone());
+        conn.set_ssh_tunnel(self.ssh_tunnel.clone());
+        if let Some(ref path) = self.ca_bundle_path {
+            conn.set_tls_ca_bundle_path(path.clone());
+        }
+        conn.set_session_config(self.session_config.clone());
+        conn.set_device_name(self.device_names.as_ref().map(|names| names.current().to_string()));
+        conn.set_environment(self.environment.clone());
+        conn.connect_with_timeout(timeout)?;
+        Ok(conn)
+    }
+}
+
 /// Asynchronous terminal controller that handles background networking
 #[derive(Debug)]
 pub struct AsyncTerminalController {
     controller: Arc<Mutex<TerminalController>>,
     running: bool,
-    handle: Option<thread::JoinHandle<()>>,
+    handle: Option<tokio::task::JoinHandle<()>>,
     // Async connect state
     connect_in_progress: Arc<AtomicBool>,
     last_connect_error: Arc<Mutex<Option<String>>>,
+    certificate_prompt: Arc<Mutex<Option<CertificatePrompt>>>,
     cancel_connect_flag: Arc<AtomicBool>,
+    reconnect_progress: Arc<Mutex<Option<ReconnectProgress>>>,
 }
 
 impl Clone for AsyncTerminalController {
@@ -1042,7 +1504,9 @@ impl Clone for AsyncTerminalController {
             handle: None, // Do not clone the handle
             connect_in_progress: Arc::clone(&self.connect_in_progress),
             last_connect_error: Arc::clone(&self.last_connect_error),
+            certificate_prompt: Arc::clone(&self.certificate_prompt),
             cancel_connect_flag: Arc::clone(&self.cancel_connect_flag),
+            reconnect_progress: Arc::clone(&self.reconnect_progress),
         }
     }
 }
@@ -1077,7 +1541,9 @@ impl AsyncTerminalController {
             handle: None,
             connect_in_progress: Arc::new(AtomicBool::new(false)),
             last_connect_error: Arc::new(Mutex::new(None)),
+            certificate_prompt: Arc::new(Mutex::new(None)),
             cancel_connect_flag: Arc::new(AtomicBool::new(false)),
+            reconnect_progress: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -1109,35 +1575,109 @@ impl AsyncTerminalController {
         // If we can't get the lock, credentials will be cleared on next successful lock attempt
     }
 
-    pub fn connect(&mut self, host: String, port: u16) -> Result<(), String> {
-        if self.running {
-            self.disconnect();
+    /// Set the password substitute algorithm (RFC 4777)
+    /// Must be called before connect() or connect_async()
+    pub fn set_password_level(&self, level: PasswordLevel) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_password_level(level);
         }
+    }
 
-        {
-            let mut ctrl = self
-                .controller
-                .try_lock()
-                .map_err(|_| "Controller locked during connect")?;
-            ctrl.connect_with_tls(host, port, None)?;
+    /// Set TLS client certificate, minimum version and certificate pin
+    /// Must be called before connect() or connect_async()
+    pub fn set_tls_options(&self, options: TlsOptions) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_tls_options(options);
         }
+    }
 
-        self.running = true;
-
-        // Start background networking thread
-        self.start_network_thread();
-
-        Ok(())
+    /// Set the HTTP CONNECT or SOCKS5 proxy (None connects directly)
+    /// Must be called before connect() or connect_async()
+    pub fn set_proxy(&self, proxy: Option<ProxySettings>) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_proxy(proxy);
+        }
     }
 
-    /// Non-blocking connect: perform network connect and telnet negotiation on a background thread
-    /// Returns immediately; use `is_connected()`/`is_connecting()`/`get_last_connect_error()` to track status
-    pub fn connect_async(&mut self, host: String, port: u16) -> Result<(), String> {
-        self.connect_async_with_tls(host, port, None)
+    /// Set the SSH tunnel (None connects directly)
+    /// Must be called before connect() or connect_async()
+    pub fn set_ssh_tunnel(&self, tunnel: Option<SshTunnel>) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_ssh_tunnel(tunnel);
+        }
     }
 
-    /// TLS-aware non-blocking connect with optional TLS override
-    pub fn connect_async_with_tls(
+    /// Set keepalive, timeouts and automatic reconnection
+    /// Must be called before connect() or connect_async()
+    pub fn set_session_config(&self, config: network::SessionConfig) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_session_config(config);
+        }
+    }
+
+    /// Request a display device name or pattern such as `QPADEV%02D` (None uses the default)
+    /// Must be called before connect() or connect_async()
+    pub fn set_device_name(&self, name: Option<String>) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_device_name(name);
+        }
+    }
+
+    /// Set the session index substituted for `%S` in the device name pattern
+    /// Must be called before connect() or connect_async()
+    pub fn set_session_index(&self, index: u32) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_session_index(index);
+        }
+    }
+
+    /// Set the NEW-ENVIRON variables (IBMPROGRAM, IBMCURLIB, custom pairs, ...)
+    /// Must be called before connect() or connect_async()
+    pub fn set_environment(&self, environment: EnvironmentSettings) {
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.set_environment(environment);
+        }
+    }
+
+    /// NEW-ENVIRON variables sent to the host on the current connection
+    pub fn negotiated_environment(&self) -> Vec<EnvironmentVariable> {
+        self.controller.try_lock().map(|ctrl| ctrl.negotiated_environment()).unwrap_or_default()
+    }
+
+    /// Device name the host accepted for the current connection
+    pub fn device_name(&self) -> Option<String> {
+        self.controller.try_lock().ok().and_then(|ctrl| ctrl.device_name())
+    }
+
+    pub fn connect(&mut self, host: String, port: u16) -> Result<(), String> {
+        if self.running {
+            self.disconnect();
+        }
+
+        {
+            let mut ctrl = self
+                .controller
+                .try_lock()
+                .map_err(|_| "Controller locked during connect")?;
+            ctrl.connect_with_tls(host, port, None)?;
+        }
+
+        self.running = true;
+
+        // Start background networking thread
+        self.start_network_thread();
+
+        Ok(())
+    }
+
+    /// Non-blocking connect: perform network connect and telnet negotiation on a background thread
+    /// Returns immediately; use `is_connected()`/`is_connecting()`/`get_last_connect_error()` to track status
+    pub fn connect_async(&mut self, host: String, port: u16) -> Result<(), String> {
+        self.connect_async_with_tls(host, port, None)
+    }
+
+    /// TLS-aware non-blocking connect with optional TLS override
+    pub fn connect_async_with_tls(
         &mut self,
         host: String,
         port: u16,
@@ -1166,31 +1706,51 @@ impl AsyncTerminalController {
             *err = None;
         }
 
+        // Credentials (RFC 4777), TLS options, proxy, SSH tunnel and session settings are applied to the new connection before it negotiates
+        let mut settings = ConnectSettings {
+            host,
+            port,
+            tls_override,
+            sign_on: None,
+            tls_options: TlsOptions::default(),
+            proxy: None,
+            ssh_tunnel: None,
+            ca_bundle_path,
+            session_config: network::SessionConfig::default(),
+            device_names: None,
+            environment: EnvironmentSettings::default(),
+        };
+        if let Ok(ctrl) = self.controller.try_lock() {
+            settings.sign_on = ctrl.sign_on_credentials();
+            settings.tls_options = ctrl.tls_options.clone();
+            settings.proxy = ctrl.proxy.clone();
+            settings.ssh_tunnel = ctrl.ssh_tunnel.clone();
+            settings.session_config = ctrl.session_config.clone();
+            settings.environment = ctrl.environment.clone();
+            settings.device_names = ctrl.device_names().inspect_err(|_| {
+                self.connect_in_progress.store(false, Ordering::SeqCst);
+            })?;
+        }
         let controller_ref = Arc::clone(&self.controller);
         let connect_flag = Arc::clone(&self.connect_in_progress);
         let err_ref = Arc::clone(&self.last_connect_error);
+        let prompt_ref = Arc::clone(&self.certificate_prompt);
         let cancel_flag = Arc::clone(&self.cancel_connect_flag);
+        let progress_ref = Arc::clone(&self.reconnect_progress);
 
-        // Spawn a single thread that performs connect then enters the processing loop
-        let handle = thread::spawn(move || {
-            // Do the blocking network connect without holding the controller lock
-            let connect_result = (|| {
+        // Spawn a single task on the network runtime that performs connect then enters the processing loop
+        let handle = network_actor::runtime().spawn(async move {
+            let connect_result = async {
                 // Early cancel check
                 if cancel_flag.load(Ordering::SeqCst) {
                     return Err("Connection canceled by user".to_string());
                 }
 
-                let mut conn = network::AS400Connection::new(host.clone(), port);
-                if let Some(tls) = tls_override {
-                    conn.set_tls(tls);
-                }
-                if let Some(ref path) = ca_bundle_path {
-                    conn.set_tls_ca_bundle_path(path.clone());
-                }
                 // Use a bounded timeout for TCP connect + then telnet negotiation handles its own timeouts
-                let timeout = Duration::from_secs(10);
-                conn.connect_with_timeout(timeout)
-                    .map_err(|e| e.to_string())?;
+                let conn = Self::open_connection(&settings).await.map_err(|e| {
+                    Self::record_certificate_prompt(&prompt_ref, &e);
+                    e.to_string()
+                })?;
 
                 // SECURITY: Use generic connection message without exposing sensitive details
                 let connected_msg = "Connected to remote system\nReady...\n".to_string();
@@ -1200,77 +1760,44 @@ impl AsyncTerminalController {
                     return Err("Connection canceled by user".to_string());
                 }
 
-                // CRITICAL FIX: Safer state update with timeout and better error handling
-                match controller_ref.try_lock() {
-                    Ok(mut ctrl) => {
-                        // Update controller state with established connection
-                        ctrl.host = host.clone();
-                        ctrl.port = port;
-                        ctrl.network_connection = Some(conn);
-                        ctrl.connected = true;
-                        // Optional: update screen message
-                        ctrl.session.display_mut().screen().clear();
-                        ctrl.session.display_mut().screen().write_string(&connected_msg);
-                        Ok(())
-                    }
-                    Err(std::sync::TryLockError::Poisoned(_poisoned)) => {
-                        eprintln!("SECURITY: Controller mutex poisoned during connection");
-                        Err("Controller lock poisoned".to_string())
-                    }
-                    Err(std::sync::TryLockError::WouldBlock) => {
-                        eprintln!("SECURITY: Controller lock blocked during connection");
-                        Err("Controller busy".to_string())
-                    }
-                }
-            })();
+                // The GUI only holds the lock briefly, so waiting for it here is short
+                let mut ctrl = controller_ref.lock().map_err(|_| {
+                    eprintln!("SECURITY: Controller mutex poisoned during connection");
+                    "Controller lock poisoned".to_string()
+                })?;
+                // Update controller state with established connection
+                ctrl.host = settings.host.clone();
+                ctrl.port = settings.port;
+                ctrl.network_connection = Some(conn);
+                ctrl.connected = true;
+                // Optional: update screen message
+                ctrl.session.display_mut().screen().clear();
+                ctrl.session.display_mut().screen().write_string(&connected_msg);
+                Ok(())
+            }
+            .await;
 
             // Mark connection attempt finished (success or error)
             connect_flag.store(false, Ordering::SeqCst);
 
             match connect_result {
                 Ok(()) => {
-                    // Enter processing loop similar to start_network_thread
-                    loop {
-                        // CRITICAL FIX: Enhanced thread safety with better error handling
-                        let mut should_break = false;
-
-                        match controller_ref.try_lock() {
-                            Ok(mut ctrl) => {
-                                if ctrl.is_connected() {
-                                    // CRITICAL FIX: Handle processing errors gracefully
-                                    match ctrl.process_incoming_data() {
-                                        Ok(_) => {
-                                            // Processing successful
-                                        }
-                                        Err(e) => {
-                                            eprintln!(
-                                                "SECURITY: Error processing incoming data: {e}"
-                                            );
-                                            // Continue processing but log the error
-                                        }
-                                    }
-                                } else {
-                                    should_break = true;
-                                }
+                    // Process until the session ends; a device name the host reports in use is replaced by the
+                    // next one from the pattern, and a dropped connection is reopened when the profile allows it
+                    let mut name_retries = 0;
+                    while let Some(last_screen) = Self::process_until_lost(&controller_ref, &cancel_flag).await {
+                        let outcome = if device_name::is_device_in_use(&last_screen) {
+                            name_retries += 1;
+                            Self::retry_device_name(&mut settings, name_retries, &controller_ref, &cancel_flag).await
+                        } else {
+                            Self::reconnect(&settings, &controller_ref, &cancel_flag, &progress_ref).await
+                        };
+                        if let Err(e) = outcome {
+                            if let Ok(mut err) = err_ref.lock() {
+                                *err = Some(e);
                             }
-                            Err(std::sync::TryLockError::Poisoned(_)) => {
-                                eprintln!(
-                                    "SECURITY: Controller mutex poisoned in processing thread"
-                                );
-                                should_break = true;
-                            }
-                            Err(std::sync::TryLockError::WouldBlock) => {
-                                // Could not lock; fall through to sleep
-                            }
-                        }
-
-                        // Check cancellation flag
-                        if cancel_flag.load(Ordering::SeqCst) || should_break {
                             break;
                         }
-
-                        // Sleep regardless of processing status to prevent busy waiting
-                        thread::sleep(Duration::from_millis(50));
                     }
                 }
                 Err(e) => {
@@ -1287,6 +1814,167 @@ impl AsyncTerminalController {
         Ok(())
     }
 
+    /// Process incoming data until the session ends; returns the last screen's text if the connection was lost
+    async fn process_until_lost(
+        controller_ref: &Arc<Mutex<TerminalController>>,
+        cancel_flag: &AtomicBool,
+    ) -> Option<String> {
+        loop {
+            {
+                let Ok(mut ctrl) = controller_ref.lock() else {
+                    eprintln!("SECURITY: Controller mutex poisoned in processing thread");
+                    return None;
+                };
+                if !ctrl.is_connected() {
+                    return None;
+                }
+                // CRITICAL FIX: Handle processing errors gracefully
+                if let Err(e) = ctrl.process_incoming_data() {
+                    eprintln!("SECURITY: Error processing incoming data: {e}");
+                    // Continue processing but log the error
+                }
+                if ctrl.is_connection_lost() && !cancel_flag.load(Ordering::SeqCst) {
+                    return ctrl.release_lost_connection();
+                }
+            }
+
+            if cancel_flag.load(Ordering::SeqCst) || !Self::wait_for_connection(controller_ref).await {
+                return None;
+            }
+        }
+    }
+
+    /// Wait until the connection queues an event or its idle timeout falls due, without holding the
+    /// controller lock; false when there is no connection to wait on
+    async fn wait_for_connection(controller_ref: &Mutex<TerminalController>) -> bool {
+        let (wakeup, idle_deadline) = {
+            let Ok(ctrl) = controller_ref.lock() else {
+                return false;
+            };
+            match ctrl.network_connection.as_ref().and_then(|conn| Some((conn.event_wakeup()?, conn.idle_deadline()))) {
+                Some(waiting) => waiting,
+                None => return false,
+            }
+        };
+        match idle_deadline {
+            Some(deadline) => {
+                let _ = tokio::time::timeout_at(deadline.into(), wakeup.notified()).await;
+            }
+            None => wakeup.notified().await,
+        }
+        true
+    }
+
+    /// Connect and negotiate on the blocking pool, so a slow host never holds up a runtime worker
+    async fn open_connection(settings: &ConnectSettings) -> std::io::Result<network::AS400Connection> {
+        let settings = settings.clone();
+        tokio::task::spawn_blocking(move || settings.open(settings.session_config.connection_timeout()))
+            .await
+            .unwrap_or_else(|e| Err(std::io::Error::other(e)))
+    }
+
+    /// Reconnect at once under the next device name after the host reported the current one in use
+    async fn retry_device_name(
+        settings: &mut ConnectSettings,
+        retries: u32,
+        controller_ref: &Arc<Mutex<TerminalController>>,
+        cancel_flag: &AtomicBool,
+    ) -> Result<(), String> {
+        let names = settings.device_names.as_mut();
+        let rejected = names.as_ref().map_or(crate::telnet_negotiation::DEFAULT_DEVICE_NAME, |names| names.current()).to_string();
+        let next = match names.and_then(|names| names.advance()) {
+            Some(next) if retries <= MAX_DEVICE_NAME_RETRIES => next.to_string(),
+            _ => return Err(format!("Device name {rejected} is already in use")),
+        };
+        println!("Controller: Device name {rejected} is in use, retrying as {next}");
+
+        let conn = Self::open_connection(settings)
+            .await
+            .map_err(|e| format!("Device name {rejected} is in use; connecting as {next} failed: {e}"))?;
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Err("Connection canceled by user".to_string());
+        }
+        let mut ctrl = controller_ref.lock().map_err(|_| "Controller lock poisoned".to_string())?;
+        ctrl.restore_connection(conn, &format!("Device {rejected} is in use, connected as {next}"));
+        Ok(())
+    }
+
+    /// Reopen a lost connection with backoff, re-running negotiation and sign-on with the same device name.
+    /// Fails with a message for the UI when reconnection is disabled, canceled or out of attempts.
+    async fn reconnect(
+        settings: &ConnectSettings,
+        controller_ref: &Arc<Mutex<TerminalController>>,
+        cancel_flag: &AtomicBool,
+        progress_ref: &Mutex<Option<ReconnectProgress>>,
+    ) -> Result<(), String> {
+        let config = &settings.session_config;
+        if !config.auto_reconnect {
+            return Err("Connection lost".to_string());
+        }
+
+        let logger = StructuredLogger::new(LogSeverity::Info);
+        let policy = config.reconnect_policy();
+        let set_progress = |progress: Option<ReconnectProgress>| {
+            if let Ok(mut slot) = progress_ref.lock() {
+                *slot = progress;
+            }
+        };
+
+        let mut attempt = 0;
+        let mut last_error = None;
+        while policy.should_retry(attempt) {
+            let delay = policy.get_delay(attempt);
+            attempt += 1;
+            let mut progress = ReconnectProgress {
+                attempt: attempt as u32,
+                max_attempts: policy.max_attempts() as u32,
+                next_attempt_at: Some(std::time::Instant::now() + delay),
+                last_error: last_error.clone(),
+            };
+            set_progress(Some(progress.clone()));
+
+            // A disconnect aborts this task, so the backoff needs no polling
+            tokio::time::sleep(delay).await;
+            if cancel_flag.load(Ordering::SeqCst) {
+                set_progress(None);
+                return Err("Reconnection canceled by user".to_string());
+            }
+
+            progress.next_attempt_at = None;
+            set_progress(Some(progress));
+            logger.log_recovery_attempt(attempt, policy.max_attempts(), "reconnect");
+
+            let restored = match Self::open_connection(settings).await {
+                Ok(_) if cancel_flag.load(Ordering::SeqCst) => Ok(false),
+                Ok(conn) => controller_ref
+                    .lock()
+                    .map(|mut ctrl| ctrl.restore_connection(conn, "Connection restored"))
+                    .map(|()| true)
+                    .map_err(|_| "Controller lock poisoned".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match restored {
+                Ok(true) => {
+                    logger.log_recovery_success("reconnect");
+                    set_progress(None);
+                    return Ok(());
+                }
+                Ok(false) => {
+                    set_progress(None);
+                    return Err("Reconnection canceled by user".to_string());
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        logger.log_recovery_failure("reconnect");
+        set_progress(None);
+        Err(match last_error {
+            Some(e) => format!("Connection lost; reconnection failed after {attempt} attempts: {e}"),
+            None => "Connection lost".to_string(),
+        })
+    }
+
     /// TLS-aware blocking connect wrapper for symmetry with sync controller
     pub fn connect_with_tls(
         &mut self,
@@ -1363,30 +2051,63 @@ impl AsyncTerminalController {
             *err = None;
         }
 
+        // Credentials (RFC 4777), TLS options, proxy, SSH tunnel and session settings are applied to the new connection before it negotiates
+        let (sign_on, tls_options, proxy, ssh_tunnel, session_config) = match self.controller.try_lock() {
+            Ok(ctrl) => (
+                ctrl.sign_on_credentials(),
+                ctrl.tls_options.clone(),
+                ctrl.proxy.clone(),
+                ctrl.ssh_tunnel.clone(),
+                ctrl.session_config.clone(),
+            ),
+            Err(_) => (None, TlsOptions::default(), None, None, network::SessionConfig::default()),
+        };
         let controller_ref = Arc::clone(&self.controller);
         let connect_flag = Arc::clone(&self.connect_in_progress);
         let err_ref = Arc::clone(&self.last_connect_error);
+        let prompt_ref = Arc::clone(&self.certificate_prompt);
         let cancel_flag = Arc::clone(&self.cancel_connect_flag);
 
-        // Spawn a single thread that performs connect then enters the processing loop
-        let handle = thread::spawn(move || {
-            // Do the blocking network connect without holding the controller lock
-            let connect_result = (|| {
-                // Early cancel check
-                if cancel_flag.load(Ordering::SeqCst) {
-                    return Err("Connection canceled by user".to_string());
+        // Spawn a single task on the network runtime that performs connect then enters the processing loop
+        let handle = network_actor::runtime().spawn(async move {
+            // Early cancel check
+            if cancel_flag.load(Ordering::SeqCst) {
+                if let Ok(mut err) = err_ref.lock() {
+                    *err = Some("Connection canceled by user".to_string());
                 }
+                connect_flag.store(false, Ordering::SeqCst);
+                return;
+            }
 
-                let mut conn = network::AS400Connection::new(host.clone(), port);
+            // Connect and negotiate on the blocking pool, so a slow host never holds up a runtime worker
+            let open_host = host.clone();
+            let open_prompt = Arc::clone(&prompt_ref);
+            let opened = tokio::task::spawn_blocking(move || {
+                let mut conn = network::AS400Connection::new(open_host, port);
                 if let Some(tls) = tls_override {
                     conn.set_tls(tls);
                 }
+                if let Some((ref username, ref password, level)) = sign_on {
+                    conn.set_credentials(username, password);
+                    conn.set_password_level(level);
+                }
+                conn.set_tls_options(tls_options);
+                conn.set_proxy(proxy);
+                conn.set_ssh_tunnel(ssh_tunnel);
+                let timeout = session_config.connection_timeout();
+                conn.set_session_config(session_config);
 
                 // Use a bounded timeout for TCP connect + then telnet negotiation handles its own timeouts
-                let timeout = Duration::from_secs(10);
-                conn.connect_with_timeout(timeout)
-                    .map_err(|e| format!("Connection failed: {e}"))?;
+                conn.connect_with_timeout(timeout).map_err(|e| {
+                    Self::record_certificate_prompt(&open_prompt, &e);
+                    format!("Connection failed: {e}")
+                })?;
+                Ok(conn)
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Connection failed: {e}")));
 
+            let connect_result = opened.and_then(|mut conn| {
                 // Validate protocol mode before setting
                 let protocol_mode = protocol.to_protocol_mode();
                 if !TerminalController::validate_protocol_mode(protocol_mode) {
@@ -1409,8 +2130,8 @@ impl AsyncTerminalController {
                     return Err("Connection canceled by user".to_string());
                 }
 
-                // CRITICAL FIX: Safer state update with timeout and better error handling
-                match controller_ref.try_lock() {
+                // The GUI only holds the lock briefly, so waiting for it here is short
+                match controller_ref.lock() {
                     Ok(mut ctrl) => {
                         // Update controller state with established connection
                         ctrl.host = host.clone();
@@ -1455,28 +2176,24 @@ impl AsyncTerminalController {
 
                         Ok(())
                     }
-                    Err(std::sync::TryLockError::Poisoned(_poisoned)) => {
+                    Err(_poisoned) => {
                         eprintln!("SECURITY: Controller mutex poisoned during connection");
                         Err("Controller lock poisoned".to_string())
                     }
-                    Err(std::sync::TryLockError::WouldBlock) => {
-                        eprintln!("SECURITY: Controller lock blocked during connection");
-                        Err("Controller busy".to_string())
-                    }
                 }
-            })();
+            });
 
             // Mark connection attempt finished (success or error)
             connect_flag.store(false, Ordering::SeqCst);
 
             match connect_result {
                 Ok(()) => {
-                    // Enter processing loop similar to start_network_thread
+                    // Process whatever the connection queues until it closes
                     loop {
                         // CRITICAL FIX: Enhanced thread safety with better error handling
                         let mut should_break = false;
 
-                        match controller_ref.try_lock() {
+                        match controller_ref.lock() {
                             Ok(mut ctrl) => {
                                 if ctrl.is_connected() {
                                     // CRITICAL FIX: Handle processing errors gracefully
@@ -1514,24 +2231,21 @@ impl AsyncTerminalController {
                                     should_break = true;
                                 }
                             }
-                            Err(std::sync::TryLockError::Poisoned(_)) => {
+                            Err(_poisoned) => {
                                 eprintln!(
                                     "SECURITY: Controller mutex poisoned in processing thread"
                                 );
                                 should_break = true;
                             }
-                            Err(std::sync::TryLockError::WouldBlock) => {
-                                // Could not lock; fall through to sleep
-                            }
                         }
 
                         // Check cancellation flag
-                        if cancel_flag.load(Ordering::SeqCst) || should_break {
+                        if cancel_flag.load(Ordering::SeqCst)
+                            || should_break
+                            || !Self::wait_for_connection(&controller_ref).await
+                        {
                             break;
                         }
-
-                        // Sleep regardless of processing status to prevent busy waiting
-                        thread::sleep(Duration::from_millis(50));
                     }
                 }
                 Err(e) => {
@@ -1595,55 +2309,18 @@ impl AsyncTerminalController {
 
     fn start_network_thread(&mut self) {
         let controller_ref = Arc::clone(&self.controller);
+        // A connection made here is live until the next disconnect
+        self.cancel_connect_flag.store(false, Ordering::SeqCst);
+        let cancel_flag = Arc::clone(&self.cancel_connect_flag);
 
-        // Stop any existing thread
-        if let Some(_handle) = self.handle.take() {
-            // In a real implementation, we'd have a proper shutdown mechanism
-            // For now, we'll just let the thread finish naturally
-        }
-
-        // Create new thread
-        let handle = thread::spawn(move || {
-            loop {
-                // Check if we should continue running
-                // In a real implementation, we'd need a shared flag for this
-                // For now, we'll just process data if connected
-                let mut lock_acquired = false;
-                let mut retry_count = 0;
-                const MAX_RETRIES: u32 = 3;
-
-                // Retry logic for acquiring the lock
-                while retry_count < MAX_RETRIES {
-                    match controller_ref.try_lock() {
-                        Ok(mut ctrl) => {
-                            if ctrl.is_connected() {
-                                // Process incoming data
-                                let _ = ctrl.process_incoming_data();
-                            } else {
-                                // If not connected, break out of the loop
-                                return;
-                            }
-                            lock_acquired = true;
-                            break;
-                        }
-                        Err(_) => {
-                            retry_count += 1;
-                            if retry_count < MAX_RETRIES {
-                                // Exponential backoff: wait longer between retries
-                                thread::sleep(Duration::from_millis(10 * (1 << retry_count)));
-                            }
-                        }
-                    }
-                }
-
-                // SECURITY: Suppress internal error details that could leak system state
-                if !lock_acquired {
-                    eprintln!("Warning: Internal synchronization issue detected");
-                }
+        // Stop any existing processing task
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
 
-                // Sleep to avoid busy waiting
-                thread::sleep(Duration::from_millis(50));
-            }
+        // Process incoming data on the network runtime until the connection closes
+        let handle = network_actor::runtime().spawn(async move {
+            Self::process_until_lost(&controller_ref, &cancel_flag).await;
         });
 
         self.handle = Some(handle);
@@ -1676,23 +2353,15 @@ impl AsyncTerminalController {
         }
 
         self.running = false;
+        if let Ok(mut progress) = self.reconnect_progress.try_lock() {
+            *progress = None;
+        }
 
-        // CRITICAL FIX: NON-BLOCKING thread cleanup
-        // DO NOT call handle.join() from GUI thread - it blocks!
-        // Instead, detach the thread and let it clean up on its own
+        // CRITICAL FIX: NON-BLOCKING task cleanup
+        // The processing task holds no lock while it waits, so aborting it is safe and
+        // the GUI thread never waits for it
         if let Some(handle) = self.handle.take() {
-            // Spawn a separate cleanup thread that will join the network thread
-            // This way the GUI thread never blocks
-            std::thread::spawn(move || match handle.join() {
-                Ok(_) => {
-                    println!("SECURITY: Background thread terminated cleanly");
-                }
-                Err(e) => {
-                    eprintln!(
-                        "SECURITY WARNING: Background thread panicked during cleanup: {e:?}"
-                    );
-                }
-            });
+            handle.abort();
         }
 
         // CRITICAL FIX: Clear any remaining error state safely - use try_lock to avoid blocking
@@ -1730,6 +2399,26 @@ impl AsyncTerminalController {
         self.connect_in_progress.load(Ordering::SeqCst)
     }
 
+    /// Automatic reconnection under way after the connection was lost (if any)
+    pub fn reconnect_progress(&self) -> Option<ReconnectProgress> {
+        // Use try_lock to avoid blocking the GUI thread - called every frame
+        self.reconnect_progress.try_lock().ok().and_then(|progress| progress.clone())
+    }
+
+    /// Certificate awaiting a trust decision after a failed connect (if any), and clear it
+    pub fn take_certificate_prompt(&self) -> Option<CertificatePrompt> {
+        self.certificate_prompt.try_lock().ok().and_then(|mut prompt| prompt.take())
+    }
+
+    /// Keep an untrusted or changed server certificate for the UI to ask about
+    fn record_certificate_prompt(slot: &Mutex<Option<CertificatePrompt>>, error: &std::io::Error) {
+        if let Some(prompt) = CertificatePrompt::from_io_error(error) {
+            if let Ok(mut pending) = slot.lock() {
+                *pending = Some(prompt);
+            }
+        }
+    }
+
     /// Get the last connect error (if any) and clear it
     pub fn take_last_connect_error(&self) -> Option<String> {
         // Use try_lock to avoid blocking the GUI thread - called every frame
@@ -1759,6 +2448,34 @@ impl AsyncTerminalController {
         }
     }
 
+    /// Revision of the screen history, to tell when it needs fetching again
+    pub fn screen_history_revision(&self) -> Result<u64, String> {
+        // Use try_lock to avoid blocking the GUI thread
+        if let Ok(ctrl) = self.controller.try_lock() {
+            Ok(ctrl.screen_history().revision())
+        } else {
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    /// Copy of the screen history; its snapshots are shared, so no screens are copied under the lock
+    pub fn get_screen_history(&self) -> Result<ScreenHistory, String> {
+        if let Ok(ctrl) = self.controller.try_lock() {
+            Ok(ctrl.screen_history().clone())
+        } else {
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn get_cell_grid(&self) -> Result<CellGrid, String> {
+        // Use try_lock to avoid blocking the GUI thread; the caller keeps its last grid
+        if let Ok(ctrl) = self.controller.try_lock() {
+            Ok(ctrl.cell_grid())
+        } else {
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
     pub fn request_login_screen(&self) -> Result<(), String> {
         self.with_controller_retry(|ctrl| ctrl.request_login_screen())
     }
@@ -1852,6 +2569,140 @@ impl AsyncTerminalController {
         }
     }
 
+    /// Paste text at the cursor; waits briefly for the controller rather than dropping the paste
+    pub fn paste_text(&self, text: &str, lines: LineHandling) -> Result<PasteOutcome, String> {
+        self.with_controller_retry(|ctrl| ctrl.paste_text(text, lines))
+    }
+
+    pub fn toggle_insert_mode(&self) -> Result<bool, String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            Ok(ctrl.toggle_insert_mode())
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn toggle_push_mode(&self) -> Result<bool, String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            Ok(ctrl.toggle_push_mode())
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn toggle_field_reverse(&self) -> Result<bool, String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.toggle_field_reverse()
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn toggle_auto_push(&self) -> Result<bool, String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.toggle_auto_push()
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn toggle_closed_field(&self) -> Result<bool, String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.toggle_closed_field()
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn toggle_screen_reverse(&self) -> Result<TextDirection, String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            Ok(ctrl.toggle_screen_reverse())
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn erase_eof(&self) -> Result<(), String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.erase_eof()
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn erase_input(&self) -> Result<(), String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.erase_input()
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn home(&self) -> Result<(), String> {
+        // Use try_lock to avoid brief GUI freezes during navigation
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.home()
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn field_exit(&self) -> Result<(), String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.field_exit()
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn attn(&self) -> Result<(), String> {
+        self.with_controller_retry(|ctrl| ctrl.attn())
+    }
+
+    pub fn sys_req(&self) -> Result<(), String> {
+        self.with_controller_retry(|ctrl| ctrl.sys_req())
+    }
+
+    pub fn reset_operator_error(&self) -> Result<(), String> {
+        // Use try_lock to avoid brief GUI freezes during input
+        if let Ok(mut ctrl) = self.controller.try_lock() {
+            ctrl.reset_operator_error();
+            Ok(())
+        } else {
+            // Can't get lock - return error but don't block
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
+    pub fn get_oia_state(&self) -> Result<OiaState, String> {
+        // Use try_lock to avoid blocking the GUI thread during rendering
+        if let Ok(ctrl) = self.controller.try_lock() {
+            Ok(ctrl.oia_state())
+        } else {
+            // Can't get lock - caller keeps its last known state
+            Err("Controller busy, try again".to_string())
+        }
+    }
+
     pub fn get_fields_info(&self) -> Result<Vec<crate::field_manager::FieldDisplayInfo>, String> {
         // Use try_lock to avoid blocking the GUI thread
         if let Ok(ctrl) = self.controller.try_lock() {
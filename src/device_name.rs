@@ -0,0 +1,99 @@
+//! Display device names (DEVNAME) for 5250 sessions
+//!
+//! A profile requests its device name with a pattern that is expanded per
+//! session. `%S` is replaced by the session index and `%D` by a counter that
+//! starts at 1 and moves on each time the host rejects the name because a device
+//! of that name is already in use; both take an optional zero-padded width, as in
+//! `QPADEV%02D`. `%%` is a literal percent sign. Patterns are case-insensitive.
+//! A pattern without `%D` names a single device, so a rejection ends the session.
+
+/// Longest IBM i device description name
+pub const MAX_DEVICE_NAME_LEN: usize = 10;
+
+/// Host messages reporting that the requested device name is already in use
+pub const DEVICE_IN_USE_MESSAGES: &[&str] = &["CPF1116"];
+
+/// True when a host screen reports that the requested device name is taken
+pub fn is_device_in_use(screen_text: &str) -> bool {
+    DEVICE_IN_USE_MESSAGES.iter().any(|id| screen_text.contains(id))
+}
+
+/// Check that `name` is a valid device description name
+pub fn validate_device_name(name: &str) -> Result<(), String> {
+    let mut chars = name.chars();
+    match chars.next() {
+        None => return Err("Device name is empty".to_string()),
+        Some(c) if !(c.is_ascii_uppercase() || matches!(c, '$' | '#' | '@')) => {
+            return Err(format!("Device name {name} must start with a letter, $, # or @"));
+        }
+        Some(_) => {}
+    }
+    if name.len() > MAX_DEVICE_NAME_LEN {
+        return Err(format!("Device name {name} is longer than {MAX_DEVICE_NAME_LEN} characters"));
+    }
+    if let Some(c) = chars.find(|&c| !(c.is_ascii_uppercase() || c.is_ascii_digit() || matches!(c, '$' | '#' | '@' | '_' | '.'))) {
+        return Err(format!("Device name {name} contains '{c}'"));
+    }
+    Ok(())
+}
+
+/// Sequence of device names to request for one session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceNames {
+    pattern: String,
+    session_index: u32,
+    counter: u32,
+    current: String,
+}
+
+impl DeviceNames {
+    /// Names for session `session_index` (1-based) from a pattern such as `QPADEV%02D` or `USER%S`
+    pub fn new(pattern: &str, session_index: u32) -> Result<Self, String> {
+        let pattern = pattern.trim().to_uppercase();
+        let current = expand(&pattern, session_index, 1)?;
+        validate_device_name(&current)?;
+        Ok(Self { pattern, session_index, counter: 1, current })
+    }
+
+    /// Name to request now
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Move on to the next name after the host rejected the current one; None when there is none
+    pub fn advance(&mut self) -> Option<&str> {
+        let next = expand(&self.pattern, self.session_index, self.counter + 1).ok()?;
+        if next == self.current || validate_device_name(&next).is_err() {
+            return None;
+        }
+        self.counter += 1;
+        self.current = next;
+        Some(&self.current)
+    }
+}
+
+/// Expand `%S`, `%D` (with optional width) and `%%` in an uppercased pattern
+fn expand(pattern: &str, session_index: u32, counter: u32) -> Result<String, String> {
+    let mut name = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            name.push(c);
+            continue;
+        }
+        let mut width = String::new();
+        while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+            width.push(d);
+            chars.next();
+        }
+        let width: usize = width.parse().unwrap_or(0);
+        match chars.next() {
+            Some('%') if width == 0 => name.push('%'),
+            Some('S') => name.push_str(&format!("{session_index:0width$}")),
+            Some('D') => name.push_str(&format!("{counter:0width$}")),
+            Some(other) => return Err(format!("Unknown placeholder %{other} in device name pattern")),
+            None => return Err("Device name pattern ends with %".to_string()),
+        }
+    }
+    Ok(name)
+}
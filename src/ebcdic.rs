@@ -58,6 +58,447 @@ pub fn ebcdic_to_ascii(ebcdic_byte: u8) -> char {
     EBCDIC_CP037_TO_ASCII[ebcdic_byte as usize]
 }
 
+/// Find the EBCDIC byte that `ebcdic_to_ascii` translates to `ch`
+///
+/// The CP037 mapping is preferred; otherwise the first matching table entry
+/// is returned so that translating the result back always yields `ch`.
+pub fn ascii_to_ebcdic(ch: char) -> Option<u8> {
+    let preferred = crate::protocol_common::ebcdic::ascii_to_ebcdic(ch);
+    if ebcdic_to_ascii(preferred) == ch {
+        return Some(preferred);
+    }
+    (0..=255u8).find(|&byte| ebcdic_to_ascii(byte) == ch)
+}
+
+/// EBCDIC CP037 (US/Canada) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP037_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\u{00E7}', '\u{00F1}', '\u{00A2}', '.', '<', '(', '+', '|',
+    '&', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', '!', '$', '*', ')', ';', '\u{00AC}',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '\u{00D1}', '\u{00A6}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '`', ':', '#', '@', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', '\u{00A4}',
+    '\u{00B5}', '~', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '^', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '\u{00A7}', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '[', ']', '\u{00AF}', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '\u{00FC}', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\\', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\u{00D6}', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP273 (Germany/Austria) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP273_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '{', '\u{00E0}', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\u{00E7}', '\u{00F1}', '\u{00C4}', '.', '<', '(', '+', '!',
+    '&', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '~', '\u{00DC}', '$', '*', ')', ';', '^',
+    '-', '/', '\u{00C2}', '[', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '\u{00D1}', '\u{00F6}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '`', ':', '#', '\u{00A7}', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', '\u{00A4}',
+    '\u{00B5}', '\u{00DF}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '@', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '\u{00AC}', '|', '\u{00AF}', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '\u{00E4}', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00A6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '\u{00FC}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '}', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\u{00D6}', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\\', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', ']', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP277 (Denmark/Norway) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP277_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E1}', '\u{00E3}', '}',
+    '\u{00E7}', '\u{00F1}', '#', '.', '<', '(', '+', '!',
+    '&', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', '\u{00A4}', '\u{00C5}', '*', ')', ';', '^',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '$',
+    '\u{00C7}', '\u{00D1}', '\u{00F8}', ',', '%', '_', '>', '?',
+    '\u{00A6}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '`', ':', '\u{00C6}', '\u{00D8}', '\'', '=', '"',
+    '@', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '{', '\u{00B8}', '[', ']',
+    '\u{00B5}', '\u{00FC}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '\u{00A7}', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '\u{00AC}', '|', '\u{00AF}', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '\u{00E6}', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '\u{00E5}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '~', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\\', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\u{00D6}', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP278 (Finland/Sweden) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP278_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '{', '\u{00E0}', '\u{00E1}', '\u{00E3}', '}',
+    '\u{00E7}', '\u{00F1}', '\u{00A7}', '.', '<', '(', '+', '!',
+    '&', '`', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', '\u{00A4}', '\u{00C5}', '*', ')', ';', '^',
+    '-', '/', '\u{00C2}', '#', '\u{00C0}', '\u{00C1}', '\u{00C3}', '$',
+    '\u{00C7}', '\u{00D1}', '\u{00F6}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '\u{00E9}', ':', '\u{00C4}', '\u{00D6}', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', ']',
+    '\u{00B5}', '\u{00FC}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '[', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '\u{00AC}', '|', '\u{00AF}', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '\u{00E4}', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00A6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '\u{00E5}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '~', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\\', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '@', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP280 (Italy) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP280_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '{', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\\', '\u{00F1}', '\u{00B0}', '.', '<', '(', '+', '!',
+    '&', ']', '\u{00EA}', '\u{00EB}', '}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '~', '\u{00DF}', '\u{00E9}', '$', '*', ')', ';', '^',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '\u{00D1}', '\u{00F2}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '\u{00F9}', ':', '\u{00A3}', '\u{00A7}', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '[', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', '\u{00A4}',
+    '\u{00B5}', '\u{00EC}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '#', '\u{00A5}', '\u{00B7}', '\u{00A9}', '@', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '\u{00AC}', '|', '\u{00AF}', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '\u{00E0}', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00F6}', '\u{00A6}', '\u{00F3}', '\u{00F5}',
+    '\u{00E8}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '\u{00FC}', '`', '\u{00FA}', '\u{00FF}',
+    '\u{00E7}', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\u{00D6}', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP284 (Spain/Latin America) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP284_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\u{00E7}', '\u{00A6}', '[', '.', '<', '(', '+', '|',
+    '&', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', ']', '$', '*', ')', ';', '\u{00AC}',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '#', '\u{00F1}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '`', ':', '\u{00D1}', '@', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', '\u{00A4}',
+    '\u{00B5}', '\u{00A8}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '\u{00A7}', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '^', '!', '\u{00AF}', '~', '\u{00B4}', '\u{00D7}',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '\u{00FC}', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\\', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\u{00D6}', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP285 (United Kingdom) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP285_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\u{00E7}', '\u{00F1}', '$', '.', '<', '(', '+', '|',
+    '&', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', '!', '\u{00A3}', '*', ')', ';', '\u{00AC}',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '\u{00D1}', '\u{00A6}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '`', ':', '#', '@', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', '\u{00A4}',
+    '\u{00B5}', '\u{203E}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '[', '\u{00A5}', '\u{00B7}', '\u{00A9}', '\u{00A7}', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '^', ']', '~', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '\u{00FC}', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\\', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\u{00D6}', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP297 (France) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP297_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '@', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\\', '\u{00F1}', '\u{00B0}', '.', '<', '(', '+', '!',
+    '&', '{', '\u{00EA}', '\u{00EB}', '}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', '\u{00A7}', '$', '*', ')', ';', '^',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '\u{00D1}', '\u{00F9}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '\u{00B5}', ':', '\u{00A3}', '\u{00E0}', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '[', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', '\u{00A4}',
+    '`', '\u{00A8}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '#', '\u{00A5}', '\u{00B7}', '\u{00A9}', ']', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '\u{00AC}', '|', '\u{00AF}', '~', '\u{00B4}', '\u{00D7}',
+    '\u{00E9}', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '\u{00E8}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '\u{00FC}', '\u{00A6}', '\u{00FA}', '\u{00FF}',
+    '\u{00E7}', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\u{00D6}', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP500 (International) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP500_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\u{00E7}', '\u{00F1}', '[', '.', '<', '(', '+', '!',
+    '&', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', ']', '$', '*', ')', ';', '^',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '\u{00D1}', '\u{00A6}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '`', ':', '#', '@', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '\u{00F0}', '\u{00FD}', '\u{00FE}', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '\u{00E6}', '\u{00B8}', '\u{00C6}', '\u{00A4}',
+    '\u{00B5}', '~', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '\u{00D0}', '\u{00DD}', '\u{00DE}', '\u{00AE}',
+    '\u{00A2}', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '\u{00A7}', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '\u{00AC}', '|', '\u{00AF}', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '\u{00F6}', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '\u{00FC}', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\\', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '\u{00D6}', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP871 (Iceland) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP871_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{00E2}', '\u{00E4}', '\u{00E0}', '\u{00E1}', '\u{00E3}', '\u{00E5}',
+    '\u{00E7}', '\u{00F1}', '\u{00FE}', '.', '<', '(', '+', '!',
+    '&', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00E8}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00EC}', '\u{00DF}', '\u{00C6}', '$', '*', ')', ';', '\u{00D6}',
+    '-', '/', '\u{00C2}', '\u{00C4}', '\u{00C0}', '\u{00C1}', '\u{00C3}', '\u{00C5}',
+    '\u{00C7}', '\u{00D1}', '\u{00A6}', ',', '%', '_', '>', '?',
+    '\u{00F8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00C8}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00CC}', '\u{00F0}', ':', '#', '\u{00D0}', '\'', '=', '"',
+    '\u{00D8}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', '`', '\u{00FD}', '{', '\u{00B1}',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{00AA}', '\u{00BA}', '}', '\u{00B8}', ']', '\u{00A4}',
+    '\u{00B5}', '\u{00F6}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{00A1}', '\u{00BF}', '@', '\u{00DD}', '[', '\u{00AE}',
+    '\u{00A2}', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '\u{00A7}', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '\u{00AC}', '|', '\u{00AF}', '\u{00A8}', '\\', '\u{00D7}',
+    '\u{00DE}', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{00F4}', '~', '\u{00F2}', '\u{00F3}', '\u{00F5}',
+    '\u{00E6}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', '\u{00FB}', '\u{00FC}', '\u{00F9}', '\u{00FA}', '\u{00FF}',
+    '\u{00B4}', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', '\u{00D4}', '^', '\u{00D2}', '\u{00D3}', '\u{00D5}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', '\u{00DB}', '\u{00DC}', '\u{00D9}', '\u{00DA}', ' ',
+];
+
+/// EBCDIC CP420 (Arabic) to Unicode for bytes 0x40-0xFF
+///
+/// Arabic letters map to their Unicode presentation forms as shaped by the host.
+pub const EBCDIC_CP420_HIGH: [char; 192] = [
+    ' ', '\u{00A0}', '\u{0651}', '\u{FE7D}', '\u{0640}', ' ', '\u{0621}', '\u{0622}',
+    '\u{FE82}', '\u{0623}', '\u{00A2}', '.', '<', '(', '+', '|',
+    '&', '\u{FE84}', '\u{0624}', ' ', ' ', '\u{0626}', '\u{0627}', '\u{FE8E}',
+    '\u{0628}', '\u{FE91}', '!', '$', '*', ')', ';', '\u{00AC}',
+    '-', '/', '\u{0629}', '\u{062A}', '\u{FE97}', '\u{062B}', '\u{FE9B}', '\u{062C}',
+    '\u{FE9F}', '\u{062D}', '\u{00A6}', ',', '%', '_', '>', '?',
+    '\u{FEA3}', '\u{062E}', '\u{FEA7}', '\u{062F}', '\u{0630}', '\u{0631}', '\u{0632}', '\u{0633}',
+    '\u{FEB3}', '\u{060C}', ':', '#', '@', '\'', '=', '"',
+    '\u{0634}', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{FEB7}', '\u{0635}', '\u{FEBB}', '\u{0636}', '\u{FEBF}', '\u{0637}',
+    '\u{0638}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', '\u{0639}', '\u{FECA}', '\u{FECB}', '\u{FECC}', '\u{063A}', '\u{FECE}',
+    '\u{FECF}', '\u{00F7}', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', '\u{FED0}', '\u{0641}', '\u{FED3}', '\u{0642}', '\u{FED7}', '\u{0643}',
+    '\u{FEDB}', '\u{0644}', '\u{FEF5}', '\u{FEF6}', '\u{FEF7}', '\u{FEF8}', ' ', ' ',
+    '\u{FEFB}', '\u{FEFC}', '\u{FEDF}', '\u{0645}', '\u{FEE3}', '\u{0646}', '\u{FEE7}', '\u{0647}',
+    '\u{061B}', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', '\u{FEEB}', ' ', '\u{FEEC}', ' ', '\u{0648}',
+    '\u{061F}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{0649}', '\u{FEF0}', '\u{064A}', '\u{FEF2}', '\u{FEF3}', '\u{0660}',
+    '\u{00D7}', ' ', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{0661}', '\u{0662}', ' ', '\u{0663}', '\u{0664}', '\u{0665}',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', ' ', '\u{0666}', '\u{0667}', '\u{0668}', '\u{0669}', ' ',
+];
+
+/// EBCDIC CP424 (Hebrew) to Unicode for bytes 0x40-0xFF
+pub const EBCDIC_CP424_HIGH: [char; 192] = [
+    ' ', '\u{05D0}', '\u{05D1}', '\u{05D2}', '\u{05D3}', '\u{05D4}', '\u{05D5}', '\u{05D6}',
+    '\u{05D7}', '\u{05D8}', '\u{00A2}', '.', '<', '(', '+', '|',
+    '&', '\u{05D9}', '\u{05DA}', '\u{05DB}', '\u{05DC}', '\u{05DD}', '\u{05DE}', '\u{05DF}',
+    '\u{05E0}', '\u{05E1}', '!', '$', '*', ')', ';', '\u{00AC}',
+    '-', '/', '\u{05E2}', '\u{05E3}', '\u{05E4}', '\u{05E5}', '\u{05E6}', '\u{05E7}',
+    '\u{05E8}', '\u{05E9}', '\u{00A6}', ',', '%', '_', '>', '?',
+    ' ', '\u{05EA}', ' ', ' ', '\u{00A0}', ' ', ' ', ' ',
+    '\u{21D4}', '`', ':', '#', '@', '\'', '=', '"',
+    ' ', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
+    'h', 'i', '\u{00AB}', '\u{00BB}', ' ', ' ', ' ', ' ',
+    '\u{00B0}', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
+    'q', 'r', ' ', ' ', ' ', '\u{00B8}', ' ', '\u{00A4}',
+    '\u{00B5}', '~', 's', 't', 'u', 'v', 'w', 'x',
+    'y', 'z', ' ', ' ', ' ', ' ', ' ', '\u{00AE}',
+    '^', '\u{00A3}', '\u{00A5}', '\u{00B7}', '\u{00A9}', '\u{00A7}', '\u{00B6}', '\u{00BC}',
+    '\u{00BD}', '\u{00BE}', '[', ']', '\u{00AF}', '\u{00A8}', '\u{00B4}', '\u{00D7}',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'I', '\u{00AD}', ' ', ' ', ' ', ' ', ' ',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P',
+    'Q', 'R', '\u{00B9}', ' ', ' ', ' ', ' ', ' ',
+    '\\', '\u{00F7}', 'S', 'T', 'U', 'V', 'W', 'X',
+    'Y', 'Z', '\u{00B2}', ' ', ' ', ' ', ' ', ' ',
+    '0', '1', '2', '3', '4', '5', '6', '7',
+    '8', '9', '\u{00B3}', ' ', ' ', ' ', ' ', ' ',
+];
+
+/// Single-byte Latin-1 code pages and their tables
+const LATIN1_CODE_PAGES: [(u16, &[char; 192]); 10] = [
+    (37, &EBCDIC_CP037_HIGH),
+    (273, &EBCDIC_CP273_HIGH),
+    (277, &EBCDIC_CP277_HIGH),
+    (278, &EBCDIC_CP278_HIGH),
+    (280, &EBCDIC_CP280_HIGH),
+    (284, &EBCDIC_CP284_HIGH),
+    (285, &EBCDIC_CP285_HIGH),
+    (297, &EBCDIC_CP297_HIGH),
+    (500, &EBCDIC_CP500_HIGH),
+    (871, &EBCDIC_CP871_HIGH),
+];
+
+/// Euro CCSIDs: the base code page with the euro sign in place of the currency sign at the given byte
+const EURO_CODE_PAGES: [(u16, u16, u8); 10] = [
+    (1140, 37, 0x9F),
+    (1141, 273, 0x9F),
+    (1142, 277, 0x5A),
+    (1143, 278, 0x5A),
+    (1144, 280, 0x9F),
+    (1145, 284, 0x9F),
+    (1146, 285, 0x9F),
+    (1147, 297, 0x9F),
+    (1148, 500, 0x9F),
+    (1149, 871, 0x9F),
+];
+
+/// Table for a Latin-1 code page and, for euro CCSIDs, the byte holding the euro sign
+fn latin1_table(code_page: u16) -> Option<(&'static [char; 192], Option<u8>)> {
+    let (base, euro_byte) = EURO_CODE_PAGES
+        .iter()
+        .find(|(ccsid, _, _)| *ccsid == code_page)
+        .map_or((code_page, None), |&(_, base, byte)| (base, Some(byte)));
+    LATIN1_CODE_PAGES
+        .iter()
+        .find(|(ccsid, _)| *ccsid == base)
+        .map(|&(_, table)| (table, euro_byte))
+}
+
+/// True for code pages with their own translation table
+pub fn is_supported_code_page(code_page: u16) -> bool {
+    matches!(code_page, 420 | 424) || latin1_table(code_page).is_some()
+}
+
+/// Convert an EBCDIC byte using the session code page
+///
+/// The Latin-1 code pages (37, 273, 277, 278, 280, 284, 285, 297, 500, 871 and
+/// their euro variants 1140-1149) and the bidirectional code pages 420 and 424
+/// have their own tables; any other code page uses the CP037 table above.
+/// Control bytes below 0x40 are shared by all of them.
+pub fn ebcdic_to_char(ebcdic_byte: u8, code_page: u16) -> char {
+    match (code_page, ebcdic_byte) {
+        (420, 0x40..=0xFF) => EBCDIC_CP420_HIGH[(ebcdic_byte - 0x40) as usize],
+        (424, 0x40..=0xFF) => EBCDIC_CP424_HIGH[(ebcdic_byte - 0x40) as usize],
+        (_, 0x40..=0xFF) => match latin1_table(code_page) {
+            Some((_, Some(euro_byte))) if euro_byte == ebcdic_byte => '\u{20AC}',
+            Some((table, _)) => table[(ebcdic_byte - 0x40) as usize],
+            None => ebcdic_to_ascii(ebcdic_byte),
+        },
+        _ => ebcdic_to_ascii(ebcdic_byte),
+    }
+}
+
+/// Find the EBCDIC byte that `ebcdic_to_char` translates to `ch` in `code_page`
+///
+/// None means the code page cannot encode `ch`.
+pub fn char_to_ebcdic(ch: char, code_page: u16) -> Option<u8> {
+    let (table, euro_byte) = match code_page {
+        420 => (&EBCDIC_CP420_HIGH, None),
+        424 => (&EBCDIC_CP424_HIGH, None),
+        _ => match latin1_table(code_page) {
+            Some(found) => found,
+            None => return ascii_to_ebcdic(ch),
+        },
+    };
+    if ch == ' ' {
+        return Some(0x40);
+    }
+    if let Some(euro_byte) = euro_byte {
+        if ch == '\u{20AC}' {
+            return Some(euro_byte);
+        }
+    }
+    table.iter()
+        .enumerate()
+        .find(|&(idx, &c)| c == ch && Some(idx as u8 + 0x40) != euro_byte)
+        .map(|(idx, _)| idx as u8 + 0x40)
+        .or_else(|| ascii_to_ebcdic(ch).filter(|&byte| byte < 0x40))
+}
+
 /// Convert an EBCDIC byte slice to an ASCII string
 /// 
 /// # Arguments
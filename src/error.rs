@@ -7,6 +7,8 @@ use std::fmt;
 use std::io;
 use std::error::Error as StdError;
 
+use crate::known_hosts::CertificateInfo;
+
 /// Top-level error type for TN5250R operations
 #[derive(Debug)]
 pub enum TN5250Error {
@@ -29,7 +31,7 @@ pub enum TN5250Error {
 }
 
 /// Network connection related errors
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum NetworkError {
     /// Connection refused by remote host
     ConnectionRefused { host: String, port: u16 },
@@ -45,6 +47,40 @@ pub enum NetworkError {
     InvalidAddress { address: String },
     /// SSL/TLS errors for secure connections
     SslError { message: String },
+    /// Client certificate or private key could not be loaded
+    TlsClientCertificate { path: String, reason: String },
+    /// Server rejected the client certificate
+    TlsClientCertificateRejected { host: String, reason: String },
+    /// Server does not support the configured minimum TLS version
+    TlsVersionUnsupported { host: String, minimum: String },
+    /// Server certificate failed validation
+    TlsCertificateInvalid { host: String, reason: String },
+    /// Configured certificate pin is not a SHA-256 hash
+    InvalidCertificatePin { pin: String },
+    /// Server public key does not match the configured pin
+    CertificatePinMismatch { host: String, expected: String, actual: String },
+    /// TLS handshake failed for another reason
+    TlsHandshake { host: String, reason: String },
+    /// Server certificate is not CA-trusted and has not been accepted by the user
+    UntrustedCertificate { certificate: Box<CertificateInfo> },
+    /// Server certificate differs from the one previously trusted for this host
+    CertificateChanged { known_fingerprint: String, certificate: Box<CertificateInfo> },
+    /// Server would not upgrade the telnet connection with START_TLS
+    StartTlsRefused { host: String },
+    /// Proxy could not be reached or did not speak the expected protocol
+    ProxyConnectFailed { proxy: String, reason: String },
+    /// Proxy rejected the configured credentials
+    ProxyAuthenticationFailed { proxy: String },
+    /// Proxy could not open a tunnel to the host
+    ProxyTargetRefused { proxy: String, target: String, reason: String },
+    /// SSH server key is not in known_hosts
+    SshHostKeyUnknown { host: String, fingerprint: String },
+    /// SSH server key differs from the one recorded in known_hosts
+    SshHostKeyChanged { host: String, fingerprint: String },
+    /// SSH server rejected the configured credentials
+    SshAuthenticationFailed { host: String, username: String },
+    /// SSH session or port forward could not be established
+    SshTunnel { host: String, reason: String },
 }
 
 /// Telnet protocol negotiation errors
@@ -207,6 +243,41 @@ impl fmt::Display for NetworkError {
                 write!(f, "Invalid network address: {address}"),
             NetworkError::SslError { message } => 
                 write!(f, "SSL/TLS error: {message}"),
+            NetworkError::TlsClientCertificate { path, reason } => 
+                write!(f, "Cannot load client certificate {path}: {reason}"),
+            NetworkError::TlsClientCertificateRejected { host, reason } => 
+                write!(f, "{host} rejected the client certificate: {reason}"),
+            NetworkError::TlsVersionUnsupported { host, minimum } => 
+                write!(f, "{host} does not support {minimum} or later"),
+            NetworkError::TlsCertificateInvalid { host, reason } => 
+                write!(f, "Certificate for {host} is not valid: {reason}"),
+            NetworkError::InvalidCertificatePin { pin } => 
+                write!(f, "Invalid certificate pin '{pin}': expected a SHA-256 hash in base64 or hex"),
+            NetworkError::CertificatePinMismatch { host, expected, actual } => 
+                write!(f, "Certificate pin mismatch for {host}: expected {expected}, got {actual}"),
+            NetworkError::TlsHandshake { host, reason } => 
+                write!(f, "TLS handshake with {host} failed: {reason}"),
+            NetworkError::UntrustedCertificate { certificate } => 
+                write!(f, "Untrusted certificate for {} (SHA-256 {})", certificate.host, certificate.fingerprint),
+            NetworkError::CertificateChanged { known_fingerprint, certificate } => 
+                write!(f, "WARNING: certificate for {} has changed (was {known_fingerprint}, now {})",
+                    certificate.host, certificate.fingerprint),
+            NetworkError::StartTlsRefused { host } => 
+                write!(f, "{host} did not agree to telnet START_TLS"),
+            NetworkError::ProxyConnectFailed { proxy, reason } => 
+                write!(f, "Cannot use proxy {proxy}: {reason}"),
+            NetworkError::ProxyAuthenticationFailed { proxy } => 
+                write!(f, "Proxy {proxy} rejected the credentials"),
+            NetworkError::ProxyTargetRefused { proxy, target, reason } => 
+                write!(f, "Proxy {proxy} could not connect to {target}: {reason}"),
+            NetworkError::SshHostKeyUnknown { host, fingerprint } => 
+                write!(f, "SSH host key for {host} is not known ({fingerprint})"),
+            NetworkError::SshHostKeyChanged { host, fingerprint } => 
+                write!(f, "WARNING: SSH host key for {host} has changed (now {fingerprint})"),
+            NetworkError::SshAuthenticationFailed { host, username } => 
+                write!(f, "SSH server {host} rejected the credentials for {username}"),
+            NetworkError::SshTunnel { host, reason } => 
+                write!(f, "SSH tunnel through {host} failed: {reason}"),
         }
     }
 }
@@ -313,6 +313,14 @@ impl RetryPolicy {
         }
     }
 
+    /// Override the delay schedule: `base_delay` before the first retry, growing by `backoff_multiplier` up to `max_delay`
+    pub fn with_backoff(mut self, base_delay: Duration, max_delay: Duration, backoff_multiplier: f64) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self.backoff_multiplier = backoff_multiplier;
+        self
+    }
+
     /// Calculate delay for given attempt number
     pub fn get_delay(&self, attempt: usize) -> Duration {
         let delay_ms = (self.base_delay.as_millis() as f64 
@@ -565,6 +573,42 @@ pub fn sanitize_error(error: &TN5250Error) -> SanitizedError {
                     "NET004".to_string(),
                 )
             },
+            NetworkError::SslError { .. }
+            | NetworkError::TlsClientCertificate { .. }
+            | NetworkError::TlsClientCertificateRejected { .. }
+            | NetworkError::TlsVersionUnsupported { .. }
+            | NetworkError::TlsCertificateInvalid { .. }
+            | NetworkError::InvalidCertificatePin { .. }
+            | NetworkError::CertificatePinMismatch { .. }
+            | NetworkError::TlsHandshake { .. }
+            | NetworkError::UntrustedCertificate { .. }
+            | NetworkError::CertificateChanged { .. }
+            | NetworkError::StartTlsRefused { .. } => {
+                SanitizedError::new(
+                    ErrorCategory::Network,
+                    "Secure connection failed".to_string(),
+                    "NET005".to_string(),
+                )
+            },
+            NetworkError::ProxyConnectFailed { .. }
+            | NetworkError::ProxyAuthenticationFailed { .. }
+            | NetworkError::ProxyTargetRefused { .. } => {
+                SanitizedError::new(
+                    ErrorCategory::Network,
+                    "Proxy connection failed".to_string(),
+                    "NET006".to_string(),
+                )
+            },
+            NetworkError::SshHostKeyUnknown { .. }
+            | NetworkError::SshHostKeyChanged { .. }
+            | NetworkError::SshAuthenticationFailed { .. }
+            | NetworkError::SshTunnel { .. } => {
+                SanitizedError::new(
+                    ErrorCategory::Network,
+                    "SSH tunnel failed".to_string(),
+                    "NET007".to_string(),
+                )
+            },
             _ => SanitizedError::new(
                 ErrorCategory::Network,
                 "Network error occurred".to_string(),
@@ -6,6 +6,8 @@ pub struct FieldDisplayInfo {
     pub is_active: bool,
     pub error_state: Option<FieldError>,
     pub highlighted: bool,
+    /// The modified data tag is set
+    pub modified: bool,
     pub start_row: usize,
     pub start_col: usize,
     pub length: usize,
@@ -16,7 +18,8 @@ pub struct FieldDisplayInfo {
 // This module provides functionality for detecting, navigating, and managing
 // input fields in AS/400 terminal screens.
 
-use crate::terminal::TerminalScreen;
+use crate::clipboard::{LineHandling, PasteOutcome};
+use crate::terminal::{TerminalScreen, TextDirection};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -65,12 +68,22 @@ pub struct FieldBehavior {
     pub right_adjust: bool,
     /// Fill with zeros vs spaces
     pub zero_fill: bool,
+    /// Mandatory fill - the field must be left empty or completely filled
+    pub mandatory_fill: bool,
     /// Auto-convert to uppercase
     pub uppercase_convert: bool,
     /// Allow duplicate field operation
     pub dup_enabled: bool,
     /// Custom next field ID for progression
     pub cursor_progression: Option<usize>,
+    /// Wrap whole words across the segments of a continued field
+    pub word_wrap: bool,
+    /// Field reverse: the cursor moves against the screen direction
+    pub reverse: bool,
+    /// Enter opposite-direction text in push mode automatically
+    pub auto_push: bool,
+    /// Closed field: data enters at the trailing edge and shifts toward the start
+    pub closed: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -87,6 +100,7 @@ pub enum FieldError {
     FieldExitRequired,
     FieldExitInvalid,
     MandatoryEnter,
+    MandatoryFill,
     FieldFull,
     NoRoomForInsert,
     
@@ -108,6 +122,7 @@ impl FieldError {
             FieldError::FieldExitRequired => "Use Field Exit key to leave field",
             FieldError::FieldExitInvalid => "Field Exit not allowed here",
             FieldError::MandatoryEnter => "Required field must be filled",
+            FieldError::MandatoryFill => "Field must be filled completely or left empty",
             FieldError::FieldFull => "Field is full",
             FieldError::NoRoomForInsert => "No room to insert character",
             FieldError::NoActiveField => "No field is currently active",
@@ -115,6 +130,38 @@ impl FieldError {
             FieldError::InvalidFieldNavigation => "Invalid field navigation",
         }
     }
+
+    /// Get the 5250 operator error code shown in the OIA for this error, if any
+    pub fn operator_error_code(&self) -> Option<u8> {
+        use crate::lib5250::codes::*;
+        match self {
+            FieldError::CursorProtected => Some(ERR_NO_FIELD),
+            FieldError::NumericOnly => Some(ERR_NUMERIC_ONLY),
+            FieldError::AlphaOnly => Some(ERR_ALPHA_ONLY),
+            FieldError::DigitsOnly => Some(ERR_DIGITS_ONLY),
+            FieldError::InvalidCharacter(_) => Some(ERR_BAD_CHAR),
+            FieldError::InvalidSignPosition => Some(ERR_LAST_SIGNED),
+            FieldError::FieldExitRequired | FieldError::FieldExitInvalid => Some(ERR_EXIT_NOT_VALID),
+            FieldError::MandatoryEnter => Some(ERR_MANDATORY_ENTRY),
+            FieldError::MandatoryFill => Some(ERR_MANADATORY_FILL),
+            FieldError::FieldFull | FieldError::NoRoomForInsert => Some(ERR_NO_ROOM),
+            FieldError::NoActiveField
+            | FieldError::FieldNotFound(_)
+            | FieldError::InvalidFieldNavigation => None,
+        }
+    }
+
+    /// Format this error as an OIA operator error line (e.g. "0012 No room to insert data.")
+    pub fn operator_message(&self) -> String {
+        let text = self
+            .operator_error_code()
+            .and_then(crate::lib5250::codes::get_error_message)
+            .unwrap_or_else(|| self.get_user_message());
+        match self.operator_error_code() {
+            Some(code) => format!("{code:04X} {text}"),
+            None => text.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -212,10 +259,22 @@ impl Field {
     
     /// Get cursor position within the field (0-based offset)
     pub fn get_cursor_offset(&self, row: usize, col: usize) -> Option<usize> {
-        if self.contains_position(row, col) {
+        if !self.contains_position(row, col) {
+            None
+        } else if self.behavior.reverse {
+            Some(self.start_col + self.length - 1 - col)
+        } else {
             Some(col - self.start_col)
+        }
+    }
+
+    /// Screen column (1-based) of edit offset `offset`; a reversed field fills from its far end
+    pub fn column_for_offset(&self, offset: usize) -> usize {
+        let offset = offset.min(self.length.saturating_sub(1));
+        if self.behavior.reverse {
+            self.start_col + self.length.saturating_sub(1) - offset
         } else {
-            None
+            self.start_col + offset
         }
     }
     
@@ -246,22 +305,37 @@ impl Field {
             return Err(error);
         }
 
+        // Work in characters so multi-byte (e.g. Hebrew/Arabic) data shifts correctly
+        let mut chars: Vec<char> = self.content.chars().collect();
+
+        // Insert mode shifts data right; a trailing blank may be pushed off the end,
+        // but an occupied last position means there is no room to insert
+        if offset < chars.len() && chars.len() >= self.max_length {
+            if chars.last() == Some(&' ') {
+                chars.pop();
+            } else {
+                let error = FieldError::NoRoomForInsert;
+                self.set_error(error.clone());
+                return Err(error);
+            }
+        }
+
         // Check length limits with safety margin
-        if self.content.len() >= self.max_length {
+        if chars.len() >= self.max_length {
             let error = FieldError::FieldFull;
             self.set_error(error.clone());
             return Err(error);
         }
 
         // Check if there's room to insert (enhanced validation)
-        if offset > self.content.len() {
+        if offset > chars.len() {
             let error = FieldError::NoRoomForInsert;
             self.set_error(error.clone());
             return Err(error);
         }
 
         // CRITICAL FIX: Additional validation for edge cases
-        if self.content.len() + 1 > self.max_length {
+        if chars.len() + 1 > self.max_length {
             let error = FieldError::FieldFull;
             self.set_error(error.clone());
             return Err(error);
@@ -271,32 +345,86 @@ impl Field {
         let sanitized_ch = self.sanitize_character(ch);
 
         // Insert character at the specified offset
-        self.content.insert(offset, sanitized_ch);
-        self.modified = true;
+        chars.insert(offset, sanitized_ch);
 
         // Apply transformations if needed (with bounds checking)
-        if (self.field_type == FieldType::UppercaseOnly || self.behavior.uppercase_convert)
-            && offset < self.content.len() {
-                if let Some(last_char) = self.content.chars().nth(offset) {
-                    let upper_char = last_char.to_uppercase().collect::<String>();
-                    if upper_char.len() == 1 && upper_char != last_char.to_string() {
-                        self.content.remove(offset);
-                        self.content.insert_str(offset, &upper_char);
-                    }
-                }
+        if self.field_type == FieldType::UppercaseOnly || self.behavior.uppercase_convert {
+            let mut upper = sanitized_ch.to_uppercase();
+            if let (Some(upper_char), None) = (upper.next(), upper.next()) {
+                chars[offset] = upper_char;
             }
+        }
+
+        self.content = chars.into_iter().collect();
+        self.modified = true;
 
         Ok(true)
     }
-    
+
+    /// Overwrite character at offset (overstrike mode), padding with blanks if needed
+    pub fn overwrite_char(&mut self, ch: char, offset: usize) -> Result<bool, FieldError> {
+        self.clear_error();
+
+        if !self.is_character_safe(ch) {
+            let error = FieldError::InvalidCharacter(ch);
+            self.set_error(error.clone());
+            return Err(error);
+        }
+
+        if let Err(error) = self.validate_character(ch) {
+            self.set_error(error.clone());
+            return Err(error);
+        }
+
+        if offset >= self.max_length {
+            let error = FieldError::FieldFull;
+            self.set_error(error.clone());
+            return Err(error);
+        }
+
+        let mut ch = self.sanitize_character(ch);
+        if self.field_type == FieldType::UppercaseOnly || self.behavior.uppercase_convert {
+            ch = ch.to_ascii_uppercase();
+        }
+
+        let mut chars: Vec<char> = self.content.chars().collect();
+        while chars.len() < offset {
+            chars.push(' ');
+        }
+        if offset < chars.len() {
+            chars[offset] = ch;
+        } else {
+            chars.push(ch);
+        }
+        self.content = chars.into_iter().collect();
+        self.modified = true;
+
+        Ok(true)
+    }
+
+    /// Erase from offset to the end of the field (Erase EOF)
+    pub fn erase_to_end(&mut self, offset: usize) -> bool {
+        if self.field_type == FieldType::Protected {
+            return false;
+        }
+
+        if offset < self.content.chars().count() {
+            self.content = self.content.chars().take(offset).collect();
+            self.modified = true;
+        }
+        true
+    }
+
     /// Delete character at offset
     pub fn delete_char(&mut self, offset: usize) -> bool {
         if self.field_type == FieldType::Protected {
             return false;
         }
         
-        if offset < self.content.len() {
-            self.content.remove(offset);
+        let mut chars: Vec<char> = self.content.chars().collect();
+        if offset < chars.len() {
+            chars.remove(offset);
+            self.content = chars.into_iter().collect();
             true
         } else {
             false
@@ -309,8 +437,10 @@ impl Field {
             return false;
         }
         
-        if offset > 0 && offset <= self.content.len() {
-            self.content.remove(offset - 1);
+        let mut chars: Vec<char> = self.content.chars().collect();
+        if offset > 0 && offset <= chars.len() {
+            chars.remove(offset - 1);
+            self.content = chars.into_iter().collect();
             true
         } else {
             false
@@ -337,7 +467,7 @@ impl Field {
     /// Get display content (with password masking)
     pub fn get_display_content(&self) -> String {
         match self.field_type {
-            FieldType::Password => "*".repeat(self.content.len()),
+            FieldType::Password => "*".repeat(self.content.chars().count()),
             _ => self.content.clone()
         }
     }
@@ -347,6 +477,10 @@ impl Field {
         if self.required && self.content.trim().is_empty() {
             return Err("Field is required".to_string());
         }
+
+        if self.breaks_mandatory_fill(&self.content) {
+            return Err("Field must be filled completely or left empty".to_string());
+        }
         
         if self.field_type == FieldType::Numeric
             && !self.content.is_empty() && self.content.parse::<f64>().is_err() {
@@ -417,6 +551,23 @@ impl Field {
         self.field_type == FieldType::Mandatory || self.behavior.mandatory || self.required
     }
     
+    /// Whether `data` would leave a mandatory fill field partly filled
+    pub fn breaks_mandatory_fill(&self, data: &str) -> bool {
+        let filled = data.trim_end_matches([' ', '\0']).chars().count();
+        self.behavior.mandatory_fill && filled != 0 && filled < self.max_length
+    }
+
+    /// Field Exit in a right-adjust field: move the data to the right edge, filling with zeros or blanks
+    pub fn right_adjust_data(&mut self) {
+        let data = self.content.trim_end_matches([' ', '\0']);
+        if !self.behavior.right_adjust || data.is_empty() {
+            return;
+        }
+        let fill = if self.behavior.zero_fill { '0' } else { ' ' };
+        let padding = self.max_length.saturating_sub(data.chars().count());
+        self.content = std::iter::repeat_n(fill, padding).chain(data.chars()).collect();
+    }
+
     /// Check if field should be bypassed during navigation
     pub fn should_bypass(&self) -> bool {
         self.field_type == FieldType::Bypass || self.behavior.bypass
@@ -483,6 +634,14 @@ impl Field {
     }
 }
 
+/// Text typed in push mode: the cursor stays at `anchor` while `count` characters push away from it
+#[derive(Debug, Clone, Copy)]
+struct PushSegment {
+    field_idx: usize,
+    anchor: usize,
+    count: usize,
+}
+
 #[derive(Debug)]
 pub struct FieldManager {
     /// List of detected fields
@@ -499,6 +658,14 @@ pub struct FieldManager {
     continued_groups: HashMap<usize, Vec<usize>>,
     /// Current error state
     error_state: Option<FieldError>,
+    /// Insert mode (true) or overstrike mode (false)
+    insert_mode: bool,
+    /// Screen direction; field reverse is relative to it
+    screen_direction: TextDirection,
+    /// Push mode toggled by the operator
+    push_mode: bool,
+    /// Push-mode text currently being entered
+    push_segment: Option<PushSegment>,
 }
 
 impl FieldManager {
@@ -511,6 +678,10 @@ impl FieldManager {
             cursor_col: 1,
             continued_groups: HashMap::new(),
             error_state: None,
+            insert_mode: false,
+            screen_direction: TextDirection::LeftToRight,
+            push_mode: false,
+            push_segment: None,
         }
     }
     
@@ -519,6 +690,58 @@ impl FieldManager {
         // Use lib5250 field detection
         let _ = crate::lib5250::field::detect_fields_from_screen(screen);
     }
+
+    /// Replace the fields with the input fields of the host's format table
+    ///
+    /// `groups` lists the continued fields of the table as indices into `table`;
+    /// field content is read from `screen`. The host's FFW and FCWs set each
+    /// field's type and behavior.
+    pub fn load_format_table(&mut self, table: &[crate::lib5250::field::Field], groups: &[Vec<usize>], screen: &TerminalScreen) {
+        let direction = self.screen_direction;
+        let insert_mode = self.insert_mode;
+        *self = Self::new();
+        self.screen_direction = direction;
+        self.insert_mode = insert_mode;
+
+        let mut indices = HashMap::new();
+        for (table_idx, entry) in table.iter().enumerate() {
+            let Some(ffw) = entry.ffw else {
+                continue;
+            };
+            let id = self.next_field_id;
+            self.next_field_id += 1;
+            let mut field = Field::new(id, field_type_from_ffw(ffw, entry.attribute_byte), entry.row + 1, entry.col + 1, entry.length);
+            field.behavior = FieldBehavior {
+                bypass: ffw & 0x2000 != 0,
+                dup_enabled: ffw & 0x1000 != 0,
+                auto_enter: ffw & 0x0080 != 0,
+                field_exit_required: ffw & 0x0040 != 0,
+                uppercase_convert: ffw & 0x0020 != 0,
+                mandatory: ffw & 0x0008 != 0,
+                // Adjust values: 5 right-adjust zero fill, 6 right-adjust blank fill, 7 mandatory fill
+                right_adjust: matches!(ffw & 0x0007, 0x0005 | 0x0006),
+                zero_fill: ffw & 0x0007 == 0x0005,
+                mandatory_fill: ffw & 0x0007 == 0x0007,
+                word_wrap: entry.word_wrap(),
+                ..FieldBehavior::default()
+            };
+            let content: String = (0..entry.length)
+                .map_while(|i| screen.get_char_at(entry.col + i, entry.row))
+                .collect();
+            field.content = content.trim_end_matches([' ', '\0']).to_string();
+            field.modified = entry.mdt;
+            indices.insert(table_idx, self.fields.len());
+            self.fields.push(field);
+        }
+
+        for group in groups {
+            let group_id = self.next_field_id;
+            self.next_field_id += 1;
+            for &field_idx in group.iter().filter_map(|table_idx| indices.get(table_idx)) {
+                self.add_field_to_continued_group(field_idx, group_id);
+            }
+        }
+    }
     
 
     
@@ -560,14 +783,7 @@ impl FieldManager {
             }
         }
         
-        // Check for continued field logic
-        if let Some(group_id) = current_field.continued_group_id {
-            if let Some(next_in_group) = self.find_next_in_continued_group(group_id, current_idx) {
-                return self.activate_field_by_index(next_in_group);
-            }
-        }
-        
-        // Standard field progression with bypass logic
+        // Standard field progression with bypass logic; a continued field is one stop
         let mut next_idx = current_idx;
         let start_idx = next_idx;
         
@@ -581,13 +797,13 @@ impl FieldManager {
             
             let candidate_field = &self.fields[next_idx];
             
-            // Skip bypass fields
-            if candidate_field.should_bypass() {
+            // Skip bypass fields and the rest of the current continued field
+            if candidate_field.should_bypass() || self.same_continued_field(current_idx, next_idx) {
                 continue;
             }
             
             // Found a valid field
-            return self.activate_field_by_index(next_idx);
+            return self.activate_field_by_index(self.navigation_target(next_idx));
         }
     }
     
@@ -607,14 +823,7 @@ impl FieldManager {
             }
         }
         
-        // Check for continued field logic
-        if let Some(group_id) = current_field.continued_group_id {
-            if let Some(prev_in_group) = self.find_prev_in_continued_group(group_id, current_idx) {
-                return self.activate_field_by_index(prev_in_group);
-            }
-        }
-        
-        // Standard field progression with bypass logic
+        // Standard field progression with bypass logic; a continued field is one stop
         let mut prev_idx = current_idx;
         let start_idx = prev_idx;
         
@@ -628,13 +837,13 @@ impl FieldManager {
             
             let candidate_field = &self.fields[prev_idx];
             
-            // Skip bypass fields
-            if candidate_field.should_bypass() {
+            // Skip bypass fields and the rest of the current continued field
+            if candidate_field.should_bypass() || self.same_continued_field(current_idx, prev_idx) {
                 continue;
             }
             
             // Found a valid field
-            return self.activate_field_by_index(prev_idx);
+            return self.activate_field_by_index(self.navigation_target(prev_idx));
         }
     }
     
@@ -683,6 +892,7 @@ impl FieldManager {
             field.active = false;
             if field.contains_position(row, col) {
                 field.active = true;
+                field.cursor_position = col - field.start_col;
                 self.active_field = Some(idx);
                 return true;
             }
@@ -718,9 +928,10 @@ impl FieldManager {
         }
     }
     
-    /// Type a character in the current active field with enhanced features
+    /// Type a character at the cursor in the active field, honouring insert/overstrike mode
     /// SECURITY: Enhanced with comprehensive input sanitization
     pub fn type_char(&mut self, ch: char) -> Result<bool, String> {
+        self.error_state = None;
         if let Some(field_idx) = self.active_field {
             // CRITICAL FIX: Enhanced field index validation with bounds checking
             if field_idx >= self.fields.len() {
@@ -728,73 +939,624 @@ impl FieldManager {
                 return Err("Invalid field index".to_string());
             }
 
-            let field = &mut self.fields[field_idx];
-            if field.field_type != FieldType::Protected {
-                // CRITICAL FIX: Enhanced character validation with multiple checks
-                if !field.is_character_safe(ch) {
-                    eprintln!("SECURITY: Dangerous character rejected: {}", ch as u32);
-                    return Err("Invalid character".to_string());
+            if let Some(segments) = self.continued_segments(field_idx) {
+                return self.type_char_continued(&segments, ch);
+            }
+            if self.fields[field_idx].field_type != FieldType::Protected {
+                if self.fields[field_idx].behavior.closed {
+                    return self.type_char_closed(field_idx, ch);
                 }
-
-                // Validate character input based on field type
-                if let Err(error) = field.validate_character(ch) {
-                    return Err(error.get_user_message().to_string());
+                if self.should_push(field_idx, ch) {
+                    return self.type_char_push(field_idx, ch);
                 }
+                self.end_push_segment();
+            }
 
-                // CRITICAL FIX: Enhanced length validation with safety checks
-                if field.content.len() >= field.max_length {
-                    return Err("Field is full".to_string());
-                }
+            let insert_mode = self.insert_mode;
+            let field = &mut self.fields[field_idx];
+            if field.field_type == FieldType::Protected {
+                self.error_state = Some(FieldError::CursorProtected);
+                return Err("Cannot type in protected field".to_string());
+            }
 
-                // Additional safety check for content length
-                if field.content.len() + 1 > field.max_length {
-                    return Err("Field would exceed maximum length".to_string());
+            // CRITICAL FIX: Enhanced character validation with multiple checks
+            if !field.is_character_safe(ch) {
+                eprintln!("SECURITY: Dangerous character rejected: {}", ch as u32);
+                self.error_state = Some(FieldError::InvalidCharacter(ch));
+                return Err("Invalid character".to_string());
+            }
+
+            let offset = field.cursor_position.min(field.max_length);
+            let result = if insert_mode {
+                // Positions past the end of the data are blank; materialize them before shifting
+                if offset > field.content.chars().count() && offset < field.max_length {
+                    while field.content.chars().count() < offset {
+                        field.content.push(' ');
+                    }
                 }
+                field.insert_char(ch, offset)
+            } else {
+                field.overwrite_char(ch, offset)
+            };
 
-                let sanitized_ch = field.sanitize_character(ch);
-                field.content.push(sanitized_ch);
-                field.modified = true;
+            if let Err(error) = result {
+                let message = error.get_user_message().to_string();
+                self.error_state = Some(error);
+                return Err(message);
+            }
 
-                // Auto-advance to next field if field is now full and auto-enter is enabled
-                let field_full = field.content.len() >= field.max_length;
-                if field_full && field.should_auto_enter() {
-                    // Try to advance to next field
-                    let _ = self.tab_to_next_field();
-                }
+            field.cursor_position = (offset + 1).min(field.max_length);
 
-                Ok(field_full)
+            // Auto-advance to next field if the last position was typed and auto-enter is enabled
+            let field_full = field.cursor_position >= field.max_length;
+            if field_full && field.should_auto_enter() {
+                // Try to advance to next field
+                let _ = self.tab_to_next_field();
             } else {
-                Err("Cannot type in protected field".to_string())
+                self.sync_cursor_to_field(field_idx);
             }
+
+            Ok(field_full)
         } else {
+            self.error_state = Some(FieldError::NoActiveField);
             Err("No field selected".to_string())
         }
     }
-    
-    /// Backspace in the current active field
+
+    /// Backspace in the current active field: delete the character left of the cursor
     pub fn backspace(&mut self) -> Result<(), String> {
+        let field_idx = self.editable_active_field()?;
+        self.push_segment = None;
+        if self.fields[field_idx].behavior.closed {
+            // Closed fields drop the newest character and shift the rest back toward the edge
+            let field = &mut self.fields[field_idx];
+            let mut chars = padded_chars(&field.content, field.max_length);
+            if chars.iter().any(|&c| c != ' ') {
+                chars.pop();
+                chars.insert(0, ' ');
+                field.content = chars.into_iter().collect();
+                field.modified = true;
+            }
+            return Ok(());
+        }
+        if let Some(segments) = self.continued_segments(field_idx) {
+            return self.edit_continued_field(&segments, |text, cursor| {
+                if *cursor > 0 {
+                    *cursor -= 1;
+                    text.remove(*cursor);
+                    text.push(' ');
+                }
+                Ok(())
+            }).map_err(|error| error.get_user_message().to_string());
+        }
+        let field = &mut self.fields[field_idx];
+        if field.cursor_position > 0 {
+            let offset = field.cursor_position;
+            if field.backspace(offset) {
+                field.modified = true;
+            }
+            field.cursor_position = offset - 1;
+        }
+        self.sync_cursor_to_field(field_idx);
+        Ok(())
+    }
+
+    /// Delete the character at the cursor, shifting the rest of the field left
+    pub fn delete(&mut self) -> Result<(), String> {
+        let field_idx = self.editable_active_field()?;
+        if let Some(segments) = self.continued_segments(field_idx) {
+            return self.edit_continued_field(&segments, |text, cursor| {
+                if *cursor < text.len() {
+                    text.remove(*cursor);
+                    text.push(' ');
+                }
+                Ok(())
+            }).map_err(|error| error.get_user_message().to_string());
+        }
+        let field = &mut self.fields[field_idx];
+        let offset = field.cursor_position;
+        if field.delete_char(offset) {
+            field.modified = true;
+        }
+        Ok(())
+    }
+
+    /// Erase EOF: clear the active field from the cursor to the end of the field
+    pub fn erase_eof(&mut self) -> Result<(), String> {
+        let field_idx = self.editable_active_field()?;
+        if let Some(segments) = self.continued_segments(field_idx) {
+            return self.edit_continued_field(&segments, |text, cursor| {
+                text[*cursor..].fill(' ');
+                Ok(())
+            }).map_err(|error| error.get_user_message().to_string());
+        }
+        let field = &mut self.fields[field_idx];
+        let offset = field.cursor_position;
+        field.erase_to_end(offset);
+        Ok(())
+    }
+
+    /// Field Exit: erase to the end of the active field, set its MDT and move to the next input field
+    ///
+    /// A right-adjust field's data moves to its right edge; a mandatory fill field
+    /// must be left empty or full, so Field Exit there fails while it is partly filled.
+    pub fn field_exit(&mut self) -> Result<(), String> {
+        if let Some(field) = self.active_field.map(|idx| &self.fields[idx]) {
+            let kept: String = field.content.chars().take(field.cursor_position).collect();
+            if field.breaks_mandatory_fill(&kept) {
+                self.error_state = Some(FieldError::MandatoryFill);
+                return Err(FieldError::MandatoryFill.get_user_message().to_string());
+            }
+        }
+        self.erase_eof()?;
         if let Some(field_idx) = self.active_field {
-            if field_idx < self.fields.len() {
-                let field = &mut self.fields[field_idx];
-                if field.field_type != FieldType::Protected {
-                    if !field.content.is_empty() {
-                        field.content.pop();
+            let field = &mut self.fields[field_idx];
+            field.modified = true;
+            if !field.is_continued() {
+                field.right_adjust_data();
+            }
+        }
+        self.next_field().map_err(|error| error.get_user_message().to_string())
+    }
+
+    /// Erase Input: clear every unprotected field, reset MDT and home the cursor
+    pub fn erase_input(&mut self) -> Result<(), String> {
+        self.error_state = None;
+        for field in &mut self.fields {
+            if field.field_type != FieldType::Protected {
+                field.clear();
+                field.clear_error();
+                field.modified = false;
+                field.cursor_position = 0;
+            }
+        }
+        // A screen without input fields leaves the cursor where it is
+        let _ = self.home_to_first_input_field();
+        Ok(())
+    }
+
+    /// Home: move the cursor to the first input field on the screen
+    pub fn home_to_first_input_field(&mut self) -> Result<(), FieldError> {
+        let first = self.fields.iter()
+            .enumerate()
+            .filter(|(_, field)| field.field_type != FieldType::Protected && !field.should_bypass())
+            .min_by_key(|(_, field)| (field.start_row, field.start_col))
+            .map(|(idx, _)| idx);
+
+        match first {
+            Some(idx) => self.activate_field_by_index(idx),
+            None => Err(FieldError::NoActiveField),
+        }
+    }
+
+    /// Paste text at the cursor, spilling into the following input fields
+    ///
+    /// Characters are typed as if by the operator, so field lengths and character
+    /// checks apply. A full field continues in the next input field on the screen,
+    /// never wrapping back to the top; what does not fit is counted as dropped.
+    /// Tabs move to the next input field and line breaks follow `lines`. The paste
+    /// stops at the first rejected character, leaving its operator error set.
+    pub fn paste_text(&mut self, text: &str, lines: LineHandling) -> Result<PasteOutcome, String> {
+        self.editable_active_field()?;
+        let text = text.replace("\r\n", "\n");
+        let text = text.strip_suffix('\n').unwrap_or(&text);
+        let start_col = self.cursor_col;
+        let mut row = self.cursor_row;
+        let mut outcome = PasteOutcome::default();
+        let mut exhausted = false;
+
+        for (line_idx, line) in text.split('\n').enumerate() {
+            let mut pending = String::new();
+            if line_idx > 0 && !exhausted {
+                match lines {
+                    LineHandling::NextField => exhausted = !self.activate_next_input_field(),
+                    LineHandling::NextLine => {
+                        row += 1;
+                        exhausted = !self.activate_input_field_from(row, start_col);
                     }
-                    Ok(())
-                } else {
-                    Err("Cannot edit protected field".to_string())
+                    LineHandling::Join => pending.push(' '),
                 }
-            } else {
-                Err("Invalid field index".to_string())
             }
+            for (cell_idx, cell) in line.split('\t').enumerate() {
+                if cell_idx > 0 && !exhausted {
+                    exhausted = !self.activate_next_input_field();
+                }
+                pending.extend(cell.chars().filter(|ch| !ch.is_control()));
+                for ch in pending.drain(..) {
+                    if !exhausted && self.paste_char(ch)? {
+                        outcome.typed.push(ch);
+                    } else {
+                        exhausted = true;
+                        outcome.dropped += 1;
+                    }
+                }
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Type one pasted character, moving to the next input field when the active one is full;
+    /// false when there is no field left to take it
+    fn paste_char(&mut self, ch: char) -> Result<bool, String> {
+        loop {
+            let field_idx = self.editable_active_field()?;
+            let field = &self.fields[field_idx];
+            if field.cursor_position >= field.max_length {
+                if !self.activate_next_input_field() {
+                    return Ok(false);
+                }
+                continue;
+            }
+            match self.type_char(ch) {
+                Ok(_) => return Ok(true),
+                Err(_) if self.error_state == Some(FieldError::FieldFull) => {
+                    self.error_state = None;
+                    if !self.activate_next_input_field() {
+                        return Ok(false);
+                    }
+                }
+                Err(message) => return Err(message),
+            }
+        }
+    }
+
+    /// Activate the first input field after the active one in screen order; false at the last one
+    fn activate_next_input_field(&mut self) -> bool {
+        match self.active_field {
+            Some(idx) => {
+                let position = (self.fields[idx].start_row, self.fields[idx].start_col);
+                self.activate_input_field_after(position)
+            }
+            None => false,
+        }
+    }
+
+    fn activate_input_field_after(&mut self, position: (usize, usize)) -> bool {
+        let next = self.fields.iter()
+            .enumerate()
+            .filter(|(_, field)| field.field_type != FieldType::Protected && !field.should_bypass())
+            .filter(|(_, field)| (field.start_row, field.start_col) > position)
+            .min_by_key(|(_, field)| (field.start_row, field.start_col))
+            .map(|(idx, _)| idx);
+        next.is_some_and(|idx| self.activate_field_by_index(idx).is_ok())
+    }
+
+    /// Activate the input field under `(row, col)`, or else the next one after it
+    fn activate_input_field_from(&mut self, row: usize, col: usize) -> bool {
+        let under = self.fields.iter().any(|field| {
+            field.contains_position(row, col) && field.field_type != FieldType::Protected && !field.should_bypass()
+        });
+        if under {
+            self.click_at_position(row, col)
         } else {
-            Err("No field selected".to_string())
+            self.activate_input_field_after((row, col))
         }
     }
-    
-    /// Delete in the current active field (acts like backspace for simplicity)
-    pub fn delete(&mut self) -> Result<(), String> {
-        self.backspace()
+
+    /// Whether typed characters are inserted (true) or overwrite (false)
+    pub fn is_insert_mode(&self) -> bool {
+        self.insert_mode
+    }
+
+    /// Set insert/overstrike mode
+    pub fn set_insert_mode(&mut self, insert_mode: bool) {
+        self.insert_mode = insert_mode;
+    }
+
+    /// Toggle insert/overstrike mode, returning the new insert state
+    pub fn toggle_insert_mode(&mut self) -> bool {
+        self.insert_mode = !self.insert_mode;
+        self.insert_mode
+    }
+
+    /// Screen direction that field reverse is relative to
+    pub fn screen_direction(&self) -> TextDirection {
+        self.screen_direction
+    }
+
+    /// Set the screen direction (from the host or the code page)
+    pub fn set_screen_direction(&mut self, direction: TextDirection) {
+        self.screen_direction = direction;
+    }
+
+    /// Direction the cursor advances in a field, taking screen and field reverse into account
+    pub fn field_direction(&self, field_idx: usize) -> TextDirection {
+        match self.fields.get(field_idx) {
+            Some(field) if field.behavior.reverse => self.screen_direction.reversed(),
+            _ => self.screen_direction,
+        }
+    }
+
+    /// Whether push mode is on
+    pub fn is_push_mode(&self) -> bool {
+        self.push_mode
+    }
+
+    /// Toggle push mode, returning the new state; leaving it moves the cursor past the pushed text
+    pub fn toggle_push_mode(&mut self) -> bool {
+        self.push_mode = !self.push_mode;
+        if !self.push_mode {
+            self.end_push_segment();
+        }
+        self.push_mode
+    }
+
+    /// Field Reverse: toggle whether the cursor in the active field moves against the screen direction
+    pub fn toggle_field_reverse(&mut self) -> Result<bool, FieldError> {
+        self.toggle_active_behavior(|behavior| &mut behavior.reverse)
+    }
+
+    /// Auto Push: toggle push mode for opposite-direction text in the active field
+    pub fn toggle_auto_push(&mut self) -> Result<bool, FieldError> {
+        self.toggle_active_behavior(|behavior| &mut behavior.auto_push)
+    }
+
+    /// Close: toggle entering data at the trailing edge of the active field
+    pub fn toggle_closed_field(&mut self) -> Result<bool, FieldError> {
+        self.toggle_active_behavior(|behavior| &mut behavior.closed)
+    }
+
+    /// Flip a behavior flag of the active field, returning its new state
+    fn toggle_active_behavior(&mut self, flag: fn(&mut FieldBehavior) -> &mut bool) -> Result<bool, FieldError> {
+        let field_idx = self.active_field.filter(|&idx| idx < self.fields.len()).ok_or(FieldError::NoActiveField)?;
+        self.end_push_segment();
+        let flag = flag(&mut self.fields[field_idx].behavior);
+        *flag = !*flag;
+        let on = *flag;
+        self.sync_cursor_to_field(field_idx);
+        Ok(on)
+    }
+
+    /// Whether a character typed into a field is entered in push mode
+    fn should_push(&self, field_idx: usize, ch: char) -> bool {
+        if self.push_mode {
+            return true;
+        }
+        if !self.fields[field_idx].behavior.auto_push {
+            return false;
+        }
+        match TextDirection::of_char(ch) {
+            Some(direction) => direction != self.field_direction(field_idx),
+            // Neutrals continue the text being pushed
+            None => self.active_push_segment(field_idx).is_some(),
+        }
+    }
+
+    /// The push segment for a field, if the cursor is still on its anchor
+    fn active_push_segment(&self, field_idx: usize) -> Option<PushSegment> {
+        self.push_segment.filter(|segment| {
+            segment.field_idx == field_idx && self.fields[field_idx].cursor_position == segment.anchor
+        })
+    }
+
+    /// Type a character in push mode: the cursor stays put and earlier pushed text moves on
+    fn type_char_push(&mut self, field_idx: usize, ch: char) -> Result<bool, String> {
+        let mut segment = self.active_push_segment(field_idx).unwrap_or(PushSegment {
+            field_idx,
+            anchor: self.fields[field_idx].cursor_position.min(self.fields[field_idx].max_length.saturating_sub(1)),
+            count: 0,
+        });
+
+        let field = &mut self.fields[field_idx];
+        // Positions past the end of the data are blank; materialize them before shifting
+        let mut chars: Vec<char> = field.content.chars().collect();
+        if chars.len() < segment.anchor {
+            chars.resize(segment.anchor, ' ');
+            field.content = chars.into_iter().collect();
+        }
+        if let Err(error) = field.insert_char(ch, segment.anchor) {
+            let message = error.get_user_message().to_string();
+            self.error_state = Some(error);
+            return Err(message);
+        }
+        field.cursor_position = segment.anchor;
+        segment.count += 1;
+        self.push_segment = Some(segment);
+        self.sync_cursor_to_field(field_idx);
+        Ok(false)
+    }
+
+    /// Leave push-mode entry, moving the cursor past the pushed text
+    fn end_push_segment(&mut self) {
+        if let Some(segment) = self.push_segment.take() {
+            if let Some(field) = self.fields.get_mut(segment.field_idx) {
+                if field.cursor_position == segment.anchor {
+                    field.cursor_position = (segment.anchor + segment.count).min(field.max_length);
+                    self.sync_cursor_to_field(segment.field_idx);
+                }
+            }
+        }
+    }
+
+    /// Type a character into a closed field: it enters at the trailing edge and earlier data shifts back
+    fn type_char_closed(&mut self, field_idx: usize, ch: char) -> Result<bool, String> {
+        let field = &mut self.fields[field_idx];
+        let check = if field.is_character_safe(ch) {
+            field.validate_character(ch)
+        } else {
+            Err(FieldError::InvalidCharacter(ch))
+        };
+        let mut chars = padded_chars(&field.content, field.max_length);
+        let result = check.and_then(|()| match chars.first() {
+            Some(' ') => Ok(()),
+            _ => Err(FieldError::NoRoomForInsert),
+        });
+        if let Err(error) = result {
+            let message = error.get_user_message().to_string();
+            self.error_state = Some(error);
+            return Err(message);
+        }
+
+        let ch = if field.field_type == FieldType::UppercaseOnly || field.behavior.uppercase_convert {
+            field.sanitize_character(ch).to_uppercase().next().unwrap_or(ch)
+        } else {
+            field.sanitize_character(ch)
+        };
+        chars.remove(0);
+        chars.push(ch);
+        field.content = chars.into_iter().collect();
+        field.modified = true;
+        field.cursor_position = field.max_length.saturating_sub(1);
+
+        let field_full = field.content.chars().next().is_some_and(|c| c != ' ');
+        if field_full && field.should_auto_enter() {
+            let _ = self.tab_to_next_field();
+        } else {
+            self.sync_cursor_to_field(field_idx);
+        }
+        Ok(field_full)
+    }
+
+    /// Segments of a field's continued group in screen order, if the group has more than one
+    fn continued_segments(&self, field_idx: usize) -> Option<Vec<usize>> {
+        let group_id = self.fields.get(field_idx)?.continued_group_id?;
+        let mut segments: Vec<usize> = self.continued_groups.get(&group_id)?
+            .iter()
+            .copied()
+            .filter(|&idx| idx < self.fields.len())
+            .collect();
+        if segments.len() < 2 {
+            return None;
+        }
+        segments.sort_by_key(|&idx| (self.fields[idx].start_row, self.fields[idx].start_col));
+        Some(segments)
+    }
+
+    /// Type a character into a continued field, flowing across its segments
+    fn type_char_continued(&mut self, segments: &[usize], ch: char) -> Result<bool, String> {
+        let field_idx = self.active_field.unwrap_or(segments[0]);
+        let field = &self.fields[field_idx];
+        let checked = if !field.is_character_safe(ch) {
+            Err(FieldError::InvalidCharacter(ch))
+        } else {
+            field.validate_character(ch)
+        };
+        if let Err(error) = checked {
+            let message = error.get_user_message().to_string();
+            self.error_state = Some(error);
+            return Err(message);
+        }
+
+        let mut ch = field.sanitize_character(ch);
+        if field.field_type == FieldType::UppercaseOnly || field.behavior.uppercase_convert {
+            ch = ch.to_ascii_uppercase();
+        }
+
+        let insert_mode = self.insert_mode;
+        let capacity: usize = segments.iter().map(|&idx| self.fields[idx].max_length).sum();
+        let mut full = false;
+        self.edit_continued_field(segments, |text, cursor| {
+            if *cursor >= capacity {
+                return Err(if insert_mode { FieldError::NoRoomForInsert } else { FieldError::FieldFull });
+            }
+            if insert_mode {
+                // The last position must be blank for data to shift right into it
+                if text.last().is_some_and(|&c| c != ' ') {
+                    return Err(FieldError::NoRoomForInsert);
+                }
+                text.pop();
+                text.insert(*cursor, ch);
+            } else {
+                text[*cursor] = ch;
+            }
+            *cursor += 1;
+            full = *cursor >= capacity;
+            Ok(())
+        }).map_err(|error| error.get_user_message().to_string())?;
+
+        if full && self.fields[segments[0]].should_auto_enter() {
+            let _ = self.tab_to_next_field();
+        }
+        Ok(full)
+    }
+
+    /// Apply an edit to a continued field as one logical field spanning all its segments
+    ///
+    /// The edit sees the segments' contents concatenated (blank padded) and the logical
+    /// cursor offset; the result is laid back out across the segments, word wrapped if
+    /// any segment asks for it, and every segment gets the MDT when anything changed.
+    fn edit_continued_field<F>(&mut self, segments: &[usize], edit: F) -> Result<(), FieldError>
+    where
+        F: FnOnce(&mut Vec<char>, &mut usize) -> Result<(), FieldError>,
+    {
+        let active = self.active_field.unwrap_or(segments[0]);
+        let lengths: Vec<usize> = segments.iter().map(|&idx| self.fields[idx].max_length).collect();
+
+        let mut text = Vec::with_capacity(lengths.iter().sum());
+        let mut cursor = 0;
+        for (&idx, &len) in segments.iter().zip(&lengths) {
+            let field = &self.fields[idx];
+            if idx == active {
+                cursor = text.len() + field.cursor_position.min(len);
+            }
+            text.extend(field.content.chars().chain(std::iter::repeat(' ')).take(len));
+        }
+
+        let result = edit(&mut text, &mut cursor).and_then(|()| {
+            let word_wrap = segments.iter().any(|&idx| self.fields[idx].behavior.word_wrap);
+            layout_continued_text(&text, &lengths, cursor, word_wrap).ok_or(FieldError::NoRoomForInsert)
+        });
+        let (contents, (segment, offset)) = match result {
+            Ok(layout) => layout,
+            Err(error) => {
+                self.error_state = Some(error.clone());
+                return Err(error);
+            }
+        };
+
+        let changed = segments.iter().zip(&contents).any(|(&idx, content)| self.fields[idx].content != *content);
+        for (&idx, content) in segments.iter().zip(contents) {
+            let field = &mut self.fields[idx];
+            field.content = content;
+            field.active = false;
+            if changed {
+                field.modified = true;
+            }
+        }
+
+        let target = segments[segment];
+        self.fields[target].active = true;
+        self.fields[target].cursor_position = offset;
+        self.active_field = Some(target);
+        self.sync_cursor_to_field(target);
+        Ok(())
+    }
+
+    /// First segment of the continued group a field belongs to (or the field itself)
+    fn navigation_target(&self, field_idx: usize) -> usize {
+        self.continued_segments(field_idx).map_or(field_idx, |segments| segments[0])
+    }
+
+    /// Whether two fields are segments of the same continued field
+    fn same_continued_field(&self, a: usize, b: usize) -> bool {
+        self.continued_segments(a).is_some_and(|segments| segments.contains(&b))
+    }
+
+    /// Resolve the active field for an editing key, recording the operator error on failure
+    fn editable_active_field(&mut self) -> Result<usize, String> {
+        self.error_state = None;
+        match self.active_field {
+            Some(idx) if idx < self.fields.len() => {
+                if self.fields[idx].field_type == FieldType::Protected {
+                    self.error_state = Some(FieldError::CursorProtected);
+                    Err("Cannot edit protected field".to_string())
+                } else {
+                    Ok(idx)
+                }
+            }
+            Some(_) => Err("Invalid field index".to_string()),
+            None => {
+                self.error_state = Some(FieldError::NoActiveField);
+                Err("No field selected".to_string())
+            }
+        }
+    }
+
+    /// Keep the screen cursor on the active field's edit position
+    fn sync_cursor_to_field(&mut self, field_idx: usize) {
+        let field = &self.fields[field_idx];
+        let (row, col) = (field.start_row, field.column_for_offset(field.cursor_position));
+        self.set_cursor_position(row, col);
     }
     
     /// Update cursor position
@@ -856,8 +1618,12 @@ impl FieldManager {
                 }
             }
 
-            // Activate new field
+            // Activate new field with the edit position under the cursor
+            let offset = self.fields[field_idx]
+                .get_cursor_offset(self.cursor_row, self.cursor_col)
+                .unwrap_or(0);
             self.fields[field_idx].active = true;
+            self.fields[field_idx].cursor_position = offset;
             self.active_field = Some(field_idx);
 
             true
@@ -902,8 +1668,9 @@ impl FieldManager {
                 if i >= field.length {
                     break;
                 }
-                if start_col + i < term_width && row < term_height {
-                    let index = terminal.index(start_col + i, row);
+                let col = field.column_for_offset(i).saturating_sub(1);
+                if col < term_width && row < term_height {
+                    let index = terminal.index(col, row);
                     if index < terminal.buffer.len() {
                         terminal.buffer[index].character = ch;
                     }
@@ -944,8 +1711,9 @@ impl FieldManager {
             self.fields[current].active = false;
         }
         
-        // Activate new field
+        // Activate new field at its first position
         self.fields[index].active = true;
+        self.fields[index].cursor_position = 0;
         self.active_field = Some(index);
         
         // Update cursor position
@@ -955,28 +1723,6 @@ impl FieldManager {
         Ok(())
     }
     
-    /// Find next field in continued group
-    fn find_next_in_continued_group(&self, group_id: usize, current_idx: usize) -> Option<usize> {
-        if let Some(group_fields) = self.continued_groups.get(&group_id) {
-            if let Some(pos) = group_fields.iter().position(|&idx| idx == current_idx) {
-                let next_pos = (pos + 1) % group_fields.len();
-                return Some(group_fields[next_pos]);
-            }
-        }
-        None
-    }
-    
-    /// Find previous field in continued group
-    fn find_prev_in_continued_group(&self, group_id: usize, current_idx: usize) -> Option<usize> {
-        if let Some(group_fields) = self.continued_groups.get(&group_id) {
-            if let Some(pos) = group_fields.iter().position(|&idx| idx == current_idx) {
-                let prev_pos = if pos == 0 { group_fields.len() - 1 } else { pos - 1 };
-                return Some(group_fields[prev_pos]);
-            }
-        }
-        None
-    }
-    
     /// Add field to continued group
     pub fn add_field_to_continued_group(&mut self, field_idx: usize, group_id: usize) {
         self.continued_groups.entry(group_id).or_default().push(field_idx);
@@ -1031,6 +1777,7 @@ impl FieldManager {
                 is_active: field.active,
                 error_state: field.error_state.clone(),
                 highlighted: field.highlighted,
+                modified: field.modified,
                 start_row: field.start_row,
                 start_col: field.start_col,
                 length: field.length,
@@ -1057,16 +1804,18 @@ impl FieldManager {
             .map(|idx| self.fields[idx].tab_order)
             .unwrap_or(0);
             
-        // Find next field by tab order, skipping bypass fields
+        // Find next field by tab order, skipping bypass fields and the current continued field
+        let current = self.active_field;
         let mut candidates: Vec<_> = self.fields.iter()
             .enumerate()
             .filter(|(_, field)| !field.should_bypass() && field.tab_order > current_tab_order)
+            .filter(|(idx, _)| !current.is_some_and(|cur| self.same_continued_field(cur, *idx)))
             .collect();
         
         candidates.sort_by_key(|(_, field)| field.tab_order);
         
         if let Some((idx, _)) = candidates.first() {
-            return self.activate_field_by_index(*idx);
+            return self.activate_field_by_index(self.navigation_target(*idx));
         }
         
         // Wrap around to first field
@@ -1078,7 +1827,7 @@ impl FieldManager {
         candidates.sort_by_key(|(_, field)| field.tab_order);
         
         if let Some((idx, _)) = candidates.first() {
-            return self.activate_field_by_index(*idx);
+            return self.activate_field_by_index(self.navigation_target(*idx));
         }
         
         Err(FieldError::InvalidFieldNavigation)
@@ -1094,16 +1843,18 @@ impl FieldManager {
             .map(|idx| self.fields[idx].tab_order)
             .unwrap_or(usize::MAX);
             
-        // Find previous field by tab order, skipping bypass fields
+        // Find previous field by tab order, skipping bypass fields and the current continued field
+        let current = self.active_field;
         let mut candidates: Vec<_> = self.fields.iter()
             .enumerate()
             .filter(|(_, field)| !field.should_bypass() && field.tab_order < current_tab_order)
+            .filter(|(idx, _)| !current.is_some_and(|cur| self.same_continued_field(cur, *idx)))
             .collect();
         
         candidates.sort_by_key(|(_, field)| std::cmp::Reverse(field.tab_order));
         
         if let Some((idx, _)) = candidates.first() {
-            return self.activate_field_by_index(*idx);
+            return self.activate_field_by_index(self.navigation_target(*idx));
         }
         
         // Wrap around to last field
@@ -1115,7 +1866,7 @@ impl FieldManager {
         candidates.sort_by_key(|(_, field)| std::cmp::Reverse(field.tab_order));
         
         if let Some((idx, _)) = candidates.first() {
-            return self.activate_field_by_index(*idx);
+            return self.activate_field_by_index(self.navigation_target(*idx));
         }
         
         Err(FieldError::InvalidFieldNavigation)
@@ -1143,6 +1894,10 @@ impl FieldManager {
         if field.is_mandatory() && field.content.trim().is_empty() {
             return Err(FieldError::MandatoryEnter);
         }
+
+        if field.breaks_mandatory_fill(&field.content) {
+            return Err(FieldError::MandatoryFill);
+        }
         
         // Check field exit required
         if field.behavior.field_exit_required {
@@ -1257,7 +2012,8 @@ impl FieldManager {
             }
 
             // Validate cursor position within field
-            if field.active && field.cursor_position >= field.length {
+            // The edit position may sit just past the last character once the field is full
+            if field.active && field.cursor_position > field.length {
                 return Err(format!("Field {} cursor position {} out of bounds (length: {})",
                                  idx, field.cursor_position, field.length));
             }
@@ -1283,3 +2039,66 @@ impl FieldManager {
 impl Default for FieldManager {
     fn default() -> Self { Self::new() }
 }
+
+/// Field type from the shift/edit bits of a Field Format Word; nondisplay fields are passwords
+fn field_type_from_ffw(ffw: u16, attribute_byte: u8) -> FieldType {
+    if attribute_byte & 0x07 == 0x07 {
+        return FieldType::Password;
+    }
+    match (ffw >> 8) & 0x07 {
+        0x01 => FieldType::AlphaOnly,
+        0x03 => FieldType::Numeric,
+        0x05 => FieldType::DigitsOnly,
+        0x07 => FieldType::NumericSigned,
+        _ => FieldType::Input,
+    }
+}
+
+/// A field's characters padded with blanks to `length`
+fn padded_chars(content: &str, length: usize) -> Vec<char> {
+    let mut chars: Vec<char> = content.chars().take(length).collect();
+    chars.resize(length, ' ');
+    chars
+}
+
+/// Lay out the logical text of a continued field across segments of the given lengths
+///
+/// Returns each segment's content and the (segment, offset) the logical `cursor` lands
+/// on, or None when the text does not fit. With word wrap, a word that would straddle
+/// two segments starts on the next one instead.
+fn layout_continued_text(text: &[char], lengths: &[usize], cursor: usize, word_wrap: bool) -> Option<(Vec<String>, (usize, usize))> {
+    let used = text.iter().rposition(|&c| c != ' ').map_or(0, |last| last + 1);
+    let mut contents = Vec::with_capacity(lengths.len());
+    let mut cursor_at = None;
+    let mut pos = 0;
+
+    for (segment, &len) in lengths.iter().enumerate() {
+        let mut take = if word_wrap { len.min(used - pos) } else { len.min(text.len().saturating_sub(pos)) };
+        if word_wrap && take == len && pos + take < used && text[pos + take] != ' ' {
+            if let Some(space) = text[pos..pos + take].iter().rposition(|&c| c == ' ') {
+                take = space + 1;
+            }
+        }
+
+        if cursor_at.is_none() && cursor >= pos && (cursor < pos + take || pos + take >= used) {
+            // Past the data, the cursor keeps its distance from the last character
+            let offset = cursor - pos;
+            cursor_at = Some((segment, offset));
+        }
+
+        contents.push(text[pos..pos + take].iter().collect::<String>().trim_end().to_string());
+        pos += take;
+    }
+
+    if pos < used {
+        return None;
+    }
+
+    // Carry an offset that runs past its segment into the following ones
+    let (mut segment, mut offset) = cursor_at.unwrap_or((lengths.len() - 1, lengths[lengths.len() - 1]));
+    while offset >= lengths[segment] && segment + 1 < lengths.len() {
+        offset -= lengths[segment];
+        segment += 1;
+    }
+    Some((contents, (segment, offset.min(lengths[segment]))))
+}
@@ -0,0 +1,167 @@
+//! Hotspots: clickable text on the terminal screen
+//!
+//! Screens print hints such as `F3=Exit  F12=Cancel`, `More...` and subfile
+//! option codes like `2=Change`. Each hotspot rule is a regular expression
+//! matched against the screen rows and an action for the matched text:
+//!
+//! - a key mnemonic or macro as in the keymap (`[pf$1]`, `[pagedn]`, `$1[enter]`),
+//!   where `$1` and `${name}` are replaced with the capture groups of the match;
+//! - `url:<address>` to open the address in the browser, e.g. `url:$0`.
+//!
+//! A profile's own rules are tried before the built-in ones; text covered by
+//! one hotspot is not matched again.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::keymap::KeyAction;
+use crate::screen_grid::CellGrid;
+
+/// Prefix of actions that open the matched address in the browser
+const URL_ACTION: &str = "url:";
+
+/// A pattern to find on the screen and the action to run when it is clicked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotspotRule {
+    /// Regular expression matched within each screen row
+    pub pattern: String,
+    /// Key mnemonic, macro or `url:` address, with `$n` replaced by capture groups
+    pub action: String,
+}
+
+impl HotspotRule {
+    pub fn new(pattern: &str, action: &str) -> Self {
+        Self { pattern: pattern.to_string(), action: action.to_string() }
+    }
+
+    /// Check that the pattern compiles and the action is a known mnemonic, macro or URL
+    pub fn validate(&self) -> Result<(), String> {
+        Regex::new(&self.pattern).map_err(|e| format!("Invalid hotspot pattern '{}': {e}", self.pattern))?;
+        if self.action.starts_with(URL_ACTION) {
+            return Ok(());
+        }
+        // Capture references are only known on the screen; check the rest of the action
+        let placeholder = Regex::new(r"\$(\d+|\{\w+\})").expect("valid regex");
+        KeyAction::parse(&placeholder.replace_all(&self.action, "1"))
+            .map(|_| ())
+            .map_err(|e| format!("Invalid hotspot action '{}': {e}", self.action))
+    }
+}
+
+/// Rules recognising the usual IBM i and mainframe screen hints
+pub fn builtin_rules() -> Vec<HotspotRule> {
+    vec![
+        HotspotRule::new(r#"https?://[^\s<>"']+"#, "url:$0"),
+        // F3=Exit, F13=Repeat all; the text ends before the next Fnn= or option code
+        HotspotRule::new(r"\bF([1-9]|1\d|2[0-4])=\S+(?: (?:[^\sF\d]|F\D)[^\s=]*)*", "[pf$1]"),
+        HotspotRule::new(r"\bMore\.\.\.", "[pagedn]"),
+        HotspotRule::new(r"\bBottom\b", "[pageup]"),
+        // Subfile options: the code is typed into the option field at the cursor
+        HotspotRule::new(r"\b(\d{1,2})=[A-Za-z]\S*", "$1"),
+    ]
+}
+
+/// Hotspot options of a profile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotspotSettings {
+    /// Make screen hints clickable
+    pub enabled: bool,
+    /// Also use the built-in rules after the profile's own
+    pub builtin: bool,
+    /// The profile's own rules, tried first
+    pub rules: Vec<HotspotRule>,
+}
+
+impl Default for HotspotSettings {
+    fn default() -> Self {
+        Self { enabled: true, builtin: true, rules: Vec::new() }
+    }
+}
+
+/// What clicking a hotspot does
+#[derive(Debug, Clone, PartialEq)]
+pub enum HotspotAction {
+    /// Press a key or type a macro, as a keymap binding would
+    Key(KeyAction),
+    /// Open the address in the browser
+    OpenUrl(String),
+}
+
+/// Clickable text found on the screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hotspot {
+    /// 1-based row and column of the first character
+    pub row: usize,
+    pub col: usize,
+    /// Length in characters
+    pub len: usize,
+    pub text: String,
+    pub action: HotspotAction,
+}
+
+impl Hotspot {
+    /// Whether the hotspot covers the cell at a 1-based row and column
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row == self.row && col >= self.col && col < self.col + self.len
+    }
+}
+
+/// Compiled hotspot rules of a profile
+#[derive(Debug, Clone, Default)]
+pub struct HotspotMatcher {
+    rules: Vec<(Regex, String)>,
+}
+
+impl HotspotMatcher {
+    /// Compile the rules of `settings`; invalid rules are left out
+    pub fn new(settings: &HotspotSettings) -> Self {
+        if !settings.enabled {
+            return Self::default();
+        }
+        let builtin = if settings.builtin { builtin_rules() } else { Vec::new() };
+        let rules = settings.rules.iter().chain(builtin.iter())
+            .filter_map(|rule| match rule.validate() {
+                Ok(()) => Some((Regex::new(&rule.pattern).ok()?, rule.action.clone())),
+                Err(e) => {
+                    eprintln!("{e}; rule skipped");
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Hotspots on the screen, in rule order; hidden (nondisplay) text is never matched
+    pub fn find(&self, grid: &CellGrid) -> Vec<Hotspot> {
+        let mut hotspots: Vec<Hotspot> = Vec::new();
+        if self.rules.is_empty() || grid.width == 0 {
+            return hotspots;
+        }
+        for (index, cells) in grid.cells.chunks(grid.width).enumerate() {
+            let row = index + 1;
+            let line: String = cells.iter().map(|cell| if cell.style.hidden { ' ' } else { cell.ch }).collect();
+            for (regex, action) in &self.rules {
+                for captures in regex.captures_iter(&line) {
+                    let matched = captures.get(0).expect("group 0 is the whole match");
+                    let col = line[..matched.start()].chars().count() + 1;
+                    let len = matched.as_str().chars().count();
+                    if len == 0 || hotspots.iter().any(|h| h.row == row && h.col < col + len && col < h.col + h.len) {
+                        continue;
+                    }
+                    let mut expanded = String::new();
+                    captures.expand(action, &mut expanded);
+                    let action = match expanded.strip_prefix(URL_ACTION) {
+                        Some(url) => HotspotAction::OpenUrl(url.to_string()),
+                        None => match KeyAction::parse(&expanded) {
+                            Ok(action) => HotspotAction::Key(action),
+                            Err(_) => continue,
+                        },
+                    };
+                    hotspots.push(Hotspot { row, col, len, text: matched.as_str().to_string(), action });
+                }
+            }
+        }
+        hotspots
+    }
+}
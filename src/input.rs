@@ -1,29 +1,20 @@
 //! Input handling for TN5250R
 //!
 //! This module handles keyboard events, text input, and function key processing.
+//! Key presses are dispatched through the active session's keymap.
 
 use eframe::egui;
 use crate::app_state::TN5250RApp;
 use crate::keyboard;
+use crate::clipboard::CopyFormat;
+use crate::keymap::KeyChord;
+use crate::ui::terminal_selection;
 
 impl TN5250RApp {
     pub fn send_function_key(&mut self, key_name: &str) {
-        // In a real implementation, this would send the actual function key
-        // For now, we'll just update the terminal content
-        self.terminal_content.push_str(&format!("\n[{key_name}] pressed"));
-
         // Parse the key name to determine which function key to send
         let func_key = self.parse_function_key_name(key_name);
-
-        // Simulate sending the function key
-        match self.controller.send_function_key(func_key) {
-            Ok(()) => {
-                // Function key sent successfully
-            }
-            Err(e) => {
-                self.terminal_content.push_str(&format!("\nError sending function key: {e}"));
-            }
-        }
+        self.send_function_key_direct(func_key);
     }
 
     pub fn parse_function_key_name(&self, key_name: &str) -> keyboard::FunctionKey {
@@ -65,105 +56,90 @@ impl TN5250RApp {
     }
 
     pub fn send_function_key_direct(&mut self, func_key: keyboard::FunctionKey) {
-        // Send the actual function key
-        match self.controller.send_function_key(func_key) {
-            Ok(()) => {
-                // Function key sent successfully
-                self.terminal_content.push_str(&format!("\n[{func_key:?}] pressed"));
-            }
-            Err(e) => {
-                self.terminal_content.push_str(&format!("\nError sending function key: {e}"));
+        // Send the actual function key to the active session
+        if let Some(session) = self.get_active_session_mut() {
+            match session.send_function_key(func_key) {
+                Ok(()) => session.terminal_content.push_str(&format!("\n[{func_key:?}] pressed")),
+                Err(e) => session.terminal_content.push_str(&format!("\nError sending function key: {e}")),
             }
         }
     }
 
-    /// Handle keyboard input events
-    pub fn handle_keyboard_input(&mut self, ctx: &egui::Context) -> bool {
+    /// Handle keyboard input events for a session, returning whether Tab moved between its fields
+    pub fn handle_keyboard_input(&mut self, ctx: &egui::Context, session_id: &str) -> bool {
         // Reset Tab flag at start of frame
         self.tab_pressed_this_frame = false;
 
-        // Handle keyboard events - check if Tab is pressed and consume it for field navigation
-        let mut tab_used_for_navigation = false;
-
-        // First, check for Tab key and handle field navigation
-        let should_handle_tab = ctx.input(|i| {
-            i.key_pressed(egui::Key::Tab) && self.connected && !self.fields_info.is_empty()
-        });
-
-        if should_handle_tab {
-            tab_used_for_navigation = true;
-            self.tab_pressed_this_frame = true;
+        // While the keymap editor is waiting for a chord, key presses belong to it
+        if self.keymap_editor.as_ref().is_some_and(|editor| editor.is_capturing()) {
+            return false;
+        }
+        // The search field takes the keys while it has focus, and a previous screen is read-only
+        if self.sessions.get_mut(session_id)
+            .is_some_and(|session| Self::handle_search_keys(ctx, session) || Self::handle_history_keys(ctx, session))
+        {
+            return false;
+        }
 
-            let is_shift = ctx.input(|i| i.modifiers.shift);
+        let Some(session) = self.sessions.get(session_id) else {
+            return false;
+        };
+        let (controller, connected) = (session.controller.clone(), session.connected);
+        let keymap = &session.keymap;
 
-            if is_shift {
-                if let Err(e) = self.controller.previous_field() {
-                    eprintln!("Failed to navigate to previous field: {e}");
-                }
-            } else if let Err(e) = self.controller.next_field() {
-                eprintln!("Failed to navigate to next field: {e}");
-            }
-        }
+        // Tab only navigates fields when there are fields; otherwise egui moves widget focus
+        let tab_navigates = connected && !session.fields_info.is_empty();
+        let mut tab_used_for_navigation = false;
 
-        // Handle other keyboard events (but not Tab if we used it for navigation)
+        // Composition in progress from a dead key or input method, when it changed this frame
+        let mut preedit = None;
+        // Clipboard shortcuts, unless a text widget has focus and takes them itself
+        let clipboard_shortcuts = ctx.memory(|m| m.focused().is_none());
+        let mut copy_requested = false;
+        let mut pasted = None;
         ctx.input(|i| {
+            // A bound printable key also produces a Text event that must not be typed
+            let mut skip_text = false;
 
-            // Handle other keyboard events
             for event in &i.events {
                 match event {
-                    egui::Event::Key { key, pressed: true, modifiers: _, .. } => {
-                        match key {
-                            egui::Key::Tab => {
-                                // Already handled above
-                            }
-                            egui::Key::Enter => {
-                                // Handle Enter in fields
-                                if let Err(e) = self.controller.send_enter() {
-                                    eprintln!("Failed to send Enter: {e}");
-                                }
-                            }
-                            egui::Key::Backspace => {
-                                if let Err(e) = self.controller.backspace() {
-                                    eprintln!("Failed to send backspace: {e}");
-                                }
-                            }
-                            egui::Key::Delete => {
-                                if let Err(e) = self.controller.delete() {
-                                    eprintln!("Failed to send delete: {e}");
-                                }
-                            }
-                            egui::Key::F1 => {
-                                if let Err(e) = self.controller.send_function_key(keyboard::FunctionKey::F1) {
-                                    eprintln!("Failed to send F1: {e}");
-                                }
-                            }
-                            egui::Key::F2 => {
-                                if let Err(e) = self.controller.send_function_key(keyboard::FunctionKey::F2) {
-                                    eprintln!("Failed to send F2: {e}");
-                                }
-                            }
-                            egui::Key::F3 => {
-                                if let Err(e) = self.controller.send_function_key(keyboard::FunctionKey::F3) {
-                                    eprintln!("Failed to send F3: {e}");
-                                }
-                            }
-                            _ => {
-                                // Let egui handle other keys normally
+                    egui::Event::Key { key, pressed: true, modifiers, .. } => {
+                        let chord = KeyChord::from_egui(*key, *modifiers);
+                        let Some(action) = keymap.action(&chord) else {
+                            // Let egui handle unbound keys normally
+                            continue;
+                        };
+                        if *key == egui::Key::Tab {
+                            if !tab_navigates {
+                                continue;
                             }
+                            tab_used_for_navigation = true;
+                        }
+                        skip_text = chord.key.chars().count() == 1 || *key == egui::Key::Space;
+                        if let Err(e) = action.perform(&controller) {
+                            eprintln!("Failed to perform {} ({chord}): {e}", action.label());
                         }
                     }
-                    egui::Event::Text(text) => {
-                        // Handle text input for fields, but only if we're connected and have fields
-                        if self.connected {
-                            for ch in text.chars() {
-                                if ch.is_ascii() && !ch.is_control() {
-                                    if let Err(e) = self.controller.type_char(ch) {
-                                        eprintln!("Failed to type character '{ch}': {e}");
-                                    }
+                    egui::Event::Text(_) if std::mem::take(&mut skip_text) => {}
+                    egui::Event::Text(text) | egui::Event::Ime(egui::ImeEvent::Commit(text)) => {
+                        preedit = Some(String::new());
+                        // Any character is typed; the controller rejects those outside the code page
+                        if connected {
+                            for ch in text.chars().filter(|ch| !ch.is_control()) {
+                                if let Err(e) = controller.type_char(ch) {
+                                    eprintln!("Failed to type character '{ch}': {e}");
                                 }
                             }
                         }
                     }
+                    egui::Event::Ime(egui::ImeEvent::Preedit(text)) => {
+                        preedit = Some(text.clone());
+                    }
+                    egui::Event::Ime(egui::ImeEvent::Disabled) => {
+                        preedit = Some(String::new());
+                    }
+                    egui::Event::Copy if clipboard_shortcuts => copy_requested = true,
+                    egui::Event::Paste(text) if clipboard_shortcuts && connected => pasted = Some(text.clone()),
                     _ => {
                         // Let egui handle other events normally
                     }
@@ -171,6 +147,20 @@ impl TN5250RApp {
             }
         });
 
+        if let Some(preedit) = preedit {
+            self.ime_preedit = preedit;
+        }
+        if copy_requested {
+            // The selection as text, or the whole screen when nothing is selected
+            terminal_selection::copy_to_clipboard(ctx, &session.shown_text(), session.selection.as_ref(), CopyFormat::Text);
+        }
+        if let Some(text) = pasted {
+            let message = terminal_selection::paste(&controller, &text, self.paste_lines);
+            if let Some(session) = self.sessions.get_mut(session_id) {
+                session.error_message = message;
+            }
+        }
+        self.tab_pressed_this_frame = tab_used_for_navigation;
         tab_used_for_navigation
     }
-}
\ No newline at end of file
+}
@@ -5,7 +5,7 @@
 
 use eframe::egui;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FunctionKey {
     F1,
     F2, 
@@ -32,6 +32,8 @@ pub enum FunctionKey {
     F23, // Attn
     F24, // Attn
     Enter, // Enter key
+    RollUp, // Page Down: the next page of a list
+    RollDown, // Page Up: the previous page of a list
 }
 
 impl FunctionKey {
@@ -63,10 +65,22 @@ impl FunctionKey {
             FunctionKey::F23 => vec![0x47, 0xF1], // F23 key (Help)
             FunctionKey::F24 => vec![0x48, 0xF1], // F24 key (Attn)
             FunctionKey::Enter => vec![0x0D], // Enter key (carriage return)
+            FunctionKey::RollUp => vec![0xF5, 0xF1], // Roll Up AID
+            FunctionKey::RollDown => vec![0xF4, 0xF1], // Roll Down AID
         }
     }
 }
 
+/// 5250 record header flag: Attention key pressed
+pub const RECORD_FLAG_ATN: u8 = 0x40;
+/// 5250 record header flag: System Request key pressed
+pub const RECORD_FLAG_SRQ: u8 = 0x04;
+
+/// Empty (no-op) 5250 record carrying `flags` in its header, ended with IAC EOR (RFC 1205)
+pub fn header_flag_record(flags: u8) -> Vec<u8> {
+    vec![0x00, 0x0A, 0x12, 0xA0, 0x00, 0x00, 0x04, flags, 0x00, 0x00, 0xFF, 0xEF]
+}
+
 /// Keyboard input types
 #[derive(Debug, Clone, PartialEq)]
 pub enum KeyboardInput {
@@ -123,6 +137,8 @@ pub fn map_virtual_key_to_function_key(key: egui::Key) -> Option<FunctionKey> {
         egui::Key::F23 => Some(FunctionKey::F23),
         egui::Key::F24 => Some(FunctionKey::F24),
         egui::Key::Enter => Some(FunctionKey::Enter),
+        egui::Key::PageDown => Some(FunctionKey::RollUp),
+        egui::Key::PageUp => Some(FunctionKey::RollDown),
         _ => None,
     }
 }
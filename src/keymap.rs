@@ -0,0 +1,665 @@
+//! Keyboard remapping
+//!
+//! A keymap binds key chords (a key plus Ctrl/Alt/Shift) to terminal actions:
+//! AID keys, Field Exit, Attn and SysReq, editing keys, or macros that type text
+//! and press keys. Each profile may name a keymap file (JSON); without one the
+//! default layout follows IBM Access Client Solutions (ACS): Enter = Field Exit,
+//! Esc = Attn, Shift+Esc = SysReq. Keymaps saved by ACS as `.kmp` files can be
+//! imported.
+//!
+//! Actions are written with ACS keyboard mnemonics (`[enter]`, `[pf5]`,
+//! `[fldext]`, `[attn]`, ...). A macro is any other text: literal characters are
+//! typed and mnemonics inside it are pressed, so `WRKSPLF[enter]` runs a command.
+//! `[[` types a literal `[`.
+//!
+//! egui, which delivers key presses to TN5250R, has no key for Right-Ctrl or
+//! Pause, so a binding on either never fires. They can still be named so that
+//! ACS files import, but the importer and the editor warn about them, and the
+//! default layout puts Enter on Ctrl+Enter and Reset on Ctrl+R instead of the
+//! ACS Right-Ctrl and Left-Ctrl.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::controller::AsyncTerminalController;
+use crate::keyboard::FunctionKey;
+
+/// Key name for the right Ctrl key on its own
+pub const RIGHT_CTRL: &str = "RightCtrl";
+/// Key name for the Pause/Break key
+pub const PAUSE: &str = "Pause";
+
+/// Keys that have no `egui::Key`: they can be named but are never pressed
+const EXTRA_KEYS: &[(&str, &[&str])] = &[
+    (RIGHT_CTRL, &["RightCtrl", "RCtrl", "RightControl", "Ctrl Right", "Right Ctrl"]),
+    (PAUSE, &["Pause", "Break"]),
+];
+
+/// Function keys in order, F1..F24
+const FUNCTION_KEYS: [FunctionKey; 24] = [
+    FunctionKey::F1, FunctionKey::F2, FunctionKey::F3, FunctionKey::F4,
+    FunctionKey::F5, FunctionKey::F6, FunctionKey::F7, FunctionKey::F8,
+    FunctionKey::F9, FunctionKey::F10, FunctionKey::F11, FunctionKey::F12,
+    FunctionKey::F13, FunctionKey::F14, FunctionKey::F15, FunctionKey::F16,
+    FunctionKey::F17, FunctionKey::F18, FunctionKey::F19, FunctionKey::F20,
+    FunctionKey::F21, FunctionKey::F22, FunctionKey::F23, FunctionKey::F24,
+];
+
+/// Canonical name for a key, accepting egui names and aliases in any case
+fn canonical_key_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    for (canonical, aliases) in EXTRA_KEYS {
+        if aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name)) {
+            return Some(canonical.to_string());
+        }
+    }
+    egui::Key::from_name(name)
+        .or_else(|| egui::Key::ALL.iter().copied().find(|key| key.name().eq_ignore_ascii_case(name)))
+        .or_else(|| match name.to_ascii_lowercase().as_str() {
+            "return" => Some(egui::Key::Enter),
+            "del" => Some(egui::Key::Delete),
+            "ins" => Some(egui::Key::Insert),
+            "pgup" => Some(egui::Key::PageUp),
+            "pgdn" => Some(egui::Key::PageDown),
+            _ => None,
+        })
+        .map(|key| key.name().to_string())
+}
+
+/// A key with the modifiers held down
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyChord {
+    /// Key name as reported by egui (`Enter`, `F1`, `A`) or `RightCtrl`/`Pause`
+    pub key: String,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    /// Chord for a key without modifiers; panics on an unknown key name
+    pub fn new(key: &str) -> Self {
+        let key = canonical_key_name(key).unwrap_or_else(|| panic!("unknown key name {key}"));
+        Self { key, ctrl: false, alt: false, shift: false }
+    }
+
+    pub fn with_ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub fn with_alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    pub fn with_shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Chord for an egui key event
+    pub fn from_egui(key: egui::Key, modifiers: egui::Modifiers) -> Self {
+        Self { key: key.name().to_string(), ctrl: modifiers.ctrl, alt: modifiers.alt, shift: modifiers.shift }
+    }
+
+    /// Whether key presses of this chord reach TN5250R at all (not for Right-Ctrl or Pause)
+    pub fn is_reachable(&self) -> bool {
+        !EXTRA_KEYS.iter().any(|(name, _)| *name == self.key)
+    }
+
+    /// Parse `Ctrl+Shift+F1`, `Shift+Esc`, `RightCtrl`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let (key, modifiers) = match parts.split_last() {
+            Some((key, modifiers)) if !key.is_empty() => (key, modifiers),
+            // "Ctrl++" binds the plus key
+            _ if text.trim().ends_with("++") => (&"Plus", &parts[..parts.len() - 2]),
+            _ => return Err(format!("Key chord '{text}' has no key")),
+        };
+        let key = canonical_key_name(key).ok_or_else(|| format!("Unknown key '{key}' in '{text}'"))?;
+        let mut chord = Self { key, ctrl: false, alt: false, shift: false };
+        for modifier in modifiers {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "alt" | "option" => chord.alt = true,
+                "shift" => chord.shift = true,
+                _ => return Err(format!("Unknown modifier '{modifier}' in '{text}'")),
+            }
+        }
+        Ok(chord)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        Self::parse(&text)
+    }
+}
+
+impl From<KeyChord> for String {
+    fn from(chord: KeyChord) -> String {
+        chord.to_string()
+    }
+}
+
+/// What a key chord does
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum KeyAction {
+    /// Enter or a function key (AID)
+    Aid(FunctionKey),
+    FieldExit,
+    Attn,
+    SysReq,
+    Reset,
+    NextField,
+    PreviousField,
+    Home,
+    Backspace,
+    Delete,
+    Insert,
+    EraseEof,
+    EraseInput,
+    ScreenReverse,
+    FieldReverse,
+    PushMode,
+    AutoPush,
+    CloseField,
+    /// Text to type, with mnemonics pressed along the way
+    Macro(String),
+}
+
+impl KeyAction {
+    /// Actions offered in the keymap editor, with an empty macro last
+    pub fn choices() -> Vec<KeyAction> {
+        let mut choices = vec![KeyAction::Aid(FunctionKey::Enter)];
+        choices.extend(FUNCTION_KEYS.iter().map(|&key| KeyAction::Aid(key)));
+        choices.extend([
+            KeyAction::Aid(FunctionKey::RollUp),
+            KeyAction::Aid(FunctionKey::RollDown),
+            KeyAction::FieldExit,
+            KeyAction::Attn,
+            KeyAction::SysReq,
+            KeyAction::Reset,
+            KeyAction::NextField,
+            KeyAction::PreviousField,
+            KeyAction::Home,
+            KeyAction::Backspace,
+            KeyAction::Delete,
+            KeyAction::Insert,
+            KeyAction::EraseEof,
+            KeyAction::EraseInput,
+            KeyAction::ScreenReverse,
+            KeyAction::FieldReverse,
+            KeyAction::PushMode,
+            KeyAction::AutoPush,
+            KeyAction::CloseField,
+            KeyAction::Macro(String::new()),
+        ]);
+        choices
+    }
+
+    /// ACS keyboard mnemonic, or the macro text
+    pub fn mnemonic(&self) -> String {
+        let mnemonic = match self {
+            KeyAction::Aid(FunctionKey::Enter) => "[enter]",
+            KeyAction::Aid(FunctionKey::RollUp) => "[pagedn]",
+            KeyAction::Aid(FunctionKey::RollDown) => "[pageup]",
+            KeyAction::Aid(key) => {
+                let n = FUNCTION_KEYS.iter().position(|k| k == key).unwrap_or(0) + 1;
+                return format!("[pf{n}]");
+            }
+            KeyAction::FieldExit => "[fldext]",
+            KeyAction::Attn => "[attn]",
+            KeyAction::SysReq => "[sysreq]",
+            KeyAction::Reset => "[reset]",
+            KeyAction::NextField => "[tab]",
+            KeyAction::PreviousField => "[backtab]",
+            KeyAction::Home => "[home]",
+            KeyAction::Backspace => "[backspace]",
+            KeyAction::Delete => "[delete]",
+            KeyAction::Insert => "[insert]",
+            KeyAction::EraseEof => "[eraseeof]",
+            KeyAction::EraseInput => "[erinp]",
+            KeyAction::ScreenReverse => "[scrrev]",
+            KeyAction::FieldReverse => "[fldrev]",
+            KeyAction::PushMode => "[push]",
+            KeyAction::AutoPush => "[autopush]",
+            KeyAction::CloseField => "[close]",
+            KeyAction::Macro(text) => return text.clone(),
+        };
+        mnemonic.to_string()
+    }
+
+    /// Name shown in the keymap editor
+    pub fn label(&self) -> String {
+        match self {
+            KeyAction::Aid(FunctionKey::Enter) => "Enter".to_string(),
+            KeyAction::Aid(FunctionKey::RollUp) => "Roll Up".to_string(),
+            KeyAction::Aid(FunctionKey::RollDown) => "Roll Down".to_string(),
+            KeyAction::Aid(key) => format!("{key:?}"),
+            KeyAction::FieldExit => "Field Exit".to_string(),
+            KeyAction::Attn => "Attn".to_string(),
+            KeyAction::SysReq => "SysReq".to_string(),
+            KeyAction::Reset => "Reset".to_string(),
+            KeyAction::NextField => "Next Field".to_string(),
+            KeyAction::PreviousField => "Previous Field".to_string(),
+            KeyAction::Home => "Home".to_string(),
+            KeyAction::Backspace => "Backspace".to_string(),
+            KeyAction::Delete => "Delete".to_string(),
+            KeyAction::Insert => "Insert".to_string(),
+            KeyAction::EraseEof => "Erase EOF".to_string(),
+            KeyAction::EraseInput => "Erase Input".to_string(),
+            KeyAction::ScreenReverse => "Screen Reverse".to_string(),
+            KeyAction::FieldReverse => "Field Reverse".to_string(),
+            KeyAction::PushMode => "Push".to_string(),
+            KeyAction::AutoPush => "Auto Push".to_string(),
+            KeyAction::CloseField => "Close".to_string(),
+            KeyAction::Macro(_) => "Macro".to_string(),
+        }
+    }
+
+    /// Action for a single mnemonic such as `[pf3]` (case-insensitive)
+    pub fn from_mnemonic(mnemonic: &str) -> Option<KeyAction> {
+        let name = mnemonic.trim().strip_prefix('[')?.strip_suffix(']')?.to_ascii_lowercase();
+        let action = match name.as_str() {
+            "enter" => KeyAction::Aid(FunctionKey::Enter),
+            "pagedn" | "rollup" => KeyAction::Aid(FunctionKey::RollUp),
+            "pageup" | "rolldown" => KeyAction::Aid(FunctionKey::RollDown),
+            "fldext" | "fieldexit" | "field+" => KeyAction::FieldExit,
+            "attn" => KeyAction::Attn,
+            "sysreq" => KeyAction::SysReq,
+            "reset" => KeyAction::Reset,
+            "tab" => KeyAction::NextField,
+            "backtab" => KeyAction::PreviousField,
+            "home" => KeyAction::Home,
+            "backspace" => KeyAction::Backspace,
+            "delete" => KeyAction::Delete,
+            "insert" => KeyAction::Insert,
+            "eraseeof" | "ereof" => KeyAction::EraseEof,
+            "erinp" | "eraseinput" => KeyAction::EraseInput,
+            "scrrev" => KeyAction::ScreenReverse,
+            "fldrev" => KeyAction::FieldReverse,
+            "push" => KeyAction::PushMode,
+            "autopush" => KeyAction::AutoPush,
+            "close" => KeyAction::CloseField,
+            other => {
+                let n: usize = other.strip_prefix("pf").or_else(|| other.strip_prefix('f'))?.parse().ok()?;
+                KeyAction::Aid(*FUNCTION_KEYS.get(n.checked_sub(1)?)?)
+            }
+        };
+        Some(action)
+    }
+
+    /// Action for a mnemonic or macro text; a macro must only use known mnemonics
+    pub fn parse(text: &str) -> Result<KeyAction, String> {
+        if let Some(action) = KeyAction::from_mnemonic(text) {
+            return Ok(action);
+        }
+        if text.is_empty() {
+            return Err("Action is empty".to_string());
+        }
+        macro_steps(text)?;
+        Ok(KeyAction::Macro(text.to_string()))
+    }
+
+    /// Perform the action on a session's controller
+    pub fn perform(&self, controller: &AsyncTerminalController) -> Result<(), String> {
+        match self {
+            KeyAction::Aid(FunctionKey::Enter) => controller.send_enter(),
+            KeyAction::Aid(key) => controller.send_function_key(*key),
+            KeyAction::FieldExit => controller.field_exit(),
+            KeyAction::Attn => controller.attn(),
+            KeyAction::SysReq => controller.sys_req(),
+            KeyAction::Reset => controller.reset_operator_error(),
+            KeyAction::NextField => controller.next_field(),
+            KeyAction::PreviousField => controller.previous_field(),
+            KeyAction::Home => controller.home(),
+            KeyAction::Backspace => controller.backspace(),
+            KeyAction::Delete => controller.delete(),
+            KeyAction::Insert => controller.toggle_insert_mode().map(|_| ()),
+            KeyAction::EraseEof => controller.erase_eof(),
+            KeyAction::EraseInput => controller.erase_input(),
+            KeyAction::ScreenReverse => controller.toggle_screen_reverse().map(|_| ()),
+            KeyAction::FieldReverse => controller.toggle_field_reverse().map(|_| ()),
+            KeyAction::PushMode => controller.toggle_push_mode().map(|_| ()),
+            KeyAction::AutoPush => controller.toggle_auto_push().map(|_| ()),
+            KeyAction::CloseField => controller.toggle_closed_field().map(|_| ()),
+            KeyAction::Macro(text) => {
+                for step in macro_steps(text)? {
+                    match step {
+                        MacroStep::Text(text) => {
+                            for ch in text.chars().filter(|ch| !ch.is_control()) {
+                                controller.type_char(ch)?;
+                            }
+                        }
+                        MacroStep::Action(action) => action.perform(controller)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for KeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())
+    }
+}
+
+impl TryFrom<String> for KeyAction {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        Self::parse(&text)
+    }
+}
+
+impl From<KeyAction> for String {
+    fn from(action: KeyAction) -> String {
+        action.mnemonic()
+    }
+}
+
+/// One step of a macro
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroStep {
+    Text(String),
+    Action(KeyAction),
+}
+
+/// Split macro text into typed text and pressed keys
+pub fn macro_steps(text: &str) -> Result<Vec<MacroStep>, String> {
+    let mut steps = Vec::new();
+    let mut literal = String::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        literal.push_str(&rest[..open]);
+        rest = &rest[open..];
+        if let Some(after) = rest.strip_prefix("[[") {
+            literal.push('[');
+            rest = after;
+            continue;
+        }
+        let close = rest.find(']').ok_or_else(|| format!("Unclosed '[' in macro '{text}'"))?;
+        let action = KeyAction::from_mnemonic(&rest[..=close])
+            .ok_or_else(|| format!("Unknown key {} in macro", &rest[..=close]))?;
+        if !literal.is_empty() {
+            steps.push(MacroStep::Text(std::mem::take(&mut literal)));
+        }
+        steps.push(MacroStep::Action(action));
+        rest = &rest[close + 1..];
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        steps.push(MacroStep::Text(literal));
+    }
+    Ok(steps)
+}
+
+/// A chord and its action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Binding {
+    pub chord: KeyChord,
+    pub action: KeyAction,
+}
+
+/// Key bindings for a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keymap {
+    pub bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::acs_default()
+    }
+}
+
+impl Keymap {
+    /// Keymap with no bindings
+    pub fn empty() -> Self {
+        Self { bindings: Vec::new() }
+    }
+
+    /// Access Client Solutions default layout
+    pub fn acs_default() -> Self {
+        let mut keymap = Self::empty();
+        keymap.bind(KeyChord::new("Enter").with_ctrl(), KeyAction::Aid(FunctionKey::Enter));
+        keymap.bind(KeyChord::new("Enter"), KeyAction::FieldExit);
+        keymap.bind(KeyChord::new("Escape"), KeyAction::Attn);
+        keymap.bind(KeyChord::new("Escape").with_shift(), KeyAction::SysReq);
+        keymap.bind(KeyChord::new("R").with_ctrl(), KeyAction::Reset);
+        keymap.bind(KeyChord::new("Tab"), KeyAction::NextField);
+        keymap.bind(KeyChord::new("Tab").with_shift(), KeyAction::PreviousField);
+        keymap.bind(KeyChord::new("Home"), KeyAction::Home);
+        keymap.bind(KeyChord::new("Backspace"), KeyAction::Backspace);
+        keymap.bind(KeyChord::new("Delete"), KeyAction::Delete);
+        keymap.bind(KeyChord::new("Insert"), KeyAction::Insert);
+        keymap.bind(KeyChord::new("End"), KeyAction::EraseEof);
+        keymap.bind(KeyChord::new("End").with_alt(), KeyAction::EraseInput);
+        keymap.bind(KeyChord::new("PageDown"), KeyAction::Aid(FunctionKey::RollUp));
+        keymap.bind(KeyChord::new("PageUp"), KeyAction::Aid(FunctionKey::RollDown));
+        keymap.bind(KeyChord::new("R").with_ctrl().with_shift(), KeyAction::ScreenReverse);
+        keymap.bind(KeyChord::new("E").with_ctrl().with_shift(), KeyAction::FieldReverse);
+        keymap.bind(KeyChord::new("P").with_ctrl().with_shift(), KeyAction::PushMode);
+        for (n, &key) in FUNCTION_KEYS.iter().enumerate() {
+            keymap.bind(KeyChord::new(&format!("F{}", n + 1)), KeyAction::Aid(key));
+        }
+        // Shift+F1..F12 are F13..F24 on keyboards without the upper row
+        for (n, &key) in FUNCTION_KEYS[12..].iter().enumerate() {
+            keymap.bind(KeyChord::new(&format!("F{}", n + 1)).with_shift(), KeyAction::Aid(key));
+        }
+        keymap
+    }
+
+    /// Action bound to `chord`
+    pub fn action(&self, chord: &KeyChord) -> Option<&KeyAction> {
+        self.bindings.iter().find(|binding| binding.chord == *chord).map(|binding| &binding.action)
+    }
+
+    /// Bind `chord`, replacing any existing binding for it
+    pub fn bind(&mut self, chord: KeyChord, action: KeyAction) {
+        match self.bindings.iter_mut().find(|binding| binding.chord == chord) {
+            Some(binding) => binding.action = action,
+            None => self.bindings.push(Binding { chord, action }),
+        }
+    }
+
+    /// Remove the binding for `chord`
+    pub fn unbind(&mut self, chord: &KeyChord) {
+        self.bindings.retain(|binding| binding.chord != *chord);
+    }
+
+    /// Chords bound more than once (possible after editing)
+    pub fn duplicates(&self) -> Vec<KeyChord> {
+        let mut duplicates = Vec::new();
+        for (i, binding) in self.bindings.iter().enumerate() {
+            if self.bindings[..i].iter().any(|earlier| earlier.chord == binding.chord) && !duplicates.contains(&binding.chord) {
+                duplicates.push(binding.chord.clone());
+            }
+        }
+        duplicates
+    }
+
+    /// Chords bound to keys that never reach TN5250R, so their bindings never fire
+    pub fn unreachable(&self) -> Vec<KeyChord> {
+        self.bindings.iter().map(|binding| &binding.chord).filter(|chord| !chord.is_reachable()).cloned().collect()
+    }
+
+    /// Directory keymap files are saved to by default
+    pub fn keymap_dir() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("tn5250r")
+            .join("keymaps")
+    }
+
+    /// Load a keymap file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Cannot read keymap {}: {e}", path.display()))?;
+        serde_json::from_str(&text).map_err(|e| format!("Invalid keymap {}: {e}", path.display()))
+    }
+
+    /// Keymap for a profile's keymap file setting; the default layout when unset
+    pub fn for_profile(path: Option<&str>) -> Result<Self, String> {
+        match path.map(str::trim).filter(|path| !path.is_empty()) {
+            Some(path) => Self::load(Path::new(path)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Save as a keymap file, creating its directory
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {e}", dir.display()))?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| format!("Cannot write keymap {}: {e}", path.display()))
+    }
+
+    /// Import an ACS keyboard file (`.kmp`) on top of the default layout
+    ///
+    /// Each `key=function` line remaps one key; `[section]` headers and `#`, `;`
+    /// or `!` comments are skipped. Keys are chord names (`Ctrl+Enter`, `RCtrl`)
+    /// or Java key codes, `keyCode[,modifiers[,location]]`, where the modifiers
+    /// are a Java InputEvent mask and location 3 is the right-hand key. Functions
+    /// are mnemonics or macro text; an empty function unbinds the key. Lines that
+    /// cannot be read are listed in the result instead of failing the import, and
+    /// so are lines bound to a key that never reaches TN5250R.
+    pub fn import_kmp(text: &str) -> KmpImport {
+        let mut keymap = Self::acs_default();
+        let mut skipped = Vec::new();
+        let mut unreachable = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(['#', ';', '!']) || (line.starts_with('[') && !line.contains('=')) {
+                continue;
+            }
+            let Some((key, function)) = line.split_once('=') else {
+                skipped.push(format!("{line}: not a key=function entry"));
+                continue;
+            };
+            let chord = match parse_kmp_key(key.trim()) {
+                Ok(chord) => chord,
+                Err(e) => {
+                    skipped.push(format!("{line}: {e}"));
+                    continue;
+                }
+            };
+            let function = function.trim();
+            if function.is_empty() {
+                keymap.unbind(&chord);
+                continue;
+            }
+            match KeyAction::parse(function) {
+                Ok(action) => {
+                    if !chord.is_reachable() {
+                        unreachable.push(format!("{line}: {} never reaches TN5250R, bind {} to another key", chord.key, action.label()));
+                    }
+                    keymap.bind(chord, action);
+                }
+                Err(e) => skipped.push(format!("{line}: {e}")),
+            }
+        }
+        KmpImport { keymap, skipped, unreachable }
+    }
+
+    /// Import an ACS keyboard file from disk
+    pub fn import_kmp_file(path: &Path) -> Result<KmpImport, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+        Ok(Self::import_kmp(&String::from_utf8_lossy(&bytes)))
+    }
+}
+
+/// Result of importing an ACS keyboard file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KmpImport {
+    pub keymap: Keymap,
+    /// Lines that were not imported, with the reason
+    pub skipped: Vec<String>,
+    /// Lines imported onto keys that are never pressed, with a warning
+    pub unreachable: Vec<String>,
+}
+
+/// Java InputEvent modifier masks (old and extended forms)
+const JAVA_SHIFT: u32 = 0x01 | 0x40;
+const JAVA_CTRL: u32 = 0x02 | 0x80;
+const JAVA_ALT: u32 = 0x08 | 0x200;
+/// Java KeyEvent.KEY_LOCATION_RIGHT
+const JAVA_LOCATION_RIGHT: u32 = 3;
+
+/// Key named by a Java KeyEvent key code
+fn java_key_name(code: u32, location: u32) -> Option<String> {
+    let name = match code {
+        8 => "Backspace",
+        9 => "Tab",
+        10 => "Enter",
+        17 if location == JAVA_LOCATION_RIGHT => RIGHT_CTRL,
+        19 => PAUSE,
+        27 => "Escape",
+        32 => "Space",
+        33 => "PageUp",
+        34 => "PageDown",
+        35 => "End",
+        36 => "Home",
+        37 => "ArrowLeft",
+        38 => "ArrowUp",
+        39 => "ArrowRight",
+        40 => "ArrowDown",
+        127 => "Delete",
+        155 => "Insert",
+        48..=57 | 65..=90 => return char::from_u32(code).map(String::from),
+        112..=123 => return Some(format!("F{}", code - 111)),
+        61440..=61451 => return Some(format!("F{}", code - 61427)),
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+/// Chord for the key side of a `.kmp` entry
+fn parse_kmp_key(key: &str) -> Result<KeyChord, String> {
+    if !key.starts_with(|c: char| c.is_ascii_digit()) {
+        return KeyChord::parse(key);
+    }
+    let numbers: Vec<u32> = key
+        .split([',', ' ', '.'])
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().map_err(|_| format!("Invalid key code '{key}'")))
+        .collect::<Result<_, _>>()?;
+    let (code, modifiers, location) = match numbers[..] {
+        [code] => (code, 0, 0),
+        [code, modifiers] => (code, modifiers, 0),
+        [code, modifiers, location] => (code, modifiers, location),
+        _ => return Err(format!("Invalid key code '{key}'")),
+    };
+    let name = java_key_name(code, location).ok_or_else(|| format!("Unsupported key code {code}"))?;
+    let mut chord = KeyChord::parse(&name)?;
+    chord.shift = modifiers & JAVA_SHIFT != 0;
+    chord.ctrl = modifiers & JAVA_CTRL != 0 && chord.key != RIGHT_CTRL;
+    chord.alt = modifiers & JAVA_ALT != 0;
+    Ok(chord)
+}
@@ -0,0 +1,252 @@
+//! Trust-on-first-use certificate store for TLS connections
+//!
+//! Like SSH's `known_hosts`, certificates that do not chain to a trusted CA can
+//! be accepted by the user, either for this run or permanently. Permanently
+//! trusted certificates are stored by SHA-256 fingerprint under the config
+//! directory; a different certificate from a known host is reported as a
+//! change rather than a first contact.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, DigitallySignedStruct, OtherError, RootCertStore, SignatureScheme};
+use sha2::{Digest, Sha256};
+
+use crate::error::NetworkError;
+
+/// Certificates accepted "once" stay trusted until the application exits
+static SESSION_TRUST: once_cell::sync::Lazy<Mutex<HashMap<String, String>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Details of a server certificate shown when asking the user to trust it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateInfo {
+    /// Host and port, e.g. "as400.example.com:992"
+    pub host: String,
+    /// SHA-256 fingerprint of the certificate, colon-separated hex
+    pub fingerprint: String,
+    pub subject: String,
+    pub issuer: String,
+    /// Expiry date
+    pub not_after: String,
+    /// Why normal validation rejected the certificate
+    pub reason: String,
+}
+
+impl CertificateInfo {
+    /// Describe a DER certificate presented by `host`
+    pub fn from_der(host: &str, der: &[u8], reason: &str) -> Result<Self, String> {
+        let (_, cert) = x509_parser::parse_x509_certificate(der).map_err(|e| format!("Invalid certificate: {e}"))?;
+        Ok(Self {
+            host: host.to_string(),
+            fingerprint: fingerprint(der),
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            not_after: cert.validity().not_after.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+}
+
+/// A certificate waiting for the user's decision
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificatePrompt {
+    pub certificate: CertificateInfo,
+    /// Fingerprint previously trusted for this host, when the certificate has changed
+    pub known_fingerprint: Option<String>,
+}
+
+impl CertificatePrompt {
+    /// Prompt for an untrusted or changed certificate error
+    pub fn from_error(error: &NetworkError) -> Option<Self> {
+        match error {
+            NetworkError::UntrustedCertificate { certificate } => {
+                Some(Self { certificate: (**certificate).clone(), known_fingerprint: None })
+            }
+            NetworkError::CertificateChanged { certificate, known_fingerprint } => Some(Self {
+                certificate: (**certificate).clone(),
+                known_fingerprint: Some(known_fingerprint.clone()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Prompt carried by an I/O error returned from a connect attempt
+    pub fn from_io_error(error: &std::io::Error) -> Option<Self> {
+        error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<NetworkError>())
+            .and_then(Self::from_error)
+    }
+
+    /// True when the host previously presented a different certificate
+    pub fn is_change(&self) -> bool {
+        self.known_fingerprint.is_some()
+    }
+}
+
+/// SHA-256 fingerprint of a DER certificate as colon-separated hex
+pub fn fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der).iter().map(|b| format!("{b:02X}")).collect::<Vec<_>>().join(":")
+}
+
+/// Trust a certificate until the application exits
+pub fn trust_once(certificate: &CertificateInfo) {
+    if let Ok(mut trusted) = SESSION_TRUST.lock() {
+        trusted.insert(certificate.host.clone(), certificate.fingerprint.clone());
+    }
+}
+
+fn trusted_once(host: &str) -> Option<String> {
+    SESSION_TRUST.lock().ok().and_then(|trusted| trusted.get(host).cloned())
+}
+
+/// Permanently trusted certificates, one `host:port fingerprint` line each
+#[derive(Debug, Clone)]
+pub struct KnownHosts {
+    path: PathBuf,
+    entries: HashMap<String, String>,
+}
+
+impl KnownHosts {
+    /// `known_hosts` under the TN5250R config directory
+    pub fn default_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("tn5250r")
+            .join("known_hosts")
+    }
+
+    /// Load the store; a missing file is an empty store
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut entries = HashMap::new();
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+                    let mut parts = line.split_whitespace();
+                    if let (Some(host), Some(fingerprint)) = (parts.next(), parts.next()) {
+                        entries.insert(host.to_string(), fingerprint.to_string());
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Cannot read {}: {e}", path.display())),
+        }
+        Ok(Self { path: path.to_path_buf(), entries })
+    }
+
+    /// Trusted fingerprint for a `host:port`
+    pub fn fingerprint(&self, host: &str) -> Option<&str> {
+        self.entries.get(host).map(String::as_str)
+    }
+
+    /// Trust a certificate permanently, replacing any previous one for its host
+    pub fn trust(&mut self, certificate: &CertificateInfo) -> Result<(), String> {
+        self.entries.insert(certificate.host.clone(), certificate.fingerprint.clone());
+        self.save()
+    }
+
+    /// Forget a host
+    pub fn remove(&mut self, host: &str) -> Result<(), String> {
+        if self.entries.remove(host).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {e}", dir.display()))?;
+        }
+        let mut hosts: Vec<_> = self.entries.iter().collect();
+        hosts.sort();
+        let mut text = String::from("# TN5250R trusted server certificates: host:port SHA-256 fingerprint\n");
+        for (host, fingerprint) in hosts {
+            text.push_str(&format!("{host} {fingerprint}\n"));
+        }
+        fs::write(&self.path, text).map_err(|e| format!("Cannot write {}: {e}", self.path.display()))
+    }
+}
+
+/// Verifier that falls back to the known-hosts store when CA validation fails
+#[derive(Debug)]
+pub struct TofuCertVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    host: String,
+    known_hosts_path: PathBuf,
+}
+
+impl TofuCertVerifier {
+    /// Verifier for connections to `host` (as `host:port`)
+    pub fn new(roots: RootCertStore, host: String, known_hosts_path: PathBuf) -> Result<Self, NetworkError> {
+        let inner = WebPkiServerVerifier::builder(Arc::new(roots))
+            .build()
+            .map_err(|e| NetworkError::SslError { message: format!("Cannot build certificate verifier: {e}") })?;
+        Ok(Self { inner, host, known_hosts_path })
+    }
+
+    fn reject(error: NetworkError) -> rustls::Error {
+        rustls::Error::InvalidCertificate(CertificateError::Other(OtherError(Arc::new(error))))
+    }
+}
+
+impl ServerCertVerifier for TofuCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let reason = match self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now) {
+            Ok(verified) => return Ok(verified),
+            Err(rustls::Error::InvalidCertificate(e)) => format!("{e:?}"),
+            Err(e) => return Err(e),
+        };
+
+        let certificate = CertificateInfo::from_der(&self.host, end_entity, &reason)
+            .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        if trusted_once(&self.host).as_deref() == Some(certificate.fingerprint.as_str()) {
+            return Ok(ServerCertVerified::assertion());
+        }
+
+        let known_hosts = KnownHosts::load(&self.known_hosts_path)
+            .map_err(|message| Self::reject(NetworkError::SslError { message }))?;
+        match known_hosts.fingerprint(&self.host) {
+            Some(known) if known == certificate.fingerprint => Ok(ServerCertVerified::assertion()),
+            Some(known) => Err(Self::reject(NetworkError::CertificateChanged {
+                known_fingerprint: known.to_string(),
+                certificate: Box::new(certificate),
+            })),
+            None => Err(Self::reject(NetworkError::UntrustedCertificate { certificate: Box::new(certificate) })),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
@@ -30,16 +30,31 @@ pub mod lib5250;
 /// Complete TN3270 protocol support for mainframe systems
 pub mod lib3270;
 pub mod ansi_processor;
+pub mod clipboard;
+pub mod color_scheme;
 pub mod config;
 pub mod controller;
 pub mod error_handling;
 pub mod error;
 pub mod field_manager;
+pub mod hotspots;
 pub mod keyboard;
+pub mod keymap;
 pub mod monitoring;
 pub mod network;
+pub mod network_tls;
+pub mod network_proxy;
+pub mod network_ssh;
+pub mod network_actor;
+pub mod known_hosts;
+pub mod password_substitute;
+pub mod device_name;
 pub mod protocol_state;
+pub mod screen_grid;
+pub mod screen_history;
+pub mod screen_search;
 pub mod telnet_negotiation;
+pub mod telnet_environment;
 pub mod terminal;
 pub mod test_field_detection;
 
@@ -73,6 +88,12 @@ pub mod ui {
     pub mod dialogs;
     pub mod function_keys;
     pub mod profile_manager_ui;
+    pub mod keymap_editor;
+    pub mod terminal_selection;
+    pub mod session_tabs;
+    pub mod split_view;
+    pub mod history_view;
+    pub mod search_bar;
 }
 
 /// Application constants
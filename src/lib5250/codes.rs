@@ -85,6 +85,15 @@ pub const FCW_SIGNED_NUMERIC: u8 = 0x83;
 pub const FCW_RIGHT_ADJUST_ZERO_FILL: u8 = 0x84;
 pub const FCW_MANDATORY_FILL: u8 = 0x85;
 pub const FCW_MANDATORY_ENTRY: u8 = 0x86;
+// BIDI screen and field direction are not taken from the data stream: the screen
+// follows the host code page, and Screen Reverse, Field Reverse, Auto Push and
+// Close are operator functions, as on IBM BIDI keyboards
+
+/// Continued field segment positions (FCW_CONTINUOUS_FIELD data)
+pub const CONTINUED_FIRST: u8 = 0x01;
+pub const CONTINUED_LAST: u8 = 0x02;
+pub const CONTINUED_MIDDLE: u8 = 0x03;
+
 pub const SF_5250_QUERY: u8 = 0x70;
 pub const SF_5250_QUERY_STATION_STATE: u8 = 0x72;
 
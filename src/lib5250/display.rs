@@ -4,7 +4,7 @@
 //! that are called by the session module during 5250 protocol processing.
 
 use crate::ebcdic;
-use crate::terminal::TerminalScreen;
+use crate::terminal::{TerminalScreen, TextDirection};
 
 /// Display buffer that manages the 5250 terminal screen state
 /// This is a bridge between lib5250 session logic and our TerminalScreen
@@ -31,6 +31,14 @@ pub struct Display {
     pending_insert: bool,
     insert_cursor_row: usize,
     insert_cursor_col: usize,
+
+    /// Host code page used to decode EBCDIC screen data
+    code_page: u16,
+}
+
+/// True for 5250 screen attribute bytes, which start a run of display attributes
+pub fn is_screen_attribute(byte: u8) -> bool {
+    (0x20..=0x3F).contains(&byte)
 }
 
 // Display indicator flags (from original lib5250)
@@ -56,6 +64,7 @@ impl Display {
             pending_insert: false,
             insert_cursor_row: 0,
             insert_cursor_col: 0,
+            code_page: 37,
         }
     }
 
@@ -90,6 +99,27 @@ impl Display {
         self.height
     }
 
+    /// Host code page used to decode EBCDIC screen data
+    pub fn code_page(&self) -> u16 {
+        self.code_page
+    }
+
+    /// Set the host code page; the screen direction follows the code page's default
+    pub fn set_code_page(&mut self, code_page: u16) {
+        self.code_page = code_page;
+        self.screen.set_direction(TextDirection::for_code_page(code_page));
+    }
+
+    /// Current screen direction
+    pub fn screen_direction(&self) -> TextDirection {
+        self.screen.direction
+    }
+
+    /// Set the screen direction (screen reverse)
+    pub fn set_screen_direction(&mut self, direction: TextDirection) {
+        self.screen.set_direction(direction);
+    }
+
     // ===== Core display functions from original lib5250 =====
 
     /// Clear the display and set to standard 24x80 size
@@ -174,13 +204,20 @@ impl Display {
     /// Add a character at the current cursor position
     /// Equivalent to tn5250_display_addch()
     pub fn addch(&mut self, ch: u8) {
-        // Convert EBCDIC to ASCII if needed
-        let ascii_char = self.ebcdic_to_ascii(ch);
-        
         if self.cursor_row < self.height && self.cursor_col < self.width {
-                            // Move cursor to position first, then add character
-                            self.screen.move_cursor(self.cursor_col, self.cursor_row);
-                self.screen.write_char(ascii_char);
+            if is_screen_attribute(ch) {
+                // Attribute bytes occupy a blank position and style what follows
+                let index = self.screen.index(self.cursor_col, self.cursor_row);
+                self.screen.buffer[index] = crate::terminal::TerminalChar {
+                    character: ' ',
+                    attribute: crate::terminal::CharAttribute::FieldAttribute(ch),
+                };
+                self.screen.dirty = true;
+            } else {
+                // Move cursor to position first, then add character
+                self.screen.move_cursor(self.cursor_col, self.cursor_row);
+                self.screen.write_char(self.ebcdic_to_ascii(ch));
+            }
             
             // Advance cursor
             self.cursor_col += 1;
@@ -202,10 +239,10 @@ impl Display {
         self.screen.erase_region(start_row, start_col, end_row, end_col);
     }
 
-    /// Roll/scroll display region
+    /// Roll/scroll display region (0-based rows, positive lines = down)
     /// Equivalent to tn5250_display_roll()
-        pub fn roll(&mut self, top: u8, bottom: u8, lines: i8) {
-            self.screen.roll(top as usize, bottom as usize, lines);
+    pub fn roll(&mut self, top: u8, bottom: u8, lines: i8) {
+        self.screen.roll(top as usize, bottom as usize, lines);
     }
 
     /// Set pending insert cursor position
@@ -221,6 +258,11 @@ impl Display {
         self.set_pending_insert(row, col);
     }
 
+    /// Get the pending insert cursor position set by an IC order, if any
+    pub fn pending_insert_cursor(&self) -> Option<(usize, usize)> {
+        self.pending_insert.then_some((self.insert_cursor_row, self.insert_cursor_col))
+    }
+
     /// Add a character at the current cursor position (alias for addch)
     pub fn add_char(&mut self, ch: u8) {
         self.addch(ch);
@@ -236,6 +278,28 @@ impl Display {
         self.cursor_col
     }
 
+    /// Get the display attribute of the cell at (row, col), 0-based
+    pub fn cell_attribute(&self, row: usize, col: usize) -> crate::terminal::CharAttribute {
+        if row < self.height && col < self.width {
+            self.screen.buffer[self.screen.index(col, row)].attribute
+        } else {
+            crate::terminal::CharAttribute::Normal
+        }
+    }
+
+    /// Get an EBCDIC byte that `addch` decodes back to the character at (row, col)
+    pub fn cell_ebcdic(&self, row: usize, col: usize) -> u8 {
+        if row < self.height && col < self.width {
+            let cell = self.screen.buffer[self.screen.index(col, row)];
+            if let crate::terminal::CharAttribute::FieldAttribute(byte) = cell.attribute {
+                return byte;
+            }
+            ebcdic::char_to_ebcdic(cell.character, self.code_page).unwrap_or(0x40)
+        } else {
+            0x40
+        }
+    }
+
     /// Get screen data as bytes for 5250 protocol transmission
     pub fn get_screen_data(&self) -> Vec<u8> {
         let mut data = Vec::new();
@@ -356,7 +420,8 @@ impl Display {
             let index = self.screen.index(self.cursor_col, self.cursor_row);
             if blinking {
                 self.screen.buffer[index].attribute = crate::terminal::CharAttribute::BlinkingCursor;
-            } else {
+            } else if self.screen.buffer[index].attribute == crate::terminal::CharAttribute::BlinkingCursor {
+                // Only clear our own attribute so unrelated highlighting survives
                 self.screen.buffer[index].attribute = crate::terminal::CharAttribute::Normal;
             }
             self.screen.dirty = true;
@@ -382,7 +447,8 @@ impl Display {
             let index = self.screen.index(self.cursor_col, self.cursor_row);
             if reverse {
                 self.screen.buffer[index].attribute = crate::terminal::CharAttribute::ReverseImage;
-            } else {
+            } else if self.screen.buffer[index].attribute == crate::terminal::CharAttribute::ReverseImage {
+                // Only clear our own attribute so unrelated highlighting survives
                 self.screen.buffer[index].attribute = crate::terminal::CharAttribute::Normal;
             }
             self.screen.dirty = true;
@@ -397,7 +463,8 @@ impl Display {
             let index = self.screen.index(self.cursor_col, self.cursor_row);
             if underline {
                 self.screen.buffer[index].attribute = crate::terminal::CharAttribute::Underline;
-            } else {
+            } else if self.screen.buffer[index].attribute == crate::terminal::CharAttribute::Underline {
+                // Only clear our own attribute so unrelated highlighting survives
                 self.screen.buffer[index].attribute = crate::terminal::CharAttribute::Normal;
             }
             self.screen.dirty = true;
@@ -450,10 +517,13 @@ impl Display {
 
     /// Set font attributes for display
     /// This is a placeholder implementation for font support
-    pub fn set_font_attributes(&mut self, _bold: bool, _italic: bool, _underline: bool) {
-        // Font attributes are not currently supported in the basic TerminalChar structure
-        // In a full implementation, this would extend TerminalChar to include font information
-        // For now, this is a no-op that maintains compatibility
+    pub fn set_font_attributes(&mut self, _bold: bool, _italic: bool, underline: bool) {
+        // Only underline has a TerminalChar representation; bold and italic are ignored
+        if underline && self.cursor_row < self.height && self.cursor_col < self.width {
+            let index = self.screen.index(self.cursor_col, self.cursor_row);
+            self.screen.buffer[index].attribute = crate::terminal::CharAttribute::Underline;
+            self.screen.dirty = true;
+        }
     }
 
     /// Set display intensity
@@ -540,9 +610,9 @@ impl Display {
 
     // ===== EBCDIC conversion =====
 
-    /// Convert EBCDIC character to ASCII using proper EBCDIC CP037 table
+    /// Convert EBCDIC character using the session code page (CP037 unless a BIDI page is set)
     fn ebcdic_to_ascii(&self, ebcdic: u8) -> char {
-        ebcdic::ebcdic_to_ascii(ebcdic)
+        ebcdic::ebcdic_to_char(ebcdic, self.code_page)
     }
 }
 
@@ -11,6 +11,19 @@ pub struct Field {
     pub attribute: FieldAttribute,
     /// Modified Data Tag - set when field content is modified by user input
     pub mdt: bool,
+    /// Field Format Word from the SF order (None for output-only fields)
+    pub ffw: Option<u16>,
+    /// Field Control Words (type, data) in the order they were received
+    pub fcws: Vec<(u8, u8)>,
+    /// Raw attribute byte from the SF order
+    pub attribute_byte: u8,
+}
+
+impl Field {
+    /// Whether the host asked for word wrap through the field's FCWs
+    pub fn word_wrap(&self) -> bool {
+        self.fcws.iter().any(|&(fcw_type, data)| fcw_type == super::codes::FCW_WORD_WRAP && data & 0x01 != 0)
+    }
 }
 
 /// Detect fields from a terminal screen and parse attributes
@@ -29,6 +42,9 @@ pub fn detect_fields_from_screen(screen: &crate::terminal::TerminalScreen) -> Ve
                 length,
                 attribute: FieldAttribute::Protected, // Stub: always Protected
                 mdt: false, // Initialize MDT as false
+                ffw: None,
+                fcws: Vec::new(),
+                attribute_byte: 0x20,
             });
         }
     }
@@ -66,6 +82,9 @@ pub fn detect_fields_from_protocol_data(data: &[u8]) -> Vec<Field> {
                     length: field_length,
                     attribute,
                     mdt: false, // Initialize MDT as false
+                    ffw: None,
+                    fcws: Vec::new(),
+                    attribute_byte,
                 });
             } else {
                 break;
@@ -237,6 +237,16 @@ impl Session {
         self.display.to_string()
     }
 
+    /// Set the host code page used to decode screen data; BIDI code pages default to a reversed screen
+    pub fn set_code_page(&mut self, code_page: u16) {
+        self.display.set_code_page(code_page);
+    }
+
+    /// Host code page used to decode screen data
+    pub fn code_page(&self) -> u16 {
+        self.display.code_page()
+    }
+
     /// Get current cursor position (1-based row, col for UI convenience)
     pub fn cursor_position(&self) -> (usize, usize) {
         let (r, c) = self.display.cursor_position();
@@ -424,6 +434,7 @@ impl Session {
     /// Clear Format Table command
     fn clear_format_table(&mut self) {
         self.display.clear_format_table();
+        self.fields.clear();
         self.read_opcode = 0;
     }
     
@@ -600,11 +611,13 @@ impl Session {
         // Reset field attributes for new field
         self.current_field_attributes = FieldAttributes::default();
 
-        if (first_byte & 0xE0) != 0x20 {
+        let mut ffw = None;
+        let mut fcws = Vec::new();
+        let attribute = if (first_byte & 0xE0) != 0x20 {
             // Input field - has Field Format Word (FFW)
             let ffw1 = first_byte;
             let ffw2 = self.get_byte()?;
-            let _ffw = (ffw1 as u16) << 8 | ffw2 as u16;
+            ffw = Some((ffw1 as u16) << 8 | ffw2 as u16);
 
             // Process Field Control Words (FCW) if present
             let mut next_byte = self.get_byte()?;
@@ -614,58 +627,77 @@ impl Session {
 
                 // Parse FCW based on type
                 self.parse_fcw(fcw_type, fcw_data)?;
+                fcws.push((fcw_type, fcw_data));
 
                 next_byte = self.get_byte()?;
             }
-
-            // Attribute byte
-            let attribute = next_byte;
-            self.display.add_char(attribute);
-
-            // Field length
-            let len1 = self.get_byte()?;
-            let len2 = self.get_byte()?;
-            let _length = (len1 as u16) << 8 | len2 as u16;
-
-            // Create and add input field with proper attributes
-            let field = Field {
-                label: None,
-                row: self.display.cursor_row(),
-                col: self.display.cursor_col(),
-                length: _length as usize,
-                attribute: super::field::parse_field_attribute(attribute),
-                mdt: false, // Initialize MDT as false
-            };
-            self.fields.push(field);
-
-            println!("5250: Start of input field - FFW: 0x{:04X}, Length: {}, Attributes: {:?}", _ffw, _length, self.current_field_attributes);
-
+            next_byte
         } else {
             // Output-only field - just attribute
-            let attribute = first_byte;
-            self.display.add_char(attribute);
+            first_byte
+        };
 
-            let len1 = self.get_byte()?;
-            let len2 = self.get_byte()?;
-            let _length = (len1 as u16) << 8 | len2 as u16;
+        self.display.add_char(attribute);
 
-            // Create and add output field
-            let field = Field {
-                label: None,
-                row: self.display.cursor_row(),
-                col: self.display.cursor_col(),
-                length: _length as usize,
-                attribute: super::field::parse_field_attribute(attribute),
-                mdt: false, // Initialize MDT as false
-            };
-            self.fields.push(field);
+        // Field length
+        let len1 = self.get_byte()?;
+        let len2 = self.get_byte()?;
+        let length = (len1 as u16) << 8 | len2 as u16;
+
+        let field = Field {
+            label: None,
+            row: self.display.cursor_row(),
+            col: self.display.cursor_col(),
+            length: length as usize,
+            attribute: super::field::parse_field_attribute(attribute),
+            // FFW bit 4 of the first byte carries the Modified Data Tag
+            mdt: ffw.is_some_and(|ffw| ffw & 0x0800 != 0),
+            ffw: ffw.map(|ffw| ffw & !0x0800),
+            fcws,
+            attribute_byte: attribute,
+        };
+
+        // A field redefined at the same position replaces the old format table entry
+        self.fields.retain(|f| f.row != field.row || f.col != field.col);
+        self.fields.push(field);
 
-            println!("5250: Start of output field - Length: {_length}");
+        match ffw {
+            Some(ffw) => println!("5250: Start of input field - FFW: 0x{:04X}, Length: {}, Attributes: {:?}", ffw, length, self.current_field_attributes),
+            None => println!("5250: Start of output field - Length: {length}"),
         }
 
         Ok(())
     }
 
+    /// Group continued field segments into logical fields (indices into `fields`)
+    ///
+    /// Segments chain from a first segment through any middle ones to a last one;
+    /// a new first segment always starts a new group.
+    pub fn continued_field_groups(&self) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut open = false;
+        for (idx, field) in self.fields.iter().enumerate() {
+            let position = field.fcws.iter()
+                .find(|(fcw_type, data)| *fcw_type == FCW_CONTINUOUS_FIELD && *data != 0)
+                .map(|&(_, data)| data);
+            match position {
+                Some(CONTINUED_FIRST) => {
+                    groups.push(vec![idx]);
+                    open = true;
+                }
+                Some(position) if open => {
+                    if let Some(group) = groups.last_mut() {
+                        group.push(idx);
+                    }
+                    open = position != CONTINUED_LAST;
+                }
+                _ => open = false,
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+
     /// Parse Field Control Word (FCW) and update field attributes
     fn parse_fcw(&mut self, fcw_type: u8, fcw_data: u8) -> Result<(), String> {
         match fcw_type {
@@ -678,8 +710,8 @@ impl Session {
 
             super::codes::FCW_CONTINUOUS_FIELD => {
                 // FCW 0x81: Continuous field control
-                // Bit 0: Continuous field (field continues to next line)
-                self.current_field_attributes.continuous = (fcw_data & 0x01) != 0;
+                // Data gives the segment position: first, middle or last
+                self.current_field_attributes.continuous = fcw_data != 0;
                 println!("5250: FCW Continuous Field - enabled: {}", self.current_field_attributes.continuous);
             }
 
@@ -792,6 +824,13 @@ impl Session {
         if attributes.contains(&0x00) {
             // Erase all characters in the region (fill with nulls)
             self.display.erase_region(start_row, start_col, end_row, end_col, 0, self.display.width());
+
+            // Fields whose attribute byte was erased no longer exist
+            let width = self.display.width();
+            self.fields.retain(|field| {
+                let (row, col) = Self::field_attribute_position(field, width);
+                row < start_row || row > end_row || col < start_col || col > end_col
+            });
         } else if attributes.contains(&0x01) {
             // Erase unprotected fields only
             self.erase_unprotected_fields_only(start_row, start_col, end_row, end_col);
@@ -1037,172 +1076,187 @@ impl Session {
     }
     
     /// Save Screen command
+    ///
+    /// The saved image is a command stream that, when sent back after a
+    /// Restore Screen command, rebuilds the text, format table, extended
+    /// attributes, indicators and cursor exactly as they were.
     fn save_screen(&mut self) -> Result<Vec<u8>, String> {
+        let width = self.display.width();
+        let height = self.display.height();
         let mut data = Vec::new();
 
-        // ESC (0x04)
+        // Start from a clean unit of the same size
         data.push(ESC);
+        if width == 132 {
+            data.push(CMD_CLEAR_UNIT_ALTERNATE);
+            data.push(0x00);
+        } else {
+            data.push(CMD_CLEAR_UNIT);
+        }
 
-        // WriteToDisplay command (0x11)
+        data.push(ESC);
         data.push(CMD_WRITE_TO_DISPLAY);
+        data.push(0x00); // CC1 - clear unit already locked the keyboard
+        data.push(self.restore_cc2());
 
-        // Sequence number
-        data.push(self.sequence_number);
-        self.sequence_number = self.sequence_number.wrapping_add(1);
-
-        // Length placeholder (2 bytes) - will be calculated
-        let length_pos = data.len();
-        data.extend_from_slice(&[0x00, 0x00]);
-
-        // Flags (0x00)
-        data.push(0x00);
-
-        // Control Character 1 - Lock keyboard, reset MDT
-        data.push(0xC0);
-
-        // Control Character 2 - Unlock keyboard after processing
-        data.push(0x02);
-
-        // Generate display orders to recreate current screen
-        self.generate_screen_display_orders(&mut data)?;
-
-        // Calculate and set length
-        let length = (data.len() - length_pos - 2) as u16;
-        data[length_pos] = (length >> 8) as u8;
-        data[length_pos + 1] = (length & 0xFF) as u8;
-
-        // Add read command if we were in a read operation
-        if self.read_opcode != 0 {
-            data.push(ESC);
-            data.push(self.read_opcode);
-            data.push(0x00); // CC1
-            data.push(0x00); // CC2
-        }
+        self.generate_screen_display_orders(&mut data, 0, 0, height, width)?;
+        self.append_saved_read_command(&mut data);
 
         Ok(data)
     }
 
-    /// Generate display orders to recreate the current screen state
-    fn generate_screen_display_orders(&self, data: &mut Vec<u8>) -> Result<(), String> {
-        let screen_data = self.display.get_screen_data();
+    /// Generate display orders to recreate a region of the screen (0-based, exclusive ends)
+    fn generate_screen_display_orders(&self, data: &mut Vec<u8>, start_row: usize, start_col: usize, end_row: usize, end_col: usize) -> Result<(), String> {
         let width = self.display.width();
-        let height = self.display.height();
 
-        // Iterate through each position on the screen
-        for row in 0..height {
-            for col in 0..width {
-                let index = row * width + col;
-                let ebcdic_char = screen_data[index];
-
-                // Skip null characters (0x00) - they represent empty/unused positions
-                if ebcdic_char != 0x00 {
-                    // Set Buffer Address (SBA) order
-                    data.push(SBA);
-                    data.push((row + 1) as u8); // 1-based row
-                    data.push((col + 1) as u8); // 1-based column
-
-                    // Add the character
-                    data.push(ebcdic_char);
+        // Text, written transparently so attribute bytes survive the round trip
+        for row in start_row..end_row {
+            let bytes: Vec<u8> = (start_col..end_col).map(|col| self.display.cell_ebcdic(row, col)).collect();
+            if bytes.iter().all(|&b| b == 0x40) {
+                continue;
+            }
+            data.extend_from_slice(&[SBA, (row + 1) as u8, (start_col + 1) as u8, TD, bytes.len() as u8]);
+            data.extend_from_slice(&bytes);
+        }
+
+        // Format table entries whose attribute byte lies in the region
+        for field in &self.fields {
+            let (row, col) = Self::field_attribute_position(field, width);
+            if row < start_row || row >= end_row || col < start_col || col >= end_col {
+                continue;
+            }
+            data.extend_from_slice(&[SBA, (row + 1) as u8, (col + 1) as u8, SF]);
+            if let Some(ffw) = field.ffw {
+                let ffw = if field.mdt { ffw | 0x0800 } else { ffw };
+                data.extend_from_slice(&ffw.to_be_bytes());
+                for &(fcw_type, fcw_data) in &field.fcws {
+                    data.extend_from_slice(&[fcw_type, fcw_data]);
+                }
+            }
+            data.push(field.attribute_byte);
+            data.extend_from_slice(&(field.length as u16).to_be_bytes());
+        }
+
+        // Extended attributes last, since writing characters resets them
+        for row in start_row..end_row {
+            for col in start_col..end_col {
+                if let Some((attr_id, value)) = Self::extended_attribute_for(self.display.cell_attribute(row, col)) {
+                    data.extend_from_slice(&[SBA, (row + 1) as u8, (col + 1) as u8, WEA, 3, attr_id, 1, value]);
                 }
             }
         }
 
+        if let Some((row, col)) = self.display.pending_insert_cursor() {
+            data.extend_from_slice(&[IC, (row + 1) as u8, (col + 1) as u8]);
+        }
+
+        let (row, col) = self.display.cursor_position();
+        data.extend_from_slice(&[SBA, (row + 1) as u8, (col + 1) as u8]);
+
         Ok(())
     }
 
-    /// Generate display orders for a partial screen region
-    /// Coordinates are 1-based as per 5250 protocol
-    fn generate_partial_screen_display_orders(&self, data: &mut Vec<u8>, top_row: usize, left_col: usize, depth: usize, width_param: usize) -> Result<(), String> {
-        let screen_data = self.display.get_screen_data();
-        let screen_width = self.display.width();
-        let screen_height = self.display.height();
+    /// Position of a field's attribute byte, which precedes its first data position
+    fn field_attribute_position(field: &Field, width: usize) -> (usize, usize) {
+        let index = (field.row * width + field.col).saturating_sub(1);
+        (index / width, index % width)
+    }
 
-        // Convert 1-based coordinates to 0-based and clamp to screen bounds
-        let start_row = (top_row.saturating_sub(1)).min(screen_height.saturating_sub(1));
-        let start_col = (left_col.saturating_sub(1)).min(screen_width.saturating_sub(1));
-        let end_row = (start_row + depth).min(screen_height);
-        let end_col = (start_col + width_param).min(screen_width);
+    /// Write Extended Attributes encoding (id, value) for a cell attribute
+    ///
+    /// Attributes applied by CC2 at the cursor are carried in the restore CC2 instead.
+    fn extended_attribute_for(attribute: crate::terminal::CharAttribute) -> Option<(u8, u8)> {
+        use crate::terminal::CharAttribute;
+        match attribute {
+            CharAttribute::HighIntensity => Some((0x03, 0x01)),
+            CharAttribute::Intensified => Some((0x03, 0x02)),
+            CharAttribute::ReverseVideo => Some((0x04, 0x01)),
+            CharAttribute::Blink => Some((0x05, 0x01)),
+            CharAttribute::Underline => Some((0x02, 0x04)),
+            _ => None,
+        }
+    }
+
+    /// CC2 that restores the indicators, keyboard state and cursor cell attributes
+    fn restore_cc2(&self) -> u8 {
+        use crate::terminal::CharAttribute;
+        let mut cc2 = 0x00;
+
+        if self.display.indicators() & crate::lib5250::display::TN5250_DISPLAY_IND_MESSAGE_WAITING != 0 {
+            cc2 |= 0x10;
+        } else {
+            cc2 |= 0x08;
+        }
+        if !self.display.keyboard_locked() {
+            cc2 |= 0x02;
+        }
 
-        // Iterate through the specified region
-        for row in start_row..end_row {
-            for col in start_col..end_col {
-                let index = row * screen_width + col;
-                let ebcdic_char = screen_data[index];
+        let (row, col) = self.display.cursor_position();
+        match self.display.cell_attribute(row, col) {
+            CharAttribute::BlinkingCursor => cc2 |= 0x20,
+            CharAttribute::ReverseImage => cc2 |= 0x40,
+            CharAttribute::Underline => cc2 |= 0x80,
+            _ => {}
+        }
 
-                // Skip null characters (0x00) - they represent empty/unused positions
-                if ebcdic_char != 0x00 {
-                    // Set Buffer Address (SBA) order
-                    data.push(SBA);
-                    data.push((row + 1) as u8); // 1-based row
-                    data.push((col + 1) as u8); // 1-based column
+        cc2
+    }
 
-                    // Add the character
-                    data.push(ebcdic_char);
-                }
-            }
+    /// Re-issue the outstanding read command, if any, at the end of a saved image
+    fn append_saved_read_command(&self, data: &mut Vec<u8>) {
+        if self.read_opcode != 0 {
+            data.push(ESC);
+            data.push(self.read_opcode);
+            data.push(0x00); // CC1
+            data.push(self.restore_cc2() & 0xE0); // CC2 - keep cursor cell attributes
         }
-
-        Ok(())
     }
-    
+
     /// Save Partial Screen command
     fn save_partial_screen(&mut self) -> Result<Vec<u8>, String> {
-        let flag_byte = self.get_byte()?;
+        let _flag_byte = self.get_byte()?;
         let top_row = self.get_byte()? as usize;
         let left_col = self.get_byte()? as usize;
         let depth = self.get_byte()? as usize;
         let width = self.get_byte()? as usize;
 
+        // Convert 1-based coordinates to 0-based and clamp to screen bounds
+        let screen_width = self.display.width();
+        let screen_height = self.display.height();
+        let start_row = top_row.saturating_sub(1).min(screen_height.saturating_sub(1));
+        let start_col = left_col.saturating_sub(1).min(screen_width.saturating_sub(1));
+        let end_row = (start_row + depth).min(screen_height);
+        let end_col = (start_col + width).min(screen_width);
+
         let mut data = Vec::new();
 
-        // ESC (0x04)
         data.push(ESC);
-
-        // WriteToDisplay command (0x11)
         data.push(CMD_WRITE_TO_DISPLAY);
+        data.push(if self.display.keyboard_locked() { 0x20 } else { 0x00 }); // CC1
+        data.push(self.restore_cc2());
 
-        // Sequence number
-        data.push(self.sequence_number);
-        self.sequence_number = self.sequence_number.wrapping_add(1);
-
-        // Length placeholder (2 bytes) - will be calculated
-        let length_pos = data.len();
-        data.extend_from_slice(&[0x00, 0x00]);
-
-        // Flags (use flag_byte from command)
-        data.push(flag_byte);
-
-        // Control Character 1 - Lock keyboard, reset MDT
-        data.push(0xC0);
-
-        // Control Character 2 - Unlock keyboard after processing
-        data.push(0x02);
-
-        // Generate display orders for the specified region
-        self.generate_partial_screen_display_orders(&mut data, top_row, left_col, depth, width)?;
-
-        // Calculate and set length
-        let length = (data.len() - length_pos - 2) as u16;
-        data[length_pos] = (length >> 8) as u8;
-        data[length_pos + 1] = (length & 0xFF) as u8;
-
-        // Add read command if we were in a read operation
-        if self.read_opcode != 0 {
-            data.push(ESC);
-            data.push(self.read_opcode);
-            data.push(0x00); // CC1
-            data.push(0x00); // CC2
+        if end_row > start_row && end_col > start_col {
+            // Wipe the region, including any fields defined there since the save
+            data.extend_from_slice(&[SBA, (start_row + 1) as u8, (start_col + 1) as u8]);
+            data.extend_from_slice(&[EA, end_row as u8, end_col as u8, 0x01, 0x00]);
         }
 
+        self.generate_screen_display_orders(&mut data, start_row, start_col, end_row, end_col)?;
+        self.append_saved_read_command(&mut data);
+
         Ok(data)
     }
-    
+
     /// Roll command - scroll screen region
     fn roll(&mut self) -> Result<(), String> {
         let direction = self.get_byte()?;
         let top = self.get_byte()?;
         let bottom = self.get_byte()?;
 
+        if top == 0 || bottom < top || bottom as usize > self.display.height() {
+            return Err(format!("Invalid roll boundaries: top {top}, bottom {bottom}"));
+        }
+
         let lines = (direction & 0x1F) as i8;
         let mut roll_lines = if (direction & 0x80) == 0 { -lines } else { lines };
 
@@ -3113,6 +3167,9 @@ impl Session {
                     FieldAttribute::Protected
                 },
                 mdt: false,
+                ffw: is_input_field.then_some(0x4000),
+                fcws: Vec::new(),
+                attribute_byte: 0x20,
             };
 
             // Update or add the field to our field list
@@ -3236,6 +3293,9 @@ impl Session {
                         FieldAttribute::Protected
                     },
                     mdt: false,
+                    ffw: is_input_field.then_some(0x4000),
+                    fcws: Vec::new(),
+                    attribute_byte: 0x20,
                 };
 
                 self.fields.push(field);
@@ -7,14 +7,26 @@ use eframe::egui;
 mod lib5250;
 mod ansi_processor;
 mod network;
+mod network_tls;
+mod network_proxy;
+mod network_ssh;
+mod network_actor;
+mod known_hosts;
+mod password_substitute;
+mod device_name;
 mod terminal;
 mod telnet_negotiation;
+mod telnet_environment;
 mod keyboard;
 mod controller;
 mod field_manager;
+mod clipboard;
+mod screen_grid;
+mod screen_history;
 
 mod monitoring;
 mod error;
+mod error_handling;
 mod protocol_state;
 mod protocol_common;
 mod lib3270;
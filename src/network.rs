@@ -22,16 +22,15 @@
 //! 4. **Security Integration**: All network operations include bounds checking,
 //!    data validation, and secure cleanup to prevent resource leaks and attacks.
 //!
-//! 5. **Performance Optimization**: Uses pooled buffers and efficient data structures
-//!    to minimize allocations during high-frequency network operations.
+//! 5. **Async Data Phase**: After telnet negotiation the stream is handed to a
+//!    connection actor on a shared tokio runtime (see `network_actor`), so sessions
+//!    do not need a receive thread each and the caller never blocks on the socket.
 
 use std::net::{TcpStream, ToSocketAddrs, SocketAddr};
 use base64::Engine;
 use std::io::{Read, Write, Result as IoResult};
 use std::time::{Duration, Instant};
-use std::sync::{mpsc, Arc, Mutex};
-use std::thread;
-use std::collections::VecDeque;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
@@ -40,8 +39,16 @@ use rustls::pki_types::{ServerName as TlsServerName};
 
 use std::fs;
 
-use crate::telnet_negotiation::TelnetNegotiator;
-use crate::error::{TN5250Error};
+use crate::telnet_negotiation::{StartTlsState, TelnetNegotiator};
+use crate::password_substitute::PasswordLevel;
+use crate::telnet_environment::{EnvironmentSettings, EnvironmentVariable};
+use crate::error::{NetworkError, TN5250Error};
+use crate::error_handling::RetryPolicy;
+use crate::network_tls::{self, PinnedCertVerifier, TlsOptions};
+use crate::network_proxy::ProxySettings;
+use crate::network_ssh::{SshStream, SshTunnel};
+use crate::network_actor::{ConnectionEvent, ConnectionHandle, Transport};
+use crate::known_hosts::{KnownHosts, TofuCertVerifier};
 use crate::monitoring::{set_component_status, set_component_error, ComponentState};
 #[cfg(windows)]
 use crate::network_platform;
@@ -53,15 +60,6 @@ struct OwnedTlsStream {
     stream: std::net::TcpStream,
 }
 
-impl OwnedTlsStream {
-    fn set_read_timeout(&self, dur: Option<std::time::Duration>) -> std::io::Result<()> {
-        self.stream.set_read_timeout(dur)
-    }
-    fn set_write_timeout(&self, dur: Option<std::time::Duration>) -> std::io::Result<()> {
-        self.stream.set_write_timeout(dur)
-    }
-}
-
 impl std::io::Read for OwnedTlsStream {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         let mut s = rustls::Stream::new(&mut self.conn, &mut self.stream);
@@ -84,6 +82,7 @@ impl std::io::Write for OwnedTlsStream {
 enum StreamType {
     Plain(std::net::TcpStream),
     Tls(Box<OwnedTlsStream>),
+    Ssh(Box<SshStream>),
 }
 
 impl std::io::Read for StreamType {
@@ -91,6 +90,21 @@ impl std::io::Read for StreamType {
         match self {
             StreamType::Plain(t) => t.read(buf),
             StreamType::Tls(t) => t.read(buf),
+            StreamType::Ssh(t) => t.read(buf),
+        }
+    }
+}
+
+impl StreamType {
+    /// Pieces the connection actor needs to drive this stream asynchronously
+    fn into_transport(self) -> Transport {
+        match self {
+            StreamType::Plain(tcp) => Transport::Plain(tcp),
+            StreamType::Tls(tls) => Transport::Tls(Box::new(tls.conn), tls.stream),
+            StreamType::Ssh(ssh) => {
+                let (session, channel, socket) = ssh.into_parts();
+                Transport::Ssh(session, channel, socket)
+            }
         }
     }
 }
@@ -100,18 +114,21 @@ impl std::io::Write for StreamType {
         match self {
             StreamType::Plain(t) => t.write(buf),
             StreamType::Tls(t) => t.write(buf),
+            StreamType::Ssh(t) => t.write(buf),
         }
     }
     fn flush(&mut self) -> std::io::Result<()> {
         match self {
             StreamType::Plain(t) => t.flush(),
             StreamType::Tls(t) => t.flush(),
+            StreamType::Ssh(t) => t.flush(),
         }
     }
 }
 
 /// Session management configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SessionConfig {
     /// Session idle timeout in seconds (default: 900 = 15 minutes)
     pub idle_timeout_secs: u64,
@@ -125,6 +142,27 @@ pub struct SessionConfig {
     pub max_reconnect_attempts: u32,
     /// Reconnection backoff multiplier (default: 2)
     pub reconnect_backoff_multiplier: u64,
+    /// Delay before the first reconnection attempt in milliseconds (default: 1000)
+    pub reconnect_initial_delay_ms: u64,
+}
+
+impl SessionConfig {
+    /// Bound on opening the connection, from `connection_timeout_secs`
+    pub fn connection_timeout(&self) -> Duration {
+        Duration::from_secs(self.connection_timeout_secs.max(1))
+    }
+
+    /// Longest wait between reconnection attempts
+    const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+    /// Backoff schedule for automatic reconnection after the connection is lost
+    pub fn reconnect_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(self.max_reconnect_attempts as usize).with_backoff(
+            Duration::from_millis(self.reconnect_initial_delay_ms),
+            Self::MAX_RECONNECT_DELAY,
+            self.reconnect_backoff_multiplier.max(1) as f64,
+        )
+    }
 }
 
 impl Default for SessionConfig {
@@ -136,6 +174,7 @@ impl Default for SessionConfig {
             auto_reconnect: false,
             max_reconnect_attempts: 3,
             reconnect_backoff_multiplier: 2,
+            reconnect_initial_delay_ms: 1000,
         }
     }
 }
@@ -287,73 +326,31 @@ impl ProtocolDetector {
     }
 }
 
-/// PERFORMANCE OPTIMIZATION: Buffer pool for reusing Vec<u8> allocations
-/// Reduces memory allocation overhead by maintaining a pool of reusable buffers
-struct BufferPool {
-    pool: Mutex<VecDeque<Vec<u8>>>,
-    max_buffers: usize,
-    buffer_size: usize,
-}
-
-impl BufferPool {
-    fn new(max_buffers: usize, buffer_size: usize) -> Self {
-        Self {
-            pool: Mutex::new(VecDeque::with_capacity(max_buffers)),
-            max_buffers,
-            buffer_size,
-        }
-    }
-
-    /// PERFORMANCE OPTIMIZATION: Get a buffer from the pool or allocate new one
-    fn get_buffer(&self) -> Vec<u8> {
-        let mut pool = self.pool.lock().unwrap_or_else(|poisoned| {
-            eprintln!("SECURITY: BufferPool mutex poisoned - recovering");
-            poisoned.into_inner()
-        });
-        pool.pop_front().unwrap_or_else(|| Vec::with_capacity(self.buffer_size))
-    }
-
-    /// PERFORMANCE OPTIMIZATION: Return buffer to pool for reuse
-    fn return_buffer(&self, mut buffer: Vec<u8>) {
-        let mut pool = self.pool.lock().unwrap_or_else(|poisoned| {
-            eprintln!("SECURITY: BufferPool mutex poisoned - recovering");
-            poisoned.into_inner()
-        });
-        if pool.len() < self.max_buffers {
-            buffer.clear();
-            buffer.shrink_to_fit(); // PERFORMANCE: Minimize memory usage
-            pool.push_back(buffer);
-        }
-        // If pool is full, buffer is dropped (memory freed)
-    }
-}
-
-// Global buffer pool instance for network operations
-lazy_static::lazy_static! {
-    static ref NETWORK_BUFFER_POOL: BufferPool = BufferPool::new(32, 8192);
-}
-
 // A helper trait alias for objects that implement both Read and Write
 trait ReadWrite: Read + Write {}
 impl<T: Read + Write> ReadWrite for T {}
 
 type DynStream = Box<StreamType>;
-type SharedStream = Arc<Mutex<DynStream>>;
 
 /// Represents a connection to an AS/400 system
 #[derive(Debug)]
 pub struct AS400Connection {
-    stream: Option<SharedStream>,
+    actor: Option<ConnectionHandle>,
     host: String,
     port: u16,
-    receiver: Option<mpsc::Receiver<Vec<u8>>>,
-    sender: Option<mpsc::SyncSender<Vec<u8>>>,
+    channel_capacity: usize,
     running: bool,
     telnet_negotiator: TelnetNegotiator,
     negotiation_complete: bool,
     use_tls: bool,
     // TLS options
     tls_ca_bundle_path: Option<String>,
+    tls_options: TlsOptions,
+    known_hosts_path: std::path::PathBuf,
+    // HTTP CONNECT or SOCKS5 proxy to reach the host through
+    proxy: Option<ProxySettings>,
+    // SSH server to tunnel the telnet connection through
+    ssh_tunnel: Option<SshTunnel>,
     // INTEGRATION: Protocol detection and mode switching
     protocol_detector: ProtocolDetector,
     detected_mode: ProtocolMode,
@@ -375,18 +372,20 @@ impl AS400Connection {
                 .map(|n| n.clamp(64, 4096))
                 .unwrap_or(512)
         }
-        let (tx, rx) = mpsc::sync_channel(channel_capacity_from_env());
         Self {
-            stream: None,
+            actor: None,
             host,
             port,
-            receiver: Some(rx),
-            sender: Some(tx),
+            channel_capacity: channel_capacity_from_env(),
             running: false,
             telnet_negotiator: TelnetNegotiator::new(),
             negotiation_complete: false,
              use_tls: port == 992, // default secure if standard SSL port
              tls_ca_bundle_path: None,
+             tls_options: TlsOptions::default(),
+             known_hosts_path: KnownHosts::default_path(),
+             proxy: None,
+             ssh_tunnel: None,
             // INTEGRATION: Initialize protocol detection
             protocol_detector: ProtocolDetector::new(),
             detected_mode: ProtocolMode::TN5250, // Default to TN5250 for AS/400 systems
@@ -417,6 +416,11 @@ impl AS400Connection {
         }
     }
 
+    /// When the idle timeout falls due if nothing arrives before then
+    pub fn idle_deadline(&self) -> Option<Instant> {
+        self.last_activity.map(|t| t + Duration::from_secs(self.session_config.idle_timeout_secs + 1))
+    }
+
     /// Update last activity timestamp
     fn update_last_activity(&mut self) {
         self.last_activity = Some(Instant::now());
@@ -447,50 +451,109 @@ impl AS400Connection {
         }
     }
 
+    /// Set client certificate, minimum TLS version and certificate pin
+    pub fn set_tls_options(&mut self, options: TlsOptions) {
+        self.tls_options = options;
+    }
+
+    /// Current TLS client options
+    pub fn tls_options(&self) -> &TlsOptions {
+        &self.tls_options
+    }
+
+    /// Use a different trust-on-first-use certificate store (defaults to the config directory)
+    pub fn set_known_hosts_path<P: Into<std::path::PathBuf>>(&mut self, path: P) {
+        self.known_hosts_path = path.into();
+    }
+
+    /// Reach the host through an HTTP CONNECT or SOCKS5 proxy (None connects directly)
+    pub fn set_proxy(&mut self, proxy: Option<ProxySettings>) {
+        self.proxy = proxy;
+    }
+
+    /// Tunnel the telnet connection through an SSH server (None connects directly).
+    /// The tunnel takes the place of TLS; START_TLS and implicit TLS are not applied inside it.
+    pub fn set_ssh_tunnel(&mut self, tunnel: Option<SshTunnel>) {
+        self.ssh_tunnel = tunnel;
+    }
+
+    /// Open the TCP connection to the host, directly or through the configured proxy
+    fn open_tcp(&self, timeout: Option<Duration>) -> IoResult<TcpStream> {
+        self.open_tcp_to(&self.host, self.port, timeout)
+    }
+
+    /// Open a TCP connection to `host:port`, directly or through the configured proxy
+    fn open_tcp_to(&self, host: &str, port: u16, timeout: Option<Duration>) -> IoResult<TcpStream> {
+        if let Some(ref proxy) = self.proxy {
+            return proxy.connect(host, port, timeout).map_err(std::io::Error::other);
+        }
+        let address = format!("{host}:{port}");
+        match timeout {
+            Some(timeout) => {
+                let addr: SocketAddr = address.to_socket_addrs()?.next().ok_or_else(|| std::io::Error::new(
+                    std::io::ErrorKind::AddrNotAvailable,
+                    "No socket addresses resolved",
+                ))?;
+                TcpStream::connect_timeout(&addr, timeout)
+            }
+            None => TcpStream::connect(&address),
+        }
+    }
+
+    /// Log in to the SSH server and open the forwarded channel to the telnet port
+    fn open_ssh_tunnel(&self, tunnel: &SshTunnel, timeout: Option<Duration>) -> IoResult<SshStream> {
+        let (ssh_host, ssh_port) = tunnel.server(&self.host);
+        let tcp = self.open_tcp_to(&ssh_host, ssh_port, timeout)?;
+        self.configure_tcp_keepalive(&tcp)?;
+        tunnel
+            .open(tcp, &self.host, self.port, Duration::from_secs(10))
+            .map_err(std::io::Error::other)
+    }
+
+    /// Connect and complete only the TLS handshake, to check certificate, version and pin settings
+    pub fn verify_tls(&self) -> Result<(), NetworkError> {
+        let config = self.build_tls_connector()?;
+        let mut tcp = self.open_tcp(None).map_err(|e| match e.get_ref().and_then(|inner| inner.downcast_ref::<NetworkError>()) {
+            Some(network_error) => network_error.clone(),
+            None => NetworkError::ConnectionLost { reason: e.to_string() },
+        })?;
+        tcp.set_read_timeout(Some(Duration::from_secs(10)))
+            .and_then(|_| tcp.set_write_timeout(Some(Duration::from_secs(10))))
+            .map_err(|e| NetworkError::ConnectionLost { reason: e.to_string() })?;
+        if self.tls_options.start_tls && !self.use_tls {
+            Self::negotiate_start_tls(&self.host, &mut TelnetNegotiator::new(), &mut tcp)?;
+        }
+        self.tls_handshake(config, tcp).map(|_| ())
+    }
+
     /// Connects to the AS/400 system
     pub fn connect(&mut self) -> IoResult<()> {
         // Monitoring: mark network as starting
         set_component_status("network", ComponentState::Starting);
-        let address = format!("{}:{}", self.host, self.port);
-        let tcp = match TcpStream::connect(&address) {
-            Ok(s) => s,
-            Err(e) => {
-                set_component_status("network", ComponentState::Error);
-                set_component_error("network", Some(format!("TCP connect failed: {e}")));
-                return Err(e);
-            }
-        };
-        
-        // SESSION MANAGEMENT: Enable TCP keepalive
-        self.configure_tcp_keepalive(&tcp)?;
-        
-        // Set read/write timeouts
-        tcp.set_read_timeout(Some(Duration::from_secs(self.session_config.connection_timeout_secs)))?;
-        tcp.set_write_timeout(Some(Duration::from_secs(self.session_config.connection_timeout_secs)))?;
 
-        // Wrap with TLS if requested
+        // Tunnel through SSH, or wrap with TLS if requested
         let mut rw: DynStream;
-        if self.use_tls {
-            // Build TLS connector with secure certificate validation
-            let tls_config = match self.build_tls_connector() {
-                Ok(cfg) => cfg,
+        if let Some(ref tunnel) = self.ssh_tunnel {
+            rw = match self.open_ssh_tunnel(tunnel, None) {
+                Ok(stream) => Box::new(StreamType::Ssh(Box::new(stream))),
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
-                    set_component_error("network", Some(format!("TLS config creation failed: {e}")));
+                    set_component_error("network", Some(format!("SSH tunnel failed: {e}")));
                     return Err(e);
                 }
             };
-
-            // Create TLS connection
-            let server_name = match TlsServerName::try_from(self.host.clone()) {
-                Ok(name) => name,
+        } else if self.use_tls {
+            // Build TLS connector with secure certificate validation
+            let tls_config = match self.build_tls_connector() {
+                Ok(cfg) => cfg,
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
-                    set_component_error("network", Some(format!("Invalid server name: {e}")));
-                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid server name: {e}")));
+                    set_component_error("network", Some(format!("TLS config creation failed: {e}")));
+                    return Err(std::io::Error::other(e));
                 }
             };
-            let tcp = match TcpStream::connect(&address) {
+
+            let tcp = match self.open_tcp(None) {
                 Ok(s) => s,
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
@@ -502,15 +565,16 @@ impl AS400Connection {
             tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
             tcp.set_write_timeout(Some(Duration::from_secs(10)))?;
 
-            let tls_conn = match ClientConnection::new(tls_config, server_name) {
-                Ok(conn) => conn,
+            // Handshake up front so certificate, version and pin failures surface as typed errors
+            let tls_stream = match self.tls_handshake(tls_config, tcp) {
+                Ok(stream) => stream,
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
                     set_component_error("network", Some(format!("TLS connection failed: {e}")));
-                    return Err(std::io::Error::other(format!("TLS connection failed: {e}")));
+                    return Err(std::io::Error::other(e));
                 }
             };
-            rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream: tcp })));
+            rw = Box::new(StreamType::Tls(Box::new(tls_stream)));
             // Record successful TLS connection in monitoring
             let monitoring = crate::monitoring::MonitoringSystem::global();
             monitoring.integration_monitor.record_integration_event(crate::monitoring::IntegrationEvent {
@@ -524,7 +588,21 @@ impl AS400Connection {
                 success: true,
             });
         } else {
-            let tcp = match TcpStream::connect(&address) {
+            // START_TLS needs the TLS config too; build it before touching the network
+            let start_tls_config = if self.tls_options.start_tls {
+                match self.build_tls_connector() {
+                    Ok(cfg) => Some(cfg),
+                    Err(e) => {
+                        set_component_status("network", ComponentState::Error);
+                        set_component_error("network", Some(format!("TLS config creation failed: {e}")));
+                        return Err(std::io::Error::other(e));
+                    }
+                }
+            } else {
+                None
+            };
+
+            let tcp = match self.open_tcp(None) {
                 Ok(s) => s,
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
@@ -535,7 +613,17 @@ impl AS400Connection {
             self.configure_tcp_keepalive(&tcp)?;
             tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
             tcp.set_write_timeout(Some(Duration::from_secs(10)))?;
-            rw = Box::new(StreamType::Plain(tcp));
+            rw = match start_tls_config {
+                Some(config) => match self.start_tls(config, tcp) {
+                    Ok(stream) => Box::new(StreamType::Tls(Box::new(stream))),
+                    Err(e) => {
+                        set_component_status("network", ComponentState::Error);
+                        set_component_error("network", Some(format!("START_TLS upgrade failed: {e}")));
+                        return Err(std::io::Error::other(e));
+                    }
+                },
+                None => Box::new(StreamType::Plain(tcp)),
+            };
         }
 
         // Telnet negotiation lifecycle signals
@@ -549,17 +637,8 @@ impl AS400Connection {
         }
         set_component_status("telnet_negotiator", ComponentState::Running);
 
-        match &mut *rw {
-            StreamType::Plain(t) => {
-                t.set_read_timeout(None)?;
-                t.set_write_timeout(None)?;
-            }
-            StreamType::Tls(t) => {
-                t.set_read_timeout(None)?;
-                t.set_write_timeout(None)?;
-            }
-        }
-    self.stream = Some(Arc::new(Mutex::new(rw)));
+    // Hand the negotiated stream to its actor for the data phase
+    self.actor = Some(ConnectionHandle::spawn(rw.into_transport(), self.channel_capacity));
     self.running = true;
     // Monitoring: mark network as running and clear last error
     set_component_status("network", ComponentState::Running);
@@ -569,9 +648,6 @@ impl AS400Connection {
         self.update_last_activity();
         self.reconnect_attempts = 0;
         
-        // Start a background thread to receive data
-        self.start_receive_thread();
-        
         Ok(())
     }
 
@@ -669,55 +745,29 @@ impl AS400Connection {
     pub fn connect_with_timeout(&mut self, timeout: Duration) -> IoResult<()> {
         // Monitoring: mark network as starting
         set_component_status("network", ComponentState::Starting);
-        // Resolve the address
-        let address = format!("{}:{}", self.host, self.port);
-        let mut addrs_iter = address.to_socket_addrs()?;
-        let addr: SocketAddr = addrs_iter.next().ok_or_else(|| std::io::Error::new(
-            std::io::ErrorKind::AddrNotAvailable,
-            "No socket addresses resolved",
-        ))?;
-
-        // Use connect_timeout for the initial TCP connect
-        let tcp = match TcpStream::connect_timeout(&addr, timeout) {
-            Ok(s) => s,
-            Err(e) => {
-                set_component_status("network", ComponentState::Error);
-                set_component_error("network", Some(format!("TCP connect (timeout) failed: {e}")));
-                return Err(e);
-            }
-        };
-
-        // SESSION MANAGEMENT: Enable TCP keepalive
-        self.configure_tcp_keepalive(&tcp)?;
-
-        // Set read/write timeouts
-        tcp.set_read_timeout(Some(Duration::from_secs(self.session_config.connection_timeout_secs)))?;
-        tcp.set_write_timeout(Some(Duration::from_secs(self.session_config.connection_timeout_secs)))?;
-
-        // Wrap with TLS if requested
+        // Tunnel through SSH, or wrap with TLS if requested
         let mut rw: DynStream;
-        if self.use_tls {
-            // Build TLS connector with secure certificate validation
-            let tls_config = match self.build_tls_connector() {
-                Ok(cfg) => cfg,
+        if let Some(ref tunnel) = self.ssh_tunnel {
+            rw = match self.open_ssh_tunnel(tunnel, Some(timeout)) {
+                Ok(stream) => Box::new(StreamType::Ssh(Box::new(stream))),
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
-                    set_component_error("network", Some(format!("TLS config creation failed: {e}")));
+                    set_component_error("network", Some(format!("SSH tunnel (timeout) failed: {e}")));
                     return Err(e);
                 }
             };
-
-            // Create TLS connection
-            let server_name = match TlsServerName::try_from(self.host.clone()) {
-                Ok(name) => name,
+        } else if self.use_tls {
+            // Build TLS connector with secure certificate validation
+            let tls_config = match self.build_tls_connector() {
+                Ok(cfg) => cfg,
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
-                    set_component_error("network", Some(format!("Invalid server name: {e}")));
-                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid server name: {e}")));
+                    set_component_error("network", Some(format!("TLS config creation failed: {e}")));
+                    return Err(std::io::Error::other(e));
                 }
             };
 
-            let tcp = match TcpStream::connect_timeout(&addr, timeout) {
+            let tcp = match self.open_tcp(Some(timeout)) {
                 Ok(s) => s,
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
@@ -729,17 +779,32 @@ impl AS400Connection {
             tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
             tcp.set_write_timeout(Some(Duration::from_secs(10)))?;
 
-let tls_conn = match ClientConnection::new(tls_config, server_name) {
-    Ok(conn) => conn,
-    Err(e) => {
-        set_component_status("network", ComponentState::Error);
-        set_component_error("network", Some(format!("TLS connection failed: {e}")));
-        return Err(std::io::Error::other(format!("TLS connection failed: {e}")))
-    },
-};
-rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream: tcp })));
+            // Handshake up front so certificate, version and pin failures surface as typed errors
+            let tls_stream = match self.tls_handshake(tls_config, tcp) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    set_component_status("network", ComponentState::Error);
+                    set_component_error("network", Some(format!("TLS connection failed: {e}")));
+                    return Err(std::io::Error::other(e));
+                }
+            };
+            rw = Box::new(StreamType::Tls(Box::new(tls_stream)));
         } else {
-            let tcp = match TcpStream::connect_timeout(&addr, timeout) {
+            // START_TLS needs the TLS config too; build it before touching the network
+            let start_tls_config = if self.tls_options.start_tls {
+                match self.build_tls_connector() {
+                    Ok(cfg) => Some(cfg),
+                    Err(e) => {
+                        set_component_status("network", ComponentState::Error);
+                        set_component_error("network", Some(format!("TLS config creation failed: {e}")));
+                        return Err(std::io::Error::other(e));
+                    }
+                }
+            } else {
+                None
+            };
+
+            let tcp = match self.open_tcp(Some(timeout)) {
                 Ok(s) => s,
                 Err(e) => {
                     set_component_status("network", ComponentState::Error);
@@ -750,7 +815,17 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
             self.configure_tcp_keepalive(&tcp)?;
             tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
             tcp.set_write_timeout(Some(Duration::from_secs(10)))?;
-            rw = Box::new(StreamType::Plain(tcp));
+            rw = match start_tls_config {
+                Some(config) => match self.start_tls(config, tcp) {
+                    Ok(stream) => Box::new(StreamType::Tls(Box::new(stream))),
+                    Err(e) => {
+                        set_component_status("network", ComponentState::Error);
+                        set_component_error("network", Some(format!("START_TLS upgrade failed: {e}")));
+                        return Err(std::io::Error::other(e));
+                    }
+                },
+                None => Box::new(StreamType::Plain(tcp)),
+            };
         }
 
         // Telnet negotiation lifecycle signals
@@ -764,18 +839,8 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
         }
         set_component_status("telnet_negotiator", ComponentState::Running);
 
-        match &mut *rw {
-            StreamType::Plain(t) => {
-                t.set_read_timeout(None)?;
-                t.set_write_timeout(None)?;
-            }
-            StreamType::Tls(t) => {
-                t.set_read_timeout(None)?;
-                t.set_write_timeout(None)?;
-            }
-        }
-
-    self.stream = Some(Arc::new(Mutex::new(rw)));
+    // Hand the negotiated stream to its actor for the data phase
+    self.actor = Some(ConnectionHandle::spawn(rw.into_transport(), self.channel_capacity));
     self.running = true;
     set_component_status("network", ComponentState::Running);
     set_component_error("network", None::<&str>);
@@ -784,9 +849,6 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
         self.update_last_activity();
         self.reconnect_attempts = 0;
 
-        // Start a background thread to receive data
-        self.start_receive_thread();
-
         // Record successful connection in monitoring
         let monitoring = crate::monitoring::MonitoringSystem::global();
         monitoring.integration_monitor.record_integration_event(crate::monitoring::IntegrationEvent {
@@ -805,19 +867,17 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
 
     /// Build a TLS connector with secure certificate validation
     /// SECURITY: Always enforces proper certificate validation to prevent MITM attacks
-    fn build_tls_connector(&self) -> IoResult<Arc<ClientConfig>> {
+    fn build_tls_connector(&self) -> Result<Arc<ClientConfig>, NetworkError> {
+        let ssl_error = |message: String| NetworkError::SslError { message };
 
-        
         // Create a root certificate store with system certificates
         let mut root_store = RootCertStore::empty();
         
         // Add native certificates
-        for cert in rustls_native_certs::load_native_certs().map_err(|e| {
-            std::io::Error::other(format!("Failed to load native certificates: {e}"))
-        })? {
-            root_store.add(cert).map_err(|e| {
-                std::io::Error::other(format!("Failed to add certificate: {e}"))
-            })?;
+        for cert in rustls_native_certs::load_native_certs()
+            .map_err(|e| ssl_error(format!("Failed to load native certificates: {e}")))?
+        {
+            root_store.add(cert).map_err(|e| ssl_error(format!("Failed to add certificate: {e}")))?;
         }
 
         // Add custom CA certificates if provided
@@ -833,22 +893,103 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
                 }
                 Err(e) => {
                     eprintln!("SECURITY ERROR: Failed to load CA bundle {path}: {e}");
-                    return Err(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        format!("Failed to load CA bundle {path}: {e}")
-                    ));
+                    return Err(ssl_error(format!("Failed to load CA bundle {path}: {e}")));
                 }
             }
         }
 
-        // Create client config with secure defaults
-        let config = ClientConfig::builder()
-            .with_root_certificates(root_store)
-            .with_no_client_auth();
+        // Protocol versions, then chain validation with either a public key pin or the
+        // trust-on-first-use store for certificates no CA vouches for
+        let options = &self.tls_options;
+        let builder = ClientConfig::builder_with_protocol_versions(options.min_version.protocol_versions());
+        let verifier: Arc<dyn rustls::client::danger::ServerCertVerifier> =
+            match options.pin_sha256.as_deref().filter(|pin| !pin.trim().is_empty()) {
+                Some(pin) => Arc::new(PinnedCertVerifier::new(root_store, network_tls::parse_pin(pin)?)?),
+                None => Arc::new(TofuCertVerifier::new(
+                    root_store,
+                    format!("{}:{}", self.host, self.port),
+                    self.known_hosts_path.clone(),
+                )?),
+            };
+        let builder = builder.dangerous().with_custom_certificate_verifier(verifier);
+
+        // Client certificate authentication (e.g. IBM i DCM requiring client certificates)
+        let config = match options.client_certificate {
+            Some(ref certificate) => {
+                let (chain, key) = certificate.load()?;
+                builder.with_client_auth_cert(chain, key).map_err(|e| {
+                    let path = match certificate {
+                        network_tls::ClientCertificate::Pem { key_path, .. } => key_path.clone(),
+                        network_tls::ClientCertificate::Pkcs12 { path, .. } => path.clone(),
+                    };
+                    NetworkError::TlsClientCertificate { path, reason: e.to_string() }
+                })?
+            }
+            None => builder.with_no_client_auth(),
+        };
 
         Ok(Arc::new(config))
     }
 
+    /// Wrap a connected socket in TLS and complete the handshake
+    fn tls_handshake(&self, config: Arc<ClientConfig>, mut tcp: TcpStream) -> Result<OwnedTlsStream, NetworkError> {
+        let server_name = TlsServerName::try_from(self.host.clone())
+            .map_err(|e| NetworkError::InvalidAddress { address: format!("{}: {e}", self.host) })?;
+        let min_version = self.tls_options.min_version;
+        let mut conn = ClientConnection::new(config, server_name)
+            .map_err(|e| network_tls::handshake_error(&self.host, min_version, e))?;
+
+        while conn.is_handshaking() {
+            if let Err(e) = conn.complete_io(&mut tcp) {
+                return Err(match e.get_ref().and_then(|inner| inner.downcast_ref::<rustls::Error>()) {
+                    Some(tls_error) => network_tls::handshake_error(&self.host, min_version, tls_error.clone()),
+                    None => NetworkError::TlsHandshake { host: self.host.clone(), reason: e.to_string() },
+                });
+            }
+        }
+        Ok(OwnedTlsStream { conn, stream: tcp })
+    }
+
+    /// Upgrade a plain telnet connection in place; option negotiation then restarts inside TLS
+    fn start_tls(&mut self, config: Arc<ClientConfig>, mut tcp: TcpStream) -> Result<OwnedTlsStream, NetworkError> {
+        Self::negotiate_start_tls(&self.host, &mut self.telnet_negotiator, &mut tcp)?;
+        let stream = self.tls_handshake(config, tcp)?;
+        self.telnet_negotiator.complete_start_tls();
+        Ok(stream)
+    }
+
+    /// Send WILL START_TLS and answer telnet commands until both sides have sent FOLLOWS
+    fn negotiate_start_tls(host: &str, negotiator: &mut TelnetNegotiator, tcp: &mut TcpStream) -> Result<(), NetworkError> {
+        let lost = |e: std::io::Error| NetworkError::ConnectionLost { reason: e.to_string() };
+        negotiator.set_start_tls(true);
+        tcp.write_all(&negotiator.start_tls_request()).map_err(lost)?;
+
+        let mut buffer = [0u8; 1024];
+        loop {
+            match negotiator.start_tls_state() {
+                StartTlsState::Ready => return Ok(()),
+                StartTlsState::Refused => return Err(NetworkError::StartTlsRefused { host: host.to_string() }),
+                _ => {}
+            }
+            match tcp.read(&mut buffer) {
+                Ok(0) => {
+                    return Err(NetworkError::ConnectionLost { reason: "Connection closed during START_TLS".to_string() })
+                }
+                Ok(n) => {
+                    let response = negotiator.process_incoming_data(&buffer[..n]);
+                    if !response.is_empty() {
+                        tcp.write_all(&response).map_err(lost)?;
+                    }
+                }
+                // A server that does not know START_TLS never answers
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {
+                    return Err(NetworkError::StartTlsRefused { host: host.to_string() })
+                }
+                Err(e) => return Err(lost(e)),
+            }
+        }
+    }
+
     /// SECURITY: Load certificates with comprehensive validation for rustls
     fn load_certificates_securely_rustls(&self, path: &str) -> IoResult<Vec<rustls::pki_types::CertificateDer<'static>>> {
 
@@ -1062,164 +1203,6 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
         }
         Ok(())
     }
-    
-    /// Starts a background thread to receive data
-    /// SECURITY: Enhanced with bounds checking and secure resource cleanup
-    fn start_receive_thread(&mut self) {
-        // CRITICAL FIX: Avoid self borrowing by cloning needed data first
-        let shared = if let Some(ref stream) = self.stream {
-            Arc::clone(stream)
-        } else {
-            return; // No stream available
-        };
-
-        let sender = if let Some(ref s) = self.sender {
-            s.clone()
-        } else {
-            return; // No sender available
-        };
-
-        thread::spawn(move || {
-                // PERFORMANCE OPTIMIZATION: Use pooled buffer to reduce allocations
-                // SECURITY: Use a reasonable buffer size with upper bound
-                const MAX_READ_SIZE: usize = 8192; // 8KB max read size
-                let mut pooled_buffer = NETWORK_BUFFER_POOL.get_buffer();
-                pooled_buffer.resize(MAX_READ_SIZE, 0);
-                let mut consecutive_errors = 0;
-                const MAX_CONSECUTIVE_ERRORS: u32 = 10;
-
-                loop {
-                    // Lock the stream only for the duration of the read
-                    let read_result = {
-                        let mut guard = match shared.lock() {
-                            Ok(g) => g,
-                            Err(poisoned) => {
-                                // SECURITY: Handle poisoned mutex gracefully
-                                eprintln!("SECURITY: Stream mutex poisoned - performing emergency cleanup");
-                                poisoned.into_inner()
-                            }
-                        };
-    
-                        // PERFORMANCE OPTIMIZATION: Use pooled buffer directly
-                        // CRITICAL FIX: Enhanced buffer handling with comprehensive validation
-                        // SECURITY: Limit read size to prevent memory exhaustion
-                        match guard.read(&mut pooled_buffer[..MAX_READ_SIZE]) {
-                            Ok(0) => Ok(0), // Connection closed
-                            Ok(n) => {
-                                // CRITICAL FIX: Additional validation of read size
-                                if n > MAX_READ_SIZE {
-                                    eprintln!("SECURITY: Read size {n} exceeds maximum allowed {MAX_READ_SIZE}");
-                                    Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Read size too large"))
-                                } else if n == 0 {
-                                    // CRITICAL FIX: Handle zero reads properly
-                                    Ok(0)
-                                } else {
-                                    // CRITICAL FIX: Validate buffer contents before copying
-                                    let safe_bytes = &pooled_buffer[..n];
-
-                                    // Check for suspicious data patterns that might indicate attacks
-                                    if AS400Connection::validate_network_data(safe_bytes) {
-                                        // PERFORMANCE OPTIMIZATION: Data is already in pooled buffer
-                                        Ok(n)
-                                    } else {
-                                        eprintln!("SECURITY: Suspicious network data detected");
-                            
-                                        // Record security event in monitoring
-                                        let monitoring = crate::monitoring::MonitoringSystem::global();
-                                        let alert = crate::monitoring::Alert {
-                                            id: uuid::Uuid::new_v4().to_string(),
-                                            timestamp: std::time::Instant::now(),
-                                            level: crate::monitoring::AlertLevel::Warning,
-                                            component: "network".to_string(),
-                                            message: "Suspicious network data pattern detected".to_string(),
-                                            details: std::collections::HashMap::new(),
-                                            acknowledged: false,
-                                            acknowledged_at: None,
-                                            resolved: false,
-                                            resolved_at: None,
-                                            occurrence_count: 1,
-                                            last_occurrence: std::time::Instant::now(),
-                                        };
-                                        monitoring.alerting_system.trigger_alert(alert);
-                            
-                                        // Record security event
-                                        let security_event = crate::monitoring::SecurityEvent {
-                                            timestamp: std::time::Instant::now(),
-                                            event_type: crate::monitoring::SecurityEventType::SuspiciousNetworkPattern,
-                                            severity: crate::monitoring::SecurityEventSeverity::Medium,
-                                            description: "Suspicious network data pattern detected".to_string(),
-                                            source_ip: None,
-                                            details: std::collections::HashMap::new(),
-                                            mitigated: true,
-                                        };
-                                        monitoring.security_monitor.record_security_event(security_event);
-                            
-                                        Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Suspicious data"))
-                                    }
-                                }
-                            }
-                            Err(e) => Err(e),
-                        }
-                    };
-
-                    match read_result {
-                        Ok(0) => {
-                            // Connection closed cleanly
-                            println!("SECURITY: Network connection closed cleanly");
-                            break;
-                        }
-                        Ok(n) => {
-                            // SECURITY: Reset error counter on successful read
-                            consecutive_errors = 0;
-
-                            // SECURITY: Validate data size before sending
-                            if n > MAX_READ_SIZE {
-                                eprintln!("SECURITY: Invalid data size received: {n}");
-                                break;
-                            }
-
-                            // PERFORMANCE OPTIMIZATION: Clone data from pooled buffer instead of allocating new Vec
-                            let data_to_send = pooled_buffer[..n].to_vec();
-                            // Avoid blocking if channel is full; drop packet to cap memory usage
-                            match sender.try_send(data_to_send) {
-                                Ok(()) => {}
-                                Err(mpsc::TrySendError::Full(_)) => {
-                                    // Drop oldest by ignoring this packet; log at debug level
-                                    eprintln!(
-                                        "PERF: Dropping incoming packet due to full channel (backpressure)"
-                                    );
-                                }
-                                Err(mpsc::TrySendError::Disconnected(_)) => {
-                                    eprintln!("SECURITY: Channel send failed - receiver may be closed");
-                                    break;
-                                }
-                            }
-                        }
-                        Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {
-                            // Just try again - these are normal for non-blocking I/O
-                            thread::sleep(Duration::from_millis(50));
-                            continue;
-                        }
-                        Err(e) => {
-                            // SECURITY: Track consecutive errors to prevent infinite retry loops
-                            consecutive_errors += 1;
-                            if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
-                                eprintln!("SECURITY: Too many consecutive read errors ({consecutive_errors}), terminating thread");
-                                break;
-                            }
-
-                            eprintln!("SECURITY: Read error ({consecutive_errors} consecutive): {e}");
-                            thread::sleep(Duration::from_millis(100 * consecutive_errors as u64));
-                        }
-                    }
-                }
-
-                // PERFORMANCE OPTIMIZATION: Return buffer to pool for reuse
-                NETWORK_BUFFER_POOL.return_buffer(pooled_buffer);
-
-                println!("SECURITY: Receive thread terminated cleanly with proper resource cleanup");
-        });
-    }
 
     /// Disconnects from the AS/400 system
     /// SECURITY: Enhanced with secure resource cleanup to prevent resource leaks
@@ -1230,18 +1213,11 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
         self.running = false;
         self.negotiation_complete = false;
 
-        // CRITICAL FIX: Safer stream cleanup with explicit drop
-        if let Some(stream) = self.stream.take() {
-            // Explicitly drop the stream to ensure cleanup
-            drop(stream);
+        // Ask the actor to close the socket; it stops on its own runtime
+        if let Some(actor) = self.actor.take() {
+            actor.disconnect();
         }
 
-        // CRITICAL FIX: Safer channel cleanup with validation
-        // Note: We can't directly drop sender/receiver as they're wrapped in Option
-        // But we can clear them to ensure they're dropped
-        self.sender = None;
-        self.receiver = None;
-
         // CRITICAL FIX: Reset telnet negotiator state with validation
         self.telnet_negotiator = TelnetNegotiator::new();
 
@@ -1301,12 +1277,11 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
             ));
         }
 
-        let result = if let Some(ref shared) = self.stream {
-            let mut guard = shared.lock().map_err(|_| std::io::Error::other("Stream lock poisoned"))?;
-
+        let result = if let Some(ref actor) = self.actor {
             // CRITICAL FIX: Validate data before sending
             if AS400Connection::validate_network_data(data) {
-                let send_result = guard.write(data);
+                // Queued for the actor; a full queue surfaces as WouldBlock rather than blocking the caller
+                let send_result = actor.send(data.to_vec()).map(|()| data.len());
                 // PERFORMANCE MONITORING: Track bytes sent
                 if let Ok(bytes) = &send_result {
                     use std::sync::atomic::Ordering;
@@ -1337,7 +1312,7 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
             ))
         };
 
-        // SESSION MANAGEMENT: Update activity on successful send (after guard is dropped)
+        // SESSION MANAGEMENT: Update activity on successful send
         if result.is_ok() {
             self.update_last_activity();
         }
@@ -1356,10 +1331,15 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
             return None;
         }
 
-        if let Some(ref receiver) = self.receiver {
-            // Try to receive data without blocking
-            match receiver.try_recv() {
-                Ok(data) => {
+        let event = self.actor.as_mut().and_then(|actor| actor.try_next_event());
+        if let Some(event) = event {
+            match event {
+                ConnectionEvent::Data(data) => {
+                    if !AS400Connection::validate_network_data(&data) {
+                        eprintln!("SECURITY: Suspicious network data detected");
+                        Self::record_suspicious_data();
+                        return None;
+                    }
                     // SESSION MANAGEMENT: Update activity timestamp on data receipt
                     self.update_last_activity();
                     println!("DEBUG: Received data from network: {} bytes", data.len());
@@ -1391,20 +1371,11 @@ rw = Box::new(StreamType::Tls(Box::new(OwnedTlsStream { conn: tls_conn, stream:
 
                     // If there's a negotiation response, send it immediately
                     if !negotiation_response.is_empty() {
-                        if let Some(ref shared) = self.stream {
-                            match shared.lock() {
-                                Ok(mut guard) => {
-                                    if let Err(e) = guard.write_all(&negotiation_response) {
-                                        eprintln!("Failed to send telnet negotiation response: {e}");
-                                    } else if let Err(e) = guard.flush() {
-                                        eprintln!("Failed to flush telnet negotiation response: {e}");
-                                    } else {
-                                        println!("Sent telnet negotiation response ({} bytes)", negotiation_response.len());
-                                    }
-                                }
-                                Err(_) => {
-                                    eprintln!("Failed to lock stream for sending telnet negotiation response");
-                                }
+                        if let Some(ref actor) = self.actor {
+                            let len = negotiation_response.len();
+                            match actor.send(negotiation_response) {
+                                Ok(()) => println!("Sent telnet negotiation response ({len} bytes)"),
+                                Err(e) => eprintln!("Failed to send telnet negotiation response: {e}"),
                             }
                         }
                     }
@@ -1446,14 +1417,19 @@ if !clean_data.is_empty() {
                         }
                     }
                 }
-                Err(mpsc::TryRecvError::Empty) => None, // No data available
-                Err(mpsc::TryRecvError::Disconnected) => {
-                    // Channel disconnected - this indicates a critical connection failure
-                    eprintln!("Network channel disconnected - connection lost");
+                ConnectionEvent::Closed => {
+                    println!("SECURITY: Network connection closed cleanly");
+                    self.running = false;
+                    set_component_status("network", ComponentState::Stopped);
+                    None
+                }
+                ConnectionEvent::Failed(reason) => {
+                    // The actor has stopped - this indicates a critical connection failure
+                    eprintln!("Network connection lost: {reason}");
                     self.running = false;
                     // Monitoring: reflect error condition
                     set_component_status("network", ComponentState::Error);
-                    set_component_error("network", Some("Network channel disconnected"));
+                    set_component_error("network", Some(format!("Network connection lost: {reason}")));
                     None
                 }
             }
@@ -1461,6 +1437,37 @@ if !clean_data.is_empty() {
             None
         }
     }
+
+    /// Raise an alert and security event for incoming data that failed validation
+    fn record_suspicious_data() {
+        let monitoring = crate::monitoring::MonitoringSystem::global();
+        let alert = crate::monitoring::Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: std::time::Instant::now(),
+            level: crate::monitoring::AlertLevel::Warning,
+            component: "network".to_string(),
+            message: "Suspicious network data pattern detected".to_string(),
+            details: std::collections::HashMap::new(),
+            acknowledged: false,
+            acknowledged_at: None,
+            resolved: false,
+            resolved_at: None,
+            occurrence_count: 1,
+            last_occurrence: std::time::Instant::now(),
+        };
+        monitoring.alerting_system.trigger_alert(alert);
+
+        let security_event = crate::monitoring::SecurityEvent {
+            timestamp: std::time::Instant::now(),
+            event_type: crate::monitoring::SecurityEventType::SuspiciousNetworkPattern,
+            severity: crate::monitoring::SecurityEventSeverity::Medium,
+            description: "Suspicious network data pattern detected".to_string(),
+            source_ip: None,
+            details: std::collections::HashMap::new(),
+            mitigated: true,
+        };
+        monitoring.security_monitor.record_security_event(security_event);
+    }
     
     /// CRITICAL FIX: Validate network data for suspicious patterns
     /// Made standalone to avoid lifetime issues in threads
@@ -1618,7 +1625,17 @@ if !clean_data.is_empty() {
 
     /// Checks if the connection is active
     pub fn is_connected(&self) -> bool {
-        self.stream.is_some() && self.running
+        self.actor.is_some() && self.running
+    }
+
+    /// Whether the actor has queued events that `receive_data_channel` has not taken yet
+    pub fn has_pending_events(&self) -> bool {
+        self.actor.as_ref().is_some_and(|actor| actor.has_pending_events())
+    }
+
+    /// Signalled when the actor queues an event or stops; None before the data phase
+    pub fn event_wakeup(&self) -> Option<Arc<tokio::sync::Notify>> {
+        self.actor.as_ref().map(|actor| actor.wakeup())
     }
 
     /// Checks if telnet negotiation is complete
@@ -1648,16 +1665,37 @@ if !clean_data.is_empty() {
     /// 
     /// # Arguments
     /// * `username` - AS/400 user profile name
-    /// * `password` - User password
-    /// 
-    /// # Security Note
-    /// Current implementation uses plain text password transmission.
-    /// For production use, implement DES or SHA password encryption per RFC 4777.
+    /// * `password` - User password (only its encrypted substitute is sent)
     pub fn set_credentials(&mut self, username: &str, password: &str) {
         self.telnet_negotiator.set_credentials(username, password);
         println!("Network: Credentials configured for telnet negotiation");
     }
 
+    /// Select the RFC 4777 password substitute algorithm (DES or SHA-1)
+    pub fn set_password_level(&mut self, level: PasswordLevel) {
+        self.telnet_negotiator.set_password_level(level);
+    }
+
+    /// Request a specific display device name (DEVNAME) from the host
+    pub fn set_device_name(&mut self, name: Option<String>) {
+        self.telnet_negotiator.set_device_name(name);
+    }
+
+    /// Device name this connection requests from the host
+    pub fn device_name(&self) -> &str {
+        self.telnet_negotiator.device_name()
+    }
+
+    /// Set the NEW-ENVIRON variables sent during negotiation
+    pub fn set_environment(&mut self, environment: EnvironmentSettings) {
+        self.telnet_negotiator.set_environment(environment);
+    }
+
+    /// NEW-ENVIRON variables sent to the host during negotiation, secrets masked
+    pub fn sent_environment(&self) -> &[EnvironmentVariable] {
+        self.telnet_negotiator.sent_environment()
+    }
+
     /// Gets the host address
     pub fn get_host(&self) -> &str {
         &self.host
@@ -1671,20 +1709,11 @@ if !clean_data.is_empty() {
     /// CRITICAL FIX: Validate connection state and resource integrity
     /// This method ensures the connection is in a valid state and resources are properly managed
     pub fn validate_connection_integrity(&self) -> Result<(), String> {
-        // Validate stream state
-        match &self.stream {
-            Some(stream) => {
-                // Try to validate the stream is still accessible
-                match stream.try_lock() {
-                    Ok(_) => {
-                        // Stream is accessible
-                    }
-                    Err(std::sync::TryLockError::Poisoned(_)) => {
-                        return Err("Connection stream mutex is poisoned".to_string());
-                    }
-                    Err(std::sync::TryLockError::WouldBlock) => {
-                        // Stream is busy, which is normal
-                    }
+        // Validate actor state
+        match &self.actor {
+            Some(actor) => {
+                if self.running && actor.is_finished() {
+                    return Err("Connection is running but its actor has stopped".to_string());
                 }
             }
             None => {
@@ -1694,12 +1723,6 @@ if !clean_data.is_empty() {
             }
         }
 
-        // Validate channel state
-        if self.running
-            && (self.sender.is_none() || self.receiver.is_none()) {
-                return Err("Connection is running but channels are not available".to_string());
-            }
-
         // Validate negotiation state consistency
         if self.negotiation_complete && !self.running {
             return Err("Negotiation complete but connection not running".to_string());
@@ -1715,14 +1738,10 @@ if !clean_data.is_empty() {
         self.running = false;
 
         // Clear all resources in safe order
-        if let Some(_stream) = self.stream.take() {
-            // Stream will be dropped here
+        if let Some(actor) = self.actor.take() {
+            actor.disconnect();
         }
 
-        // Clear channels
-        self.sender = None;
-        self.receiver = None;
-
         // Reset state
         self.negotiation_complete = false;
         self.telnet_negotiator = TelnetNegotiator::new();
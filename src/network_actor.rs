@@ -0,0 +1,337 @@
+//! Async connection actor
+//!
+//! Once a connection is open and telnet negotiation has finished, its stream is
+//! handed to an actor task on a runtime shared by all sessions. Callers talk to
+//! it through a [`ConnectionHandle`]: commands go in and events come out over
+//! bounded channels, so a consumer that falls behind holds back reads from the
+//! socket instead of dropping data, and nothing on the caller's side blocks.
+//! A caller that cannot hold the handle across an await waits on its wakeup
+//! instead, which is signalled whenever an event is queued or the actor stops.
+
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use rustls::ClientConnection;
+use tokio::io::{AsyncWriteExt, Interest};
+use tokio::net::TcpStream;
+use tokio::runtime::Runtime;
+use tokio::sync::{mpsc, Notify};
+use tokio::sync::mpsc::error::{TryRecvError, TrySendError};
+use tokio::task::JoinHandle;
+
+/// Largest chunk read from the socket at once
+const READ_BUFFER_SIZE: usize = 8192;
+
+/// How long a queued send may wait for the socket before the connection is failed
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Poll interval while libssh2 has no room to send (its readiness is internal to the session)
+const SSH_WRITE_RETRY: Duration = Duration::from_millis(5);
+
+static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("tn5250r-network")
+        .enable_all()
+        .build()
+        .expect("failed to start network runtime")
+});
+
+/// Runtime shared by all connection actors
+pub fn runtime() -> &'static Runtime {
+    &RUNTIME
+}
+
+/// Request to a connection actor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionCommand {
+    /// Write bytes to the host
+    Send(Vec<u8>),
+    /// Close the connection and stop the actor
+    Disconnect,
+}
+
+/// Notification from a connection actor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// Bytes received from the host
+    Data(Vec<u8>),
+    /// The host or the caller closed the connection; no further events follow
+    Closed,
+    /// Reading or writing failed; no further events follow
+    Failed(String),
+}
+
+/// Established stream to hand over to an actor
+pub enum Transport {
+    Plain(std::net::TcpStream),
+    Tls(Box<ClientConnection>, std::net::TcpStream),
+    /// SSH session and channel, with a second handle on the session's socket for readiness
+    Ssh(ssh2::Session, ssh2::Channel, std::net::TcpStream),
+}
+
+/// Caller's side of a connection actor
+#[derive(Debug)]
+pub struct ConnectionHandle {
+    commands: mpsc::Sender<ConnectionCommand>,
+    events: mpsc::Receiver<ConnectionEvent>,
+    wakeup: Arc<Notify>,
+    task: JoinHandle<()>,
+}
+
+impl ConnectionHandle {
+    /// Start an actor for `transport`; `capacity` bounds both the send queue and the event queue
+    pub fn spawn(transport: Transport, capacity: usize) -> Self {
+        let (command_tx, command_rx) = mpsc::channel(capacity.max(1));
+        let (event_tx, event_rx) = mpsc::channel(capacity.max(1));
+        let wakeup = Arc::new(Notify::new());
+        let events = Events { queue: event_tx, wakeup: Arc::clone(&wakeup) };
+        let task = runtime().spawn(run(transport, command_rx, events));
+        Self { commands: command_tx, events: event_rx, wakeup, task }
+    }
+
+    /// Queue bytes for the host; fails with `WouldBlock` when the send queue is full
+    pub fn send(&self, data: Vec<u8>) -> io::Result<()> {
+        self.commands.try_send(ConnectionCommand::Send(data)).map_err(|e| match e {
+            TrySendError::Full(_) => io::Error::new(io::ErrorKind::WouldBlock, "send queue full"),
+            TrySendError::Closed(_) => io::Error::new(io::ErrorKind::NotConnected, "connection closed"),
+        })
+    }
+
+    /// Next event if one is waiting; never blocks
+    pub fn try_next_event(&mut self) -> Option<ConnectionEvent> {
+        match self.events.try_recv() {
+            Ok(event) => Some(event),
+            Err(TryRecvError::Empty) => None,
+            // The actor always reports Closed or Failed before it stops
+            Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// Wait for the next event; None once the actor has stopped and all events were taken
+    pub async fn next_event(&mut self) -> Option<ConnectionEvent> {
+        self.events.recv().await
+    }
+
+    /// Whether events are queued for the caller
+    pub fn has_pending_events(&self) -> bool {
+        !self.events.is_empty()
+    }
+
+    /// Signalled when an event is queued or the actor stops; a wakeup that finds no waiter is kept for the next one
+    pub fn wakeup(&self) -> Arc<Notify> {
+        Arc::clone(&self.wakeup)
+    }
+
+    /// Ask the actor to close the connection; stops it outright if its queue is full
+    pub fn disconnect(&self) {
+        if self.commands.try_send(ConnectionCommand::Disconnect).is_err() {
+            self.task.abort();
+        }
+    }
+
+    /// Whether the actor has stopped
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl Drop for ConnectionHandle {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+/// Actor's side of the event queue
+struct Events {
+    queue: mpsc::Sender<ConnectionEvent>,
+    wakeup: Arc<Notify>,
+}
+
+impl Events {
+    /// Queue an event, waiting for room; fails once the caller has dropped its handle
+    async fn send(&self, event: ConnectionEvent) -> Result<(), ()> {
+        self.queue.send(event).await.map_err(|_| ())?;
+        self.wakeup.notify_one();
+        Ok(())
+    }
+}
+
+impl Drop for Events {
+    // Also runs when the actor is aborted, so a waiting caller always learns it stopped
+    fn drop(&mut self) {
+        self.wakeup.notify_one();
+    }
+}
+
+async fn run(transport: Transport, mut commands: mpsc::Receiver<ConnectionCommand>, events: Events) {
+    let result = match AsyncTransport::new(transport) {
+        Ok(mut transport) => transport.serve(&mut commands, &events).await,
+        Err(e) => Err(e),
+    };
+    let last = match result {
+        Ok(()) => ConnectionEvent::Closed,
+        Err(e) => ConnectionEvent::Failed(e.to_string()),
+    };
+    // Refuse further sends before the caller can see the final event
+    drop(commands);
+    let _ = events.send(last).await;
+}
+
+enum Kind {
+    Plain,
+    Tls(Box<ClientConnection>),
+    Ssh(ssh2::Session, ssh2::Channel),
+}
+
+/// Transport registered with the runtime
+struct AsyncTransport {
+    socket: TcpStream,
+    kind: Kind,
+    raw: Vec<u8>,
+}
+
+impl AsyncTransport {
+    fn new(transport: Transport) -> io::Result<Self> {
+        let (socket, kind) = match transport {
+            Transport::Plain(tcp) => (tcp, Kind::Plain),
+            Transport::Tls(conn, tcp) => (tcp, Kind::Tls(conn)),
+            Transport::Ssh(session, channel, tcp) => {
+                session.set_blocking(false);
+                (tcp, Kind::Ssh(session, channel))
+            }
+        };
+        socket.set_read_timeout(None)?;
+        socket.set_write_timeout(None)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket: TcpStream::from_std(socket)?, kind, raw: vec![0; READ_BUFFER_SIZE] })
+    }
+
+    async fn serve(
+        &mut self,
+        commands: &mut mpsc::Receiver<ConnectionCommand>,
+        events: &Events,
+    ) -> io::Result<()> {
+        let mut buf = vec![0u8; READ_BUFFER_SIZE];
+        loop {
+            tokio::select! {
+                command = commands.recv() => match command {
+                    Some(ConnectionCommand::Send(data)) => {
+                        tokio::time::timeout(WRITE_TIMEOUT, self.write_all(&data))
+                            .await
+                            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "write timed out"))??;
+                        // Sending can pull incoming SSH packets into the session's buffer
+                        if !self.drain(&mut buf, events, false).await? {
+                            return Ok(());
+                        }
+                    }
+                    Some(ConnectionCommand::Disconnect) | None => {
+                        self.shutdown().await;
+                        return Ok(());
+                    }
+                },
+                ready = self.socket.readable() => {
+                    ready?;
+                    if !self.drain(&mut buf, events, true).await? {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Forward everything readable without waiting; false once the host has closed or the caller is gone
+    async fn drain(&mut self, buf: &mut [u8], events: &Events, readable: bool) -> io::Result<bool> {
+        loop {
+            match self.try_read(buf, readable) {
+                Ok(0) => return Ok(false),
+                Ok(n) => {
+                    // Awaiting here is the backpressure: nothing more is read until the caller catches up
+                    if events.send(ConnectionEvent::Data(buf[..n].to_vec())).await.is_err() {
+                        return Ok(false);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        self.flush_tls().await?;
+        Ok(true)
+    }
+
+    fn try_read(&mut self, buf: &mut [u8], readable: bool) -> io::Result<usize> {
+        match self.kind {
+            Kind::Plain => self.socket.try_read(buf),
+            Kind::Tls(ref mut conn) => loop {
+                match conn.reader().read(buf) {
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                    other => return other,
+                }
+                let n = self.socket.try_read(&mut self.raw)?;
+                if n == 0 {
+                    return Ok(0);
+                }
+                let mut records = &self.raw[..n];
+                while !records.is_empty() {
+                    conn.read_tls(&mut records)?;
+                    conn.process_new_packets().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                }
+            },
+            // Only clear the socket's readiness when the wakeup came from it
+            Kind::Ssh(_, ref mut channel) if readable => self.socket.try_io(Interest::READABLE, || channel.read(buf)),
+            Kind::Ssh(_, ref mut channel) => channel.read(buf),
+        }
+    }
+
+    async fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        match self.kind {
+            Kind::Plain => self.socket.write_all(data).await,
+            Kind::Tls(ref mut conn) => {
+                conn.writer().write_all(data)?;
+                self.flush_tls().await
+            }
+            Kind::Ssh(_, ref mut channel) => {
+                let mut rest = data;
+                while !rest.is_empty() {
+                    match channel.write(rest) {
+                        Ok(n) => rest = &rest[n..],
+                        Err(e) if e.kind() == io::ErrorKind::WouldBlock => tokio::time::sleep(SSH_WRITE_RETRY).await,
+                        Err(e) => return Err(e),
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Write out any TLS records rustls has queued
+    async fn flush_tls(&mut self) -> io::Result<()> {
+        if let Kind::Tls(ref mut conn) = self.kind {
+            while conn.wants_write() {
+                let mut records = Vec::new();
+                conn.write_tls(&mut records)?;
+                self.socket.write_all(&records).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Best-effort orderly close
+    async fn shutdown(&mut self) {
+        match self.kind {
+            Kind::Plain => {}
+            Kind::Tls(ref mut conn) => {
+                conn.send_close_notify();
+                let _ = self.flush_tls().await;
+            }
+            Kind::Ssh(ref session, ref mut channel) => {
+                let _ = channel.send_eof();
+                let _ = channel.close();
+                let _ = session.disconnect(None, "session closed", None);
+            }
+        }
+        let _ = self.socket.shutdown().await;
+    }
+}
@@ -0,0 +1,238 @@
+//! Outbound proxies for AS/400 connections
+//!
+//! HTTP CONNECT with optional basic authentication, and SOCKS5 (RFC 1928) with
+//! optional username/password authentication (RFC 1929). The proxy resolves the
+//! AS/400 host name, so it does not need to be resolvable locally. TLS and telnet
+//! negotiation then run over the tunnel exactly as over a direct socket.
+
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::error::NetworkError;
+
+/// Longest HTTP response header accepted from a proxy
+const MAX_HTTP_RESPONSE: usize = 8192;
+
+/// Proxy protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProxyKind {
+    /// HTTP CONNECT tunnel
+    #[default]
+    Http,
+    /// SOCKS5 with remote DNS
+    Socks5,
+}
+
+impl ProxyKind {
+    /// Display name, e.g. "HTTP CONNECT"
+    pub fn name(self) -> &'static str {
+        match self {
+            ProxyKind::Http => "HTTP CONNECT",
+            ProxyKind::Socks5 => "SOCKS5",
+        }
+    }
+
+    /// Conventional port (3128 for HTTP proxies, 1080 for SOCKS)
+    pub fn default_port(self) -> u16 {
+        match self {
+            ProxyKind::Http => 3128,
+            ProxyKind::Socks5 => 1080,
+        }
+    }
+}
+
+/// Proxy used to reach the AS/400, configurable per profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    pub kind: ProxyKind,
+    pub host: String,
+    pub port: u16,
+    /// Basic (HTTP) or RFC 1929 (SOCKS5) user name
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl ProxySettings {
+    /// Proxy address as "host:port"
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// Connect to the proxy and open a tunnel to `host:port`
+    pub fn connect(&self, host: &str, port: u16, timeout: Option<Duration>) -> Result<TcpStream, NetworkError> {
+        let unreachable = |reason: String| NetworkError::ProxyConnectFailed { proxy: self.address(), reason };
+        let addr: SocketAddr = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| unreachable(e.to_string()))?
+            .next()
+            .ok_or_else(|| unreachable("no address resolved".to_string()))?;
+        let mut tcp = match timeout {
+            Some(timeout) => TcpStream::connect_timeout(&addr, timeout),
+            None => TcpStream::connect(addr),
+        }
+        .map_err(|e| unreachable(e.to_string()))?;
+
+        let handshake_timeout = Some(timeout.unwrap_or(Duration::from_secs(10)));
+        tcp.set_read_timeout(handshake_timeout)
+            .and_then(|_| tcp.set_write_timeout(handshake_timeout))
+            .map_err(|e| unreachable(e.to_string()))?;
+
+        match self.kind {
+            ProxyKind::Http => self.http_connect(&mut tcp, host, port)?,
+            ProxyKind::Socks5 => self.socks5_connect(&mut tcp, host, port)?,
+        }
+        Ok(tcp)
+    }
+
+    fn io_error(&self, e: std::io::Error) -> NetworkError {
+        NetworkError::ProxyConnectFailed { proxy: self.address(), reason: e.to_string() }
+    }
+
+    fn refused(&self, target: &str, reason: String) -> NetworkError {
+        NetworkError::ProxyTargetRefused { proxy: self.address(), target: target.to_string(), reason }
+    }
+
+    /// CONNECT host:port and wait for a 2xx status
+    fn http_connect(&self, tcp: &mut TcpStream, host: &str, port: u16) -> Result<(), NetworkError> {
+        let target = match host.parse::<IpAddr>() {
+            Ok(IpAddr::V6(_)) => format!("[{host}]:{port}"),
+            _ => format!("{host}:{port}"),
+        };
+        let mut request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n");
+        if let Some(ref username) = self.username {
+            let credentials = format!("{username}:{}", self.password.as_deref().unwrap_or(""));
+            let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+            request.push_str(&format!("Proxy-Authorization: Basic {encoded}\r\n"));
+        }
+        request.push_str("\r\n");
+        tcp.write_all(request.as_bytes()).map_err(|e| self.io_error(e))?;
+
+        // Read byte by byte so nothing after the header (e.g. telnet from the host) is consumed
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() >= MAX_HTTP_RESPONSE {
+                return Err(self.refused(&target, "response header too long".to_string()));
+            }
+            match tcp.read(&mut byte).map_err(|e| self.io_error(e))? {
+                0 => return Err(self.refused(&target, "proxy closed the connection".to_string())),
+                _ => response.push(byte[0]),
+            }
+        }
+
+        let text = String::from_utf8_lossy(&response);
+        let status_line = text.lines().next().unwrap_or_default().trim().to_string();
+        let status = status_line.split_whitespace().nth(1).and_then(|code| code.parse::<u16>().ok());
+        match status {
+            Some(200..=299) => Ok(()),
+            Some(407) => Err(NetworkError::ProxyAuthenticationFailed { proxy: self.address() }),
+            _ => Err(self.refused(&target, status_line)),
+        }
+    }
+
+    /// RFC 1928 greeting, optional RFC 1929 authentication, then CONNECT by domain name
+    fn socks5_connect(&self, tcp: &mut TcpStream, host: &str, port: u16) -> Result<(), NetworkError> {
+        let target = format!("{host}:{port}");
+        let methods: &[u8] = if self.username.is_some() { &[0x00, 0x02] } else { &[0x00] };
+        let mut greeting = vec![0x05, methods.len() as u8];
+        greeting.extend_from_slice(methods);
+        tcp.write_all(&greeting).map_err(|e| self.io_error(e))?;
+
+        let mut reply = [0u8; 2];
+        tcp.read_exact(&mut reply).map_err(|e| self.io_error(e))?;
+        if reply[0] != 0x05 {
+            return Err(NetworkError::ProxyConnectFailed {
+                proxy: self.address(),
+                reason: "not a SOCKS5 proxy".to_string(),
+            });
+        }
+        match reply[1] {
+            0x00 => {}
+            0x02 if self.username.is_some() => self.socks5_authenticate(tcp)?,
+            _ => return Err(NetworkError::ProxyAuthenticationFailed { proxy: self.address() }),
+        }
+
+        let mut request = vec![0x05, 0x01, 0x00];
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                request.push(0x01);
+                request.extend_from_slice(&ip.octets());
+            }
+            Ok(IpAddr::V6(ip)) => {
+                request.push(0x04);
+                request.extend_from_slice(&ip.octets());
+            }
+            Err(_) => {
+                let name = u8::try_from(host.len())
+                    .map_err(|_| self.refused(&target, "host name longer than 255 bytes".to_string()))?;
+                request.push(0x03);
+                request.push(name);
+                request.extend_from_slice(host.as_bytes());
+            }
+        }
+        request.extend_from_slice(&port.to_be_bytes());
+        tcp.write_all(&request).map_err(|e| self.io_error(e))?;
+
+        let mut header = [0u8; 4];
+        tcp.read_exact(&mut header).map_err(|e| self.io_error(e))?;
+        if header[1] != 0x00 {
+            return Err(self.refused(&target, socks5_reply_message(header[1]).to_string()));
+        }
+
+        // Discard the bound address and port
+        let bound_len = match header[3] {
+            0x01 => 4,
+            0x04 => 16,
+            0x03 => {
+                let mut len = [0u8; 1];
+                tcp.read_exact(&mut len).map_err(|e| self.io_error(e))?;
+                len[0] as usize
+            }
+            other => return Err(self.refused(&target, format!("unknown address type {other}"))),
+        };
+        let mut bound = vec![0u8; bound_len + 2];
+        tcp.read_exact(&mut bound).map_err(|e| self.io_error(e))
+    }
+
+    fn socks5_authenticate(&self, tcp: &mut TcpStream) -> Result<(), NetworkError> {
+        let rejected = || NetworkError::ProxyAuthenticationFailed { proxy: self.address() };
+        let username = self.username.as_deref().unwrap_or("");
+        let password = self.password.as_deref().unwrap_or("");
+        let username_len = u8::try_from(username.len()).map_err(|_| rejected())?;
+        let password_len = u8::try_from(password.len()).map_err(|_| rejected())?;
+
+        let mut request = vec![0x01, username_len];
+        request.extend_from_slice(username.as_bytes());
+        request.push(password_len);
+        request.extend_from_slice(password.as_bytes());
+        tcp.write_all(&request).map_err(|e| self.io_error(e))?;
+
+        let mut reply = [0u8; 2];
+        tcp.read_exact(&mut reply).map_err(|e| self.io_error(e))?;
+        if reply[1] != 0x00 {
+            return Err(rejected());
+        }
+        Ok(())
+    }
+}
+
+/// RFC 1928 reply field meaning
+fn socks5_reply_message(code: u8) -> &'static str {
+    match code {
+        0x01 => "general SOCKS server failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown SOCKS error",
+    }
+}
@@ -0,0 +1,206 @@
+//! SSH tunnel transport for AS/400 connections
+//!
+//! For partitions that only expose SSH: the connection logs in over SSH, checks
+//! the server's host key against an OpenSSH `known_hosts` file, and opens a
+//! `direct-tcpip` channel to the telnet port on the far side. Telnet negotiation
+//! and the 5250/3270 data stream then run over the channel unchanged.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use ssh2::{Channel, CheckResult, KnownHostFileKind, Session};
+
+use crate::error::NetworkError;
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+/// How to authenticate to the SSH server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SshAuth {
+    Password { password: String },
+    /// OpenSSH or PEM private key file, optionally encrypted
+    PublicKey { private_key_path: String, passphrase: Option<String> },
+}
+
+impl Default for SshAuth {
+    fn default() -> Self {
+        SshAuth::Password { password: String::new() }
+    }
+}
+
+/// SSH server to tunnel the telnet connection through, configurable per profile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SshTunnel {
+    /// SSH server; empty means the session host itself
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    pub username: String,
+    pub auth: SshAuth,
+    /// OpenSSH known_hosts file (defaults to ~/.ssh/known_hosts)
+    #[serde(default)]
+    pub known_hosts_path: Option<String>,
+    /// Record keys of hosts not yet in known_hosts, like `StrictHostKeyChecking=accept-new`
+    #[serde(default)]
+    pub accept_new_host_keys: bool,
+}
+
+impl Default for SshTunnel {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: default_ssh_port(),
+            username: String::new(),
+            auth: SshAuth::default(),
+            known_hosts_path: None,
+            accept_new_host_keys: false,
+        }
+    }
+}
+
+impl SshTunnel {
+    /// `~/.ssh/known_hosts`
+    pub fn default_known_hosts_path() -> PathBuf {
+        dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")).join(".ssh").join("known_hosts")
+    }
+
+    /// SSH server to log in to for a session on `session_host`
+    pub fn server(&self, session_host: &str) -> (String, u16) {
+        let host = if self.host.trim().is_empty() { session_host } else { self.host.trim() };
+        (host.to_string(), self.port)
+    }
+
+    /// Where the channel is forwarded; a partition reached directly sees its own telnet server as localhost
+    pub fn forward_target(&self, session_host: &str, session_port: u16) -> (String, u16) {
+        if self.host.trim().is_empty() {
+            ("localhost".to_string(), session_port)
+        } else {
+            (session_host.to_string(), session_port)
+        }
+    }
+
+    /// Log in over `tcp` (already connected to the SSH server) and open a channel to `session_host:session_port`
+    pub fn open(
+        &self,
+        tcp: TcpStream,
+        session_host: &str,
+        session_port: u16,
+        timeout: Duration,
+    ) -> Result<SshStream, NetworkError> {
+        let (ssh_host, ssh_port) = self.server(session_host);
+        let server = format!("{ssh_host}:{ssh_port}");
+        let failed = |reason: String| NetworkError::SshTunnel { host: server.clone(), reason };
+
+        let mut session = Session::new().map_err(|e| failed(e.to_string()))?;
+        session.set_timeout(timeout.as_millis().min(u32::MAX as u128) as u32);
+        let socket = tcp.try_clone().map_err(|e| failed(e.to_string()))?;
+        session.set_tcp_stream(tcp);
+        session.handshake().map_err(|e| failed(format!("handshake failed: {e}")))?;
+
+        self.check_host_key(&session, &ssh_host, ssh_port)?;
+
+        let authenticated = match self.auth {
+            SshAuth::Password { ref password } => session.userauth_password(&self.username, password),
+            SshAuth::PublicKey { ref private_key_path, ref passphrase } => session.userauth_pubkey_file(
+                &self.username,
+                None,
+                Path::new(private_key_path),
+                passphrase.as_deref(),
+            ),
+        };
+        if authenticated.is_err() || !session.authenticated() {
+            return Err(NetworkError::SshAuthenticationFailed { host: server, username: self.username.clone() });
+        }
+
+        let (target_host, target_port) = self.forward_target(session_host, session_port);
+        let channel = session
+            .channel_direct_tcpip(&target_host, target_port, None)
+            .map_err(|e| failed(format!("cannot forward to {target_host}:{target_port}: {e}")))?;
+        Ok(SshStream { session, channel, socket })
+    }
+
+    fn check_host_key(&self, session: &Session, ssh_host: &str, ssh_port: u16) -> Result<(), NetworkError> {
+        let server = format!("{ssh_host}:{ssh_port}");
+        let failed = |reason: String| NetworkError::SshTunnel { host: server.clone(), reason };
+        let (key, key_type) = session.host_key().ok_or_else(|| failed("server sent no host key".to_string()))?;
+        let fingerprint = host_key_fingerprint(key);
+
+        let path = self.known_hosts_path.as_ref().map(PathBuf::from).unwrap_or_else(Self::default_known_hosts_path);
+        let mut known_hosts = session.known_hosts().map_err(|e| failed(e.to_string()))?;
+        if path.exists() {
+            known_hosts
+                .read_file(&path, KnownHostFileKind::OpenSSH)
+                .map_err(|e| failed(format!("cannot read {}: {e}", path.display())))?;
+        }
+
+        match known_hosts.check_port(ssh_host, ssh_port, key) {
+            CheckResult::Match => Ok(()),
+            CheckResult::Mismatch => Err(NetworkError::SshHostKeyChanged { host: server, fingerprint }),
+            CheckResult::NotFound if self.accept_new_host_keys => {
+                // OpenSSH writes non-default ports as [host]:port
+                let entry = if ssh_port == 22 { ssh_host.to_string() } else { format!("[{ssh_host}]:{ssh_port}") };
+                known_hosts
+                    .add(&entry, key, "added by TN5250R", key_type.into())
+                    .map_err(|e| failed(e.to_string()))?;
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|e| failed(format!("cannot create {}: {e}", dir.display())))?;
+                }
+                known_hosts
+                    .write_file(&path, KnownHostFileKind::OpenSSH)
+                    .map_err(|e| failed(format!("cannot write {}: {e}", path.display())))
+            }
+            CheckResult::NotFound => Err(NetworkError::SshHostKeyUnknown { host: server, fingerprint }),
+            CheckResult::Failure => Err(failed("known_hosts check failed".to_string())),
+        }
+    }
+}
+
+/// OpenSSH-style SHA-256 fingerprint of a host key blob, e.g. "SHA256:47DEQpj8..."
+pub fn host_key_fingerprint(key: &[u8]) -> String {
+    format!("SHA256:{}", base64::engine::general_purpose::STANDARD_NO_PAD.encode(Sha256::digest(key)))
+}
+
+/// Forwarded channel to the telnet server, keeping its SSH session alive
+pub struct SshStream {
+    session: Session,
+    channel: Channel,
+    // Second handle on the session's socket, for readiness polling
+    socket: TcpStream,
+}
+
+impl SshStream {
+    /// Session, channel and a handle on the underlying socket, for the connection actor
+    pub(crate) fn into_parts(self) -> (Session, Channel, TcpStream) {
+        (self.session, self.channel, self.socket)
+    }
+}
+
+impl std::fmt::Debug for SshStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SshStream").finish_non_exhaustive()
+    }
+}
+
+impl Read for SshStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.channel.read(buf)
+    }
+}
+
+impl Write for SshStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.channel.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.channel.flush()
+    }
+}
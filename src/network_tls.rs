@@ -0,0 +1,256 @@
+//! TLS client options for AS/400 connections
+//!
+//! Client certificate authentication (PEM or PKCS#12), the minimum protocol
+//! version, SHA-256 public key pinning and telnet START_TLS. Failures are reported as typed
+//! [`NetworkError`]s so the UI can tell a bad key file from a pin mismatch.
+
+use std::fs;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use base64::Engine;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use rustls::{AlertDescription, CertificateError, DigitallySignedStruct, OtherError, RootCertStore, SignatureScheme};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::NetworkError;
+
+static TLS13_ONLY: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS13];
+
+/// Lowest TLS protocol version the client will negotiate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TlsMinVersion {
+    /// TLS 1.2 or 1.3
+    #[default]
+    Tls12,
+    /// TLS 1.3 only
+    Tls13,
+}
+
+impl TlsMinVersion {
+    /// Protocol versions rustls may offer
+    pub fn protocol_versions(self) -> &'static [&'static rustls::SupportedProtocolVersion] {
+        match self {
+            TlsMinVersion::Tls12 => rustls::ALL_VERSIONS,
+            TlsMinVersion::Tls13 => TLS13_ONLY,
+        }
+    }
+
+    /// Display name, e.g. "TLS 1.2"
+    pub fn name(self) -> &'static str {
+        match self {
+            TlsMinVersion::Tls12 => "TLS 1.2",
+            TlsMinVersion::Tls13 => "TLS 1.3",
+        }
+    }
+}
+
+/// Client certificate presented when the server (e.g. IBM i DCM) requests one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientCertificate {
+    /// PEM certificate chain and PEM private key (PKCS#8, PKCS#1 or SEC1)
+    Pem { cert_path: String, key_path: String },
+    /// PKCS#12 (.p12/.pfx) bundle with its password
+    Pkcs12 { path: String, password: String },
+}
+
+impl ClientCertificate {
+    /// Load the certificate chain and private key
+    pub fn load(&self) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), NetworkError> {
+        match self {
+            ClientCertificate::Pem { cert_path, key_path } => {
+                let chain = load_pem_chain(cert_path)?;
+                let key = load_pem_key(key_path)?;
+                Ok((chain, key))
+            }
+            ClientCertificate::Pkcs12 { path, password } => load_pkcs12(path, password),
+        }
+    }
+}
+
+/// TLS settings beyond the CA bundle, configurable per profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsOptions {
+    /// Certificate for TLS client authentication
+    #[serde(default)]
+    pub client_certificate: Option<ClientCertificate>,
+    /// Minimum protocol version
+    #[serde(default)]
+    pub min_version: TlsMinVersion,
+    /// SHA-256 of the server's SubjectPublicKeyInfo, base64 (optionally "sha256/" prefixed) or hex
+    #[serde(default)]
+    pub pin_sha256: Option<String>,
+    /// Upgrade a plain telnet connection with START_TLS instead of connecting to a TLS port
+    #[serde(default)]
+    pub start_tls: bool,
+}
+
+/// SHA-256 hash of a certificate's SubjectPublicKeyInfo
+pub fn spki_sha256(cert: &[u8]) -> Result<[u8; 32], String> {
+    let (_, parsed) = x509_parser::parse_x509_certificate(cert).map_err(|e| format!("Invalid certificate: {e}"))?;
+    Ok(Sha256::digest(parsed.tbs_certificate.subject_pki.raw).into())
+}
+
+/// Parse a configured pin into its 32-byte hash
+pub fn parse_pin(pin: &str) -> Result<[u8; 32], NetworkError> {
+    let invalid = || NetworkError::InvalidCertificatePin { pin: pin.to_string() };
+    let trimmed = pin.trim();
+    let trimmed = trimmed.strip_prefix("sha256/").unwrap_or(trimmed);
+
+    let hex: String = trimmed.chars().filter(|c| *c != ':').collect();
+    let bytes = if hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        (0..32)
+            .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?
+    } else {
+        base64::engine::general_purpose::STANDARD.decode(trimmed).map_err(|_| invalid())?
+    };
+    bytes.try_into().map_err(|_| invalid())
+}
+
+/// Base64 form of a pin, as shown in errors
+pub fn format_pin(hash: &[u8; 32]) -> String {
+    format!("sha256/{}", base64::engine::general_purpose::STANDARD.encode(hash))
+}
+
+/// Certificate verifier that adds a public key pin check to normal WebPKI validation
+#[derive(Debug)]
+pub struct PinnedCertVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pin: [u8; 32],
+}
+
+impl PinnedCertVerifier {
+    pub fn new(roots: RootCertStore, pin: [u8; 32]) -> Result<Self, NetworkError> {
+        let inner = WebPkiServerVerifier::builder(Arc::new(roots))
+            .build()
+            .map_err(|e| NetworkError::SslError { message: format!("Cannot build certificate verifier: {e}") })?;
+        Ok(Self { inner, pin })
+    }
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)?;
+        let actual = spki_sha256(end_entity).map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+        if actual != self.pin {
+            let mismatch = NetworkError::CertificatePinMismatch {
+                host: server_name.to_str().into_owned(),
+                expected: format_pin(&self.pin),
+                actual: format_pin(&actual),
+            };
+            return Err(rustls::Error::InvalidCertificate(CertificateError::Other(OtherError(Arc::new(mismatch)))));
+        }
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Map a rustls handshake failure to a typed network error
+pub fn handshake_error(host: &str, min_version: TlsMinVersion, err: rustls::Error) -> NetworkError {
+    let host = host.to_string();
+    match err {
+        rustls::Error::InvalidCertificate(CertificateError::Other(other)) => {
+            match other.0.downcast_ref::<NetworkError>() {
+                Some(e) => e.clone(),
+                None => NetworkError::TlsCertificateInvalid { host, reason: other.to_string() },
+            }
+        }
+        rustls::Error::InvalidCertificate(e) => NetworkError::TlsCertificateInvalid { host, reason: format!("{e:?}") },
+        rustls::Error::PeerIncompatible(_) | rustls::Error::AlertReceived(AlertDescription::ProtocolVersion) => {
+            NetworkError::TlsVersionUnsupported { host, minimum: min_version.name().to_string() }
+        }
+        rustls::Error::AlertReceived(
+            alert @ (AlertDescription::BadCertificate
+            | AlertDescription::CertificateRequired
+            | AlertDescription::UnknownCA
+            | AlertDescription::CertificateUnknown
+            | AlertDescription::CertificateRevoked
+            | AlertDescription::CertificateExpired
+            | AlertDescription::UnsupportedCertificate),
+        ) => NetworkError::TlsClientCertificateRejected { host, reason: format!("{alert:?}") },
+        other => NetworkError::TlsHandshake { host, reason: other.to_string() },
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, NetworkError> {
+    fs::read(path).map_err(|e| NetworkError::TlsClientCertificate { path: path.to_string(), reason: e.to_string() })
+}
+
+fn load_pem_chain(path: &str) -> Result<Vec<CertificateDer<'static>>, NetworkError> {
+    let bytes = read_file(path)?;
+    let chain = rustls_pemfile::certs(&mut BufReader::new(bytes.as_slice()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| NetworkError::TlsClientCertificate { path: path.to_string(), reason: e.to_string() })?;
+    if chain.is_empty() {
+        return Err(NetworkError::TlsClientCertificate {
+            path: path.to_string(),
+            reason: "no PEM certificate found".to_string(),
+        });
+    }
+    Ok(chain)
+}
+
+fn load_pem_key(path: &str) -> Result<PrivateKeyDer<'static>, NetworkError> {
+    let bytes = read_file(path)?;
+    rustls_pemfile::private_key(&mut BufReader::new(bytes.as_slice()))
+        .map_err(|e| NetworkError::TlsClientCertificate { path: path.to_string(), reason: e.to_string() })?
+        .ok_or_else(|| NetworkError::TlsClientCertificate {
+            path: path.to_string(),
+            reason: "no PEM private key found".to_string(),
+        })
+}
+
+fn load_pkcs12(
+    path: &str,
+    password: &str,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), NetworkError> {
+    let bytes = read_file(path)?;
+    let error = |reason: String| NetworkError::TlsClientCertificate { path: path.to_string(), reason };
+    let store = p12_keystore::KeyStore::from_pkcs12(&bytes, password).map_err(|e| error(e.to_string()))?;
+    let (_, key_chain) = store
+        .private_key_chain()
+        .ok_or_else(|| error("no private key in PKCS#12 bundle".to_string()))?;
+    let chain = key_chain
+        .chain()
+        .iter()
+        .map(|cert| CertificateDer::from(cert.as_der().to_vec()))
+        .collect::<Vec<_>>();
+    if chain.is_empty() {
+        return Err(error("no certificate in PKCS#12 bundle".to_string()));
+    }
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_chain.key().to_vec()));
+    Ok((chain, key))
+}
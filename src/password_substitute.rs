@@ -0,0 +1,182 @@
+//! Encrypted password substitutes for 5250 auto sign-on (RFC 4777 Section 5)
+//!
+//! Instead of sending the password, the client answers the server's IBMRSEED
+//! seed with its own random seed and an IBMSUBSPW password substitute derived
+//! from the user ID, password and both seeds. The algorithm depends on the
+//! system's password level (QPWDLVL): DES for levels 0 and 1, SHA-1 for 2 and 3.
+
+use des::cipher::generic_array::GenericArray;
+use des::cipher::{BlockEncrypt, KeyInit};
+use des::Des;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+
+/// Password sequence number (PWSEQs); Telnet computes a single substitute per sign-on
+const PASSWORD_SEQUENCE: u64 = 1;
+
+/// Password substitute algorithm, selected by the system password level (QPWDLVL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PasswordLevel {
+    /// QPWDLVL 0 or 1: DES substitute, passwords up to 10 characters, case-insensitive
+    #[default]
+    Des,
+    /// QPWDLVL 2 or 3: SHA-1 substitute, passwords up to 128 characters, case-sensitive
+    Sha1,
+}
+
+impl PasswordLevel {
+    /// Algorithm for a QPWDLVL system value
+    pub fn from_qpwdlvl(level: u8) -> Self {
+        if level >= 2 {
+            PasswordLevel::Sha1
+        } else {
+            PasswordLevel::Des
+        }
+    }
+}
+
+/// Generate a random 8-byte client seed (RDs)
+pub fn client_seed() -> Result<[u8; 8], String> {
+    let mut seed = [0u8; 8];
+    getrandom::getrandom(&mut seed).map_err(|e| format!("Unable to generate client seed: {e}"))?;
+    Ok(seed)
+}
+
+/// Compute the IBMSUBSPW password substitute for the given password level
+pub fn password_substitute(
+    level: PasswordLevel,
+    user_id: &str,
+    password: &str,
+    server_seed: &[u8; 8],
+    client_seed: &[u8; 8],
+) -> Result<Vec<u8>, String> {
+    match level {
+        PasswordLevel::Des => des_substitute(user_id, password, server_seed, client_seed).map(|s| s.to_vec()),
+        PasswordLevel::Sha1 => sha1_substitute(user_id, password, server_seed, client_seed).map(|s| s.to_vec()),
+    }
+}
+
+/// DES password substitute (QPWDLVL 0/1)
+pub fn des_substitute(
+    user_id: &str,
+    password: &str,
+    server_seed: &[u8; 8],
+    client_seed: &[u8; 8],
+) -> Result<[u8; 8], String> {
+    let user_id = ebcdic_padded::<10>(user_id, "User ID")?;
+    let password = ebcdic_padded::<10>(password, "Password")?;
+    let token = des_token(&user_id, &password);
+
+    let sequence = PASSWORD_SEQUENCE.to_be_bytes();
+    let rdr_seq = u64::from_be_bytes(*server_seed).wrapping_add(PASSWORD_SEQUENCE).to_be_bytes();
+
+    let mut id_tail = [0x40u8; 8];
+    id_tail[..2].copy_from_slice(&user_id[8..]);
+
+    // DES-CBC over (RDrSEQ, RDs, ID xor RDrSEQ, ID tail xor RDrSEQ, PWSEQs); the last block is the substitute
+    let blocks = [
+        rdr_seq,
+        *client_seed,
+        xor8(&user_id[..8], &rdr_seq),
+        xor8(&id_tail, &rdr_seq),
+        sequence,
+    ];
+    let mut chain = [0u8; 8];
+    for block in &blocks {
+        chain = des_encrypt(&token, &xor8(&chain, block));
+    }
+    Ok(chain)
+}
+
+/// SHA-1 password substitute (QPWDLVL 2/3)
+pub fn sha1_substitute(
+    user_id: &str,
+    password: &str,
+    server_seed: &[u8; 8],
+    client_seed: &[u8; 8],
+) -> Result<[u8; 20], String> {
+    if user_id.is_empty() || user_id.chars().count() > 10 {
+        return Err("User ID must be 1 to 10 characters".to_string());
+    }
+    if password.is_empty() || password.chars().count() > 128 {
+        return Err("Password must be 1 to 128 characters".to_string());
+    }
+
+    // The user ID is upper-cased and blank-padded to 10 characters; both are UTF-16BE
+    let user_id = utf16_be(&format!("{:<10}", user_id.to_uppercase()));
+    let password = utf16_be(password);
+
+    let token = Sha1::new().chain_update(&user_id).chain_update(&password).finalize();
+    let substitute = Sha1::new()
+        .chain_update(token)
+        .chain_update(server_seed)
+        .chain_update(client_seed)
+        .chain_update(&user_id)
+        .chain_update(PASSWORD_SEQUENCE.to_be_bytes())
+        .finalize();
+    Ok(substitute.into())
+}
+
+/// DES password token: the folded user ID encrypted under the shifted password
+fn des_token(user_id: &[u8; 10], password: &[u8; 10]) -> [u8; 8] {
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&user_id[..8]);
+    if user_id[8] != 0x40 {
+        // Fold characters 9 and 10 of a long user ID into the first eight, two bits at a time
+        for (i, byte) in id.iter_mut().enumerate() {
+            let extra = user_id[8 + i / 4];
+            let shift = 6 - 2 * (i % 4);
+            *byte ^= ((extra >> shift) & 0x03) << 6;
+        }
+    }
+
+    let mut first = [0u8; 8];
+    first.copy_from_slice(&password[..8]);
+    let token = des_encrypt(&shifted_key(&first), &id);
+    if password[8] == 0x40 {
+        return token;
+    }
+
+    // Passwords of 9 or 10 characters: XOR the tokens of both 8-byte halves
+    let mut second = [0x40u8; 8];
+    second[..2].copy_from_slice(&password[8..]);
+    xor8(&token, &des_encrypt(&shifted_key(&second), &id))
+}
+
+/// XOR with 0x55 and shift the 64-bit value left by one bit
+fn shifted_key(password: &[u8; 8]) -> [u8; 8] {
+    let value = u64::from_be_bytes(xor8(password, &[0x55; 8]));
+    (value << 1).to_be_bytes()
+}
+
+fn des_encrypt(key: &[u8; 8], data: &[u8; 8]) -> [u8; 8] {
+    let cipher = Des::new(GenericArray::from_slice(key));
+    let mut block = GenericArray::clone_from_slice(data);
+    cipher.encrypt_block(&mut block);
+    block.into()
+}
+
+fn xor8(a: &[u8], b: &[u8; 8]) -> [u8; 8] {
+    let mut out = [0u8; 8];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = a[i] ^ b[i];
+    }
+    out
+}
+
+/// Upper-case EBCDIC text, blank (0x40) padded to `N` bytes
+fn ebcdic_padded<const N: usize>(text: &str, what: &str) -> Result<[u8; N], String> {
+    let count = text.chars().count();
+    if count == 0 || count > N {
+        return Err(format!("{what} must be 1 to {N} characters"));
+    }
+    let mut out = [0x40u8; N];
+    for (slot, ch) in out.iter_mut().zip(text.to_uppercase().chars()) {
+        *slot = crate::protocol_common::ebcdic::ascii_to_ebcdic(ch);
+    }
+    Ok(out)
+}
+
+fn utf16_be(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_be_bytes).collect()
+}
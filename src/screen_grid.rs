@@ -0,0 +1,217 @@
+//! Cell grid of the screen for rendering
+//!
+//! The renderer draws from this grid rather than from screen text: every cell
+//! carries its character and the display style in force at that position. On a
+//! 5250 screen the style comes from the last screen attribute byte (0x20-0x3F)
+//! before the cell, in reading order and across row ends; attributes written
+//! to single cells (Write Extended Attribute, CC2, ANSI SGR) are applied on top.
+
+use crate::terminal::{CharAttribute, TerminalScreen, TextDirection};
+
+/// The seven colors of a 5250 color display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScreenColor {
+    #[default]
+    Green,
+    White,
+    Red,
+    Turquoise,
+    Yellow,
+    Pink,
+    Blue,
+}
+
+impl ScreenColor {
+    pub const ALL: [ScreenColor; 7] = [
+        ScreenColor::Green,
+        ScreenColor::White,
+        ScreenColor::Red,
+        ScreenColor::Turquoise,
+        ScreenColor::Yellow,
+        ScreenColor::Pink,
+        ScreenColor::Blue,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScreenColor::Green => "Green",
+            ScreenColor::White => "White",
+            ScreenColor::Red => "Red",
+            ScreenColor::Turquoise => "Turquoise",
+            ScreenColor::Yellow => "Yellow",
+            ScreenColor::Pink => "Pink",
+            ScreenColor::Blue => "Blue",
+        }
+    }
+}
+
+/// Display style of one cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellStyle {
+    pub color: ScreenColor,
+    pub reverse: bool,
+    pub underline: bool,
+    pub blink: bool,
+    pub column_separator: bool,
+    /// Nondisplay: the cell is drawn blank
+    pub hidden: bool,
+}
+
+impl CellStyle {
+    /// Style started by a 5250 screen attribute byte, as shown on a color display
+    pub fn from_attribute_byte(byte: u8) -> Self {
+        use ScreenColor::*;
+        let (color, reverse, underline, blink, column_separator) = match byte {
+            0x20 => (Green, false, false, false, false),
+            0x21 => (Green, true, false, false, false),
+            0x22 => (White, false, false, false, false),
+            0x23 => (White, true, false, false, false),
+            0x24 => (Green, false, true, false, false),
+            0x25 => (Green, true, true, false, false),
+            0x26 => (White, false, true, false, false),
+            0x28 => (Red, false, false, false, false),
+            0x29 => (Red, true, false, false, false),
+            0x2A => (Red, false, false, true, false),
+            0x2B => (Red, true, false, true, false),
+            0x2C => (Red, false, true, false, false),
+            0x2D => (Red, true, true, false, false),
+            0x2E => (Red, false, true, true, false),
+            0x30 => (Turquoise, false, false, false, true),
+            0x31 => (Turquoise, true, false, false, true),
+            0x32 => (Yellow, false, false, false, true),
+            0x33 => (White, true, false, false, true),
+            0x34 => (Turquoise, false, true, false, false),
+            0x35 => (Turquoise, true, true, false, false),
+            0x36 => (Yellow, false, true, false, false),
+            0x38 => (Pink, false, false, false, false),
+            0x39 => (Pink, true, false, false, false),
+            0x3A => (Blue, false, false, false, false),
+            0x3B => (Blue, true, false, false, false),
+            0x3C => (Pink, false, true, false, false),
+            0x3D => (Pink, true, true, false, false),
+            0x3E => (Blue, false, true, false, false),
+            // 0x27, 0x2F, 0x37 and 0x3F are nondisplay
+            _ => return Self { hidden: true, ..Self::default() },
+        };
+        Self { color, reverse, underline, blink, column_separator, hidden: false }
+    }
+
+    /// Apply an attribute set on a single cell
+    fn with_cell_attribute(mut self, attribute: CharAttribute) -> Self {
+        match attribute {
+            CharAttribute::Intensified | CharAttribute::HighIntensity => self.color = ScreenColor::White,
+            CharAttribute::NonDisplay | CharAttribute::Hidden => self.hidden = true,
+            CharAttribute::ReverseImage | CharAttribute::ReverseVideo => self.reverse = true,
+            CharAttribute::Underline => self.underline = true,
+            CharAttribute::Blink => self.blink = true,
+            _ => {}
+        }
+        self
+    }
+}
+
+/// One screen position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GridCell {
+    pub ch: char,
+    pub style: CellStyle,
+    /// The position holds a screen attribute byte
+    pub attribute: bool,
+}
+
+impl GridCell {
+    /// Character as drawn: blank in a nondisplay cell
+    pub fn shown_char(&self) -> char {
+        if self.style.hidden { ' ' } else { self.ch }
+    }
+}
+
+/// Screen cells in display order, with the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellGrid {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<GridCell>,
+    /// Cursor position (1-based row, column) as displayed
+    pub cursor: (usize, usize),
+}
+
+impl Default for CellGrid {
+    fn default() -> Self {
+        Self::new(crate::terminal::TERMINAL_WIDTH, crate::terminal::TERMINAL_HEIGHT)
+    }
+}
+
+impl CellGrid {
+    /// Blank grid
+    pub fn new(width: usize, height: usize) -> Self {
+        let blank = GridCell { ch: ' ', ..GridCell::default() };
+        Self { width, height, cells: vec![blank; width * height], cursor: (1, 1) }
+    }
+
+    /// Grid of a screen buffer; a right-to-left screen is mirrored like its text
+    pub fn from_screen(screen: &TerminalScreen, cursor: (usize, usize)) -> Self {
+        let mut grid = Self::new(screen.width, screen.height);
+        grid.cursor = cursor;
+        let mut style = CellStyle::default();
+        for (index, cell) in screen.buffer.iter().enumerate().take(grid.cells.len()) {
+            let (row, col) = (index / screen.width, index % screen.width);
+            let col = match screen.direction {
+                TextDirection::LeftToRight => col,
+                TextDirection::RightToLeft => screen.width - 1 - col,
+            };
+            grid.cells[row * grid.width + col] = match cell.attribute {
+                CharAttribute::FieldAttribute(byte) => {
+                    style = CellStyle::from_attribute_byte(byte);
+                    GridCell { ch: ' ', style: CellStyle::default(), attribute: true }
+                }
+                attribute => GridCell { ch: cell.character, style: style.with_cell_attribute(attribute), attribute: false },
+            };
+        }
+        grid
+    }
+
+    /// Grid of plain text such as a status message, one line per row
+    pub fn from_text(text: &str, cursor: (usize, usize)) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0).max(crate::terminal::TERMINAL_WIDTH);
+        let height = lines.len().max(crate::terminal::TERMINAL_HEIGHT);
+        let mut grid = Self::new(width, height);
+        grid.cursor = cursor;
+        for (row, line) in lines.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                grid.cells[row * width + col].ch = ch;
+            }
+        }
+        grid
+    }
+
+    /// Cell at a 0-based row and column
+    pub fn cell(&self, row: usize, col: usize) -> Option<&GridCell> {
+        if row < self.height && col < self.width {
+            self.cells.get(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /// Text of the grid, one line per row like the controller's screen text
+    pub fn text(&self) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(|cell| cell.ch));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Text as drawn, with nondisplay cells blank, for searching, copying and exporting
+    pub fn visible_text(&self) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(GridCell::shown_char));
+            out.push('\n');
+        }
+        out
+    }
+}
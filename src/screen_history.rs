@@ -0,0 +1,202 @@
+//! Screen history for TN5250R
+//!
+//! Each session keeps a ring of the screens the host sent, so earlier screens
+//! can be looked at again once they are replaced. A snapshot is a clone of the
+//! [`TerminalScreen`] (text, attributes and direction) with the cursor position.
+//! Its text is taken as drawn, so nondisplay fields stay blank when a snapshot
+//! is searched, copied or exported.
+//!
+//! Host data updates the newest snapshot until the operator leaves the screen
+//! with an AID key; the next screen from the host then starts a new snapshot.
+//! A screen built from several records, or filled in by the operator, is
+//! therefore kept once, as it was last seen.
+//!
+//! Snapshots are shared, so copying a history for the GUI copies no screens.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::clipboard::{self, CopyFormat};
+use crate::screen_grid::CellGrid;
+use crate::terminal::TerminalScreen;
+
+/// Screens kept per session before the oldest are dropped
+pub const DEFAULT_CAPACITY: usize = 100;
+
+/// A screen as it was shown
+#[derive(Debug, Clone)]
+pub struct ScreenSnapshot {
+    /// Sequence number in the session, which stays the same as older screens are dropped
+    pub number: u64,
+    pub screen: TerminalScreen,
+    /// 1-based row and column of the cursor
+    pub cursor: (usize, usize),
+    /// When the screen was last updated
+    pub taken_at: chrono::DateTime<chrono::Local>,
+}
+
+impl ScreenSnapshot {
+    pub fn new(number: u64, screen: &TerminalScreen, cursor: (usize, usize)) -> Self {
+        Self { number, screen: screen.clone(), cursor, taken_at: chrono::Local::now() }
+    }
+
+    /// Screen text in display order, one line per row, with nondisplay cells blank
+    pub fn text(&self) -> String {
+        self.grid().visible_text()
+    }
+
+    /// Screen cells with their display styles, for the renderer
+    pub fn grid(&self) -> CellGrid {
+        CellGrid::from_screen(&self.screen, self.cursor)
+    }
+
+    /// First line with text, usually the screen title
+    pub fn title(&self) -> String {
+        self.text().lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("(blank screen)").to_string()
+    }
+
+    /// The whole screen as plain text, tab-delimited columns or an HTML table
+    pub fn export(&self, format: CopyFormat) -> String {
+        clipboard::copy_text(&self.text(), None, format)
+    }
+
+    /// File name for an export, e.g. `screen-12-20240131-154500.html`
+    pub fn file_name(&self, format: CopyFormat) -> String {
+        let extension = match format {
+            CopyFormat::Text => "txt",
+            CopyFormat::TabDelimited => "tsv",
+            CopyFormat::Html => "html",
+        };
+        format!("screen-{}-{}.{extension}", self.number, self.taken_at.format("%Y%m%d-%H%M%S"))
+    }
+
+    /// Write an export of the screen to a file
+    pub fn save(&self, path: &Path, format: CopyFormat) -> Result<(), String> {
+        fs::write(path, self.export(format)).map_err(|e| format!("Cannot write {}: {e}", path.display()))
+    }
+}
+
+/// Where a search term was found in the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryMatch {
+    /// Number of the snapshot
+    pub snapshot: u64,
+    /// 1-based row and column of the first character
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Ring of the latest screens of a session
+#[derive(Debug, Clone)]
+pub struct ScreenHistory {
+    snapshots: VecDeque<Arc<ScreenSnapshot>>,
+    capacity: usize,
+    /// The newest snapshot is still being sent or filled in
+    open: bool,
+    /// Number of the next new snapshot
+    next_number: u64,
+    /// Changes whenever a snapshot is added or updated
+    revision: u64,
+}
+
+impl Default for ScreenHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl ScreenHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self { snapshots: VecDeque::new(), capacity: capacity.max(1), open: false, next_number: 1, revision: 0 }
+    }
+
+    /// Keep the screen after host data: it updates the newest snapshot until the operator leaves it
+    pub fn record(&mut self, screen: &TerminalScreen, cursor: (usize, usize)) {
+        if screen.buffer.iter().all(|cell| cell.character.is_whitespace()) {
+            return;
+        }
+        let number = match self.snapshots.back() {
+            Some(newest) if self.open => newest.number,
+            _ => self.next_number,
+        };
+        let snapshot = Arc::new(ScreenSnapshot::new(number, screen, cursor));
+        if self.open {
+            self.snapshots.pop_back();
+        } else {
+            self.next_number += 1;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+        self.open = true;
+        self.revision += 1;
+    }
+
+    /// The operator left the screen: keep it as it is now, with what was typed, and close the snapshot
+    pub fn seal(&mut self, screen: &TerminalScreen, cursor: (usize, usize)) {
+        if self.open {
+            self.record(screen, cursor);
+            self.open = false;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Snapshot at `index`, oldest first
+    pub fn get(&self, index: usize) -> Option<&ScreenSnapshot> {
+        self.snapshots.get(index).map(Arc::as_ref)
+    }
+
+    /// Position of the snapshot numbered `number`, if it is still kept
+    pub fn position(&self, number: u64) -> Option<usize> {
+        self.snapshots.iter().position(|snapshot| snapshot.number == number)
+    }
+
+    /// Snapshot numbered `number`, if it is still kept
+    pub fn find(&self, number: u64) -> Option<&ScreenSnapshot> {
+        self.position(number).and_then(|index| self.get(index))
+    }
+
+    /// The most recent snapshot
+    pub fn newest(&self) -> Option<&ScreenSnapshot> {
+        self.snapshots.back().map(Arc::as_ref)
+    }
+
+    /// Snapshots, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &ScreenSnapshot> {
+        self.snapshots.iter().map(Arc::as_ref)
+    }
+
+    /// Number that changes whenever the history changes
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Every place `query` appears, ignoring case, newest snapshot first
+    pub fn search(&self, query: &str) -> Vec<HistoryMatch> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches = Vec::new();
+        for snapshot in self.snapshots.iter().rev() {
+            for (row, line) in snapshot.text().lines().enumerate() {
+                let line = line.to_lowercase();
+                for (start, _) in line.match_indices(&query) {
+                    let col = line[..start].chars().count() + 1;
+                    matches.push(HistoryMatch { snapshot: snapshot.number, row: row + 1, col });
+                }
+            }
+        }
+        matches
+    }
+}
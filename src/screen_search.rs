@@ -0,0 +1,144 @@
+//! Text search on the screen for TN5250R
+//!
+//! Ctrl+F opens a search of the screen shown, the live one or a screen looked
+//! at in the history. The search ignores case and takes either plain text or a
+//! regular expression; hidden (nondisplay) text is never matched. Matches are
+//! found row by row, so a match does not continue onto the next row.
+
+use regex::{Regex, RegexBuilder};
+
+use crate::field_manager::FieldDisplayInfo;
+use crate::screen_grid::{CellGrid, GridCell};
+
+/// Text found on the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// 1-based row and column of the first character
+    pub row: usize,
+    pub col: usize,
+    /// Length in characters
+    pub len: usize,
+}
+
+impl SearchMatch {
+    /// Whether the match starts in an input field
+    pub fn in_field(&self, field: &FieldDisplayInfo) -> bool {
+        self.row == field.start_row && self.col >= field.start_col && self.col < field.start_col + field.length
+    }
+}
+
+/// Compile the query, escaped unless it is a regular expression
+pub fn compile(query: &str, regex: bool) -> Result<Regex, String> {
+    let pattern = if regex { query.to_string() } else { regex::escape(query) };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid search pattern '{query}': {e}"))
+}
+
+/// Every match of `regex` on the screen, in reading order
+pub fn find_matches(grid: &CellGrid, regex: &Regex) -> Vec<SearchMatch> {
+    let mut matches = Vec::new();
+    if grid.width == 0 {
+        return matches;
+    }
+    for (index, cells) in grid.cells.chunks(grid.width).enumerate() {
+        let line: String = cells.iter().map(GridCell::shown_char).collect();
+        for found in regex.find_iter(&line).filter(|found| !found.is_empty()) {
+            matches.push(SearchMatch {
+                row: index + 1,
+                col: line[..found.start()].chars().count() + 1,
+                len: found.as_str().chars().count(),
+            });
+        }
+    }
+    matches
+}
+
+/// Identifies a screen shown in a session, see `Session::shown_screen`
+pub type ShownScreen = (u64, u64, Option<u64>);
+
+/// State of the search bar of a session
+#[derive(Debug, Clone, Default)]
+pub struct ScreenSearch {
+    /// The search bar is shown
+    pub open: bool,
+    pub query: String,
+    /// Take the query as a regular expression
+    pub regex: bool,
+    /// Matches on the screen shown, in reading order
+    pub matches: Vec<SearchMatch>,
+    /// Index of the match picked with next and previous
+    pub current: Option<usize>,
+    /// Why the query could not be used
+    pub error: Option<String>,
+    /// The search field had the keyboard when the bar was last drawn
+    pub focused: bool,
+    /// Query, regex flag and screen the matches were found for
+    pub searched: Option<(String, bool, ShownScreen)>,
+}
+
+impl ScreenSearch {
+    /// Find the query again on the screen shown; the current match stays picked if it is still there
+    pub fn update(&mut self, grid: &CellGrid) {
+        let picked = self.current_match();
+        self.error = None;
+        self.matches = if self.query.is_empty() {
+            Vec::new()
+        } else {
+            match compile(&self.query, self.regex) {
+                Ok(regex) => find_matches(grid, &regex),
+                Err(e) => {
+                    self.error = Some(e);
+                    Vec::new()
+                }
+            }
+        };
+        self.current = match picked {
+            Some(picked) => self.matches.iter().position(|m| *m == picked).or(Some(0)),
+            None => Some(0),
+        }
+        .filter(|_| !self.matches.is_empty());
+    }
+
+    /// Whether the query, the regex flag or the screen shown changed since the matches were found
+    pub fn is_stale(&self, screen: ShownScreen) -> bool {
+        self.searched.as_ref().is_none_or(|(query, regex, searched)| {
+            *query != self.query || *regex != self.regex || *searched != screen
+        })
+    }
+
+    /// Find the query again on `grid`, the cells of `screen`
+    pub fn update_shown(&mut self, screen: ShownScreen, grid: &CellGrid) {
+        self.update(grid);
+        self.searched = Some((self.query.clone(), self.regex, screen));
+    }
+
+    pub fn current_match(&self) -> Option<SearchMatch> {
+        self.current.and_then(|index| self.matches.get(index).copied())
+    }
+
+    /// Pick the next match, after the last one the first
+    pub fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = Some(self.current.map_or(0, |index| (index + 1) % self.matches.len()));
+        }
+    }
+
+    /// Pick the previous match, before the first one the last
+    pub fn previous(&mut self) {
+        if !self.matches.is_empty() {
+            let last = self.matches.len() - 1;
+            self.current = Some(self.current.map_or(last, |index| index.checked_sub(1).unwrap_or(last)));
+        }
+    }
+
+    /// Pick the first match in an input field after the cursor, wrapping to the top of the screen
+    pub fn next_in_field(&mut self, fields: &[FieldDisplayInfo], cursor: (usize, usize)) -> Option<SearchMatch> {
+        let in_field = |m: &SearchMatch| fields.iter().any(|field| m.in_field(field));
+        let index = self.matches.iter().position(|m| (m.row, m.col) > cursor && in_field(m))
+            .or_else(|| self.matches.iter().position(in_field))?;
+        self.current = Some(index);
+        self.current_match()
+    }
+}
@@ -3,9 +3,19 @@
 //! This module defines the Session struct that represents an active terminal connection
 //! with its associated controller and UI state.
 
-use crate::controller::AsyncTerminalController;
+use crate::clipboard::Selection;
+use crate::controller::{AsyncTerminalController, OiaState, ReconnectProgress};
 use crate::field_manager::FieldDisplayInfo;
+use crate::hotspots::{Hotspot, HotspotMatcher};
+use crate::keymap::Keymap;
+use crate::screen_grid::CellGrid;
+use crate::screen_history::{ScreenHistory, ScreenSnapshot};
+use crate::screen_search::{ScreenSearch, ShownScreen};
 use crate::session_profile::SessionProfile;
+use crate::monitoring;
+
+/// Profile id of the unsaved session that connects to the `connection.host` setting
+pub const QUICK_CONNECT_PROFILE_ID: &str = "quick-connect";
 
 /// Represents an active terminal session
 #[derive(Debug)]
@@ -14,16 +24,26 @@ pub struct Session {
     pub id: String,
     /// The profile this session was created from
     pub profile: SessionProfile,
+    /// Number of this session among those open for the same profile (1-based), used for `%S` in device names
+    pub index: u32,
+    /// Device name the host accepted for the current connection
+    pub device_name: Option<String>,
     /// The terminal controller handling the connection
     pub controller: AsyncTerminalController,
     /// Current terminal content for display
     pub terminal_content: String,
+    /// Screen cells with their display attributes, for the renderer
+    pub grid: CellGrid,
+    /// Changes whenever the live screen does, so work derived from it is redone only then
+    pub screen_revision: u64,
     /// Field information for UI highlighting
     pub fields_info: Vec<FieldDisplayInfo>,
     /// Connection status
     pub connected: bool,
     /// Connection in progress
     pub connecting: bool,
+    /// Automatic reconnection under way after the connection was lost
+    pub reconnect: Option<ReconnectProgress>,
     /// Connection start time
     pub connection_time: Option<std::time::Instant>,
     /// Last error message
@@ -34,27 +54,162 @@ pub struct Session {
     pub input_buffer: String,
     /// Saved screen content for session persistence
     pub saved_screen: Option<String>,
+    /// Operator information area state for the status line
+    pub oia: OiaState,
+    /// Key bindings from the profile's keymap file
+    pub keymap: Keymap,
+    /// Rectangle selected with the mouse for copying
+    pub selection: Option<Selection>,
+    /// `host:port` being typed for a Quick Connect session
+    pub address: String,
+    /// The sign-on screen was requested after connecting
+    pub login_screen_requested: bool,
+    /// Shown in a window of its own instead of a tab of the main window
+    pub detached: bool,
+    /// Clickable screen hints on the current screen
+    pub hotspots: Vec<Hotspot>,
+    /// The profile's hotspot rules, compiled
+    hotspot_matcher: HotspotMatcher,
+    /// Previous screens, as last fetched from the controller
+    pub history: ScreenHistory,
+    /// Number of the snapshot shown read-only instead of the live screen
+    pub history_view: Option<u64>,
+    /// Text searched for in the history
+    pub history_query: String,
+    /// Outcome of the last snapshot export
+    pub history_status: Option<Result<String, String>>,
+    /// Ctrl+F search of the screen shown
+    pub search: ScreenSearch,
 }
 
 impl Session {
     /// Create a new session from a profile
     pub fn new(profile: SessionProfile) -> Self {
-        Self {
+        Self::with_index(profile, 1)
+    }
+
+    /// Create a new session from a profile with its index among the profile's open sessions
+    pub fn with_index(profile: SessionProfile, index: u32) -> Self {
+        let mut session = Self {
             id: uuid::Uuid::new_v4().to_string(),
+            index,
+            device_name: None,
             controller: AsyncTerminalController::new(),
             terminal_content: String::new(),
+            grid: CellGrid::default(),
+            screen_revision: 0,
             fields_info: Vec::new(),
             connected: false,
             connecting: false,
+            reconnect: None,
             connection_time: None,
             error_message: None,
             profile,
             show_monitoring_dashboard: false,
             input_buffer: String::new(),
             saved_screen: None,
+            oia: OiaState::default(),
+            keymap: Keymap::default(),
+            selection: None,
+            address: String::new(),
+            login_screen_requested: false,
+            detached: false,
+            hotspots: Vec::new(),
+            hotspot_matcher: HotspotMatcher::default(),
+            history: ScreenHistory::default(),
+            history_view: None,
+            history_query: String::new(),
+            history_status: None,
+            search: ScreenSearch::default(),
+        };
+        if !session.profile.host.is_empty() {
+            session.address = format!("{}:{}", session.profile.host, session.profile.port);
+        }
+        session.load_keymap();
+        session.load_hotspot_rules();
+        session
+    }
+
+    /// Load the profile's keymap, keeping the default layout if the file cannot be read
+    pub fn load_keymap(&mut self) {
+        match Keymap::for_profile(self.profile.keymap.as_deref()) {
+            Ok(keymap) => self.keymap = keymap,
+            Err(e) => {
+                self.keymap = Keymap::default();
+                self.error_message = Some(e);
+            }
+        }
+    }
+
+    /// Compile the profile's hotspot rules and find the hotspots on the current screen
+    pub fn load_hotspot_rules(&mut self) {
+        self.hotspot_matcher = HotspotMatcher::new(&self.profile.hotspots);
+        self.refresh_hotspots();
+    }
+
+    /// Find the hotspots on the screen shown, which is the status text until the host sends a screen
+    pub fn refresh_hotspots(&mut self) {
+        self.hotspots = if self.grid.text() == self.terminal_content {
+            self.hotspot_matcher.find(&self.grid)
+        } else {
+            self.hotspot_matcher.find(&CellGrid::from_text(&self.terminal_content, (0, 0)))
+        };
+    }
+
+    /// Hotspot under a 1-based row and column
+    pub fn hotspot_at(&self, row: usize, col: usize) -> Option<&Hotspot> {
+        self.hotspots.iter().find(|hotspot| hotspot.contains(row, col))
+    }
+
+    /// Snapshot shown instead of the live screen, if it is still kept
+    pub fn viewed_snapshot(&self) -> Option<&ScreenSnapshot> {
+        self.history_view.and_then(|number| self.history.find(number))
+    }
+
+    /// Identifies the screen shown: the live screen's revision, the history's revision and the snapshot looked at
+    pub fn shown_screen(&self) -> ShownScreen {
+        (self.screen_revision, self.history.revision(), self.history_view)
+    }
+
+    /// Cells of the screen shown: the one looked at in the history, else the live screen
+    pub fn shown_grid(&self) -> CellGrid {
+        match self.viewed_snapshot() {
+            Some(snapshot) => snapshot.grid(),
+            None if self.grid.text() == self.terminal_content => self.grid.clone(),
+            None => CellGrid::from_text(&self.terminal_content, (0, 0)),
+        }
+    }
+
+    /// Text of the screen shown with nondisplay cells blank, for copying
+    pub fn shown_text(&self) -> String {
+        self.shown_grid().visible_text()
+    }
+
+    /// Show the screen before the one shown; the live screen is the newest snapshot
+    pub fn history_back(&mut self) {
+        let shown = match self.history_view.and_then(|number| self.history.position(number)) {
+            Some(position) => position,
+            None => self.history.len().saturating_sub(1),
+        };
+        if let Some(snapshot) = self.history.get(shown.saturating_sub(1)) {
+            self.history_view = Some(snapshot.number);
         }
     }
 
+    /// Show the screen after the one shown, or the live screen after the newest snapshot
+    pub fn history_forward(&mut self) {
+        let next = self.history_view
+            .and_then(|number| self.history.position(number))
+            .and_then(|position| self.history.get(position + 1))
+            .filter(|snapshot| snapshot.number != self.history.newest().map_or(0, |newest| newest.number));
+        self.history_view = next.map(|snapshot| snapshot.number);
+    }
+
+    /// Session opened from the connection settings rather than a saved profile
+    pub fn is_quick_connect(&self) -> bool {
+        self.profile.id == QUICK_CONNECT_PROFILE_ID
+    }
+
     /// Get the display name for this session
     pub fn display_name(&self) -> String {
         format!("{} ({})",
@@ -63,6 +218,14 @@ impl Session {
         )
     }
 
+    /// Label for the session tab: the profile name and the device name in use
+    pub fn tab_label(&self) -> String {
+        match &self.device_name {
+            Some(device_name) => format!("{} [{device_name}]", self.profile.name),
+            None => self.profile.name.clone(),
+        }
+    }
+
     /// Check if the session needs UI updates
     pub fn needs_update(&self) -> bool {
         // This would check if terminal content or fields have changed
@@ -99,65 +262,143 @@ impl Session {
 
     /// Connect the session using profile credentials
     pub fn connect(&mut self) {
+        // For now, use default TLS settings (port 992 uses SSL, others don't)
+        let use_tls = self.profile.port == 992;
+        self.connect_with_tls(use_tls, false, None);
+    }
+
+    /// Connect the session using profile credentials and the given TLS settings
+    pub fn connect_with_tls(&mut self, use_tls: bool, insecure: bool, ca_bundle_path: Option<String>) {
         // Clear any previous error message
         self.error_message = None;
 
         // Configure credentials from profile if available (RFC 4777 authentication)
         if let (Some(username), Some(password)) = (&self.profile.username, &self.profile.password) {
             self.controller.set_credentials(username, password);
+            self.controller.set_password_level(self.profile.password_level);
             println!("Session {}: Configured credentials for user: {}", self.id, username);
         } else {
             // Clear credentials if not set in profile
             self.controller.clear_credentials();
         }
 
+        self.controller.set_tls_options(self.profile.tls.clone());
+        self.controller.set_proxy(self.profile.proxy.clone());
+        self.controller.set_ssh_tunnel(self.profile.ssh_tunnel.clone());
+        self.controller.set_session_config(self.profile.session_config.clone());
+        self.controller.set_device_name(self.profile.device_name.clone());
+        self.controller.set_session_index(self.index);
+        self.controller.set_environment(self.profile.environment.clone());
+        self.load_keymap();
+
         // Set connecting state
         self.connecting = true;
+        self.login_screen_requested = false;
         self.connection_time = Some(std::time::Instant::now());
         self.terminal_content = format!("Connecting to {}:{}...\n", self.profile.host, self.profile.port);
 
         // Use non-blocking connect with TLS options
-        // For now, use default TLS settings (port 992 uses SSL, others don't)
-        let use_tls = self.profile.port == 992;
-
         if let Err(e) = self.controller.connect_async_with_tls_options(
             self.profile.host.clone(),
             self.profile.port,
             Some(use_tls),
-            Some(false), // insecure = false
-            None, // ca_bundle_path = None
+            Some(insecure),
+            ca_bundle_path,
         ) {
             self.terminal_content = format!("Connection failed to start: {e}\n");
             self.connecting = false;
+            self.connection_time = None;
             self.error_message = Some(format!("Connection failed: {e}"));
+            self.record_connect_failure(&e);
         }
+        self.screen_revision += 1;
     }
 
     /// Disconnect the session
     pub fn disconnect(&mut self) {
-        self.controller.cancel_connect();
+        self.controller.disconnect();
         self.connected = false;
         self.connecting = false;
+        self.reconnect = None;
+        self.device_name = None;
         self.connection_time = None;
+        self.login_screen_requested = false;
+        self.terminal_content = "Disconnected from AS/400 system\nReady for new connection...\n".to_string();
+        self.screen_revision += 1;
+        self.refresh_hotspots();
     }
 
-    /// Update session state from controller
-    pub fn update_from_controller(&mut self) {
+    /// Update session state from controller, returning whether anything shown changed
+    pub fn update_from_controller(&mut self) -> bool {
+        let was_connected = self.connected;
+        let was_connecting = self.connecting;
+        let previous_oia = self.oia.clone();
+
         // Connection state
         self.connected = self.controller.is_connected();
         self.connecting = self.controller.is_connecting();
+        self.reconnect = self.controller.reconnect_progress();
+        let mut changed = self.connected != was_connected || self.connecting != was_connecting;
+        if self.connected && !was_connected && was_connecting {
+            self.error_message = None;
+            self.record_connect_success();
+        }
+        if self.connected {
+            if let Some(device_name) = self.controller.device_name() {
+                self.device_name = Some(device_name);
+            }
+        } else if !self.connecting && self.reconnect.is_none() {
+            self.device_name = None;
+        }
 
         // Check for connection errors
         if let Some(err) = self.controller.take_last_connect_error() {
+            if was_connecting {
+                self.record_connect_failure(&err);
+                self.terminal_content = if err.to_lowercase().contains("timed out") {
+                    format!("Connection timed out to {}:{}\n", self.profile.host, self.profile.port)
+                } else if err.to_lowercase().contains("canceled") {
+                    "Connection canceled by user\n".to_string()
+                } else {
+                    format!("Connection failed: {err}\n")
+                };
+            }
             self.error_message = Some(err.clone());
             self.connecting = false;
             self.connected = false;
+            self.connection_time = None;
+            self.login_screen_requested = false;
+            changed = true;
+        }
+
+        // Pull latest terminal content; while disconnected the status text stays on screen
+        if self.connected {
+            if let Ok(content) = self.controller.get_terminal_content() {
+                if !content.is_empty() && content != self.terminal_content {
+                    self.terminal_content = content;
+                    changed = true;
+                }
+            }
+            if let Ok(grid) = self.controller.get_cell_grid() {
+                changed |= grid != self.grid;
+                self.grid = grid;
+            }
+        }
+        if changed {
+            self.screen_revision += 1;
+            self.refresh_hotspots();
         }
 
-        // Pull latest terminal content
-        if let Ok(content) = self.controller.get_terminal_content() {
-            if !content.is_empty() {
-                self.terminal_content = content;
+        // Fetch the screen history again when it has changed
+        if let Ok(revision) = self.controller.screen_history_revision() {
+            if revision != self.history.revision() {
+                if let Ok(history) = self.controller.get_screen_history() {
+                    self.history = history;
+                    // The screen looked at may have been dropped from the ring
+                    if self.history_view.is_some() && self.viewed_snapshot().is_none() {
+                        self.history_view = None;
+                    }
+                }
             }
         }
 
@@ -165,5 +406,64 @@ impl Session {
         if let Ok(fields) = self.controller.get_fields_info() {
             self.fields_info = fields;
         }
+
+        // Operator information area (insert mode, keyboard lock, operator errors)
+        if let Ok(oia) = self.controller.get_oia_state() {
+            self.oia = oia;
+        }
+        changed |= self.oia != previous_oia;
+
+        // Ask for the sign-on screen once the connection has settled
+        if self.connected && !self.login_screen_requested {
+            if let Some(connection_time) = self.connection_time {
+                if connection_time.elapsed() >= std::time::Duration::from_secs(2) {
+                    if let Err(e) = self.controller.request_login_screen() {
+                        eprintln!("Failed to request login screen: {e}");
+                    }
+                    self.login_screen_requested = true;
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Record a successful connection in monitoring
+    fn record_connect_success(&self) {
+        let monitoring = monitoring::MonitoringSystem::global();
+        monitoring.integration_monitor.record_integration_event(monitoring::IntegrationEvent {
+            timestamp: std::time::Instant::now(),
+            event_type: monitoring::IntegrationEventType::IntegrationSuccess,
+            source_component: "network".to_string(),
+            target_component: Some("controller".to_string()),
+            description: format!("Successfully established connection to {}:{}", self.profile.host, self.profile.port),
+            details: std::collections::HashMap::new(),
+            duration_us: self.connection_time.map(|t| t.elapsed().as_micros() as u64),
+            success: true,
+        });
+    }
+
+    /// Raise a monitoring alert for a connection that could not be made
+    fn record_connect_failure(&self, err: &str) {
+        let monitoring = monitoring::MonitoringSystem::global();
+        let level = if err.to_lowercase().contains("timed out") || err.to_lowercase().contains("canceled") {
+            monitoring::AlertLevel::Warning
+        } else {
+            monitoring::AlertLevel::Critical
+        };
+        monitoring.alerting_system.trigger_alert(monitoring::Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: std::time::Instant::now(),
+            level,
+            component: "network".to_string(),
+            message: format!("Connection error to {}:{}: {err}", self.profile.host, self.profile.port),
+            details: std::collections::HashMap::new(),
+            acknowledged: false,
+            acknowledged_at: None,
+            resolved: false,
+            resolved_at: None,
+            occurrence_count: 1,
+            last_occurrence: std::time::Instant::now(),
+        });
     }
 }
\ No newline at end of file
@@ -5,7 +5,14 @@
 
 use serde::{Deserialize, Serialize};
 use crate::lib3270::display::ScreenSize;
-use crate::network::ProtocolMode;
+use crate::network::{ProtocolMode, SessionConfig};
+use crate::password_substitute::PasswordLevel;
+use crate::network_tls::TlsOptions;
+use crate::network_proxy::ProxySettings;
+use crate::network_ssh::SshTunnel;
+use crate::telnet_environment::EnvironmentSettings;
+use crate::color_scheme::ColorScheme;
+use crate::hotspots::HotspotSettings;
 
 /// A connection profile containing all settings needed to connect to a terminal session
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +33,36 @@ pub struct SessionProfile {
     pub username: Option<String>,
     /// Optional password for authentication (consider secure storage in production)
     pub password: Option<String>,
+    /// Password substitute algorithm for auto sign-on (DES for QPWDLVL 0/1, SHA-1 for 2/3)
+    #[serde(default)]
+    pub password_level: PasswordLevel,
+    /// TLS client certificate, minimum version and certificate pin
+    #[serde(default)]
+    pub tls: TlsOptions,
+    /// HTTP CONNECT or SOCKS5 proxy to connect through
+    #[serde(default)]
+    pub proxy: Option<ProxySettings>,
+    /// SSH server to tunnel the telnet connection through
+    #[serde(default)]
+    pub ssh_tunnel: Option<SshTunnel>,
+    /// Keepalive, timeouts and automatic reconnection
+    #[serde(default)]
+    pub session_config: SessionConfig,
+    /// Display device name or pattern such as `QPADEV%02D` (see `device_name`); None lets the host choose
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// NEW-ENVIRON variables: keyboard, code page, auto sign-on program/library/menu and custom pairs
+    #[serde(default)]
+    pub environment: EnvironmentSettings,
+    /// Keymap file (JSON, see `keymap`); None uses the ACS-style default layout
+    #[serde(default)]
+    pub keymap: Option<String>,
+    /// Screen colors, field state shading and cursor color
+    #[serde(default)]
+    pub color_scheme: ColorScheme,
+    /// Clickable screen hints and the profile's own hotspot patterns
+    #[serde(default)]
+    pub hotspots: HotspotSettings,
     /// Terminal screen size
     pub screen_size: ScreenSize,
     /// Whether to auto-connect when profile is loaded
@@ -49,6 +86,16 @@ impl SessionProfile {
             protocol: ProtocolMode::TN5250,
             username: None,
             password: None,
+            password_level: PasswordLevel::default(),
+            tls: TlsOptions::default(),
+            proxy: None,
+            ssh_tunnel: None,
+            session_config: SessionConfig::default(),
+            device_name: None,
+            environment: EnvironmentSettings::default(),
+            keymap: None,
+            color_scheme: ColorScheme::default(),
+            hotspots: HotspotSettings::default(),
             screen_size: ScreenSize::Model2,
             auto_connect: false,
             created_at: now,
@@ -68,6 +115,16 @@ impl SessionProfile {
             protocol: ProtocolMode::TN5250,
             username: None,
             password: None,
+            password_level: PasswordLevel::default(),
+            tls: TlsOptions::default(),
+            proxy: None,
+            ssh_tunnel: None,
+            session_config: SessionConfig::default(),
+            device_name: None,
+            environment: EnvironmentSettings::default(),
+            keymap: None,
+            color_scheme: ColorScheme::default(),
+            hotspots: HotspotSettings::default(),
             screen_size: ScreenSize::Model2,
             auto_connect: false,
             created_at: now,
@@ -0,0 +1,174 @@
+//! NEW-ENVIRON variables sent to the host (RFC 1572, RFC 4777)
+//!
+//! IBM i reads a set of well-known USERVARs during telnet negotiation: with auto
+//! sign-on, IBMPROGRAM, IBMCURLIB and IBMIMENU pick the program, current library
+//! and menu the job starts in, skipping the initial menus. Profiles set these
+//! through typed fields and may add arbitrary VAR/USERVAR pairs on top; custom
+//! pairs win over typed fields and built-in defaults of the same name. The device
+//! name and sign-on variables are never taken from profile pairs.
+
+use serde::{Deserialize, Serialize};
+
+/// NEW-ENVIRON VAR type byte
+pub const VAR: u8 = 0;
+/// NEW-ENVIRON VALUE type byte
+pub const VALUE: u8 = 1;
+/// NEW-ENVIRON ESC type byte
+pub const ESC: u8 = 2;
+/// NEW-ENVIRON USERVAR type byte
+pub const USERVAR: u8 = 3;
+
+/// Variables the negotiator fills in itself (device name and sign-on)
+pub const RESERVED_NAMES: &[&str] = &["DEVNAME", "USER", "IBMRSEED", "IBMSUBSPW"];
+
+/// True for variables a profile cannot set
+pub fn is_reserved(name: &str) -> bool {
+    RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Well-known (VAR) or user-defined (USERVAR) variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VariableKind {
+    /// Well-known variable such as USER
+    Var,
+    /// User-defined variable, including the IBM-defined ones such as IBMPROGRAM
+    #[default]
+    UserVar,
+}
+
+impl VariableKind {
+    /// Type byte preceding the name
+    pub fn type_byte(self) -> u8 {
+        match self {
+            VariableKind::Var => VAR,
+            VariableKind::UserVar => USERVAR,
+        }
+    }
+
+    /// Kind for a VAR or USERVAR type byte
+    pub fn from_type_byte(byte: u8) -> Option<Self> {
+        match byte {
+            VAR => Some(VariableKind::Var),
+            USERVAR => Some(VariableKind::UserVar),
+            _ => None,
+        }
+    }
+
+    /// "VAR" or "USERVAR"
+    pub fn name(self) -> &'static str {
+        match self {
+            VariableKind::Var => "VAR",
+            VariableKind::UserVar => "USERVAR",
+        }
+    }
+}
+
+/// A name/value pair
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct EnvironmentVariable {
+    pub kind: VariableKind,
+    pub name: String,
+    pub value: String,
+}
+
+impl EnvironmentVariable {
+    pub fn new(kind: VariableKind, name: &str, value: &str) -> Self {
+        Self { kind, name: name.to_string(), value: value.to_string() }
+    }
+
+    /// Append `kind name VALUE value` to a NEW-ENVIRON IS message
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.kind.type_byte());
+        push_escaped(out, self.name.as_bytes());
+        out.push(VALUE);
+        push_escaped(out, self.value.as_bytes());
+    }
+}
+
+/// Append NEW-ENVIRON text, ESC-quoting VAR/VALUE/ESC/USERVAR bytes and doubling IAC
+pub fn push_escaped(out: &mut Vec<u8>, bytes: &[u8]) {
+    for &byte in bytes {
+        match byte {
+            VAR..=USERVAR => out.extend_from_slice(&[ESC, byte]),
+            0xFF => out.extend_from_slice(&[0xFF, 0xFF]),
+            _ => out.push(byte),
+        }
+    }
+}
+
+/// Variables a profile sends during negotiation; empty fields keep the built-in defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentSettings {
+    /// KBDTYPE, e.g. USB
+    pub keyboard_type: String,
+    /// CODEPAGE, e.g. 37
+    pub code_page: String,
+    /// CHARSET, e.g. 697
+    pub charset: String,
+    /// IBMPROGRAM: program called after auto sign-on
+    pub program: String,
+    /// IBMCURLIB: current library for the job
+    pub current_library: String,
+    /// IBMIMENU: initial menu shown after auto sign-on
+    pub initial_menu: String,
+    /// IBMSENDCONFREC: ask the host for the startup configuration record
+    pub send_config_record: bool,
+    /// IBMFONT: font for 3812/5553 printer emulation
+    pub font: String,
+    /// IBMASSOCPRT: printer device associated with the display
+    pub associated_printer: String,
+    /// Additional VAR/USERVAR pairs
+    pub custom: Vec<EnvironmentVariable>,
+}
+
+impl EnvironmentSettings {
+    /// Typed variables that are set, as USERVARs (RFC 4777)
+    pub fn typed(&self) -> Vec<EnvironmentVariable> {
+        let fields = [
+            ("KBDTYPE", self.keyboard_type.as_str()),
+            ("CODEPAGE", self.code_page.as_str()),
+            ("CHARSET", self.charset.as_str()),
+            ("IBMPROGRAM", self.program.as_str()),
+            ("IBMCURLIB", self.current_library.as_str()),
+            ("IBMIMENU", self.initial_menu.as_str()),
+            ("IBMSENDCONFREC", if self.send_config_record { "YES" } else { "" }),
+            ("IBMFONT", self.font.as_str()),
+            ("IBMASSOCPRT", self.associated_printer.as_str()),
+        ];
+        fields
+            .iter()
+            .map(|(name, value)| (name, value.trim()))
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| EnvironmentVariable::new(VariableKind::UserVar, name, &value.to_uppercase()))
+            .collect()
+    }
+
+    /// Configured value for `name`: a custom pair first, then a typed field
+    pub fn value(&self, name: &str) -> Option<String> {
+        if is_reserved(name) {
+            return None;
+        }
+        self.custom
+            .iter()
+            .find(|var| var.name.eq_ignore_ascii_case(name))
+            .map(|var| var.value.clone())
+            .or_else(|| self.typed().into_iter().find(|var| var.name == name.to_uppercase()).map(|var| var.value))
+    }
+
+    /// Host code page from CODEPAGE, when it is a number
+    pub fn host_code_page(&self) -> Option<u16> {
+        self.value("CODEPAGE").and_then(|value| value.trim().parse().ok())
+    }
+
+    /// Every configured variable: typed fields not overridden, then the custom pairs
+    pub fn variables(&self) -> Vec<EnvironmentVariable> {
+        let mut vars: Vec<_> = self
+            .typed()
+            .into_iter()
+            .filter(|var| !self.custom.iter().any(|custom| custom.name.eq_ignore_ascii_case(&var.name)))
+            .collect();
+        vars.extend(self.custom.iter().filter(|var| !var.name.trim().is_empty() && !is_reserved(&var.name)).cloned());
+        vars
+    }
+}
@@ -14,7 +14,8 @@
 //! 2. **AS/400 Environment Variables**: Comprehensive environment variable support
 //!    including DEVNAME, KBDTYPE, CODEPAGE, IBMRSEED, IBMSUBSPW, USER, TERM, LANG,
 //!    DISPLAY, and LFA. This resolves Environment Variable Handling issues by
-//!    providing complete AS/400 compatibility.
+//!    providing complete AS/400 compatibility. Profiles add IBMPROGRAM, IBMCURLIB,
+//!    IBMIMENU and arbitrary VAR/USERVAR pairs through `telnet_environment`.
 //!
 //! 3. **Security-First Design**: All input validation includes bounds checking,
 //!    whitelist validation, and sanitization to prevent command injection and
@@ -29,6 +30,8 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use crate::password_substitute::{self, PasswordLevel};
+use crate::telnet_environment::{self, EnvironmentSettings, EnvironmentVariable, VariableKind};
 use tokio::task::JoinHandle;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -40,6 +43,7 @@ pub enum TelnetOption {
     TerminalType = 24,
     NewEnvironment = 39,
     TN3270E = 40,  // TN3270 Enhanced protocol option
+    StartTls = 46, // Telnet START_TLS (draft-altman-telnet-starttls)
 }
 
 impl TelnetOption {
@@ -52,6 +56,7 @@ impl TelnetOption {
             24 => Some(TelnetOption::TerminalType),
             39 => Some(TelnetOption::NewEnvironment),
             40 => Some(TelnetOption::TN3270E),
+            46 => Some(TelnetOption::StartTls),
             _ => None,
         }
     }
@@ -160,6 +165,26 @@ impl TN3270EDeviceType {
     }
 }
 
+/// START_TLS FOLLOWS subcommand
+const START_TLS_FOLLOWS: u8 = 1;
+
+/// Progress of an in-band TLS upgrade on a plain telnet connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartTlsState {
+    /// START_TLS is refused if the server offers it
+    Disabled,
+    /// Wanted, WILL START_TLS not yet sent
+    Enabled,
+    /// WILL START_TLS sent, waiting for the server's FOLLOWS
+    Requested,
+    /// FOLLOWS exchanged; the TLS handshake must start now
+    Ready,
+    /// The connection has been upgraded to TLS
+    Active,
+    /// The server answered WONT/DONT START_TLS
+    Refused,
+}
+
 /// TN3270E Session States
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TN3270ESessionState {
@@ -371,6 +396,9 @@ pub struct TelnetNegotiator {
     /// Optional password for auto-sign-on (RFC 4777 Section 5)
     password: Option<String>,
 
+    /// Password substitute algorithm matching the host's QPWDLVL
+    password_level: PasswordLevel,
+
     /// TN3270E session state
     tn3270e_session_state: TN3270ESessionState,
 
@@ -379,8 +407,23 @@ pub struct TelnetNegotiator {
 
     /// Logical unit name for session binding
     logical_unit_name: Option<String>,
+
+    /// In-band TLS upgrade state
+    start_tls: StartTlsState,
+
+    /// Display device name requested from the host (DEVNAME)
+    device_name: Option<String>,
+
+    /// Profile NEW-ENVIRON variables (IBMPROGRAM, IBMCURLIB, custom pairs, ...)
+    environment: EnvironmentSettings,
+
+    /// Variables sent to the host so far, secrets masked
+    sent_environment: Vec<EnvironmentVariable>,
 }
 
+/// Device name sent when none is configured
+pub const DEFAULT_DEVICE_NAME: &str = "TN5250R";
+
 impl Default for TelnetNegotiator {
     fn default() -> Self {
         Self::new()
@@ -405,9 +448,14 @@ impl TelnetNegotiator {
             buffer_pool: BufferPool::new(),
             username: None,
             password: None,
+            password_level: PasswordLevel::default(),
             tn3270e_session_state: TN3270ESessionState::NotConnected,
             tn3270e_device_type: None,
             logical_unit_name: None,
+            start_tls: StartTlsState::Disabled,
+            device_name: None,
+            environment: EnvironmentSettings::default(),
+            sent_environment: Vec::new(),
         };
         
         // Initialize all options to Initial state
@@ -419,19 +467,105 @@ impl TelnetNegotiator {
     }
     
     /// Set credentials for auto-sign-on authentication (RFC 4777 Section 5)
-    /// The username and password will be sent in response to IBMRSEED requests
+    /// The username and an encrypted password substitute are sent in response to IBMRSEED requests
     /// 
     /// # Arguments
     /// * `username` - AS/400 user profile name (uppercase recommended)
-    /// * `password` - User password (will be sent as plain text if no encryption)
-    ///
-    /// # Security Note
-    /// This implementation uses plain text password transmission (IBMRSEED empty).
-    /// For production use, implement DES or SHA password encryption per RFC 4777.
+    /// * `password` - User password (never sent; only its substitute is)
     pub fn set_credentials(&mut self, username: &str, password: &str) {
         self.username = Some(username.to_uppercase());
         self.password = Some(password.to_string());
     }
+
+    /// Select the password substitute algorithm (DES for QPWDLVL 0/1, SHA-1 for 2/3)
+    pub fn set_password_level(&mut self, level: PasswordLevel) {
+        self.password_level = level;
+    }
+
+    /// Password substitute algorithm in use
+    pub fn password_level(&self) -> PasswordLevel {
+        self.password_level
+    }
+
+    /// Request a specific display device name; None sends the default
+    pub fn set_device_name(&mut self, name: Option<String>) {
+        self.device_name = name.map(|n| n.trim().to_uppercase()).filter(|n| !n.is_empty());
+    }
+
+    /// Device name sent in DEVNAME
+    pub fn device_name(&self) -> &str {
+        self.device_name.as_deref().unwrap_or(DEFAULT_DEVICE_NAME)
+    }
+
+    /// Set the profile's NEW-ENVIRON variables
+    pub fn set_environment(&mut self, environment: EnvironmentSettings) {
+        self.environment = environment;
+    }
+
+    /// Variables sent to the host, with IBMRSEED and IBMSUBSPW values masked
+    pub fn sent_environment(&self) -> &[EnvironmentVariable] {
+        &self.sent_environment
+    }
+
+    /// Value answered for a requested variable: the profile's setting, then the built-in default
+    fn environment_value(&self, name: &str) -> Option<String> {
+        match name {
+            "DEVNAME" => return Some(self.device_name().to_string()),
+            "USER" => return Some(self.username.clone().unwrap_or_else(|| "GUEST".to_string())),
+            _ => {}
+        }
+        if let Some(value) = self.environment.value(name) {
+            return Some(value);
+        }
+        let default = match name {
+            "KBDTYPE" => "USB",
+            "CODEPAGE" | "CHARSET" => "37",
+            "LFA" => "1", // Standard local format
+            "TERM" => "IBM-3179-2",
+            "LANG" => "EN_US",
+            "DISPLAY" => ":0.0",
+            _ => return None,
+        };
+        Some(default.to_string())
+    }
+
+    /// Append a variable to an IS response and remember it for diagnostics
+    fn answer_variable(&mut self, response: &mut Vec<u8>, var: EnvironmentVariable) {
+        var.encode(response);
+        self.sent_environment.push(var);
+    }
+
+    /// Upgrade the connection with telnet START_TLS before negotiating anything else
+    pub fn set_start_tls(&mut self, enabled: bool) {
+        self.start_tls = if enabled { StartTlsState::Enabled } else { StartTlsState::Disabled };
+    }
+
+    /// Current START_TLS state
+    pub fn start_tls_state(&self) -> StartTlsState {
+        self.start_tls
+    }
+
+    /// IAC WILL START_TLS, sent instead of the initial negotiation when an upgrade is wanted
+    pub fn start_tls_request(&mut self) -> Vec<u8> {
+        if self.start_tls != StartTlsState::Enabled {
+            return Vec::new();
+        }
+        self.start_tls = StartTlsState::Requested;
+        vec![TelnetCommand::IAC as u8, TelnetCommand::WILL as u8, TelnetOption::StartTls as u8]
+    }
+
+    /// Forget all option state after the TLS handshake; negotiation restarts inside TLS
+    pub fn complete_start_tls(&mut self) {
+        for state in self.negotiation_states.values_mut() {
+            *state = NegotiationState::Initial;
+        }
+        self.input_buffer.clear();
+        self.output_buffer.clear();
+        self.negotiation_complete = false;
+        self.tn3270e_session_state = TN3270ESessionState::NotConnected;
+        self.tn3270e_device_type = None;
+        self.start_tls = StartTlsState::Active;
+    }
     
     /// Escape IAC bytes in data stream (important for binary mode)
     pub fn escape_iac_in_data(data: &[u8]) -> Vec<u8> {
@@ -496,6 +630,7 @@ impl TelnetNegotiator {
                                 if remaining.len() >= 3 {
                                     if let Some(option) = TelnetOption::from_u8(remaining[2]) {
                                         match cmd {
+                                            _ if option == TelnetOption::StartTls => self.handle_start_tls_command(cmd),
                                             TelnetCommand::DO => self.handle_do_command(option),
                                             TelnetCommand::DONT => self.handle_dont_command(option),
                                             TelnetCommand::WILL => self.handle_will_command(option),
@@ -550,6 +685,11 @@ impl TelnetNegotiator {
                                     self.handle_subnegotiation(&sub_data);
                                     // end_pos is already positioned after IAC SE
                                     pos = end_pos; // Skip to after SE
+                                    if self.start_tls == StartTlsState::Ready {
+                                        // Nothing after FOLLOWS is telnet; the TLS handshake follows
+                                        pos = buffer_len;
+                                        break;
+                                    }
                                     continue;
                                 } else {
                                     // PROTOCOL VIOLATION: Subnegotiation without proper termination
@@ -649,6 +789,8 @@ impl TelnetNegotiator {
                         // Usually we don't want echo in 5250 mode
                         self.negotiation_states.insert(option, NegotiationState::Inactive);
                     }
+                    // START_TLS is requested separately, before any other option
+                    TelnetOption::StartTls => {}
                 }
             }
         }
@@ -806,6 +948,25 @@ impl TelnetNegotiator {
         }
     }
     
+    /// Handle DO/DONT/WILL/WONT START_TLS; only the client side of the upgrade is supported
+    fn handle_start_tls_command(&mut self, command: TelnetCommand) {
+        match (command, self.start_tls) {
+            // Server agreed to our WILL; FOLLOWS comes next
+            (TelnetCommand::DO, StartTlsState::Requested) => {}
+            (TelnetCommand::DO, StartTlsState::Enabled) => {
+                self.start_tls = StartTlsState::Requested;
+                self.send_will(TelnetOption::StartTls);
+            }
+            // Disabled, or already inside TLS
+            (TelnetCommand::DO, _) => self.send_wont(TelnetOption::StartTls),
+            (TelnetCommand::WILL, _) => self.send_dont(TelnetOption::StartTls),
+            (TelnetCommand::DONT | TelnetCommand::WONT, StartTlsState::Enabled | StartTlsState::Requested) => {
+                self.start_tls = StartTlsState::Refused;
+            }
+            _ => {}
+        }
+    }
+
     /// Send WILL command for an option
     fn send_will(&mut self, option: TelnetOption) {
         self.output_buffer.extend_from_slice(&[
@@ -885,6 +1046,19 @@ impl TelnetNegotiator {
                         self.handle_tn3270e_subnegotiation(&data[1..]);
                     }
                 },
+                TelnetOption::StartTls => {
+                    if data[1..] == [START_TLS_FOLLOWS] && self.start_tls == StartTlsState::Requested {
+                        self.output_buffer.extend_from_slice(&[
+                            TelnetCommand::IAC as u8,
+                            TelnetCommand::SB as u8,
+                            TelnetOption::StartTls as u8,
+                            START_TLS_FOLLOWS,
+                            TelnetCommand::IAC as u8,
+                            TelnetCommand::SE as u8,
+                        ]);
+                        self.start_tls = StartTlsState::Ready;
+                    }
+                },
                 _ => {
                     eprintln!("SECURITY: Unhandled subnegotiation for option: {option:?}");
                 }
@@ -1107,6 +1281,14 @@ impl TelnetNegotiator {
 
         let mut i = 0;
         while i < data.len() {
+            if data[i] == 3 && data[i + 1..].starts_with(b"IBMRSEED") {
+                // RFC 4777: USERVAR "IBMRSEED" is followed directly by the 8-byte server seed
+                i += 1 + b"IBMRSEED".len();
+                let (seed, consumed) = Self::read_server_seed(&data[i..]);
+                i += consumed;
+                self.append_sign_on_variables(&mut response, seed);
+                continue;
+            }
             if data[i] == 0 || data[i] == 3 { // VAR or USERVAR
                 let var_type = data[i];
                 i += 1;
@@ -1135,141 +1317,16 @@ impl TelnetNegotiator {
                             if var_type == 3 { "USERVAR" } else { "VAR" },
                             var_name_str);
 
-                    // INTEGRATION: Send comprehensive AS/400 environment variables
-                    // Enhanced whitelist with all required AS/400 variables
-                    match var_name_str.as_ref() {
-                        "DEVNAME" => {
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"DEVNAME");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b"TN5250R");
-                        },
-                        "KBDTYPE" => {
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"KBDTYPE");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b"USB");
-                        },
-                        "CODEPAGE" => {
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"CODEPAGE");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b"37");
-                        },
-                        "CHARSET" => {
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"CHARSET");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b"37");
-                        },
-                        "USER" => {
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"USER");
-                            response.push(1); // VALUE type
-                            // Use configured username or default to GUEST
-                            let user = self.username.as_ref().map(|s| s.as_bytes()).unwrap_or(b"GUEST");
-                            response.extend_from_slice(user);
-                        },
-                        "IBMRSEED" => {
-                            // RFC 4777 Section 5: IBMRSEED response for authentication
-                            // When server sends USERVAR request for IBMRSEED, we respond with:
-                            // 1. VAR "USER" VALUE "<username>"
-                            // 2. USERVAR "IBMRSEED" VALUE "" (empty for plain text) OR client seed for encryption
-                            // 3. USERVAR "IBMSUBSPW" VALUE "<password>" (plain text or encrypted)
-
-                            println!("INTEGRATION: Server requested IBMRSEED - sending authentication credentials");
-
-                            // Send USER variable
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"USER");
-                            response.push(1); // VALUE type
-                            let user = self.username.as_ref().map(|s| s.as_bytes()).unwrap_or(b"GUEST");
-                            response.extend_from_slice(user);
-                            println!("   USER: {}", String::from_utf8_lossy(user));
-
-                            // Send IBMRSEED with empty value for plain text password
-                            response.push(3); // USERVAR type
-                            response.extend_from_slice(b"IBMRSEED");
-                            response.push(1); // VALUE type
-                            // Empty value indicates plain text password mode
-                            println!("   IBMRSEED: <empty> (plain text mode)");
-
-                            // Send IBMSUBSPW with password
-                            response.push(3); // USERVAR type
-                            response.extend_from_slice(b"IBMSUBSPW");
-                            response.push(1); // VALUE type
-                            let pass = self.password.as_ref().map(|s| s.as_bytes()).unwrap_or(b"");
-                            response.extend_from_slice(pass);
-                            println!("   IBMSUBSPW: {} characters", pass.len());
-                        },
-                        name if name.starts_with("IBMRSEED") => {
-                            // INTEGRATION: Handle IBMRSEED requests with seed data embedded in variable name
-                            // Some AS/400 servers send: USERVAR "IBMRSEED<8-byte-hex-seed>"
-                            // We extract the seed but for now use plain text authentication
-
-                            println!("INTEGRATION: Server requested IBMRSEED with embedded seed - sending authentication");
-
-                            // Send USER variable
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"USER");
-                            response.push(1); // VALUE type
-                            let user = self.username.as_ref().map(|s| s.as_bytes()).unwrap_or(b"GUEST");
-                            response.extend_from_slice(user);
-                            println!("   USER: {}", String::from_utf8_lossy(user));
-
-                            // Send IBMRSEED with empty value for plain text
-                            response.push(3); // USERVAR type
-                            response.extend_from_slice(b"IBMRSEED");
-                            response.push(1); // VALUE type
-                            println!("   IBMRSEED: <empty> (plain text mode)");
-
-                            // Send IBMSUBSPW with password
-                            response.push(3); // USERVAR type
-                            response.extend_from_slice(b"IBMSUBSPW");
-                            response.push(1); // VALUE type
-                            let pass = self.password.as_ref().map(|s| s.as_bytes()).unwrap_or(b"");
-                            response.extend_from_slice(pass);
-                            println!("   IBMSUBSPW: {} characters", pass.len());
-                        },
-                        "IBMSUBSPW" => {
-                            // INTEGRATION: Subsystem password
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"IBMSUBSPW");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b""); // Empty for guest access
-                        },
-                        "LFA" => {
-                            // INTEGRATION: Local format attribute
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"LFA");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b"1"); // Standard format
-                        },
-                        "TERM" => {
-                            // INTEGRATION: Terminal type
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"TERM");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b"IBM-3179-2");
-                        },
-                        "LANG" => {
-                            // INTEGRATION: Language setting
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"LANG");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b"EN_US");
-                        },
-                        "DISPLAY" => {
-                            // INTEGRATION: Display device
-                            response.push(0); // VAR type
-                            response.extend_from_slice(b"DISPLAY");
-                            response.push(1); // VALUE type
-                            response.extend_from_slice(b":0.0");
-                        },
-                        _ => {
-                            let sanitized_name = self.sanitize_string_output(&var_name_str);
-                            eprintln!("INTEGRATION: Requested unknown environment variable: {sanitized_name}");
-                        }
+                    // Answer with the same type the host asked for
+                    let kind = VariableKind::from_type_byte(var_type).unwrap_or_default();
+                    if var_name_str == "IBMSUBSPW" {
+                        // The substitute is only sent alongside IBMRSEED; never send a password on its own
+                        println!("INTEGRATION: Ignoring IBMSUBSPW request without IBMRSEED");
+                    } else if let Some(value) = self.environment_value(&var_name_str) {
+                        self.answer_variable(&mut response, EnvironmentVariable::new(kind, &var_name_str, &value));
+                    } else {
+                        let sanitized_name = self.sanitize_string_output(&var_name_str);
+                        eprintln!("INTEGRATION: Requested unknown environment variable: {sanitized_name}");
                     }
                 }
             } else {
@@ -1285,6 +1342,68 @@ impl TelnetNegotiator {
         self.output_buffer.extend_from_slice(&response);
     }
 
+    /// Read the 8-byte IBMRSEED server seed, undoing NEW-ENVIRON ESC quoting
+    ///
+    /// Returns the seed (None if the server sent fewer than 8 bytes) and the number of bytes consumed.
+    fn read_server_seed(data: &[u8]) -> (Option<[u8; 8]>, usize) {
+        let mut seed = [0u8; 8];
+        let mut filled = 0;
+        let mut i = 0;
+        while filled < seed.len() && i < data.len() {
+            let mut byte = data[i];
+            if byte == 2 && i + 1 < data.len() { // ESC quotes the next byte
+                i += 1;
+                byte = data[i];
+            } else if byte == 0 || byte == 3 {
+                break; // Next VAR/USERVAR: no (or a short) seed
+            }
+            seed[filled] = byte;
+            filled += 1;
+            i += 1;
+        }
+        ((filled == seed.len()).then_some(seed), i)
+    }
+
+    /// Answer IBMRSEED: USER, our client seed and the encrypted IBMSUBSPW password substitute
+    ///
+    /// Without a server seed or a usable password only USER is sent, so the host
+    /// falls back to its sign-on screen; the password itself is never transmitted.
+    fn append_sign_on_variables(&mut self, response: &mut Vec<u8>, server_seed: Option<[u8; 8]>) {
+        let user = self.username.clone().unwrap_or_else(|| "GUEST".to_string());
+        println!("   USER: {}", self.sanitize_string_output(&user));
+        self.answer_variable(response, EnvironmentVariable::new(VariableKind::Var, "USER", &user));
+        let user = user.as_str();
+
+        let (Some(server_seed), Some(password)) = (server_seed, self.password.as_deref()) else {
+            println!("   IBMSUBSPW: not sent (no server seed or password)");
+            return;
+        };
+        let substitute = password_substitute::client_seed().and_then(|client_seed| {
+            password_substitute::password_substitute(self.password_level, user, password, &server_seed, &client_seed)
+                .map(|substitute| (client_seed, substitute))
+        });
+        match substitute {
+            Ok((client_seed, substitute)) => {
+                response.push(telnet_environment::USERVAR);
+                response.extend_from_slice(b"IBMRSEED");
+                response.push(telnet_environment::VALUE);
+                telnet_environment::push_escaped(response, &client_seed);
+
+                response.push(telnet_environment::USERVAR);
+                response.extend_from_slice(b"IBMSUBSPW");
+                response.push(telnet_environment::VALUE);
+                telnet_environment::push_escaped(response, &substitute);
+                println!("   IBMSUBSPW: {:?} substitute ({} bytes)", self.password_level, substitute.len());
+
+                for (name, len) in [("IBMRSEED", client_seed.len()), ("IBMSUBSPW", substitute.len())] {
+                    let masked = EnvironmentVariable::new(VariableKind::UserVar, name, &format!("<{len} bytes>"));
+                    self.sent_environment.push(masked);
+                }
+            }
+            Err(e) => eprintln!("   IBMSUBSPW: not sent ({e})"),
+        }
+    }
+
     /// Parse environment variables sent by the remote side
     /// ENHANCED: Allows unknown AS/400 variables while maintaining basic security
     fn parse_received_environment_variables(&mut self, data: &[u8]) {
@@ -1559,26 +1678,21 @@ impl TelnetNegotiator {
             2, // IS command
         ];
 
-        // INTEGRATION: Core AS/400 environment variables
-        let env_vars = [
-            ("DEVNAME", "TN5250R"),
-            ("KBDTYPE", "USB"),
-            ("CODEPAGE", "37"),
-            ("CHARSET", "37"),
-            ("USER", "GUEST"),
-            ("IBMRSEED", "12345678"),
-            ("IBMSUBSPW", ""),
-            ("LFA", "1"),
-            ("TERM", "IBM-3179-2"),
-            ("LANG", "EN_US"),
-            ("DISPLAY", ":0.0"),
-        ];
-
-        for (name, value) in &env_vars {
-            response.push(0); // VAR type
-            response.extend_from_slice(name.as_bytes());
-            response.push(1); // VALUE type
-            response.extend_from_slice(value.as_bytes());
+        // INTEGRATION: Core AS/400 environment variables, then the profile's own
+        const CORE_VARIABLES: [&str; 9] = ["DEVNAME", "KBDTYPE", "CODEPAGE", "CHARSET", "USER", "LFA", "TERM", "LANG", "DISPLAY"];
+        let mut env_vars: Vec<EnvironmentVariable> = CORE_VARIABLES
+            .iter()
+            .filter_map(|name| Some(EnvironmentVariable::new(VariableKind::Var, name, &self.environment_value(name)?)))
+            .collect();
+        env_vars.extend(
+            self.environment
+                .variables()
+                .into_iter()
+                .filter(|var| !CORE_VARIABLES.iter().any(|core| var.name.eq_ignore_ascii_case(core))),
+        );
+
+        for var in &env_vars {
+            self.answer_variable(&mut response, var.clone());
         }
 
         response.extend_from_slice(&[
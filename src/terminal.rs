@@ -30,6 +30,42 @@ pub enum CharAttribute {
     HighIntensity,    // High intensity
     ReverseVideo,     // Reverse video
     Blink,            // Blinking text
+    FieldAttribute(u8), // 5250 screen attribute byte (0x20-0x3F), displayed as a blank
+}
+
+/// Reading direction of the screen or of a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Default screen direction for a host code page (420 Arabic and 424 Hebrew are RTL)
+    pub fn for_code_page(code_page: u16) -> Self {
+        match code_page {
+            420 | 424 => TextDirection::RightToLeft,
+            _ => TextDirection::LeftToRight,
+        }
+    }
+
+    /// The other direction
+    pub fn reversed(self) -> Self {
+        match self {
+            TextDirection::LeftToRight => TextDirection::RightToLeft,
+            TextDirection::RightToLeft => TextDirection::LeftToRight,
+        }
+    }
+
+    /// Natural direction of a character (digits count as left-to-right), or None for spaces and punctuation
+    pub fn of_char(ch: char) -> Option<Self> {
+        match ch as u32 {
+            0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF => Some(TextDirection::RightToLeft),
+            _ if ch.is_alphanumeric() => Some(TextDirection::LeftToRight),
+            _ => None,
+        }
+    }
 }
 
 // Represents a single character on the terminal screen
@@ -61,6 +97,8 @@ pub struct TerminalScreen {
     pub dirty: bool, // Flag to indicate if screen needs to be redrawn
     pub width: usize,
     pub height: usize,
+    /// Screen direction; a right-to-left screen is displayed mirrored, column 1 at the right edge
+    pub direction: TextDirection,
 }
 
 impl TerminalScreen {
@@ -73,6 +111,7 @@ impl TerminalScreen {
             dirty: true,
             width: TERMINAL_WIDTH,
             height: TERMINAL_HEIGHT,
+            direction: TextDirection::LeftToRight,
         };
         set_component_status("terminal", ComponentState::Running);
         set_component_error("terminal", None::<&str>);
@@ -90,6 +129,7 @@ impl TerminalScreen {
             dirty: true,
             width: w,
             height: h,
+            direction: TextDirection::LeftToRight,
         };
         set_component_status("terminal", ComponentState::Running);
         set_component_error("terminal", None::<&str>);
@@ -494,14 +534,81 @@ impl TerminalScreen {
         // No-op for now - character addition is handled differently
     }
 
-    /// Erase region (placeholder for compatibility)
-    pub fn erase_region(&mut self, _start_row: usize, _start_col: usize, _end_row: usize, _end_col: usize) {
-        // No-op for now - region erase not implemented in basic terminal
+    /// Erase the rectangle spanning (start_row, start_col) to (end_row, end_col), inclusive
+    pub fn erase_region(&mut self, start_row: usize, start_col: usize, end_row: usize, end_col: usize) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+        let end_row = end_row.min(self.height - 1);
+        let end_col = end_col.min(self.width - 1);
+        for row in start_row..=end_row {
+            for col in start_col..=end_col {
+                let index = self.index(col, row);
+                self.buffer[index] = TerminalChar::default();
+            }
+        }
+        self.dirty = true;
     }
 
-    /// Roll screen (placeholder for compatibility)
-    pub fn roll(&mut self, _top: usize, _bottom: usize, _lines: i8) {
-        // No-op for now - screen rolling not implemented in basic terminal
+    /// Roll rows top..=bottom by `lines` (positive = down, negative = up), clearing vacated rows
+    pub fn roll(&mut self, top: usize, bottom: usize, lines: i8) {
+        if self.height == 0 || top > bottom || top >= self.height {
+            return;
+        }
+        let bottom = bottom.min(self.height - 1);
+        let shift = lines.unsigned_abs() as usize;
+        let width = self.width;
+
+        for i in 0..=(bottom - top) {
+            // Walk away from the direction of travel so source rows are read before being overwritten
+            let row = if lines > 0 { bottom - i } else { top + i };
+            let source = if lines > 0 {
+                row.checked_sub(shift).filter(|&r| r >= top)
+            } else {
+                Some(row + shift).filter(|&r| r <= bottom)
+            };
+            let dest = row * width;
+            match source {
+                Some(src) => self.buffer.copy_within(src * width..(src + 1) * width, dest),
+                None => self.buffer[dest..dest + width].fill(TerminalChar::default()),
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Set the screen direction (screen reverse)
+    pub fn set_direction(&mut self, direction: TextDirection) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.dirty = true;
+        }
+    }
+
+    /// Column (0-based) at which buffer column `col` is displayed
+    pub fn visual_column(&self, col: usize) -> usize {
+        match self.direction {
+            TextDirection::LeftToRight => col,
+            TextDirection::RightToLeft => self.width.saturating_sub(col + 1),
+        }
+    }
+
+    /// Buffer column (0-based) displayed at visual column `col`; mirroring is its own inverse
+    pub fn buffer_column(&self, col: usize) -> usize {
+        self.visual_column(col)
+    }
+
+    /// Screen content in display order, with each row mirrored on a right-to-left screen
+    pub fn to_visual_string(&self) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in 0..self.height {
+            let cells = &self.buffer[row * self.width..(row + 1) * self.width];
+            match self.direction {
+                TextDirection::LeftToRight => out.extend(cells.iter().map(|c| c.character)),
+                TextDirection::RightToLeft => out.extend(cells.iter().rev().map(|c| c.character)),
+            }
+            out.push('\n');
+        }
+        out
     }
 
     /// Lock keyboard (placeholder for compatibility)
@@ -524,6 +631,7 @@ impl Clone for TerminalScreen {
             dirty: self.dirty,
             width: self.width,
             height: self.height,
+            direction: self.direction,
         }
     }
 }
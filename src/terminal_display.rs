@@ -1,231 +1,401 @@
 //! Terminal display rendering for TN5250R
 //!
 //! This module handles the visual rendering of the terminal content, including cursor positioning
-//! and field highlighting.
+//! and field highlighting. The screen is drawn cell by cell from a [`CellGrid`], so every
+//! character sits on its own column whatever the font, and each cell shows the color,
+//! reverse image, underline, column separator and blink of its screen attributes.
+
+use std::borrow::Cow;
 
 use eframe::egui;
 use crate::app_state::TN5250RApp;
+use crate::config::SessionConfig;
+use crate::field_manager::FieldDisplayInfo;
+use crate::color_scheme::{ColorScheme, Rgb};
+use crate::screen_grid::CellGrid;
+
+/// Font family registered for a user-selected terminal font
+const TERMINAL_FONT: &str = "terminal";
+
+/// Half period of the cursor and blink attribute, in seconds
+const BLINK_INTERVAL: f64 = 0.5;
+
+/// Shape of the terminal cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorStyle {
+    #[default]
+    Block,
+    Underline,
+}
+
+impl CursorStyle {
+    pub const ALL: [CursorStyle; 2] = [CursorStyle::Block, CursorStyle::Underline];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CursorStyle::Block => "Block",
+            CursorStyle::Underline => "Underline",
+        }
+    }
+}
+
+/// How the terminal screen is drawn, from the `terminal.*` settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplaySettings {
+    /// Font size in points; the starting size when fitting to the window
+    pub font_size: f32,
+    /// TrueType or OpenType font file to draw the screen with instead of the built-in monospace font
+    pub font_file: Option<String>,
+    /// Scale the font so the whole screen fits the window
+    pub auto_fit: bool,
+    pub cursor_style: CursorStyle,
+    pub cursor_blink: bool,
+    /// Draw the column separators of fields that request them
+    pub column_separators: bool,
+    /// Draw underlined fields with a line rather than ignoring the attribute
+    pub underlines: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            font_size: 14.0,
+            font_file: None,
+            auto_fit: false,
+            cursor_style: CursorStyle::Block,
+            cursor_blink: true,
+            column_separators: true,
+            underlines: true,
+        }
+    }
+}
+
+impl DisplaySettings {
+    pub const MIN_FONT_SIZE: f32 = 6.0;
+    pub const MAX_FONT_SIZE: f32 = 48.0;
+
+    /// Settings from the configuration, with defaults for anything missing or invalid
+    pub fn from_config(cfg: &SessionConfig) -> Self {
+        let default = Self::default();
+        let font_size = cfg
+            .get_float_property("terminal.fontSize")
+            .or_else(|| cfg.get_int_property("terminal.fontSize").map(|size| size as f64))
+            .map(|size| (size as f32).clamp(Self::MIN_FONT_SIZE, Self::MAX_FONT_SIZE))
+            .unwrap_or(default.font_size);
+        let cursor_style = cfg
+            .get_string_property("terminal.cursorStyle")
+            .and_then(|name| CursorStyle::ALL.into_iter().find(|style| format!("{style:?}") == name))
+            .unwrap_or_default();
+        Self {
+            font_size,
+            font_file: cfg.get_string_property("terminal.fontFile").filter(|path| !path.is_empty()),
+            auto_fit: cfg.get_boolean_property_or("terminal.autoFit", default.auto_fit),
+            cursor_style,
+            cursor_blink: cfg.get_boolean_property_or("terminal.cursorBlink", default.cursor_blink),
+            column_separators: cfg.get_boolean_property_or("terminal.columnSeparators", default.column_separators),
+            underlines: cfg.get_boolean_property_or("terminal.underlines", default.underlines),
+        }
+    }
 
-impl TN5250RApp {
-    pub fn draw_terminal_with_cursor_for_session(ui: &mut egui::Ui, session: &crate::session::Session) {
-        // Get cursor position
-        let cursor_pos = session.get_cursor_position();
+    /// Store the settings in the configuration
+    pub fn save(&self, cfg: &mut SessionConfig) {
+        cfg.set_property("terminal.fontSize", self.font_size as f64);
+        cfg.set_property("terminal.fontFile", self.font_file.as_deref().unwrap_or(""));
+        cfg.set_property("terminal.autoFit", self.auto_fit);
+        cfg.set_property("terminal.cursorStyle", format!("{:?}", self.cursor_style).as_str());
+        cfg.set_property("terminal.cursorBlink", self.cursor_blink);
+        cfg.set_property("terminal.columnSeparators", self.column_separators);
+        cfg.set_property("terminal.underlines", self.underlines);
+    }
 
-        // Split terminal content into lines
-        let lines: Vec<&str> = session.terminal_content.lines().collect();
+    /// Register the selected font file with egui, or go back to the built-in fonts
+    ///
+    /// The new fonts are used from the next frame.
+    pub fn install_font(&self, ctx: &egui::Context) -> Result<(), String> {
+        let mut fonts = egui::FontDefinitions::default();
+        let result = match &self.font_file {
+            Some(path) => match std::fs::read(path) {
+                Ok(bytes) => {
+                    fonts.font_data.insert(TERMINAL_FONT.to_string(), egui::FontData::from_owned(bytes).into());
+                    // Fall back to the monospace fonts for characters the file lacks
+                    let mut family = vec![TERMINAL_FONT.to_string()];
+                    family.extend(fonts.families.get(&egui::FontFamily::Monospace).cloned().unwrap_or_default());
+                    fonts.families.insert(egui::FontFamily::Name(TERMINAL_FONT.into()), family);
+                    Ok(())
+                }
+                Err(e) => Err(format!("Cannot load terminal font {path}: {e}")),
+            },
+            None => Ok(()),
+        };
+        ctx.set_fonts(fonts);
+        result
+    }
+}
+
+/// Where the screen cells were drawn, for mapping the pointer back to cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLayout {
+    /// Top left corner of row 1, column 1
+    pub origin: egui::Pos2,
+    /// Size of one cell
+    pub cell: egui::Vec2,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl GridLayout {
+    /// Area covered by the cells
+    pub fn rect(&self) -> egui::Rect {
+        egui::Rect::from_min_size(self.origin, egui::vec2(self.cols as f32 * self.cell.x, self.rows as f32 * self.cell.y))
+    }
 
-        // Calculate character size for positioning
-        let font = egui::FontId::monospace(14.0);
-        let char_width = ui.fonts(|f| f.glyph_width(&font, ' '));
-        let line_height = ui.fonts(|f| f.row_height(&font));
-
-        // Create a layout area for the terminal content
-        let available_size = ui.available_size();
-        let (rect, _response) = ui.allocate_exact_size(available_size, egui::Sense::hover());
-
-        // Draw the terminal content
-        if ui.is_rect_visible(rect) {
-            let mut y_offset = 0.0;
-
-            for (line_idx, line) in lines.iter().enumerate() {
-                let line_number = line_idx + 1; // 1-based line numbers
-
-                // Draw each character in the line
-                for (char_idx, ch) in line.chars().enumerate() {
-                    let col_number = char_idx + 1; // 1-based column numbers
-
-                    let char_pos = rect.min + egui::vec2(char_idx as f32 * char_width, y_offset);
-
-                    // Check if this is the cursor position
-                    let is_cursor = cursor_pos.0 == line_number && cursor_pos.1 == col_number;
-
-                    // Determine background color based on field status
-                    let mut bg_color = egui::Color32::TRANSPARENT;
-                    let mut text_color = egui::Color32::WHITE;
-
-                    // Check if position is in an error field
-                    for field in &session.fields_info {
-                        if line_number == field.start_row &&
-                            col_number >= field.start_col &&
-                            col_number < field.start_col + field.length {
-                            if field.error_state.is_some() {
-                                bg_color = egui::Color32::RED; // Red background for error fields
-                                text_color = egui::Color32::WHITE;
-                            } else if field.highlighted {
-                                bg_color = egui::Color32::YELLOW; // Yellow background for highlighted fields
-                                text_color = egui::Color32::BLACK;
-                            } else if field.is_active {
-                                bg_color = egui::Color32::BLUE; // Blue background for active field
-                                text_color = egui::Color32::WHITE;
-                            }
-                            break;
-                        }
-                    }
-
-                    // Override for cursor
-                    if is_cursor {
-                        bg_color = egui::Color32::GREEN;
-                        text_color = egui::Color32::BLACK;
-                    }
-
-                    // Draw background if needed
-                    if bg_color != egui::Color32::TRANSPARENT {
-                        let char_rect = egui::Rect::from_min_size(
-                            char_pos,
-                            egui::vec2(char_width, line_height)
-                        );
-                        ui.painter().rect_filled(char_rect, egui::CornerRadius::ZERO, bg_color);
-                    }
-
-                    // Draw the character
-                    ui.painter().text(
-                        char_pos,
-                        egui::Align2::LEFT_TOP,
-                        ch,
-                        font.clone(),
-                        text_color,
-                    );
+    /// Rectangle of the cell at a 1-based row and column
+    pub fn cell_rect(&self, row: usize, col: usize) -> egui::Rect {
+        egui::Rect::from_min_size(
+            self.origin + egui::vec2(col.saturating_sub(1) as f32 * self.cell.x, row.saturating_sub(1) as f32 * self.cell.y),
+            self.cell,
+        )
+    }
+
+    /// Cell (1-based row, column) under `pos`, clamped to the screen
+    pub fn cell_at(&self, pos: egui::Pos2) -> (usize, usize) {
+        let relative_pos = pos - self.origin;
+        let col = (relative_pos.x / self.cell.x).floor().max(0.0) as usize + 1;
+        let row = (relative_pos.y / self.cell.y).floor().max(0.0) as usize + 1;
+        (row.clamp(1, self.rows.max(1)), col.clamp(1, self.cols.max(1)))
+    }
+}
+
+fn color32(rgb: Rgb) -> egui::Color32 {
+    let [r, g, b] = rgb.0;
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// Font for the terminal at `size` points: the user's font when it is installed, else monospace
+fn terminal_font(ui: &egui::Ui, size: f32) -> egui::FontId {
+    let family = egui::FontFamily::Name(TERMINAL_FONT.into());
+    if ui.fonts(|f| f.families().contains(&family)) {
+        egui::FontId::new(size, family)
+    } else {
+        egui::FontId::monospace(size)
+    }
+}
+
+/// The controller's grid when it matches the displayed text, else the text as a plain grid
+///
+/// The displayed text is not always the screen: it may be a connection message.
+fn grid_for_content<'a>(grid: &'a CellGrid, content: &str, cursor: (usize, usize)) -> Cow<'a, CellGrid> {
+    if grid.text() != content {
+        return Cow::Owned(CellGrid::from_text(content, cursor));
+    }
+    if grid.cursor == cursor {
+        return Cow::Borrowed(grid);
+    }
+    let mut grid = grid.clone();
+    grid.cursor = cursor;
+    Cow::Owned(grid)
+}
+
+/// Draw the screen cells and the cursor into the space left in `ui`
+///
+/// Cells in an error, highlighted, active or modified input field are shaded over
+/// their screen attributes so field state stays visible on any screen.
+pub fn draw_terminal_grid(
+    ui: &mut egui::Ui,
+    grid: &CellGrid,
+    fields: &[FieldDisplayInfo],
+    settings: &DisplaySettings,
+    scheme: &ColorScheme,
+    preedit: &str,
+) -> GridLayout {
+    let rows = grid.height.max(1);
+    let cols = grid.width.max(1);
+    let available_size = ui.available_size();
+
+    let mut font = terminal_font(ui, settings.font_size);
+    let cell_size = |font: &egui::FontId| ui.fonts(|f| egui::vec2(f.glyph_width(font, ' '), f.row_height(font)));
+    if settings.auto_fit {
+        // Glyph sizes grow roughly with the font size, so one measurement gives the scale;
+        // pixel rounding can leave it slightly too large, which the steps down correct
+        let cell = cell_size(&font);
+        let scale = (available_size.x / (cols as f32 * cell.x)).min(available_size.y / (rows as f32 * cell.y));
+        if scale.is_finite() && scale > 0.0 {
+            font.size = ((font.size * scale * 2.0).floor() / 2.0)
+                .clamp(DisplaySettings::MIN_FONT_SIZE, DisplaySettings::MAX_FONT_SIZE);
+            while font.size > DisplaySettings::MIN_FONT_SIZE {
+                let cell = cell_size(&font);
+                if cols as f32 * cell.x <= available_size.x && rows as f32 * cell.y <= available_size.y {
+                    break;
                 }
+                font.size -= 0.5;
+            }
+        }
+    }
+    let egui::Vec2 { x: char_width, y: line_height } = cell_size(&font);
+
+    let grid_size = egui::vec2(cols as f32 * char_width, rows as f32 * line_height);
+    let (rect, _response) = ui.allocate_exact_size(grid_size, egui::Sense::hover());
+    let layout = GridLayout { origin: rect.min, cell: egui::vec2(char_width, line_height), rows, cols };
+    if !ui.is_rect_visible(rect) {
+        return layout;
+    }
+
+    let painter = ui.painter();
+    painter.rect_filled(layout.rect(), egui::CornerRadius::ZERO, color32(scheme.background));
 
-                y_offset += line_height;
+    let time = ui.input(|i| i.time);
+    let blink_on = ((time / BLINK_INTERVAL) as u64).is_multiple_of(2);
+    let mut blinking = false;
+
+    for (index, cell) in grid.cells.iter().enumerate() {
+        let (row, col) = (index / cols + 1, index % cols + 1);
+        let cell_rect = layout.cell_rect(row, col);
+        let style = cell.style;
+
+        let mut text_color = color32(scheme.color(style.color));
+        let mut bg_color = None;
+        if style.reverse {
+            bg_color = Some(text_color);
+            text_color = color32(scheme.reverse_text);
+        }
+        let field = fields.iter().find(|field| {
+            row == field.start_row && col >= field.start_col && col < field.start_col + field.length
+        });
+        let field_colors = field.and_then(|field| {
+            if field.error_state.is_some() {
+                Some(scheme.error_field)
+            } else if field.highlighted {
+                Some(scheme.highlighted_field)
+            } else if field.is_active {
+                Some(scheme.active_field)
+            } else if field.modified {
+                Some(scheme.modified_field)
+            } else {
+                None
             }
+        });
+        if let Some(colors) = field_colors {
+            bg_color = Some(color32(colors.background));
+            text_color = color32(colors.foreground);
+        }
+        if let Some(bg_color) = bg_color {
+            painter.rect_filled(cell_rect, egui::CornerRadius::ZERO, bg_color);
+        }
 
-            // Draw cursor if it's beyond the text content
-            if cursor_pos.0 > lines.len() ||
-                (cursor_pos.0 <= lines.len() &&
-                 cursor_pos.1 > lines.get(cursor_pos.0 - 1).map_or(0, |l| l.len())) {
-
-                let cursor_char_pos = rect.min + egui::vec2(
-                    (cursor_pos.1 - 1) as f32 * char_width,
-                    (cursor_pos.0 - 1) as f32 * line_height
-                );
-
-                let cursor_rect = egui::Rect::from_min_size(
-                    cursor_char_pos,
-                    egui::vec2(char_width, line_height)
-                );
-                ui.painter().rect_filled(cursor_rect, egui::CornerRadius::ZERO, egui::Color32::GREEN);
-
-                // Draw a space character at cursor
-                ui.painter().text(
-                    cursor_char_pos,
-                    egui::Align2::LEFT_TOP,
-                    ' ',
-                    font,
-                    egui::Color32::BLACK,
-                );
+        blinking |= style.blink;
+        let visible = !style.hidden && (!style.blink || blink_on);
+        if visible && cell.ch != ' ' {
+            painter.text(cell_rect.min, egui::Align2::LEFT_TOP, cell.ch, font.clone(), text_color);
+        }
+        if visible && style.underline && settings.underlines {
+            painter.hline(cell_rect.x_range(), cell_rect.bottom() - 0.5, (1.0, text_color));
+        }
+        if style.column_separator && settings.column_separators {
+            // Short ticks at both edges of the position, like the dots of a 5250 display
+            let ticks = (cell_rect.bottom() - line_height * 0.25)..=cell_rect.bottom();
+            painter.vline(cell_rect.left() + 0.5, ticks.clone(), (1.0, text_color));
+            painter.vline(cell_rect.right() - 0.5, ticks, (1.0, text_color));
+        }
+    }
+
+    let (cursor_row, cursor_col) = grid.cursor;
+    if (1..=rows).contains(&cursor_row) && (1..=cols).contains(&cursor_col) && (!settings.cursor_blink || blink_on) {
+        let cursor_rect = layout.cell_rect(cursor_row, cursor_col);
+        match settings.cursor_style {
+            CursorStyle::Block => {
+                painter.rect_filled(cursor_rect, egui::CornerRadius::ZERO, color32(scheme.cursor));
+                let cell = grid.cell(cursor_row - 1, cursor_col - 1).filter(|cell| !cell.style.hidden);
+                if let Some(cell) = cell.filter(|cell| cell.ch != ' ') {
+                    painter.text(cursor_rect.min, egui::Align2::LEFT_TOP, cell.ch, font.clone(), color32(scheme.background));
+                }
+            }
+            CursorStyle::Underline => {
+                let height = (line_height * 0.12).max(2.0);
+                let bar = egui::Rect::from_min_max(egui::pos2(cursor_rect.left(), cursor_rect.bottom() - height), cursor_rect.max);
+                painter.rect_filled(bar, egui::CornerRadius::ZERO, color32(scheme.cursor));
             }
         }
     }
 
-    pub fn draw_terminal_with_cursor(&mut self, ui: &mut egui::Ui) {
-        // Get cursor position
-        let cursor_pos = self.controller.get_cursor_position().unwrap_or((1, 1));
+    if blinking || settings.cursor_blink {
+        let until_toggle = BLINK_INTERVAL - time % BLINK_INTERVAL;
+        ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(until_toggle));
+    }
 
-        // Split terminal content into lines
-        let lines: Vec<&str> = self.terminal_content.lines().collect();
+    draw_ime_at_cursor(ui, rect, &layout, grid.cursor, &font, preedit);
+    layout
+}
+
+/// Place the platform input method at the terminal cursor and show its pending composition
+///
+/// Dead keys and IME input only reach the terminal while egui has an IME target; it is
+/// claimed only when no text widget has focus so dialogs keep their own input.
+fn draw_ime_at_cursor(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    layout: &GridLayout,
+    cursor_pos: (usize, usize),
+    font: &egui::FontId,
+    preedit: &str,
+) {
+    if ui.ctx().memory(|m| m.focused().is_some()) {
+        return;
+    }
+    let cursor_rect = layout.cell_rect(cursor_pos.0, cursor_pos.1);
+    ui.ctx().output_mut(|o| o.ime = Some(egui::output::IMEOutput { rect, cursor_rect }));
+
+    if !preedit.is_empty() {
+        let galley = ui.painter().layout_no_wrap(preedit.to_string(), font.clone(), egui::Color32::BLACK);
+        let preedit_rect = egui::Rect::from_min_size(cursor_rect.min, galley.size());
+        ui.painter().rect_filled(preedit_rect, egui::CornerRadius::ZERO, egui::Color32::LIGHT_YELLOW);
+        ui.painter().galley(preedit_rect.min, galley, egui::Color32::BLACK);
+        ui.painter().hline(preedit_rect.x_range(), preedit_rect.bottom(), (1.0, egui::Color32::BLACK));
+    }
+}
+
+impl TN5250RApp {
+    pub fn draw_terminal_with_cursor_for_session(
+        ui: &mut egui::Ui,
+        session: &crate::session::Session,
+        settings: &DisplaySettings,
+        preedit: &str,
+    ) -> GridLayout {
+        let grid = grid_for_content(&session.grid, &session.terminal_content, session.get_cursor_position());
+        draw_terminal_grid(ui, &grid, &session.fields_info, settings, &session.profile.color_scheme, preedit)
+    }
 
-        // Calculate character size for positioning
+    /// Draw the operator information area: keyboard lock, insert mode and operator errors
+    pub fn draw_oia(ui: &mut egui::Ui, oia: &crate::controller::OiaState) {
         let font = egui::FontId::monospace(14.0);
-        let char_width = ui.fonts(|f| f.glyph_width(&font, ' '));
-        let line_height = ui.fonts(|f| f.row_height(&font));
-
-        // Create a layout area for the terminal content
-        let available_size = ui.available_size();
-        let (rect, _response) = ui.allocate_exact_size(available_size, egui::Sense::hover());
-
-        // Draw the terminal content
-        if ui.is_rect_visible(rect) {
-            let mut y_offset = 0.0;
-
-            for (line_idx, line) in lines.iter().enumerate() {
-                let line_number = line_idx + 1; // 1-based line numbers
-
-                // Draw each character in the line
-                for (char_idx, ch) in line.chars().enumerate() {
-                    let col_number = char_idx + 1; // 1-based column numbers
-
-                    let char_pos = rect.min + egui::vec2(char_idx as f32 * char_width, y_offset);
-
-                    // Check if this is the cursor position
-                    let is_cursor = cursor_pos.0 == line_number && cursor_pos.1 == col_number;
-
-                    // Determine background color based on field status
-                    let mut bg_color = egui::Color32::TRANSPARENT;
-                    let mut text_color = egui::Color32::WHITE;
-
-                    // Check if position is in an error field
-                    for field in &self.fields_info {
-                        if line_number == field.start_row &&
-                            col_number >= field.start_col &&
-                            col_number < field.start_col + field.length {
-                            if field.error_state.is_some() {
-                                bg_color = egui::Color32::RED; // Red background for error fields
-                                text_color = egui::Color32::WHITE;
-                            } else if field.highlighted {
-                                bg_color = egui::Color32::YELLOW; // Yellow background for highlighted fields
-                                text_color = egui::Color32::BLACK;
-                            } else if field.is_active {
-                                bg_color = egui::Color32::BLUE; // Blue background for active field
-                                text_color = egui::Color32::WHITE;
-                            }
-                            break;
-                        }
-                    }
-
-                    // Override for cursor
-                    if is_cursor {
-                        bg_color = egui::Color32::GREEN;
-                        text_color = egui::Color32::BLACK;
-                    }
-
-                    // Draw background if needed
-                    if bg_color != egui::Color32::TRANSPARENT {
-                        let char_rect = egui::Rect::from_min_size(
-                            char_pos,
-                            egui::vec2(char_width, line_height)
-                        );
-                        ui.painter().rect_filled(char_rect, egui::CornerRadius::ZERO, bg_color);
-                    }
-
-                    // Draw the character
-                    ui.painter().text(
-                        char_pos,
-                        egui::Align2::LEFT_TOP,
-                        ch,
-                        font.clone(),
-                        text_color,
-                    );
-                }
 
-                y_offset += line_height;
-            }
+        if oia.keyboard_locked {
+            ui.label(egui::RichText::new("X SYSTEM").font(font.clone()).color(egui::Color32::YELLOW));
+        }
+        if oia.message_waiting {
+            ui.label(egui::RichText::new("MW").font(font.clone()).color(egui::Color32::YELLOW));
+        }
+        if let Some(error) = &oia.operator_error {
+            ui.label(
+                egui::RichText::new(error.operator_message())
+                    .font(font.clone())
+                    .color(egui::Color32::RED),
+            );
+        } else if !oia.keyboard_locked {
+            ui.label("Ready");
+        }
 
-            // Draw cursor if it's beyond the text content
-            if cursor_pos.0 > lines.len() ||
-                (cursor_pos.0 <= lines.len() &&
-                 cursor_pos.1 > lines.get(cursor_pos.0 - 1).map_or(0, |l| l.len())) {
-
-                let cursor_char_pos = rect.min + egui::vec2(
-                    (cursor_pos.1 - 1) as f32 * char_width,
-                    (cursor_pos.0 - 1) as f32 * line_height
-                );
-
-                let cursor_rect = egui::Rect::from_min_size(
-                    cursor_char_pos,
-                    egui::vec2(char_width, line_height)
-                );
-                ui.painter().rect_filled(cursor_rect, egui::CornerRadius::ZERO, egui::Color32::GREEN);
-
-                // Draw a space character at cursor
-                ui.painter().text(
-                    cursor_char_pos,
-                    egui::Align2::LEFT_TOP,
-                    ' ',
-                    font,
-                    egui::Color32::BLACK,
-                );
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let mode = if oia.insert_mode { "INS" } else { "OVR" };
+            ui.label(egui::RichText::new(mode).font(font.clone()).color(egui::Color32::LIGHT_GRAY))
+                .on_hover_text("Insert key toggles insert/overstrike mode");
+            if oia.push_mode {
+                ui.label(egui::RichText::new("PUSH").font(font.clone()).color(egui::Color32::LIGHT_GRAY))
+                    .on_hover_text("Ctrl+Shift+P toggles push mode");
             }
-        }
+            if oia.screen_direction == crate::terminal::TextDirection::RightToLeft {
+                ui.label(egui::RichText::new("RTL").font(font).color(egui::Color32::LIGHT_GRAY))
+                    .on_hover_text("Ctrl+Shift+R reverses the screen");
+            }
+        });
     }
-}
\ No newline at end of file
+}
@@ -5,8 +5,12 @@
 use eframe::egui;
 use crate::app_state::TN5250RApp;
 use crate::config;
+use crate::terminal_display::{CursorStyle, DisplaySettings};
+use crate::color_scheme::{ColorScheme, FieldColors};
+use crate::screen_grid::ScreenColor;
 use crate::lib3270::display::ScreenSize;
 use crate::network::ProtocolMode;
+use crate::known_hosts::{self, KnownHosts};
 
 impl TN5250RApp {
     /// Show debug information panel for troubleshooting
@@ -20,48 +24,12 @@ impl TN5250RApp {
                 ui.separator();
 
                 egui::ScrollArea::vertical().show(ui, |ui| {
-                    ui.collapsing("Connection State", |ui| {
-                        ui.label(format!("Connected: {}", self.connected));
-                        ui.label(format!("Connecting: {}", self.connecting));
-                        ui.label(format!("Host: {}:{}", self.host, self.port));
-                        ui.label(format!("Username: {}", if self.username.is_empty() { "<none>" } else { &self.username }));
-                        ui.label(format!("Password: {}", if self.password.is_empty() { "<none>" } else { "****" }));
-                        if let Some(time) = self.connection_time {
-                            ui.label(format!("Connection duration: {:.2}s", time.elapsed().as_secs_f32()));
+                    match self.get_active_session() {
+                        Some(session) => Self::show_session_debug_info(ui, session),
+                        None => {
+                            ui.label("No session is open");
                         }
-                    });
-
-                    ui.separator();
-
-                    ui.collapsing("Terminal Content", |ui| {
-                        ui.label(format!("Content length: {} chars", self.terminal_content.len()));
-                        ui.label(format!("Content lines: {}", self.terminal_content.lines().count()));
-                        ui.separator();
-                        ui.label("First 500 chars:");
-                        ui.code(self.terminal_content.chars().take(500).collect::<String>());
-                        ui.separator();
-                        ui.label("Last 200 chars:");
-                        let skip = self.terminal_content.len().saturating_sub(200);
-                        ui.code(self.terminal_content.chars().skip(skip).collect::<String>());
-                    });
-
-                    ui.separator();
-
-                    ui.collapsing("Field Information", |ui| {
-                        ui.label(format!("Number of fields: {}", self.fields_info.len()));
-                        for (i, field) in self.fields_info.iter().enumerate() {
-                            ui.group(|ui| {
-                                ui.label(format!("Field {}:", i + 1));
-                                ui.label(format!("  Label: {}", field.label));
-                                ui.label(format!("  Content: '{}'", field.content));
-                                ui.label(format!("  Active: {}", field.is_active));
-                                ui.label(format!("  Highlighted: {}", field.highlighted));
-                                if let Some(error) = &field.error_state {
-                                    ui.colored_label(egui::Color32::RED, format!("  Error: {}", error.get_user_message()));
-                                }
-                            });
-                        }
-                    });
+                    }
 
                     ui.separator();
 
@@ -78,20 +46,6 @@ impl TN5250RApp {
 
                     ui.separator();
 
-                    ui.collapsing("Controller State", |ui| {
-                        if let Ok(content) = self.controller.get_terminal_content() {
-                            ui.label(format!("Controller content length: {} chars", content.len()));
-                            ui.separator();
-                            ui.label("Raw controller content (first 1000 bytes as hex):");
-                            let hex: String = content.bytes().take(1000)
-                                .map(|b| format!("{b:02x} "))
-                                .collect();
-                            ui.code(hex);
-                        }
-                    });
-
-                    ui.separator();
-
                     if ui.button("Close Debug Panel").clicked() {
                         self.show_debug_panel = false;
                     }
@@ -99,6 +53,84 @@ impl TN5250RApp {
             });
     }
 
+    /// Connection, screen and field details of a session for the debug panel
+    fn show_session_debug_info(ui: &mut egui::Ui, session: &crate::session::Session) {
+        ui.collapsing("Connection State", |ui| {
+            ui.label(format!("Session: {}", session.tab_label()));
+            ui.label(format!("Connected: {}", session.connected));
+            ui.label(format!("Connecting: {}", session.connecting));
+            ui.label(format!("Host: {}:{}", session.profile.host, session.profile.port));
+            ui.label(format!("Username: {}", session.profile.username.as_deref().unwrap_or("<none>")));
+            ui.label(format!("Password: {}", if session.profile.password.is_none() { "<none>" } else { "****" }));
+            if let Some(time) = session.connection_time {
+                ui.label(format!("Connection duration: {:.2}s", time.elapsed().as_secs_f32()));
+            }
+        });
+
+        ui.separator();
+
+        ui.collapsing("Telnet Environment (NEW-ENVIRON)", |ui| {
+            let environment = session.controller.negotiated_environment();
+            if environment.is_empty() {
+                ui.label("No variables sent yet");
+            }
+            egui::Grid::new("debug_environment").striped(true).show(ui, |ui| {
+                for var in &environment {
+                    ui.label(var.kind.name());
+                    ui.monospace(&var.name);
+                    ui.monospace(&var.value);
+                    ui.end_row();
+                }
+            });
+        });
+
+        ui.separator();
+
+        ui.collapsing("Terminal Content", |ui| {
+            ui.label(format!("Content length: {} chars", session.terminal_content.len()));
+            ui.label(format!("Content lines: {}", session.terminal_content.lines().count()));
+            ui.separator();
+            ui.label("First 500 chars:");
+            ui.code(session.terminal_content.chars().take(500).collect::<String>());
+            ui.separator();
+            ui.label("Last 200 chars:");
+            let skip = session.terminal_content.len().saturating_sub(200);
+            ui.code(session.terminal_content.chars().skip(skip).collect::<String>());
+        });
+
+        ui.separator();
+
+        ui.collapsing("Field Information", |ui| {
+            ui.label(format!("Number of fields: {}", session.fields_info.len()));
+            for (i, field) in session.fields_info.iter().enumerate() {
+                ui.group(|ui| {
+                    ui.label(format!("Field {}:", i + 1));
+                    ui.label(format!("  Label: {}", field.label));
+                    ui.label(format!("  Content: '{}'", field.content));
+                    ui.label(format!("  Active: {}", field.is_active));
+                    ui.label(format!("  Highlighted: {}", field.highlighted));
+                    if let Some(error) = &field.error_state {
+                        ui.colored_label(egui::Color32::RED, format!("  Error: {}", error.get_user_message()));
+                    }
+                });
+            }
+        });
+
+        ui.separator();
+
+        ui.collapsing("Controller State", |ui| {
+            if let Ok(content) = session.controller.get_terminal_content() {
+                ui.label(format!("Controller content length: {} chars", content.len()));
+                ui.separator();
+                ui.label("Raw controller content (first 1000 bytes as hex):");
+                let hex: String = content.bytes().take(1000)
+                    .map(|b| format!("{b:02x} "))
+                    .collect();
+                ui.code(hex);
+            }
+        });
+    }
+
     /// Show the advanced settings dialog
     pub fn show_advanced_settings_dialog(&mut self, ctx: &egui::Context) {
         egui::Window::new("Advanced Connection Settings")
@@ -115,11 +147,12 @@ impl TN5250RApp {
                     .striped(true)
                     .show(ui, |ui| {
                         ui.label("Use TLS (SSL):");
+                        let port = self.get_active_session().map_or(23, |session| session.profile.port);
                         let mut ssl_enabled = {
                             if let Ok(cfg) = self.config.try_lock() {
-                                cfg.get_boolean_property_or("connection.ssl", self.port == 992)
+                                cfg.get_boolean_property_or("connection.ssl", port == 992)
                             } else {
-                                self.port == 992  // Default: TLS on port 992
+                                port == 992  // Default: TLS on port 992
                             }
                         };
                         let checkbox = ui.checkbox(&mut ssl_enabled, "Enable TLS encryption");
@@ -162,6 +195,32 @@ impl TN5250RApp {
                             config::save_shared_config_async(&self.config);
                         }
                         ui.end_row();
+
+                        ui.label("Reconnect:");
+                        let (mut auto_reconnect, mut attempts) = {
+                            if let Ok(cfg) = self.config.try_lock() {
+                                (
+                                    cfg.get_boolean_property_or("session.autoReconnect", false),
+                                    cfg.get_int_property_or("session.maxReconnectAttempts", 3),
+                                )
+                            } else {
+                                (false, 3)  // Default: no automatic reconnection
+                            }
+                        };
+                        ui.horizontal(|ui| {
+                            let toggled = ui.checkbox(&mut auto_reconnect, "Reconnect automatically").changed();
+                            let retried = ui
+                                .add(egui::DragValue::new(&mut attempts).range(1..=100).suffix(" attempts"))
+                                .changed();
+                            if toggled || retried {
+                                if let Ok(mut cfg) = self.config.try_lock() {
+                                    cfg.set_property("session.autoReconnect", auto_reconnect);
+                                    cfg.set_property("session.maxReconnectAttempts", attempts);
+                                }
+                                config::save_shared_config_async(&self.config);
+                            }
+                        });
+                        ui.end_row();
                     });
 
                 ui.separator();
@@ -224,37 +283,7 @@ impl TN5250RApp {
                                 config::save_shared_config_async(&self.config);
                                 
                                 // Apply current screen size based on protocol mode
-                                match self.selected_protocol_mode {
-                                    ProtocolMode::TN5250 => {
-                                        tokio::spawn({
-                                            let controller = self.controller.clone();
-                                            let screen_size = self.selected_screen_size;
-                                            async move {
-                                                controller.apply_tn5250_screen_size_async(screen_size).await;
-                                            }
-                                        });
-                                    }
-                                    ProtocolMode::TN3270 => {
-                                        tokio::spawn({
-                                            let controller = self.controller.clone();
-                                            let screen_size = self.selected_screen_size;
-                                            async move {
-                                                controller.apply_tn3270_screen_size_async(screen_size).await;
-                                            }
-                                        });
-                                    }
-                                    ProtocolMode::AutoDetect | ProtocolMode::NVT => {
-                                        // For AutoDetect and NVT modes, we can apply TN5250 sizing as a reasonable default
-                                        // since TN5250 is the primary protocol for this terminal emulator
-                                        tokio::spawn({
-                                            let controller = self.controller.clone();
-                                            let screen_size = self.selected_screen_size;
-                                            async move {
-                                                controller.apply_tn5250_screen_size_async(screen_size).await;
-                                            }
-                                        });
-                                    }
-                                }
+                                self.apply_screen_size();
                             }
                         });
                         ui.end_row();
@@ -302,36 +331,7 @@ impl TN5250RApp {
                                 config::save_shared_config_async(&self.config);
                                 
                                 // Apply screen size to controller based on protocol mode
-                                match self.selected_protocol_mode {
-                                    ProtocolMode::TN5250 => {
-                                        tokio::spawn({
-                                            let controller = self.controller.clone();
-                                            let screen_size = self.selected_screen_size;
-                                            async move {
-                                                controller.apply_tn5250_screen_size_async(screen_size).await;
-                                            }
-                                        });
-                                    }
-                                    ProtocolMode::TN3270 => {
-                                        tokio::spawn({
-                                            let controller = self.controller.clone();
-                                            let screen_size = self.selected_screen_size;
-                                            async move {
-                                                controller.apply_tn3270_screen_size_async(screen_size).await;
-                                            }
-                                        });
-                                    }
-                                    ProtocolMode::AutoDetect | ProtocolMode::NVT => {
-                                        // For AutoDetect and NVT modes, apply TN5250 sizing as default
-                                        tokio::spawn({
-                                            let controller = self.controller.clone();
-                                            let screen_size = self.selected_screen_size;
-                                            async move {
-                                                controller.apply_tn5250_screen_size_async(screen_size).await;
-                                            }
-                                        });
-                                    }
-                                }
+                                self.apply_screen_size();
                             }
                         });
                         ui.end_row();
@@ -360,6 +360,14 @@ impl TN5250RApp {
                         ui.end_row();
                     });
 
+                ui.separator();
+                ui.heading("Display");
+                self.show_display_settings(ui);
+
+                ui.separator();
+                ui.heading("Colors");
+                self.show_color_settings(ui);
+
                 ui.separator();
 
                 ui.horizontal(|ui| {
@@ -380,9 +388,295 @@ impl TN5250RApp {
                                 cfg.set_property("terminal.cols", 80i64);
                             }
                             config::save_shared_config_async(&self.config);
+
+                            self.display_settings = DisplaySettings::default();
+                            self.fonts_changed = true;
+                            self.save_display_settings();
+                        }
+                    });
+                });
+            });
+    }
+
+    /// Font, scaling, cursor and attribute options of the terminal screen
+    fn show_display_settings(&mut self, ui: &mut egui::Ui) {
+        let before = self.display_settings.clone();
+        let settings = &mut self.display_settings;
+        let mut font_file = settings.font_file.clone().unwrap_or_default();
+        let mut font_file_entered = false;
+
+        egui::Grid::new("display_settings_grid")
+            .num_columns(2)
+            .spacing([40.0, 8.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Font Size:");
+                ui.horizontal(|ui| {
+                    ui.add(egui::Slider::new(
+                        &mut settings.font_size,
+                        DisplaySettings::MIN_FONT_SIZE..=DisplaySettings::MAX_FONT_SIZE,
+                    ).step_by(0.5).suffix(" pt"));
+                    ui.checkbox(&mut settings.auto_fit, "Fit to window")
+                        .on_hover_text("Scale the font so the whole screen, 80 or 132 columns, fits the window");
+                });
+                ui.end_row();
+
+                ui.label("Font File:");
+                font_file_entered = ui.add(egui::TextEdit::singleline(&mut font_file).hint_text("Built-in monospace"))
+                    .on_hover_text("Path of a TrueType or OpenType font; leave empty for the built-in font")
+                    .lost_focus();
+                ui.end_row();
+
+                ui.label("Cursor:");
+                ui.horizontal(|ui| {
+                    for style in CursorStyle::ALL {
+                        ui.radio_value(&mut settings.cursor_style, style, style.label());
+                    }
+                    ui.checkbox(&mut settings.cursor_blink, "Blink");
+                });
+                ui.end_row();
+
+                ui.label("Attributes:");
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut settings.column_separators, "Column separators");
+                    ui.checkbox(&mut settings.underlines, "Underlines");
+                });
+                ui.end_row();
+            });
+
+        let font_file = font_file.trim();
+        settings.font_file = (!font_file.is_empty()).then(|| font_file.to_string());
+        // The font is loaded once the path is entered, not on every keystroke
+        if font_file_entered {
+            self.fonts_changed = true;
+        }
+        if self.display_settings != before {
+            self.save_display_settings();
+        }
+    }
+
+    /// Apply the selected screen size to the active session's controller for the selected protocol
+    fn apply_screen_size(&self) {
+        let Some(session) = self.get_active_session() else {
+            return;
+        };
+        let controller = session.controller.clone();
+        let screen_size = self.selected_screen_size;
+        match self.selected_protocol_mode {
+            ProtocolMode::TN3270 => {
+                tokio::spawn(async move {
+                    controller.apply_tn3270_screen_size_async(screen_size).await;
+                });
+            }
+            // For AutoDetect and NVT modes, TN5250 sizing is a reasonable default
+            // since TN5250 is the primary protocol for this terminal emulator
+            ProtocolMode::TN5250 | ProtocolMode::AutoDetect | ProtocolMode::NVT => {
+                tokio::spawn(async move {
+                    controller.apply_tn5250_screen_size_async(screen_size).await;
+                });
+            }
+        }
+    }
+
+    /// Color scheme of the active session's profile
+    fn show_color_settings(&mut self, ui: &mut egui::Ui) {
+        let Some(session_id) = self.active_session_id.clone() else {
+            ui.label("Open a session to change its colors");
+            return;
+        };
+        let Some(session) = self.sessions.get_mut(&session_id) else {
+            return;
+        };
+        ui.label(format!("Colors of profile '{}'", session.profile.name));
+        let scheme = &mut session.profile.color_scheme;
+
+        ui.horizontal(|ui| {
+            ui.label("Scheme:");
+            egui::ComboBox::from_id_salt("color_scheme_choice")
+                .selected_text(scheme.name.clone())
+                .show_ui(ui, |ui| {
+                    for name in ColorScheme::BUILT_IN {
+                        if ui.selectable_label(scheme.name == name, name).clicked() {
+                            *scheme = ColorScheme::built_in(name).unwrap_or_default();
                         }
+                    }
+                });
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut scheme.name);
+        });
+
+        egui::Grid::new("color_scheme_grid")
+            .num_columns(4)
+            .spacing([24.0, 6.0])
+            .show(ui, |ui| {
+                for pair in ScreenColor::ALL.chunks(2) {
+                    for &color in pair {
+                        ui.label(color.label());
+                        ui.color_edit_button_srgb(&mut scheme.color_mut(color).0);
+                    }
+                    ui.end_row();
+                }
+                ui.label("Background");
+                ui.color_edit_button_srgb(&mut scheme.background.0);
+                ui.label("Cursor");
+                ui.color_edit_button_srgb(&mut scheme.cursor.0);
+                ui.end_row();
+                ui.label("Reverse Text");
+                ui.color_edit_button_srgb(&mut scheme.reverse_text.0);
+                ui.end_row();
+            });
+
+        ui.label("Field states (text, background):");
+        egui::Grid::new("field_colors_grid")
+            .num_columns(2)
+            .spacing([24.0, 6.0])
+            .show(ui, |ui| {
+                let states: [(&str, &mut FieldColors); 4] = [
+                    ("Active", &mut scheme.active_field),
+                    ("Error", &mut scheme.error_field),
+                    ("Highlighted", &mut scheme.highlighted_field),
+                    ("Modified", &mut scheme.modified_field),
+                ];
+                for (label, colors) in states {
+                    ui.label(label);
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_srgb(&mut colors.foreground.0);
+                        ui.color_edit_button_srgb(&mut colors.background.0);
                     });
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal(|ui| {
+            if ui.button("Save Colors").on_hover_text("Store the scheme with the profile").clicked() {
+                self.color_status = Some(self.save_color_scheme(&session_id));
+            }
+            match &self.color_status {
+                Some(Ok(message)) => {
+                    ui.colored_label(egui::Color32::GREEN, message);
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(egui::Color32::RED, e);
+                }
+                None => {}
+            }
+        });
+    }
+
+    /// Store the edited colors with the session's profile, or as the setting of a Quick Connect session
+    fn save_color_scheme(&mut self, session_id: &str) -> Result<String, String> {
+        let Some(session) = self.sessions.get(session_id) else {
+            return Err("The session was closed".to_string());
+        };
+        if session.is_quick_connect() {
+            if let Ok(mut cfg) = self.config.try_lock() {
+                cfg.set_property("terminal.colorScheme", session.profile.color_scheme.to_json().as_str());
+            }
+            config::save_shared_config_async(&self.config);
+            return Ok("Saved".to_string());
+        }
+        if self.profile_manager.get_profile(&session.profile.id).is_none() {
+            return Err("The session's profile is not saved; the colors last until it is closed".to_string());
+        }
+        let name = session.profile.name.clone();
+        self.profile_manager
+            .update_profile(session.profile.clone())
+            .map_err(|e| format!("Failed to save profile: {e}"))?;
+        Ok(format!("Saved with profile '{name}'"))
+    }
+
+    /// Pick up a certificate prompt from any session
+    pub fn poll_certificate_prompts(&mut self) {
+        if self.certificate_prompt.is_some() {
+            return;
+        }
+        for (id, session) in &self.sessions {
+            if let Some(prompt) = session.controller.take_certificate_prompt() {
+                self.certificate_prompt = Some((id.clone(), prompt));
+                return;
+            }
+        }
+    }
+
+    /// Show the fingerprint of an untrusted or changed certificate and let the user trust it
+    pub fn show_certificate_dialog(&mut self, ctx: &egui::Context) {
+        let Some((session_id, prompt)) = self.certificate_prompt.clone() else {
+            return;
+        };
+        let certificate = &prompt.certificate;
+        let title = if prompt.is_change() { "⚠ Server Certificate Changed" } else { "Untrusted Server Certificate" };
+
+        let mut decision = None;
+        egui::Window::new(title)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                if let Some(ref known) = prompt.known_fingerprint {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("The certificate for {} is NOT the one you trusted before.", certificate.host),
+                    );
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        "Someone may be intercepting this connection. Only continue if the system's certificate was renewed.",
+                    );
+                    ui.label(format!("Previously trusted: {known}"));
+                } else {
+                    ui.label(format!("{} presented a certificate that is not signed by a trusted CA.", certificate.host));
+                }
+                ui.separator();
+
+                egui::Grid::new("certificate_prompt_grid").num_columns(2).spacing([20.0, 4.0]).show(ui, |ui| {
+                    ui.label("SHA-256:");
+                    ui.monospace(&certificate.fingerprint);
+                    ui.end_row();
+                    ui.label("Subject:");
+                    ui.label(&certificate.subject);
+                    ui.end_row();
+                    ui.label("Issuer:");
+                    ui.label(&certificate.issuer);
+                    ui.end_row();
+                    ui.label("Expires:");
+                    ui.label(&certificate.not_after);
+                    ui.end_row();
+                    ui.label("Problem:");
+                    ui.label(&certificate.reason);
+                    ui.end_row();
+                });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    if ui.button("Accept Once").clicked() {
+                        decision = Some(Some(false));
+                    }
+                    let permanent = if prompt.is_change() { "Replace Trusted Certificate" } else { "Accept Permanently" };
+                    if ui.button(permanent).clicked() {
+                        decision = Some(Some(true));
+                    }
+                    if ui.button("Reject").clicked() {
+                        decision = Some(None);
+                    }
                 });
             });
+
+        let Some(decision) = decision else {
+            return;
+        };
+        self.certificate_prompt = None;
+        let Some(permanent) = decision else {
+            return;
+        };
+
+        if permanent {
+            let saved = KnownHosts::load(&KnownHosts::default_path()).and_then(|mut hosts| hosts.trust(certificate));
+            if let Err(e) = saved {
+                self.error_message = Some(format!("Could not save trusted certificate: {e}"));
+            }
+        }
+        known_hosts::trust_once(certificate);
+
+        // Retry the connection now that the certificate is trusted
+        self.connect_session(&session_id);
     }
-}
\ No newline at end of file
+}
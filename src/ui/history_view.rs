@@ -0,0 +1,154 @@
+//! Screen history view for TN5250R
+//!
+//! The History button above the terminal opens a timeline of the screens kept
+//! for the session. While a previous screen is shown it is read-only: the
+//! keyboard and mouse do not reach the host until the operator returns to the
+//! live screen (Live, or Escape). Alt+Left and Alt+Right step back and forward.
+
+use eframe::egui;
+use crate::app_state::TN5250RApp;
+use crate::clipboard::CopyFormat;
+use crate::session::Session;
+use crate::terminal_display::{draw_terminal_grid, DisplaySettings, GridLayout};
+use crate::ui::terminal_selection;
+
+/// Search results listed under the timeline
+const MAX_RESULTS: usize = 50;
+
+impl TN5250RApp {
+    /// History button and, while looking back, the timeline with search and export
+    pub fn show_history_bar(ui: &mut egui::Ui, session: &mut Session) {
+        ui.horizontal(|ui| {
+            let viewing = session.history_view.is_some();
+            let button = ui.add_enabled(!session.history.is_empty(), egui::Button::new("🕘 History").selected(viewing))
+                .on_hover_text("Look back at previous screens (Alt+Left)");
+            if button.clicked() {
+                if viewing {
+                    session.history_view = None;
+                } else {
+                    session.history_back();
+                }
+            }
+            if !viewing {
+                return;
+            }
+
+            if ui.button("◀ Back").on_hover_text("Alt+Left").clicked() {
+                session.history_back();
+            }
+            if ui.button("Forward ▶").on_hover_text("Alt+Right").clicked() {
+                session.history_forward();
+            }
+            if let Some(position) = session.history_view.and_then(|number| session.history.position(number)) {
+                let mut shown = position + 1;
+                let slider = egui::Slider::new(&mut shown, 1..=session.history.len()).show_value(false);
+                if ui.add(slider).changed() {
+                    session.history_view = session.history.get(shown - 1).map(|snapshot| snapshot.number);
+                }
+            }
+            if let Some(snapshot) = session.viewed_snapshot() {
+                ui.label(format!(
+                    "Screen {} of {} at {}: {}",
+                    session.history.position(snapshot.number).map_or(0, |position| position + 1),
+                    session.history.len(),
+                    snapshot.taken_at.format("%H:%M:%S"),
+                    snapshot.title(),
+                ));
+            }
+            if ui.button("⏵ Live").on_hover_text("Back to the live screen (Escape)").clicked() {
+                session.history_view = None;
+            }
+        });
+        if session.history_view.is_none() {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Search:");
+            ui.add(egui::TextEdit::singleline(&mut session.history_query).hint_text("Text on earlier screens"));
+            let matches = session.history.search(&session.history_query);
+            if !session.history_query.trim().is_empty() {
+                ui.label(format!("{} found", matches.len()));
+            }
+
+            ui.separator();
+            let Some(snapshot) = session.viewed_snapshot() else {
+                return;
+            };
+            if ui.button("📋 Copy").on_hover_text("Copy this screen as text").clicked() {
+                terminal_selection::copy_to_clipboard(ui.ctx(), &snapshot.text(), None, CopyFormat::Text);
+            }
+            if ui.button("📋 Copy HTML").clicked() {
+                terminal_selection::copy_to_clipboard(ui.ctx(), &snapshot.text(), None, CopyFormat::Html);
+            }
+            let mut status = None;
+            for (label, format) in [("💾 Save Text", CopyFormat::Text), ("💾 Save HTML", CopyFormat::Html)] {
+                if ui.button(label).on_hover_text("Save this screen to the documents folder").clicked() {
+                    let folder = dirs::document_dir().or_else(dirs::home_dir).unwrap_or_else(|| ".".into());
+                    let path = folder.join(snapshot.file_name(format));
+                    status = Some(snapshot.save(&path, format).map(|()| format!("Saved {}", path.display())));
+                }
+            }
+            if status.is_some() {
+                session.history_status = status;
+            }
+        });
+        match &session.history_status {
+            Some(Ok(message)) => { ui.colored_label(egui::Color32::GREEN, message); }
+            Some(Err(e)) => { ui.colored_label(egui::Color32::RED, format!("⚠ {e}")); }
+            None => {}
+        }
+
+        let matches = session.history.search(&session.history_query);
+        if !matches.is_empty() {
+            egui::ScrollArea::vertical().id_salt(("history_results", &session.id)).max_height(120.0).show(ui, |ui| {
+                for found in matches.iter().take(MAX_RESULTS) {
+                    let Some(snapshot) = session.history.find(found.snapshot) else {
+                        continue;
+                    };
+                    let line = snapshot.text().lines().nth(found.row - 1).unwrap_or_default().trim().to_string();
+                    let label = format!("{} row {}: {line}", snapshot.taken_at.format("%H:%M:%S"), found.row);
+                    let selected = session.history_view == Some(found.snapshot);
+                    if ui.selectable_label(selected, label).clicked() {
+                        session.history_view = Some(found.snapshot);
+                    }
+                }
+            });
+        }
+    }
+
+    /// Keys of the history view; returns true while a previous screen is shown so
+    /// that no key reaches the host
+    pub fn handle_history_keys(ctx: &egui::Context, session: &mut Session) -> bool {
+        if session.history.is_empty() {
+            return false;
+        }
+        let (back, forward, live) = ctx.input_mut(|i| (
+            i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowLeft),
+            i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowRight),
+            session.history_view.is_some() && i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+        ));
+        if back {
+            session.history_back();
+        } else if forward {
+            session.history_forward();
+        } else if live {
+            session.history_view = None;
+        }
+        session.history_view.is_some()
+    }
+}
+
+/// Draw the screen picked in the history, read-only, with the search matches marked;
+/// `None` when the live screen is shown
+pub fn draw_history_screen(ui: &mut egui::Ui, session: &Session, settings: &DisplaySettings) -> Option<GridLayout> {
+    let snapshot = session.viewed_snapshot()?;
+    let layout = draw_terminal_grid(ui, &snapshot.grid(), &[], settings, &session.profile.color_scheme, "");
+    let stroke = egui::Stroke::new(1.5, egui::Color32::YELLOW);
+    let query_len = session.history_query.trim().chars().count();
+    for found in session.history.search(&session.history_query).iter().filter(|found| found.snapshot == snapshot.number) {
+        let rect = layout.cell_rect(found.row, found.col).union(layout.cell_rect(found.row, found.col + query_len - 1));
+        ui.painter().rect_stroke(rect, egui::CornerRadius::ZERO, stroke, egui::StrokeKind::Outside);
+    }
+    Some(layout)
+}
@@ -0,0 +1,311 @@
+//! Keymap editor dialog
+//!
+//! Edits the key bindings of the active session (or the legacy session), imports
+//! ACS `.kmp` keyboard files and saves the result as the profile's keymap file.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use crate::app_state::TN5250RApp;
+use crate::config;
+use crate::keymap::{self, Binding, KeyAction, KeyChord, Keymap};
+
+/// State of the open keymap editor
+pub struct KeymapEditor {
+    /// Session whose keymap is edited; None for the legacy session
+    pub session_id: Option<String>,
+    pub keymap: Keymap,
+    /// Keymap file written on save
+    pub path: String,
+    /// ACS keyboard file to import
+    pub import_path: String,
+    /// Chord typed by name instead of pressed
+    pub chord_text: String,
+    /// Lines of the last import that were not understood
+    pub skipped: Vec<String>,
+    /// Lines of the last import bound to keys that are never pressed
+    pub unreachable: Vec<String>,
+    pub status: Option<Result<String, String>>,
+    /// Binding waiting for a key press
+    capturing: Option<usize>,
+}
+
+impl KeymapEditor {
+    pub fn new(session_id: Option<String>, keymap: Keymap, path: String) -> Self {
+        Self {
+            session_id,
+            keymap,
+            path,
+            import_path: String::new(),
+            chord_text: String::new(),
+            skipped: Vec::new(),
+            unreachable: Vec::new(),
+            status: None,
+            capturing: None,
+        }
+    }
+
+    /// True while the editor takes the next key press as a chord
+    pub fn is_capturing(&self) -> bool {
+        self.capturing.is_some()
+    }
+
+    /// Take the first key press of the frame as the captured chord; Escape cancels
+    fn capture_chord(&mut self, ui: &egui::Ui) {
+        let Some(index) = self.capturing else {
+            return;
+        };
+        let pressed = ui.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Key { key, pressed: true, modifiers, .. } => Some(KeyChord::from_egui(*key, *modifiers)),
+                _ => None,
+            })
+        });
+        if let Some(chord) = pressed {
+            if chord != KeyChord::new("Escape") {
+                if let Some(binding) = self.keymap.bindings.get_mut(index) {
+                    binding.chord = chord;
+                }
+            }
+            self.capturing = None;
+        }
+    }
+
+    /// Binding rows; returns true when the user asked to save
+    fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        self.capture_chord(ui);
+        let mut save = false;
+
+        ui.label("Click a key to rebind it, then press the new key combination (Esc cancels).");
+        let duplicates = self.keymap.duplicates();
+        if !duplicates.is_empty() {
+            let names: Vec<String> = duplicates.iter().map(KeyChord::to_string).collect();
+            ui.colored_label(egui::Color32::YELLOW, format!("⚠ Bound more than once: {}", names.join(", ")));
+        }
+        let unreachable = self.keymap.unreachable();
+        if !unreachable.is_empty() {
+            let names: Vec<String> = unreachable.iter().map(KeyChord::to_string).collect();
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("⚠ These keys never reach TN5250R, so their bindings do nothing: {}", names.join(", ")),
+            );
+        }
+
+        let mut remove = None;
+        egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+            egui::Grid::new("keymap_bindings").num_columns(4).striped(true).show(ui, |ui| {
+                ui.strong("Key");
+                ui.strong("Action");
+                ui.strong("Macro");
+                ui.end_row();
+                for (index, Binding { chord, action }) in self.keymap.bindings.iter_mut().enumerate() {
+                    let label = if self.capturing == Some(index) { "Press a key…".to_string() } else { chord.to_string() };
+                    let button = ui.button(label);
+                    let button = if chord.is_reachable() {
+                        button
+                    } else {
+                        button.on_hover_text("This key never reaches TN5250R; bind the action to another key")
+                    };
+                    if button.clicked() {
+                        self.capturing = Some(index);
+                    }
+                    egui::ComboBox::from_id_salt(("keymap_action", index))
+                        .selected_text(action.label())
+                        .show_ui(ui, |ui| {
+                            for choice in KeyAction::choices() {
+                                let selected = std::mem::discriminant(&choice) == std::mem::discriminant(action)
+                                    && (matches!(choice, KeyAction::Macro(_)) || choice == *action);
+                                if ui.selectable_label(selected, choice.label()).clicked() && !selected {
+                                    *action = choice;
+                                }
+                            }
+                        });
+                    if let KeyAction::Macro(text) = action {
+                        ui.vertical(|ui| {
+                            ui.add(egui::TextEdit::singleline(text).hint_text("WRKSPLF[enter]"));
+                            if let Err(e) = keymap::macro_steps(text) {
+                                ui.colored_label(egui::Color32::RED, e);
+                            }
+                        });
+                    } else {
+                        ui.monospace(action.mnemonic());
+                    }
+                    if ui.small_button("🗑").on_hover_text("Remove binding").clicked() {
+                        remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some(index) = remove {
+            self.keymap.bindings.remove(index);
+            self.capturing = None;
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.chord_text).hint_text("e.g. Ctrl+Shift+F1").desired_width(120.0))
+                .on_hover_text("Leave empty to press the key instead");
+            if ui.button("➕ Add Binding").clicked() {
+                let chord = if self.chord_text.trim().is_empty() {
+                    Ok(None)
+                } else {
+                    KeyChord::parse(&self.chord_text).map(Some)
+                };
+                match chord {
+                    Ok(chord) => {
+                        let capture = chord.is_none();
+                        let chord = chord.unwrap_or_else(|| KeyChord::new("F1"));
+                        self.keymap.bindings.push(Binding { chord, action: KeyAction::Aid(crate::keyboard::FunctionKey::Enter) });
+                        if capture {
+                            self.capturing = Some(self.keymap.bindings.len() - 1);
+                        }
+                        self.chord_text.clear();
+                        self.status = None;
+                    }
+                    Err(e) => self.status = Some(Err(e)),
+                }
+            }
+            if ui.button("Reset to ACS Defaults").clicked() {
+                self.keymap = Keymap::acs_default();
+                self.capturing = None;
+            }
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Import ACS keyboard (.kmp):");
+            ui.text_edit_singleline(&mut self.import_path);
+            if ui.button("Import").clicked() {
+                match Keymap::import_kmp_file(Path::new(self.import_path.trim())) {
+                    Ok(import) => {
+                        self.status = Some(Ok(format!(
+                            "Imported {} bindings, {} lines skipped",
+                            import.keymap.bindings.len(),
+                            import.skipped.len()
+                        )));
+                        self.keymap = import.keymap;
+                        self.skipped = import.skipped;
+                        self.unreachable = import.unreachable;
+                        self.capturing = None;
+                    }
+                    Err(e) => self.status = Some(Err(e)),
+                }
+            }
+        });
+        if !self.unreachable.is_empty() {
+            ui.collapsing(format!("⚠ Imported onto keys that are never pressed ({})", self.unreachable.len()), |ui| {
+                for line in &self.unreachable {
+                    ui.monospace(line);
+                }
+            });
+        }
+        if !self.skipped.is_empty() {
+            ui.collapsing(format!("Skipped lines ({})", self.skipped.len()), |ui| {
+                for line in &self.skipped {
+                    ui.monospace(line);
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Keymap file:");
+            ui.text_edit_singleline(&mut self.path);
+            if ui.button("💾 Save").clicked() {
+                save = true;
+            }
+        });
+
+        match &self.status {
+            Some(Ok(message)) => {
+                ui.colored_label(egui::Color32::GREEN, message);
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+            None => {}
+        }
+        save
+    }
+}
+
+/// Default keymap file for a profile name
+fn default_keymap_path(name: &str) -> String {
+    let file: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    Keymap::keymap_dir().join(format!("{file}.json")).to_string_lossy().into_owned()
+}
+
+impl TN5250RApp {
+    /// Open the keymap editor on the active session's keymap
+    pub fn open_keymap_editor(&mut self) {
+        let editor = match self.get_active_session() {
+            Some(session) => KeymapEditor::new(
+                Some(session.id.clone()),
+                session.keymap.clone(),
+                session.profile.keymap.clone().unwrap_or_else(|| default_keymap_path(&session.profile.name)),
+            ),
+            None => {
+                let path = self.config.try_lock().ok().and_then(|cfg| cfg.get_string_property("keyboard.keymapFile"));
+                let keymap = TN5250RApp::configured_keymap(&self.config);
+                KeymapEditor::new(None, keymap, path.unwrap_or_else(|| default_keymap_path("default")))
+            }
+        };
+        self.keymap_editor = Some(editor);
+    }
+
+    /// Show the keymap editor dialog
+    pub fn show_keymap_editor_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut editor) = self.keymap_editor.take() else {
+            return;
+        };
+        let mut open = true;
+        let mut save = false;
+        egui::Window::new("⌨ Keyboard Map")
+            .open(&mut open)
+            .resizable(true)
+            .default_size([620.0, 520.0])
+            .show(ctx, |ui| save = editor.ui(ui));
+
+        if save {
+            editor.status = Some(self.save_keymap(&editor).map(|path| format!("Saved to {path}")));
+        }
+        if open {
+            self.keymap_editor = Some(editor);
+        }
+    }
+
+    /// Write the edited keymap and use it for its session
+    fn save_keymap(&mut self, editor: &KeymapEditor) -> Result<String, String> {
+        if let Some(macro_error) = editor.keymap.bindings.iter().find_map(|binding| match &binding.action {
+            KeyAction::Macro(text) => KeyAction::parse(text).err().map(|e| format!("{}: {e}", binding.chord)),
+            _ => None,
+        }) {
+            return Err(macro_error);
+        }
+        let path = PathBuf::from(editor.path.trim());
+        editor.keymap.save(&path)?;
+        let path = path.to_string_lossy().into_owned();
+
+        let session = editor.session_id.as_ref().and_then(|id| self.sessions.get_mut(id));
+        let quick_connect = session.as_ref().is_none_or(|session| session.is_quick_connect());
+        if let Some(session) = session {
+            session.keymap = editor.keymap.clone();
+            session.profile.keymap = Some(path.clone());
+            if self.profile_manager.get_profile(&session.profile.id).is_some() {
+                self.profile_manager
+                    .update_profile(session.profile.clone())
+                    .map_err(|e| format!("Keymap saved but the profile was not updated: {e}"))?;
+            }
+        }
+        // Quick Connect sessions take their keymap from the settings
+        if quick_connect {
+            if let Ok(mut cfg) = self.config.try_lock() {
+                cfg.set_property("keyboard.keymapFile", path.as_str());
+            }
+            config::save_shared_config_async(&self.config);
+        }
+        Ok(path)
+    }
+}
@@ -5,4 +5,10 @@
 pub mod monitoring_ui;
 pub mod dialogs;
 pub mod function_keys;
-pub mod profile_manager_ui;
\ No newline at end of file
+pub mod profile_manager_ui;
+pub mod keymap_editor;
+pub mod terminal_selection;
+pub mod session_tabs;
+pub mod split_view;
+pub mod history_view;
+pub mod search_bar;
@@ -9,6 +9,13 @@ use crate::session_profile::SessionProfile;
 use crate::profile_manager::ProfileManager;
 use crate::lib3270::display::ScreenSize;
 use crate::network::ProtocolMode;
+use crate::password_substitute::PasswordLevel;
+use crate::device_name::DeviceNames;
+use crate::telnet_environment::{self, EnvironmentVariable, VariableKind};
+use crate::network_tls::{ClientCertificate, TlsMinVersion};
+use crate::network_proxy::{ProxyKind, ProxySettings};
+use crate::network_ssh::{SshAuth, SshTunnel};
+use crate::hotspots::HotspotRule;
 
 impl TN5250RApp {
     /// Show the profile management sidebar
@@ -84,6 +91,9 @@ impl TN5250RApp {
                     if let Some(username) = &profile.username {
                         ui.label(format!("User: {username}"));
                     }
+                    if let Some(device_name) = &profile.device_name {
+                        ui.label(format!("Device: {}", device_name.to_uppercase()));
+                    }
                     ui.label(format!("Screen: {}x{}", profile.screen_size.cols(), profile.screen_size.rows()));
                 });
 
@@ -195,6 +205,325 @@ impl TN5250RApp {
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Password Level:");
+                egui::ComboBox::from_id_salt("password_level")
+                    .selected_text(match profile.password_level {
+                        PasswordLevel::Des => "QPWDLVL 0/1 (DES)",
+                        PasswordLevel::Sha1 => "QPWDLVL 2/3 (SHA-1)",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut profile.password_level, PasswordLevel::Des, "QPWDLVL 0/1 (DES)");
+                        ui.selectable_value(&mut profile.password_level, PasswordLevel::Sha1, "QPWDLVL 2/3 (SHA-1)");
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Device Name:");
+                let mut device_name = profile.device_name.clone().unwrap_or_default();
+                let edit = egui::TextEdit::singleline(&mut device_name).hint_text("QPADEV%02D");
+                if ui.add(edit).on_hover_text("DEVNAME requested from the host. %S is the session number, %D counts up \
+                    when the host reports the name in use (e.g. %02D); leave empty for the default").changed() {
+                    profile.device_name = if device_name.trim().is_empty() { None } else { Some(device_name) };
+                }
+            });
+            if let Some(Err(e)) = profile.device_name.as_deref().map(|pattern| DeviceNames::new(pattern, 1)) {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Keymap File:");
+                let mut keymap = profile.keymap.clone().unwrap_or_default();
+                let edit = egui::TextEdit::singleline(&mut keymap).hint_text("ACS default layout");
+                if ui.add(edit).on_hover_text("JSON keymap saved from Settings > Keyboard Map; \
+                    leave empty for the ACS-style defaults").changed() {
+                    profile.keymap = if keymap.trim().is_empty() { None } else { Some(keymap) };
+                }
+            });
+            if let Some(path) = profile.keymap.as_deref().filter(|path| !std::path::Path::new(path.trim()).is_file()) {
+                ui.colored_label(egui::Color32::RED, format!("Keymap file {path} not found"));
+            }
+
+            ui.collapsing("Environment (NEW-ENVIRON)", |ui| {
+                let environment = &mut profile.environment;
+                egui::Grid::new("profile_environment").num_columns(2).show(ui, |ui| {
+                    let typed = [
+                        ("Keyboard (KBDTYPE):", &mut environment.keyboard_type, "USB"),
+                        ("Code Page (CODEPAGE):", &mut environment.code_page, "37"),
+                        ("Character Set (CHARSET):", &mut environment.charset, "37"),
+                        ("Program (IBMPROGRAM):", &mut environment.program, "MYPGM"),
+                        ("Current Library (IBMCURLIB):", &mut environment.current_library, "MYLIB"),
+                        ("Initial Menu (IBMIMENU):", &mut environment.initial_menu, "MAIN"),
+                        ("Font (IBMFONT):", &mut environment.font, "11"),
+                        ("Printer (IBMASSOCPRT):", &mut environment.associated_printer, "PRT01"),
+                    ];
+                    for (label, value, hint) in typed {
+                        ui.label(label);
+                        ui.add(egui::TextEdit::singleline(value).hint_text(hint));
+                        ui.end_row();
+                    }
+                });
+                match environment.host_code_page() {
+                    Some(code_page) if !crate::ebcdic::is_supported_code_page(code_page) => {
+                        ui.colored_label(egui::Color32::YELLOW, format!("Code page {code_page} has no table; screens and keyboard use code page 37"));
+                    }
+                    None if !environment.code_page.trim().is_empty() => {
+                        ui.colored_label(egui::Color32::YELLOW, "CODEPAGE should be a number; screens and keyboard use code page 37");
+                    }
+                    _ => {}
+                }
+                ui.checkbox(&mut environment.send_config_record, "Request startup configuration record (IBMSENDCONFREC)");
+
+                ui.label("Custom variables:");
+                let mut remove = None;
+                for (i, var) in environment.custom.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_salt(("environment_kind", i))
+                            .width(80.0)
+                            .selected_text(var.kind.name())
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut var.kind, VariableKind::Var, "VAR");
+                                ui.selectable_value(&mut var.kind, VariableKind::UserVar, "USERVAR");
+                            });
+                        ui.add(egui::TextEdit::singleline(&mut var.name).hint_text("Name").desired_width(120.0));
+                        ui.add(egui::TextEdit::singleline(&mut var.value).hint_text("Value").desired_width(160.0));
+                        if ui.button("✕").on_hover_text("Remove variable").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                    if telnet_environment::is_reserved(&var.name) {
+                        ui.colored_label(egui::Color32::YELLOW, format!("{} is set by the emulator and will not be sent", var.name.trim()));
+                    }
+                }
+                if let Some(i) = remove {
+                    environment.custom.remove(i);
+                }
+                if ui.button("➕ Add Variable").clicked() {
+                    environment.custom.push(EnvironmentVariable::default());
+                }
+            });
+
+            ui.collapsing("Hotspots", |ui| {
+                let hotspots = &mut profile.hotspots;
+                ui.checkbox(&mut hotspots.enabled, "Click screen hints (F3=Exit, More..., option codes, URLs)");
+                ui.add_enabled(hotspots.enabled, egui::Checkbox::new(&mut hotspots.builtin, "Use the built-in patterns"));
+
+                ui.label("Custom patterns, tried first:")
+                    .on_hover_text("A regular expression and a key mnemonic, macro or url: address; \
+                        $1 is replaced with the first capture group. Example: (\\d+)=Select  →  $1[enter]");
+                let mut remove = None;
+                for (i, rule) in hotspots.rules.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut rule.pattern).hint_text("Pattern").desired_width(160.0));
+                        ui.add(egui::TextEdit::singleline(&mut rule.action).hint_text("[pf$1]").desired_width(120.0));
+                        if ui.button("✕").on_hover_text("Remove pattern").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                    if let Err(e) = rule.validate() {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                }
+                if let Some(i) = remove {
+                    hotspots.rules.remove(i);
+                }
+                if ui.button("➕ Add Pattern").clicked() {
+                    hotspots.rules.push(HotspotRule::new("", ""));
+                }
+            });
+
+            ui.checkbox(&mut profile.tls.start_tls, "Upgrade with telnet START_TLS")
+                .on_hover_text("Encrypt a plain telnet connection (e.g. port 23) instead of using a TLS port");
+
+            ui.horizontal(|ui| {
+                ui.label("Minimum TLS:");
+                egui::ComboBox::from_id_salt("tls_min_version")
+                    .selected_text(profile.tls.min_version.name())
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut profile.tls.min_version, TlsMinVersion::Tls12, "TLS 1.2");
+                        ui.selectable_value(&mut profile.tls.min_version, TlsMinVersion::Tls13, "TLS 1.3");
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Certificate Pin (SHA-256):");
+                let mut pin = profile.tls.pin_sha256.clone().unwrap_or_default();
+                if ui.text_edit_singleline(&mut pin).changed() {
+                    profile.tls.pin_sha256 = if pin.trim().is_empty() { None } else { Some(pin) };
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Client Certificate:");
+                let selected = match profile.tls.client_certificate {
+                    None => "None",
+                    Some(ClientCertificate::Pem { .. }) => "PEM",
+                    Some(ClientCertificate::Pkcs12 { .. }) => "PKCS#12",
+                };
+                egui::ComboBox::from_id_salt("tls_client_certificate")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        if ui.selectable_label(selected == "None", "None").clicked() {
+                            profile.tls.client_certificate = None;
+                        }
+                        if ui.selectable_label(selected == "PEM", "PEM").clicked() && selected != "PEM" {
+                            profile.tls.client_certificate =
+                                Some(ClientCertificate::Pem { cert_path: String::new(), key_path: String::new() });
+                        }
+                        if ui.selectable_label(selected == "PKCS#12", "PKCS#12").clicked() && selected != "PKCS#12" {
+                            profile.tls.client_certificate =
+                                Some(ClientCertificate::Pkcs12 { path: String::new(), password: String::new() });
+                        }
+                    });
+            });
+
+            match profile.tls.client_certificate {
+                Some(ClientCertificate::Pem { ref mut cert_path, ref mut key_path }) => {
+                    ui.horizontal(|ui| {
+                        ui.label("Certificate File:");
+                        ui.text_edit_singleline(cert_path);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Key File:");
+                        ui.text_edit_singleline(key_path);
+                    });
+                }
+                Some(ClientCertificate::Pkcs12 { ref mut path, ref mut password }) => {
+                    ui.horizontal(|ui| {
+                        ui.label("PKCS#12 File:");
+                        ui.text_edit_singleline(path);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("PKCS#12 Password:");
+                        ui.add(egui::TextEdit::singleline(password).password(true));
+                    });
+                }
+                None => {}
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Proxy:");
+                let selected = profile.proxy.as_ref().map(|p| p.kind.name()).unwrap_or("None");
+                egui::ComboBox::from_id_salt("proxy_kind")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        if ui.selectable_label(profile.proxy.is_none(), "None").clicked() {
+                            profile.proxy = None;
+                        }
+                        for kind in [ProxyKind::Http, ProxyKind::Socks5] {
+                            if ui.selectable_label(selected == kind.name(), kind.name()).clicked() {
+                                let proxy = profile.proxy.get_or_insert_with(ProxySettings::default);
+                                proxy.kind = kind;
+                                if proxy.port == 0 {
+                                    proxy.port = kind.default_port();
+                                }
+                            }
+                        }
+                    });
+            });
+
+            if let Some(ref mut proxy) = profile.proxy {
+                ui.horizontal(|ui| {
+                    ui.label("Proxy Host:");
+                    ui.text_edit_singleline(&mut proxy.host);
+                    ui.label("Port:");
+                    ui.add(egui::DragValue::new(&mut proxy.port).range(1..=65535));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Proxy User:");
+                    let mut username = proxy.username.clone().unwrap_or_default();
+                    if ui.text_edit_singleline(&mut username).changed() {
+                        proxy.username = if username.is_empty() { None } else { Some(username) };
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Proxy Password:");
+                    let mut password = proxy.password.clone().unwrap_or_default();
+                    if ui.add(egui::TextEdit::singleline(&mut password).password(true)).changed() {
+                        proxy.password = if password.is_empty() { None } else { Some(password) };
+                    }
+                });
+            }
+
+            let mut use_ssh = profile.ssh_tunnel.is_some();
+            if ui.checkbox(&mut use_ssh, "Tunnel through SSH").changed() {
+                profile.ssh_tunnel = if use_ssh { Some(SshTunnel::default()) } else { None };
+            }
+
+            if let Some(ref mut tunnel) = profile.ssh_tunnel {
+                ui.horizontal(|ui| {
+                    ui.label("SSH Host:");
+                    ui.text_edit_singleline(&mut tunnel.host).on_hover_text("Leave empty to log in to the session host");
+                    ui.label("Port:");
+                    ui.add(egui::DragValue::new(&mut tunnel.port).range(1..=65535));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("SSH User:");
+                    ui.text_edit_singleline(&mut tunnel.username);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("SSH Auth:");
+                    let selected = match tunnel.auth {
+                        SshAuth::Password { .. } => "Password",
+                        SshAuth::PublicKey { .. } => "Private key",
+                    };
+                    egui::ComboBox::from_id_salt("ssh_auth")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            if ui.selectable_label(selected == "Password", "Password").clicked() {
+                                tunnel.auth = SshAuth::Password { password: String::new() };
+                            }
+                            if ui.selectable_label(selected == "Private key", "Private key").clicked() {
+                                tunnel.auth = SshAuth::PublicKey { private_key_path: String::new(), passphrase: None };
+                            }
+                        });
+                });
+                match tunnel.auth {
+                    SshAuth::Password { ref mut password } => {
+                        ui.horizontal(|ui| {
+                            ui.label("SSH Password:");
+                            ui.add(egui::TextEdit::singleline(password).password(true));
+                        });
+                    }
+                    SshAuth::PublicKey { ref mut private_key_path, ref mut passphrase } => {
+                        ui.horizontal(|ui| {
+                            ui.label("Private Key File:");
+                            ui.text_edit_singleline(private_key_path);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Passphrase:");
+                            let mut text = passphrase.clone().unwrap_or_default();
+                            if ui.add(egui::TextEdit::singleline(&mut text).password(true)).changed() {
+                                *passphrase = if text.is_empty() { None } else { Some(text) };
+                            }
+                        });
+                    }
+                }
+                ui.horizontal(|ui| {
+                    ui.label("known_hosts:");
+                    let mut path = tunnel.known_hosts_path.clone().unwrap_or_default();
+                    let hint = SshTunnel::default_known_hosts_path().display().to_string();
+                    if ui.add(egui::TextEdit::singleline(&mut path).hint_text(hint)).changed() {
+                        tunnel.known_hosts_path = if path.is_empty() { None } else { Some(path) };
+                    }
+                });
+                ui.checkbox(&mut tunnel.accept_new_host_keys, "Add unknown host keys to known_hosts");
+            }
+
+            let session = &mut profile.session_config;
+            ui.checkbox(&mut session.auto_reconnect, "Reconnect automatically")
+                .on_hover_text("Reopen the session with the same device name and sign-on if the connection drops");
+            if session.auto_reconnect {
+                ui.horizontal(|ui| {
+                    ui.label("Attempts:");
+                    ui.add(egui::DragValue::new(&mut session.max_reconnect_attempts).range(1..=100));
+                    ui.label("First Delay (ms):");
+                    ui.add(egui::DragValue::new(&mut session.reconnect_initial_delay_ms).range(0..=60_000));
+                    ui.label("Backoff:");
+                    ui.add(egui::DragValue::new(&mut session.reconnect_backoff_multiplier).range(1..=10).suffix("x"));
+                });
+            }
+
             ui.separator();
 
             let mut save_clicked = false;
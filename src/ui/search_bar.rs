@@ -0,0 +1,125 @@
+//! Search bar for TN5250R
+//!
+//! Ctrl+F shows a search bar above the terminal. Enter and Shift+Enter step
+//! through the matches, which are highlighted on the screen; Go to Field moves
+//! the cursor to the next match in an input field. Escape closes the bar.
+
+use eframe::egui;
+use crate::app_state::TN5250RApp;
+use crate::screen_search::ScreenSearch;
+use crate::session::Session;
+use crate::terminal_display::GridLayout;
+
+/// Id of the search text field of a session, for keyboard focus
+fn search_field_id(session: &Session) -> egui::Id {
+    egui::Id::new(("screen_search", &session.id))
+}
+
+impl TN5250RApp {
+    /// Search bar of the session, while it is open
+    pub fn show_search_bar(ui: &mut egui::Ui, session: &mut Session) {
+        if !session.search.open {
+            return;
+        }
+        let field_id = search_field_id(session);
+
+        let mut go_to_field = false;
+        ui.horizontal(|ui| {
+            ui.label("Find:");
+            let hint = if session.search.regex { "Regular expression" } else { "Text on the screen" };
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut session.search.query)
+                    .id(field_id)
+                    .hint_text(hint)
+                    .desired_width(240.0),
+            );
+            session.search.focused = response.has_focus();
+            ui.checkbox(&mut session.search.regex, "Regex").on_hover_text("Take the text as a regular expression");
+            // Search again only when the query, the regex flag or the screen shown changed
+            let screen = session.shown_screen();
+            if session.search.is_stale(screen) {
+                let grid = session.shown_grid();
+                session.search.update_shown(screen, &grid);
+            }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                if ui.input(|i| i.modifiers.shift) {
+                    session.search.previous();
+                } else {
+                    session.search.next();
+                }
+                response.request_focus();
+            }
+
+            if ui.button("▲").on_hover_text("Previous match (Shift+Enter)").clicked() {
+                session.search.previous();
+            }
+            if ui.button("▼").on_hover_text("Next match (Enter)").clicked() {
+                session.search.next();
+            }
+            // Only the live screen has a cursor to move
+            let in_field = session.connected && session.history_view.is_none() && session.search.matches.iter()
+                .any(|found| session.fields_info.iter().any(|field| found.in_field(field)));
+            go_to_field = ui.add_enabled(in_field, egui::Button::new("⤵ Go to Field"))
+                .on_hover_text("Move the cursor to the next match in an input field")
+                .clicked();
+
+            let search = &session.search;
+            match (&search.error, search.current) {
+                (Some(e), _) => { ui.colored_label(egui::Color32::RED, format!("⚠ {e}")); }
+                (None, Some(index)) => { ui.label(format!("{} of {}", index + 1, search.matches.len())); }
+                (None, None) if !search.query.is_empty() => { ui.weak("No matches"); }
+                (None, None) => {}
+            }
+            if ui.button("✕").on_hover_text("Close (Escape)").clicked() {
+                session.search.open = false;
+            }
+        });
+
+        if go_to_field {
+            let cursor = session.get_cursor_position();
+            if let Some(found) = session.search.next_in_field(&session.fields_info, cursor) {
+                if let Err(e) = session.controller.click_at_position(found.row, found.col) {
+                    session.error_message = Some(e);
+                }
+            }
+        }
+    }
+
+    /// Show the search bar of the session with the keyboard in its text field
+    pub fn open_search(ctx: &egui::Context, session: &mut Session) {
+        session.search.open = true;
+        ctx.memory_mut(|m| m.request_focus(search_field_id(session)));
+    }
+
+    /// Ctrl+F opens the search bar and Escape in its text field closes it; returns
+    /// true while the search field has the keyboard so that no key reaches the host
+    pub fn handle_search_keys(ctx: &egui::Context, session: &mut Session) -> bool {
+        let id = search_field_id(session);
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F)) {
+            Self::open_search(ctx, session);
+        }
+        // egui drops the focus on Escape before the frame is drawn, so the last frame counts too
+        let focused = session.search.open && (session.search.focused || ctx.memory(|m| m.has_focus(id)));
+        if focused && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
+            session.search.open = false;
+            session.search.focused = false;
+            ctx.memory_mut(|m| m.surrender_focus(id));
+        }
+        focused
+    }
+}
+
+/// Mark the matches of an open search on the screen drawn at `layout`, the picked one filled
+pub fn highlight_matches(ui: &egui::Ui, layout: &GridLayout, search: &ScreenSearch) {
+    if !search.open {
+        return;
+    }
+    let stroke = egui::Stroke::new(1.5, egui::Color32::YELLOW);
+    for (index, found) in search.matches.iter().enumerate() {
+        let rect = layout.cell_rect(found.row, found.col).union(layout.cell_rect(found.row, found.col + found.len - 1));
+        if search.current == Some(index) {
+            ui.painter().rect_filled(rect, egui::CornerRadius::ZERO, egui::Color32::from_rgba_unmultiplied(255, 165, 0, 90));
+        }
+        ui.painter().rect_stroke(rect, egui::CornerRadius::ZERO, stroke, egui::StrokeKind::Outside);
+    }
+}
@@ -0,0 +1,174 @@
+//! Session tabs for TN5250R
+//!
+//! Each open session has a tab with badges for its connection, message waiting
+//! and keyboard lock state. Tabs are dragged to reorder, detached into a window
+//! of their own, and switched with Ctrl+Tab, Ctrl+Shift+Tab and Ctrl+1..9.
+
+use eframe::egui;
+use crate::app_state::TN5250RApp;
+use crate::session::Session;
+
+/// Keys of the Ctrl+1..9 shortcuts, for the first nine tabs
+const TAB_NUMBER_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1, egui::Key::Num2, egui::Key::Num3,
+    egui::Key::Num4, egui::Key::Num5, egui::Key::Num6,
+    egui::Key::Num7, egui::Key::Num8, egui::Key::Num9,
+];
+
+/// Status badges of a session: symbol, color and explanation
+pub fn session_badges(session: &Session) -> Vec<(&'static str, egui::Color32, &'static str)> {
+    let mut badges = Vec::new();
+    if session.connected {
+        badges.push(("●", egui::Color32::GREEN, "Connected"));
+    } else if session.connecting || session.reconnect.is_some() {
+        badges.push(("●", egui::Color32::YELLOW, "Connecting"));
+    } else {
+        badges.push(("○", egui::Color32::GRAY, "Disconnected"));
+    }
+    if session.oia.message_waiting {
+        badges.push(("✉", egui::Color32::LIGHT_BLUE, "Message waiting"));
+    }
+    if session.oia.keyboard_locked {
+        badges.push(("X", egui::Color32::YELLOW, "Keyboard locked (X SYSTEM)"));
+    }
+    badges
+}
+
+impl TN5250RApp {
+    /// Switch tabs with Ctrl+Tab, Ctrl+Shift+Tab and Ctrl+1..9, before the keymap sees the keys
+    pub fn handle_session_shortcuts(&mut self, ctx: &egui::Context) {
+        let (previous, next, number) = ctx.input_mut(|i| {
+            // Ctrl alone also matches Ctrl+Shift, so the shifted chord goes first
+            let previous = i.consume_key(egui::Modifiers::CTRL | egui::Modifiers::SHIFT, egui::Key::Tab);
+            let next = i.consume_key(egui::Modifiers::CTRL, egui::Key::Tab);
+            let number = TAB_NUMBER_KEYS.iter().position(|&key| i.consume_key(egui::Modifiers::CTRL, key));
+            (previous, next, number)
+        });
+        if previous {
+            self.cycle_session(-1);
+        }
+        if next {
+            self.cycle_session(1);
+        }
+        if let Some(index) = number {
+            self.switch_to_tab(index);
+        }
+    }
+
+    /// Tab strip of the open sessions
+    pub fn show_session_tabs(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        let mut moved = None;
+        let mut detach = None;
+        let mut close = None;
+
+        ui.horizontal_wrapped(|ui| {
+            for (index, session_id) in self.session_order.iter().enumerate() {
+                let Some(session) = self.sessions.get(session_id) else {
+                    continue;
+                };
+                let is_active = self.active_session_id.as_ref() == Some(session_id);
+
+                let mut label = egui::text::LayoutJob::default();
+                for (symbol, color, _) in session_badges(session) {
+                    label.append(symbol, 0.0, egui::TextFormat { color, ..Default::default() });
+                    label.append(" ", 0.0, egui::TextFormat::default());
+                }
+                let name = if session.detached { format!("↗ {}", session.tab_label()) } else { session.tab_label() };
+                label.append(&name, 0.0, egui::TextFormat { color: ui.visuals().text_color(), ..Default::default() });
+
+                let hover = session_badges(session).iter().map(|(_, _, text)| *text).collect::<Vec<_>>().join(", ");
+                let shortcut = if index < TAB_NUMBER_KEYS.len() { format!(" (Ctrl+{})", index + 1) } else { String::new() };
+                let response = ui
+                    .add(egui::Button::selectable(is_active, label).sense(egui::Sense::click_and_drag()))
+                    .on_hover_text(format!("{hover}{shortcut}"));
+
+                // Drag a tab onto another to move it there
+                response.dnd_set_drag_payload(index);
+                if let Some(from) = response.dnd_release_payload::<usize>() {
+                    moved = Some((*from, index));
+                }
+                if let Some(from) = response.dnd_hover_payload::<usize>().filter(|from| **from != index) {
+                    // The dragged tab lands on the far side of this one
+                    let x = if *from < index { response.rect.right() } else { response.rect.left() };
+                    ui.painter().vline(x, response.rect.y_range(), ui.visuals().selection.stroke);
+                }
+
+                if response.clicked() {
+                    switch_to = Some(session_id.clone());
+                }
+                response.context_menu(|ui| {
+                    if session.detached {
+                        if ui.button("Return to Main Window").clicked() {
+                            switch_to = Some(session_id.clone());
+                            ui.close();
+                        }
+                    } else if ui.button("Detach to Window").clicked() {
+                        detach = Some(session_id.clone());
+                        ui.close();
+                    }
+                    if ui.button("Close Session").clicked() {
+                        close = Some(session_id.clone());
+                        ui.close();
+                    }
+                });
+                if ui.small_button("✕").on_hover_text("Close session").clicked() {
+                    close = Some(session_id.clone());
+                }
+                ui.separator();
+            }
+            if ui.button("+").on_hover_text("New Quick Connect session").clicked() {
+                self.open_quick_connect_session(None, None);
+            }
+        });
+
+        if let Some((from, to)) = moved {
+            self.move_session(from, to);
+        }
+        if let Some(id) = switch_to {
+            self.switch_to_session(id);
+        }
+        if let Some(id) = detach {
+            self.detach_session(&id);
+        }
+        if let Some(id) = close {
+            self.close_session(&id);
+        }
+    }
+
+    /// Show each detached session in a window of its own; closing the window returns it to the tabs
+    pub fn show_detached_sessions(&mut self, ctx: &egui::Context) {
+        let detached: Vec<(String, String)> = self.session_order.iter()
+            .filter_map(|id| self.sessions.get(id))
+            .filter(|session| session.detached)
+            .map(|session| (session.id.clone(), session.tab_label()))
+            .collect();
+
+        for (session_id, title) in detached {
+            let viewport_id = egui::ViewportId::from_hash_of(("detached_session", &session_id));
+            let builder = egui::ViewportBuilder::default()
+                .with_title(format!("TN5250R - {title}"))
+                .with_inner_size([800.0, 600.0]);
+            let keep_open = ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+                if class == egui::ViewportClass::Embedded {
+                    // Without native windows the session gets a window inside the main one
+                    let mut open = true;
+                    egui::Window::new(format!("TN5250R - {title}"))
+                        .id(egui::Id::new(("detached_session", &session_id)))
+                        .open(&mut open)
+                        .default_size([800.0, 600.0])
+                        .show(ctx, |ui| self.show_session_content(ui, &session_id));
+                    return open;
+                }
+
+                // Keys typed into the session's own window go to that session
+                self.handle_keyboard_input(ctx, &session_id);
+                egui::CentralPanel::default().show(ctx, |ui| self.show_session_content(ui, &session_id));
+                !ctx.input(|i| i.viewport().close_requested())
+            });
+            if !keep_open {
+                self.attach_session(&session_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Split view for TN5250R
+//!
+//! Instead of one session at a time, the main window can tile the open sessions
+//! in a grid of panes (2x1, 2x2 or a custom size). Panes show the sessions in tab
+//! order, each screen scaled to fit its pane; the focused pane is the active
+//! session and receives the keyboard. The layout is kept in `workspace.splitLayout`.
+
+use eframe::egui;
+use crate::app_state::TN5250RApp;
+use crate::config::{self, SessionConfig};
+use crate::terminal_display::DisplaySettings;
+use crate::ui::session_tabs::session_badges;
+use crate::ui::{search_bar, terminal_selection};
+
+/// Largest number of rows or columns of a custom layout
+pub const MAX_SPLIT: usize = 4;
+
+/// How the main window shows the open sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitLayout {
+    /// One session at a time, chosen with the tabs
+    #[default]
+    Tabs,
+    /// Two sessions side by side
+    SideBySide,
+    /// Four sessions in two rows of two
+    Quad,
+    /// Any grid up to [`MAX_SPLIT`] by [`MAX_SPLIT`]
+    Custom { rows: usize, cols: usize },
+}
+
+impl SplitLayout {
+    /// Rows and columns of panes
+    pub fn grid(self) -> (usize, usize) {
+        match self {
+            SplitLayout::Tabs => (1, 1),
+            SplitLayout::SideBySide => (1, 2),
+            SplitLayout::Quad => (2, 2),
+            SplitLayout::Custom { rows, cols } => (rows.clamp(1, MAX_SPLIT), cols.clamp(1, MAX_SPLIT)),
+        }
+    }
+
+    /// Number of panes
+    pub fn panes(self) -> usize {
+        let (rows, cols) = self.grid();
+        rows * cols
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            SplitLayout::Tabs => "Tabs".to_string(),
+            SplitLayout::SideBySide => "2x1 Side by Side".to_string(),
+            SplitLayout::Quad => "2x2 Grid".to_string(),
+            SplitLayout::Custom { rows, cols } => format!("Custom {cols}x{rows}"),
+        }
+    }
+
+    /// Name stored in the configuration: `tabs`, `2x1`, `2x2` or `<columns>x<rows>`
+    pub fn name(self) -> String {
+        match self {
+            SplitLayout::Tabs => "tabs".to_string(),
+            SplitLayout::SideBySide => "2x1".to_string(),
+            SplitLayout::Quad => "2x2".to_string(),
+            SplitLayout::Custom { .. } => {
+                let (rows, cols) = self.grid();
+                format!("{cols}x{rows}")
+            }
+        }
+    }
+
+    /// Layout from its configuration name
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "tabs" | "" => Ok(SplitLayout::Tabs),
+            "2x1" => Ok(SplitLayout::SideBySide),
+            "2x2" => Ok(SplitLayout::Quad),
+            other => {
+                let size = other.split_once('x').and_then(|(cols, rows)| Some((cols.parse().ok()?, rows.parse().ok()?)));
+                match size {
+                    Some((cols, rows)) if (1..=MAX_SPLIT).contains(&cols) && (1..=MAX_SPLIT).contains(&rows) => {
+                        Ok(SplitLayout::Custom { rows, cols })
+                    }
+                    _ => Err(format!("Unknown split layout '{name}'")),
+                }
+            }
+        }
+    }
+
+    /// Layout from the `workspace.splitLayout` setting, tabs if it is missing or invalid
+    pub fn from_config(cfg: &SessionConfig) -> Self {
+        let name = cfg.get_string_property_or("workspace.splitLayout", "tabs");
+        Self::parse(&name).unwrap_or_else(|e| {
+            eprintln!("{e}; showing sessions in tabs");
+            SplitLayout::Tabs
+        })
+    }
+
+    /// Store the layout in the configuration
+    pub fn save(self, cfg: &mut SessionConfig) {
+        cfg.set_property("workspace.splitLayout", self.name().as_str());
+    }
+}
+
+impl TN5250RApp {
+    /// Split layout from the `workspace.splitLayout` setting
+    pub(crate) fn configured_split_layout(config: &config::SharedSessionConfig) -> SplitLayout {
+        config.try_lock().map(|cfg| SplitLayout::from_config(&cfg)).unwrap_or_default()
+    }
+
+    /// Change the split layout and remember it in the configuration
+    pub fn set_split_layout(&mut self, layout: SplitLayout) {
+        self.split_layout = layout;
+        if let Ok(mut cfg) = self.config.try_lock() {
+            layout.save(&mut cfg);
+        }
+        config::save_shared_config_async(&self.config);
+    }
+
+    /// Sessions of the main window in tab order, one per pane; `None` for empty panes
+    ///
+    /// With more sessions than panes, the active session takes the last pane so the
+    /// focus never moves to a session out of sight.
+    pub fn split_panes(&self) -> Vec<Option<String>> {
+        let mut attached = self.session_order.iter()
+            .filter(|id| self.sessions.get(*id).is_some_and(|s| !s.detached))
+            .cloned();
+        let mut panes: Vec<Option<String>> = (0..self.split_layout.panes()).map(|_| attached.next()).collect();
+        if let Some(active) = self.active_session_id.as_ref() {
+            if !panes.contains(&Some(active.clone())) && self.sessions.get(active).is_some_and(|s| !s.detached) {
+                if let Some(last) = panes.last_mut() {
+                    *last = Some(active.clone());
+                }
+            }
+        }
+        panes
+    }
+
+    /// Layout choices for the View menu
+    pub fn show_split_layout_menu(&mut self, ui: &mut egui::Ui) {
+        let mut layout = self.split_layout;
+        for choice in [SplitLayout::Tabs, SplitLayout::SideBySide, SplitLayout::Quad] {
+            ui.radio_value(&mut layout, choice, choice.label());
+        }
+        let (mut rows, mut cols) = match layout {
+            SplitLayout::Custom { .. } => layout.grid(),
+            _ => (2, 3),
+        };
+        let custom = matches!(layout, SplitLayout::Custom { .. });
+        ui.horizontal(|ui| {
+            if ui.radio(custom, "Custom").clicked() {
+                layout = SplitLayout::Custom { rows, cols };
+            }
+            let columns = ui.add(egui::DragValue::new(&mut cols).range(1..=MAX_SPLIT).suffix(" columns"));
+            let rows_changed = ui.add(egui::DragValue::new(&mut rows).range(1..=MAX_SPLIT).suffix(" rows"));
+            if columns.changed() || rows_changed.changed() {
+                layout = SplitLayout::Custom { rows, cols };
+            }
+        });
+        if layout != self.split_layout {
+            self.set_split_layout(layout);
+        }
+    }
+
+    /// Tile the sessions of the main window; clicking a pane focuses its session
+    pub fn show_split_view(&mut self, ui: &mut egui::Ui) {
+        let (rows, cols) = self.split_layout.grid();
+        let spacing = ui.spacing().item_spacing;
+        let area = ui.available_rect_before_wrap();
+        let pane_size = egui::vec2(
+            ((area.width() - spacing.x * (cols - 1) as f32) / cols as f32).max(0.0),
+            ((area.height() - spacing.y * (rows - 1) as f32) / rows as f32).max(0.0),
+        );
+        // Every pane scales its screen to fit, whatever the font size setting
+        let settings = DisplaySettings { auto_fit: true, ..self.display_settings.clone() };
+
+        let mut focus = None;
+        for (index, session_id) in self.split_panes().into_iter().enumerate() {
+            let (row, col) = (index / cols, index % cols);
+            let min = area.min + egui::vec2(col as f32 * (pane_size.x + spacing.x), row as f32 * (pane_size.y + spacing.y));
+            let rect = egui::Rect::from_min_size(min, pane_size);
+            let focused = session_id.is_some() && session_id == self.active_session_id;
+            let stroke = if focused { ui.visuals().selection.stroke } else { ui.visuals().widgets.noninteractive.bg_stroke };
+
+            let mut pane_ui = ui.new_child(egui::UiBuilder::new().max_rect(rect).id_salt(("split_pane", index)));
+            egui::Frame::group(pane_ui.style()).stroke(stroke).show(&mut pane_ui, |ui| {
+                ui.set_min_size(ui.available_size());
+                let Some(session) = session_id.as_ref().and_then(|id| self.sessions.get_mut(id)) else {
+                    ui.centered_and_justified(|ui| ui.weak("No session"));
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    for (symbol, color, _) in session_badges(session) {
+                        ui.colored_label(color, symbol);
+                    }
+                    ui.label(egui::RichText::new(session.tab_label()).strong());
+                });
+                if focused {
+                    TN5250RApp::show_search_bar(ui, session);
+                }
+                let layout = TN5250RApp::draw_terminal_with_cursor_for_session(
+                    ui,
+                    session,
+                    &settings,
+                    if focused { &self.ime_preedit } else { "" },
+                );
+
+                // A click focuses the pane; in the focused pane it runs a hotspot or positions the cursor
+                let response = ui.interact(rect, egui::Id::new(("split_pane", &session.id)), egui::Sense::click());
+                if focused {
+                    terminal_selection::highlight_hotspot(ui, &response, &layout, &session.hotspots);
+                    search_bar::highlight_matches(ui, &layout, &session.search);
+                }
+                if response.clicked() {
+                    if focused {
+                        if let Some(pos) = response.interact_pointer_pos().filter(|pos| layout.rect().contains(*pos)) {
+                            let (row, col) = layout.cell_at(pos);
+                            terminal_selection::click_cell(ui.ctx(), session, row, col);
+                        }
+                    } else {
+                        focus = Some(session.id.clone());
+                    }
+                }
+            });
+        }
+        ui.allocate_rect(area, egui::Sense::hover());
+
+        if let Some(session_id) = focus {
+            self.switch_to_session(session_id);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Mouse selection and clipboard for the terminal area
+//!
+//! A click runs the hotspot under the pointer or positions the cursor, a drag
+//! selects a rectangle of cells and the context menu copies the selection (or
+//! the whole screen) and pastes into the input fields.
+
+use eframe::egui;
+use crate::clipboard::{self, CopyFormat, LineHandling, PasteOutcome, Selection};
+use crate::controller::AsyncTerminalController;
+use crate::hotspots::{Hotspot, HotspotAction};
+use crate::session::Session;
+use crate::terminal_display::GridLayout;
+
+/// What the operator asked for in the terminal area this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TerminalAreaAction {
+    /// Position the cursor on a cell
+    Click(usize, usize),
+    /// Copy the selection, or the whole screen when `whole_screen` is set
+    Copy { format: CopyFormat, whole_screen: bool },
+    /// Paste the system clipboard at the cursor
+    Paste,
+}
+
+/// Track clicks and drags on the terminal area, draw the selection and show the context menu
+pub fn handle_terminal_area(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    layout: &GridLayout,
+    selection: &mut Option<Selection>,
+    paste_lines: &mut LineHandling,
+) -> Option<TerminalAreaAction> {
+    let mut action = None;
+    if let Some(pos) = response.interact_pointer_pos() {
+        let cell = layout.cell_at(pos);
+        if response.drag_started_by(egui::PointerButton::Primary) {
+            *selection = Some(Selection::new(cell, cell));
+        } else if response.dragged_by(egui::PointerButton::Primary) {
+            if let Some(selection) = selection.as_mut() {
+                selection.end = cell;
+            }
+        } else if response.clicked() {
+            *selection = None;
+            action = Some(TerminalAreaAction::Click(cell.0, cell.1));
+        }
+    }
+
+    if let Some(selection) = selection.as_ref() {
+        draw_selection(ui, layout, selection);
+    }
+
+    response.context_menu(|ui| {
+        let has_selection = selection.is_some();
+        let copies = [
+            ("Copy", CopyFormat::Text),
+            ("Copy as Tab-Delimited", CopyFormat::TabDelimited),
+            ("Copy as HTML", CopyFormat::Html),
+        ];
+        for (label, format) in copies {
+            if ui.add_enabled(has_selection, egui::Button::new(label)).clicked() {
+                action = Some(TerminalAreaAction::Copy { format, whole_screen: false });
+                ui.close();
+            }
+        }
+        if ui.button("Copy Screen").clicked() {
+            action = Some(TerminalAreaAction::Copy { format: CopyFormat::Text, whole_screen: true });
+            ui.close();
+        }
+        ui.separator();
+        if ui.button("Paste").clicked() {
+            action = Some(TerminalAreaAction::Paste);
+            ui.close();
+        }
+        ui.menu_button("Paste Options", |ui| {
+            for lines in LineHandling::ALL {
+                ui.radio_value(paste_lines, lines, lines.label());
+            }
+        });
+    });
+    action
+}
+
+/// Underline the hotspot under the pointer and show a hand cursor over it
+pub fn highlight_hotspot(ui: &egui::Ui, response: &egui::Response, layout: &GridLayout, hotspots: &[Hotspot]) {
+    let Some(pos) = response.hover_pos().filter(|pos| layout.rect().contains(*pos)) else {
+        return;
+    };
+    let (row, col) = layout.cell_at(pos);
+    if let Some(hotspot) = hotspots.iter().find(|hotspot| hotspot.contains(row, col)) {
+        let rect = layout.cell_rect(hotspot.row, hotspot.col).union(layout.cell_rect(hotspot.row, hotspot.col + hotspot.len - 1));
+        ui.painter().hline(rect.x_range(), rect.bottom() - 1.0, (1.0, egui::Color32::LIGHT_BLUE));
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+}
+
+/// Run the hotspot on a clicked cell, or position the cursor there
+pub fn click_cell(ctx: &egui::Context, session: &mut Session, row: usize, col: usize) {
+    match session.hotspot_at(row, col).map(|hotspot| hotspot.action.clone()) {
+        Some(HotspotAction::OpenUrl(url)) => ctx.open_url(egui::OpenUrl::new_tab(url)),
+        Some(HotspotAction::Key(action)) => {
+            if let Err(e) = action.perform(&session.controller) {
+                session.error_message = Some(e);
+            }
+        }
+        None => {
+            // Handle session-specific click: position cursor and handle field navigation
+            if let Err(e) = session.controller.click_at_position(row, col) {
+                eprintln!("Failed to click at position ({row}, {col}): {e}");
+            }
+        }
+    }
+}
+
+/// Shade the selected cells
+fn draw_selection(ui: &egui::Ui, layout: &GridLayout, selection: &Selection) {
+    let ((top, left), (bottom, right)) = selection.bounds();
+    let rect = layout.cell_rect(top, left).union(layout.cell_rect(bottom, right));
+    ui.painter().rect_filled(rect, egui::CornerRadius::ZERO, egui::Color32::from_rgba_unmultiplied(100, 150, 255, 80));
+    ui.painter().rect_stroke(
+        rect,
+        egui::CornerRadius::ZERO,
+        (1.0, egui::Color32::LIGHT_BLUE),
+        egui::StrokeKind::Inside,
+    );
+}
+
+/// Put screen text on the system clipboard; HTML also carries the plain text for other targets
+pub fn copy_to_clipboard(ctx: &egui::Context, screen: &str, selection: Option<&Selection>, format: CopyFormat) {
+    let text = clipboard::copy_text(screen, selection, format);
+    if format == CopyFormat::Html {
+        let plain = clipboard::copy_text(screen, selection, CopyFormat::Text);
+        match arboard::Clipboard::new().and_then(|mut board| board.set_html(&text, Some(&plain))) {
+            Ok(()) => return,
+            Err(e) => eprintln!("HTML clipboard unavailable, copying the markup as text: {e}"),
+        }
+    }
+    ctx.copy_text(text);
+}
+
+/// Text on the system clipboard
+pub fn clipboard_text() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut board| board.get_text())
+        .map_err(|e| format!("Clipboard unavailable: {e}"))
+}
+
+/// Paste into the session's fields; the message describes a paste that stopped or did not fit
+pub fn paste(controller: &AsyncTerminalController, text: &str, lines: LineHandling) -> Option<String> {
+    match controller.paste_text(text, lines) {
+        Ok(PasteOutcome { dropped: 0, .. }) => None,
+        Ok(PasteOutcome { typed, dropped }) => Some(format!(
+            "Pasted {} characters; {dropped} did not fit in the input fields",
+            typed.chars().count()
+        )),
+        Err(e) => Some(format!("Paste stopped: {e}")),
+    }
+}
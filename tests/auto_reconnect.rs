@@ -0,0 +1,127 @@
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tn5250r::controller::AsyncTerminalController;
+use tn5250r::network::SessionConfig;
+
+/// Binary and SGA both ways, DO NEW-ENVIRON, then SEND VAR "DEVNAME"
+fn host_offer() -> Vec<u8> {
+    let mut offer = vec![255, 253, 0, 255, 251, 0, 255, 253, 3, 255, 251, 3, 255, 253, 39];
+    offer.extend_from_slice(&[255, 250, 39, 1, 0]);
+    offer.extend_from_slice(b"DEVNAME");
+    offer.extend_from_slice(&[255, 240]);
+    offer
+}
+
+/// Telnet host that negotiates and then drops each of `sessions` connections, then stops
+/// listening; reports what the client sent on each
+fn dropping_host(sessions: usize) -> (u16, mpsc::Receiver<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for _ in 0..sessions {
+            let (mut tcp, _) = listener.accept().unwrap();
+            tcp.write_all(&host_offer()).unwrap();
+            tcp.shutdown(Shutdown::Write).unwrap();
+            let mut received = Vec::new();
+            let _ = tcp.read_to_end(&mut received);
+            let _ = tx.send(received);
+        }
+    });
+    (port, rx)
+}
+
+fn reconnecting(attempts: u32) -> SessionConfig {
+    SessionConfig {
+        auto_reconnect: true,
+        max_reconnect_attempts: attempts,
+        reconnect_initial_delay_ms: 200,
+        ..SessionConfig::default()
+    }
+}
+
+/// Poll the controller's connect error until it appears
+fn wait_for_error(controller: &AsyncTerminalController, within: Duration) -> String {
+    let deadline = Instant::now() + within;
+    while Instant::now() < deadline {
+        if let Some(err) = controller.take_last_connect_error() {
+            return err;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    panic!("no connect error within {within:?}");
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[test]
+fn lost_connection_is_reopened_with_the_same_device_name() {
+    let (port, sessions) = dropping_host(2);
+    let mut controller = AsyncTerminalController::new();
+    controller.set_session_config(reconnecting(3));
+    controller.set_device_name(Some("qpadev0042".to_string()));
+    controller.connect_async_with_tls("127.0.0.1".to_string(), port, Some(false)).unwrap();
+
+    let timeout = Duration::from_secs(10);
+    let first = sessions.recv_timeout(timeout).expect("first session");
+    let second = sessions.recv_timeout(timeout).expect("reconnected session");
+    let devname = [b"DEVNAME".as_slice(), &[1], b"QPADEV0042"].concat();
+    assert!(contains(&first, &devname), "device name requested: {first:?}");
+    assert!(contains(&second, &devname), "same device name requested again: {second:?}");
+
+    // The host is gone now; reconnecting shows progress and then gives up
+    let deadline = Instant::now() + timeout;
+    let progress = loop {
+        if let Some(progress) = controller.reconnect_progress() {
+            break progress;
+        }
+        assert!(Instant::now() < deadline, "no reconnection progress reported");
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(progress.max_attempts, 3);
+    assert!(progress.summary().starts_with("Connection lost - reconnecting"));
+
+    let err = wait_for_error(&controller, timeout);
+    assert!(err.starts_with("Connection lost; reconnection failed after 3 attempts"), "{err}");
+    assert!(controller.reconnect_progress().is_none());
+    assert!(!controller.is_connected());
+}
+
+#[test]
+fn lost_connection_without_auto_reconnect_is_reported() {
+    let (port, sessions) = dropping_host(1);
+    let mut controller = AsyncTerminalController::new();
+    controller.connect_async_with_tls("127.0.0.1".to_string(), port, Some(false)).unwrap();
+
+    sessions.recv_timeout(Duration::from_secs(10)).expect("session");
+    assert_eq!(wait_for_error(&controller, Duration::from_secs(5)), "Connection lost");
+    assert!(controller.reconnect_progress().is_none());
+    assert!(!controller.is_connected());
+}
+
+#[test]
+fn disconnect_stops_a_pending_reconnection() {
+    let (port, sessions) = dropping_host(1);
+    let mut controller = AsyncTerminalController::new();
+    controller.set_session_config(SessionConfig { reconnect_initial_delay_ms: 60_000, ..reconnecting(3) });
+    controller.connect_async_with_tls("127.0.0.1".to_string(), port, Some(false)).unwrap();
+
+    sessions.recv_timeout(Duration::from_secs(10)).expect("session");
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while controller.reconnect_progress().is_none() {
+        assert!(Instant::now() < deadline, "no reconnection progress reported");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    controller.disconnect();
+    assert!(controller.reconnect_progress().is_none());
+    thread::sleep(Duration::from_millis(300));
+    assert!(controller.reconnect_progress().is_none());
+    assert!(!controller.is_connected());
+}
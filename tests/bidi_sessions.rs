@@ -0,0 +1,136 @@
+use tn5250r::controller::TerminalController;
+use tn5250r::field_manager::*;
+use tn5250r::lib5250::codes::*;
+use tn5250r::lib5250::session::Session;
+use tn5250r::terminal::TextDirection;
+
+const ESC: u8 = 0x04;
+
+fn hebrew_session() -> Session {
+    let mut session = Session::new();
+    session.authenticate("testuser", "testpass").unwrap();
+    session.set_code_page(424);
+    session
+}
+
+fn bidi_field(behavior: FieldBehavior) -> FieldManager {
+    let mut manager = FieldManager::new();
+    let mut field = Field::new(1, FieldType::Input, 1, 1, 6);
+    field.set_behavior(behavior);
+    manager.add_field_for_test(field);
+    manager.set_active_field_for_test(Some(0));
+    manager
+}
+
+#[test]
+fn hebrew_code_page_decodes_and_mirrors_screen() {
+    let mut session = hebrew_session();
+    assert_eq!(session.display().screen_direction(), TextDirection::RightToLeft);
+
+    // Alef, bet at row 1, column 1
+    let data = [ESC, CMD_CLEAR_UNIT, ESC, CMD_WRITE_TO_DISPLAY, 0x00, 0x00, SBA, 1, 1, 0x41, 0x42];
+    session.process_stream(&data).expect("write hebrew");
+
+    let screen = session.display().screen_ref();
+    assert_eq!(screen.get_char_at(0, 0), Some('\u{05D0}'));
+    let first_row = screen.to_visual_string().lines().next().unwrap().to_string();
+    assert!(first_row.ends_with("\u{05D1}\u{05D0}"));
+    assert_eq!(screen.visual_column(0), screen.width - 1);
+    assert_eq!(session.display().cell_ebcdic(0, 1), 0x42);
+}
+
+#[test]
+fn operator_sets_field_direction_through_the_controller() {
+    assert!(TerminalController::new().toggle_field_reverse().is_err(), "no field to reverse");
+
+    // Two input fields from the host, the cursor in the first
+    let data = [
+        ESC, CMD_CLEAR_UNIT, ESC, CMD_WRITE_TO_DISPLAY, 0x00, 0x00,
+        SBA, 3, 10, SF, 0x40, 0x00, 0x20, 0x00, 0x06,
+        SBA, 5, 10, SF, 0x40, 0x00, 0x20, 0x00, 0x06,
+        IC, 3, 11,
+    ];
+    let mut controller = TerminalController::new();
+    controller.process_host_data(&data);
+
+    // Field Reverse: the cursor starts at the right end of the field and moves left
+    assert!(controller.toggle_field_reverse().unwrap());
+    assert_eq!(controller.ui_cursor_position(), (3, 16));
+    controller.type_char('A').unwrap();
+    controller.type_char('B').unwrap();
+    assert_eq!(controller.ui_cursor_position(), (3, 14));
+    assert!(controller.get_terminal_content().lines().nth(2).unwrap().trim_end().ends_with("BA"));
+
+    // Close: data enters at the trailing edge of the next field
+    controller.next_field().unwrap();
+    assert!(controller.toggle_closed_field().unwrap());
+    controller.type_char('1').unwrap();
+    controller.type_char('2').unwrap();
+    assert_eq!(controller.get_fields_info()[1].content, "    12");
+
+    assert!(controller.toggle_auto_push().unwrap());
+    assert!(!controller.toggle_auto_push().unwrap());
+}
+
+#[test]
+fn reversed_field_moves_cursor_from_its_right_end() {
+    let mut manager = bidi_field(FieldBehavior { reverse: true, ..Default::default() });
+
+    manager.type_char('A').unwrap();
+    manager.type_char('B').unwrap();
+
+    let field = &manager.get_fields()[0];
+    assert_eq!(field.content, "AB");
+    assert_eq!(field.column_for_offset(0), 6);
+    assert_eq!(field.column_for_offset(1), 5);
+    assert_eq!(manager.get_cursor_position(), (1, 4));
+    assert_eq!(manager.field_direction(0), TextDirection::RightToLeft);
+}
+
+#[test]
+fn auto_push_keeps_cursor_while_entering_opposite_direction_text() {
+    let mut manager = bidi_field(FieldBehavior { auto_push: true, ..Default::default() });
+    manager.set_screen_direction(TextDirection::RightToLeft);
+
+    manager.type_char('\u{05D0}').unwrap();
+    manager.type_char('1').unwrap();
+    manager.type_char('2').unwrap();
+    assert_eq!(manager.get_cursor_position(), (1, 2));
+    manager.type_char('\u{05D1}').unwrap();
+
+    // Buffer order; the mirrored screen shows the digits reading "12"
+    assert_eq!(manager.get_fields()[0].content, "\u{05D0}21\u{05D1}");
+    assert_eq!(manager.get_cursor_position(), (1, 5));
+}
+
+#[test]
+fn push_mode_toggle_ends_at_pushed_text() {
+    let mut manager = bidi_field(FieldBehavior::default());
+
+    assert!(manager.toggle_push_mode());
+    manager.type_char('X').unwrap();
+    manager.type_char('Y').unwrap();
+    assert!(!manager.toggle_push_mode());
+    manager.type_char('Z').unwrap();
+
+    assert_eq!(manager.get_fields()[0].content, "YXZ");
+}
+
+#[test]
+fn closed_field_enters_data_at_trailing_edge() {
+    let mut manager = bidi_field(FieldBehavior { closed: true, ..Default::default() });
+
+    manager.type_char('1').unwrap();
+    manager.type_char('2').unwrap();
+    assert_eq!(manager.get_fields()[0].content, "    12");
+    assert_eq!(manager.get_cursor_position(), (1, 6));
+
+    manager.backspace().unwrap();
+    assert_eq!(manager.get_fields()[0].content, "     1");
+
+    for ch in "23456".chars() {
+        manager.type_char(ch).unwrap();
+    }
+    assert!(manager.type_char('7').is_err());
+    assert_eq!(manager.get_error_state(), Some(&FieldError::NoRoomForInsert));
+}
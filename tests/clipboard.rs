@@ -0,0 +1,119 @@
+use tn5250r::clipboard::{columns, copy_text, CopyFormat, LineHandling, Selection};
+use tn5250r::field_manager::{Field, FieldError, FieldManager, FieldType};
+use tn5250r::screen_grid::CellGrid;
+use tn5250r::session::Session;
+use tn5250r::session_profile::SessionProfile;
+use tn5250r::terminal::{CharAttribute, TerminalChar, TerminalScreen};
+
+const SCREEN: &str = "\
+ Work with Objects
+  Opt  Object      Type
+   5   QSYS        *LIB
+   2   <QGPL> & co  *LIB";
+
+#[test]
+fn selection_is_normalized_to_a_rectangle() {
+    let selection = Selection::new((4, 12), (2, 3));
+    assert_eq!(selection.bounds(), ((2, 3), (4, 12)));
+    assert!(selection.contains(3, 3));
+    assert!(!selection.contains(1, 5));
+    assert!(!selection.contains(3, 13));
+}
+
+#[test]
+fn copies_text_tab_delimited_and_html() {
+    let selection = Selection::new((2, 3), (3, 17));
+    assert_eq!(copy_text(SCREEN, Some(&selection), CopyFormat::Text), "Opt  Object\n 5   QSYS");
+    assert_eq!(copy_text(SCREEN, Some(&selection), CopyFormat::TabDelimited), "Opt\tObject\n5\tQSYS");
+
+    let whole = copy_text(SCREEN, None, CopyFormat::Text);
+    assert_eq!(whole, SCREEN);
+
+    assert_eq!(columns("  Work with   Objects "), ["Work with", "Objects"]);
+
+    let html = copy_text(SCREEN, Some(&Selection::new((4, 1), (4, 30))), CopyFormat::Html);
+    assert!(html.contains("<tr><td>2</td><td>&lt;QGPL&gt; &amp; co</td><td>*LIB</td></tr>"), "{html}");
+}
+
+#[test]
+fn copying_the_screen_leaves_nondisplay_fields_blank() {
+    // A password field: nondisplay attribute 0x27 before "SECRET", normal 0x20 after it
+    let mut screen = TerminalScreen::new();
+    screen.write_chars_to_row(0, 1, &"Password".chars().collect::<Vec<_>>(), CharAttribute::Normal);
+    screen.set_char_at(10, 0, TerminalChar { character: ' ', attribute: CharAttribute::FieldAttribute(0x27) });
+    screen.write_chars_to_row(0, 11, &"SECRET".chars().collect::<Vec<_>>(), CharAttribute::Normal);
+    screen.set_char_at(17, 0, TerminalChar { character: ' ', attribute: CharAttribute::FieldAttribute(0x20) });
+
+    let mut session = Session::new(SessionProfile::new("Copy".to_string(), "copy.example.com".to_string(), 23));
+    session.grid = CellGrid::from_screen(&screen, (1, 12));
+    session.terminal_content = session.grid.text();
+    assert!(session.terminal_content.contains("SECRET"));
+
+    let whole = copy_text(&session.shown_text(), None, CopyFormat::Text);
+    assert_eq!(whole.lines().next(), Some(" Password"));
+    let field = copy_text(&session.shown_text(), Some(&Selection::new((1, 12), (1, 17))), CopyFormat::Text);
+    assert_eq!(field, "");
+}
+
+fn form() -> FieldManager {
+    let mut manager = FieldManager::new();
+    let fields = [
+        Field::new(1, FieldType::Input, 2, 10, 4),
+        Field::new(2, FieldType::Protected, 2, 20, 5),
+        Field::new(3, FieldType::Input, 2, 30, 3),
+        Field::new(4, FieldType::Numeric, 3, 10, 4),
+        Field::new(5, FieldType::Input, 3, 30, 3),
+    ];
+    for field in fields {
+        manager.add_field_for_test(field);
+    }
+    manager.click_at_position(2, 10);
+    manager
+}
+
+fn contents(manager: &FieldManager) -> Vec<String> {
+    manager.get_fields().iter().map(|field| field.content.clone()).collect()
+}
+
+#[test]
+fn paste_spills_into_following_input_fields() {
+    let mut manager = form();
+    let outcome = manager.paste_text("ABCDEFG12", LineHandling::NextField).unwrap();
+    assert_eq!(outcome.typed, "ABCDEFG12");
+    assert_eq!(outcome.dropped, 0);
+    assert_eq!(contents(&manager), ["ABCD", "", "EFG", "12", ""]);
+}
+
+#[test]
+fn tabs_and_line_breaks_move_between_fields() {
+    let mut manager = form();
+    manager.paste_text("AB\tXY\r\n42\n", LineHandling::NextField).unwrap();
+    assert_eq!(contents(&manager), ["AB", "", "XY", "42", ""]);
+
+    // Each line continues on the next row, below where the paste started
+    let mut manager = form();
+    manager.click_at_position(2, 30);
+    manager.paste_text("ABC\nXYZ", LineHandling::NextLine).unwrap();
+    assert_eq!(contents(&manager), ["", "", "ABC", "", "XYZ"]);
+
+    let mut manager = form();
+    manager.paste_text("AB\nC", LineHandling::Join).unwrap();
+    assert_eq!(contents(&manager), ["AB C", "", "", "", ""]);
+}
+
+#[test]
+fn paste_honours_field_validation_and_runs_out_of_fields() {
+    let mut manager = form();
+    let error = manager.paste_text("ABCDEFG1x", LineHandling::NextField).unwrap_err();
+    assert!(!error.is_empty());
+    assert_eq!(manager.get_error(), Some(&FieldError::NumericOnly));
+    assert_eq!(contents(&manager), ["ABCD", "", "EFG", "1", ""]);
+
+    // The last field does not wrap back to the top
+    let mut manager = form();
+    manager.click_at_position(3, 30);
+    let outcome = manager.paste_text("ABCDE", LineHandling::NextField).unwrap();
+    assert_eq!(outcome.typed, "ABC");
+    assert_eq!(outcome.dropped, 2);
+    assert_eq!(contents(&manager), ["", "", "", "", "ABC"]);
+}
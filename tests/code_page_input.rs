@@ -0,0 +1,53 @@
+use tn5250r::controller::TerminalController;
+use tn5250r::ebcdic::{char_to_ebcdic, ebcdic_to_char, is_supported_code_page};
+use tn5250r::field_manager::FieldError;
+use tn5250r::telnet_environment::EnvironmentSettings;
+
+#[test]
+fn national_characters_round_trip_through_their_code_page() {
+    // German: ä ö ü ß and the section sign
+    for (ch, byte) in [('ä', 0xC0), ('ö', 0x6A), ('ü', 0xD0), ('ß', 0xA1), ('§', 0x7C)] {
+        assert_eq!(char_to_ebcdic(ch, 273), Some(byte), "{ch}");
+        assert_eq!(ebcdic_to_char(byte, 273), ch);
+    }
+    // French: à ç é è
+    for (ch, byte) in [('à', 0x7C), ('ç', 0xE0), ('é', 0xC0), ('è', 0xD0)] {
+        assert_eq!(char_to_ebcdic(ch, 297), Some(byte), "{ch}");
+        assert_eq!(ebcdic_to_char(byte, 297), ch);
+    }
+    // Every character the US table shows encodes back to the same byte
+    for byte in 0x41..=0xFEu8 {
+        let ch = ebcdic_to_char(byte, 37);
+        assert_eq!(char_to_ebcdic(ch, 37), Some(byte), "{byte:02X} {ch}");
+    }
+    assert_eq!(char_to_ebcdic('A', 500), Some(0xC1));
+}
+
+#[test]
+fn euro_code_pages_replace_the_currency_sign() {
+    assert_eq!(char_to_ebcdic('€', 1141), Some(0x9F));
+    assert_eq!(ebcdic_to_char(0x9F, 1141), '€');
+    assert_eq!(char_to_ebcdic('ä', 1141), char_to_ebcdic('ä', 273));
+    assert_eq!(char_to_ebcdic('¤', 1141), None);
+    assert_eq!(char_to_ebcdic('€', 1142), Some(0x5A));
+
+    assert!(is_supported_code_page(37));
+    assert!(is_supported_code_page(1147));
+    assert!(is_supported_code_page(424));
+    assert!(!is_supported_code_page(930));
+}
+
+#[test]
+fn characters_outside_the_code_page_are_rejected() {
+    assert_eq!(char_to_ebcdic('€', 37), None);
+    assert_eq!(char_to_ebcdic('漢', 273), None);
+
+    let environment = EnvironmentSettings { code_page: "273".to_string(), ..Default::default() };
+    assert_eq!(environment.host_code_page(), Some(273));
+
+    let mut controller = TerminalController::new();
+    controller.set_environment(environment);
+    assert!(controller.type_char('漢').is_err());
+    assert_eq!(controller.oia_state().operator_error, Some(FieldError::InvalidCharacter('漢')));
+    assert!(controller.get_pending_input().is_empty());
+}
@@ -0,0 +1,53 @@
+use tn5250r::color_scheme::{ColorScheme, Rgb};
+use tn5250r::screen_grid::ScreenColor;
+use tn5250r::session_profile::SessionProfile;
+
+#[test]
+fn colors_are_stored_as_hex() {
+    let json = serde_json::to_string(&Rgb::new(0, 160, 255)).unwrap();
+    assert_eq!(json, "\"#00A0FF\"");
+    assert_eq!(serde_json::from_str::<Rgb>("\"#00a0ff\"").unwrap(), Rgb::new(0, 160, 255));
+    assert!(serde_json::from_str::<Rgb>("\"#00A0F\"").is_err());
+    assert!(serde_json::from_str::<Rgb>("\"green\"").is_err());
+}
+
+#[test]
+fn built_in_schemes_cover_every_color() {
+    for name in ColorScheme::BUILT_IN {
+        let scheme = ColorScheme::built_in(name).unwrap();
+        assert_eq!(scheme.name, name);
+        for color in ScreenColor::ALL {
+            assert_ne!(scheme.color(color), scheme.background, "{name}: {color:?} is invisible");
+        }
+    }
+    assert!(ColorScheme::built_in("Sepia").is_none());
+    assert_eq!(ColorScheme::default(), ColorScheme::classic_green());
+
+    let mut scheme = ColorScheme::light();
+    *scheme.color_mut(ScreenColor::Pink) = Rgb::new(1, 2, 3);
+    assert_eq!(scheme.pink, Rgb::new(1, 2, 3));
+}
+
+#[test]
+fn schemes_round_trip_with_the_profile() {
+    let mut profile = SessionProfile::new("Payroll".to_string(), "as400.example.com".to_string(), 23);
+    profile.color_scheme = ColorScheme::high_contrast();
+    profile.color_scheme.modified_field.background = Rgb::new(10, 20, 30);
+
+    let json = serde_json::to_string(&profile).unwrap();
+    let loaded: SessionProfile = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.color_scheme, profile.color_scheme);
+
+    // Profiles saved before schemes existed get the default colors
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value.as_object_mut().unwrap().remove("color_scheme");
+    let old: SessionProfile = serde_json::from_value(value).unwrap();
+    assert_eq!(old.color_scheme, ColorScheme::default());
+
+    // A hand-written scheme only needs the colors it changes
+    let partial = ColorScheme::from_json(r##"{"name": "Team", "background": "#101010"}"##).unwrap();
+    assert_eq!(partial.background, Rgb::new(16, 16, 16));
+    assert_eq!(partial.green, ColorScheme::default().green);
+    assert_eq!(ColorScheme::from_json(&partial.to_json()).unwrap(), partial);
+    assert!(ColorScheme::from_json(r#"{"red": "bright"}"#).is_err());
+}
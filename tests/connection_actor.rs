@@ -0,0 +1,113 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use tn5250r::network_actor::{self, ConnectionEvent, ConnectionHandle, Transport};
+
+/// Connected client/server socket pair on loopback
+fn socket_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    (client, server)
+}
+
+fn next_event(handle: &mut ConnectionHandle) -> Option<ConnectionEvent> {
+    network_actor::runtime().block_on(async {
+        tokio::time::timeout(Duration::from_secs(5), handle.next_event())
+            .await
+            .expect("no event from connection actor")
+    })
+}
+
+/// Collect data events until `len` bytes have arrived
+fn read_exact(handle: &mut ConnectionHandle, len: usize) -> Vec<u8> {
+    let mut received = Vec::new();
+    while received.len() < len {
+        match next_event(handle) {
+            Some(ConnectionEvent::Data(data)) => received.extend(data),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+    received
+}
+
+#[test]
+fn forwards_data_in_both_directions() {
+    let (client, mut server) = socket_pair();
+    let mut handle = ConnectionHandle::spawn(Transport::Plain(client), 8);
+
+    handle.send(b"hello host".to_vec()).unwrap();
+    let mut buf = [0u8; 10];
+    server.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello host");
+
+    server.write_all(b"hello client").unwrap();
+    assert_eq!(read_exact(&mut handle, 12), b"hello client");
+}
+
+#[test]
+fn reports_closed_when_host_hangs_up() {
+    let (client, server) = socket_pair();
+    let mut handle = ConnectionHandle::spawn(Transport::Plain(client), 8);
+
+    drop(server);
+    assert_eq!(next_event(&mut handle), Some(ConnectionEvent::Closed));
+    assert_eq!(next_event(&mut handle), None);
+}
+
+/// Wait for the handle's wakeup without touching the handle, as the controller does
+fn wait_for_wakeup(handle: &ConnectionHandle) {
+    let wakeup = handle.wakeup();
+    network_actor::runtime().block_on(async {
+        tokio::time::timeout(Duration::from_secs(5), wakeup.notified())
+            .await
+            .expect("connection actor never signalled its wakeup")
+    })
+}
+
+#[test]
+fn wakeup_is_signalled_for_data_and_for_close() {
+    let (client, mut server) = socket_pair();
+    let mut handle = ConnectionHandle::spawn(Transport::Plain(client), 8);
+    assert!(!handle.has_pending_events());
+
+    server.write_all(b"screen").unwrap();
+    wait_for_wakeup(&handle);
+    assert!(handle.has_pending_events());
+    assert_eq!(read_exact(&mut handle, 6), b"screen");
+
+    drop(server);
+    wait_for_wakeup(&handle);
+    assert_eq!(next_event(&mut handle), Some(ConnectionEvent::Closed));
+}
+
+#[test]
+fn disconnect_closes_the_socket() {
+    let (client, mut server) = socket_pair();
+    let mut handle = ConnectionHandle::spawn(Transport::Plain(client), 8);
+
+    handle.disconnect();
+    assert_eq!(next_event(&mut handle), Some(ConnectionEvent::Closed));
+    server.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut buf = [0u8; 1];
+    assert_eq!(server.read(&mut buf).unwrap(), 0);
+    assert!(handle.send(b"late".to_vec()).is_err());
+}
+
+#[test]
+fn slow_consumer_holds_back_reads_without_losing_data() {
+    let (client, mut server) = socket_pair();
+    let mut handle = ConnectionHandle::spawn(Transport::Plain(client), 1);
+
+    // Far more than fits in the event queue, left untaken while the host keeps writing
+    let payload: Vec<u8> = (0..256 * 1024).map(|i| (i % 251) as u8).collect();
+    let expected = payload.clone();
+    let writer = thread::spawn(move || server.write_all(&payload).map(|()| server));
+    thread::sleep(Duration::from_millis(200));
+
+    let received = read_exact(&mut handle, expected.len());
+    drop(writer.join().unwrap().unwrap());
+    assert_eq!(received, expected);
+}
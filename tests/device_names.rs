@@ -0,0 +1,162 @@
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tn5250r::controller::AsyncTerminalController;
+use tn5250r::device_name::{is_device_in_use, validate_device_name, DeviceNames};
+
+#[test]
+fn counter_pattern_moves_to_the_next_name() {
+    let mut names = DeviceNames::new("qpadev%02d", 3).unwrap();
+    assert_eq!(names.current(), "QPADEV01");
+    assert_eq!(names.advance(), Some("QPADEV02"));
+    assert_eq!(names.current(), "QPADEV02");
+}
+
+#[test]
+fn session_index_pattern_names_a_single_device() {
+    let mut names = DeviceNames::new("USER%S", 4).unwrap();
+    assert_eq!(names.current(), "USER4");
+    assert_eq!(names.advance(), None);
+    assert_eq!(DeviceNames::new("DSP%02S%D", 2).unwrap().current(), "DSP021");
+}
+
+#[test]
+fn counter_stops_at_the_name_length_limit() {
+    let mut names = DeviceNames::new("ABCDEFGHI%D", 1).unwrap();
+    for n in 2..=9 {
+        assert_eq!(names.advance(), Some(format!("ABCDEFGHI{n}").as_str()));
+    }
+    assert_eq!(names.advance(), None);
+    assert_eq!(names.current(), "ABCDEFGHI9");
+}
+
+#[test]
+fn invalid_patterns_are_rejected() {
+    assert!(DeviceNames::new("QPADEV%X", 1).is_err());
+    assert!(DeviceNames::new("QPADEV%", 1).is_err());
+    assert!(DeviceNames::new("1DEV", 1).is_err());
+    assert!(DeviceNames::new("TOOLONGNAME%D", 1).is_err());
+    assert!(validate_device_name("QPA-DEV").is_err());
+    assert!(validate_device_name("$DEV_1.A").is_ok());
+}
+
+#[test]
+fn device_in_use_message_is_recognised() {
+    assert!(is_device_in_use("CPF1116: Next not valid sign-on attempt varies off device"));
+    assert!(!is_device_in_use("Sign On\nSystem . . . . . : AS400"));
+}
+
+/// Options, TN3270E BIND (so negotiation completes) and SEND VAR "DEVNAME"
+fn host_offer() -> Vec<u8> {
+    let mut offer = vec![255, 253, 0, 255, 251, 0, 255, 253, 3, 255, 251, 3, 255, 253, 39, 255, 253, 40];
+    offer.extend_from_slice(&[255, 250, 40, 6, b'L', b'U', b'0', b'1', 0, 255, 240]);
+    offer.extend_from_slice(&[255, 250, 39, 1, 0]);
+    offer.extend_from_slice(b"DEVNAME");
+    offer.extend_from_slice(&[255, 240]);
+    offer
+}
+
+/// Negotiate and return the DEVNAME value the client sent
+fn negotiate(tcp: &mut TcpStream) -> String {
+    tcp.write_all(&host_offer()).unwrap();
+    tcp.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut received = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = tcp.read(&mut buf).unwrap();
+        assert!(n > 0, "client closed during negotiation");
+        received.extend_from_slice(&buf[..n]);
+        let marker = [b"DEVNAME".as_slice(), &[1]].concat();
+        if let Some(at) = received.windows(marker.len()).position(|w| w == marker) {
+            let value = &received[at + marker.len()..];
+            if let Some(end) = value.iter().position(|&b| b == 255 || b == 0 || b == 3) {
+                return String::from_utf8_lossy(&value[..end]).into_owned();
+            }
+        }
+    }
+}
+
+/// Host that reports the first `in_use` device names as taken and accepts the next; reports each DEVNAME
+fn busy_host(in_use: usize) -> (u16, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for n in 0..=in_use {
+            let (mut tcp, _) = listener.accept().unwrap();
+            let device_name = negotiate(&mut tcp);
+            let _ = tx.send(device_name.clone());
+            thread::sleep(Duration::from_millis(300));
+            if n < in_use {
+                let screen = format!("\x1b[2J\x1b[HCPF1116 Device {device_name} already in use");
+                tcp.write_all(screen.as_bytes()).unwrap();
+                tcp.shutdown(Shutdown::Write).unwrap();
+            } else {
+                tcp.write_all(b"\x1b[2J\x1b[HSign On").unwrap();
+            }
+            let mut rest = Vec::new();
+            tcp.set_read_timeout(None).unwrap();
+            let _ = tcp.read_to_end(&mut rest);
+        }
+    });
+    (port, rx)
+}
+
+fn wait_until(within: Duration, mut done: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + within;
+    while Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    false
+}
+
+#[test]
+fn device_name_in_use_is_retried_with_the_next_name() {
+    let (port, device_names) = busy_host(2);
+    let mut controller = AsyncTerminalController::new();
+    controller.set_device_name(Some("QPADEV%02D".to_string()));
+    controller.connect_async_with_tls("127.0.0.1".to_string(), port, Some(false)).unwrap();
+
+    let timeout = Duration::from_secs(10);
+    let requested: Vec<String> = (0..3).map(|_| device_names.recv_timeout(timeout).unwrap()).collect();
+    assert_eq!(requested, ["QPADEV01", "QPADEV02", "QPADEV03"]);
+
+    assert!(wait_until(timeout, || controller.get_terminal_content().is_ok_and(|c| c.contains("Sign On"))));
+    assert!(controller.is_connected());
+    assert_eq!(controller.device_name().as_deref(), Some("QPADEV03"));
+    assert_eq!(controller.take_last_connect_error(), None);
+    controller.disconnect();
+}
+
+#[test]
+fn single_device_name_in_use_ends_the_session() {
+    let (port, device_names) = busy_host(1);
+    let mut controller = AsyncTerminalController::new();
+    controller.set_device_name(Some("USER%S".to_string()));
+    controller.set_session_index(3);
+    controller.connect_async_with_tls("127.0.0.1".to_string(), port, Some(false)).unwrap();
+
+    assert_eq!(device_names.recv_timeout(Duration::from_secs(10)).unwrap(), "USER3");
+    let mut err = None;
+    assert!(wait_until(Duration::from_secs(10), || {
+        err = controller.take_last_connect_error();
+        err.is_some()
+    }));
+    assert_eq!(err.as_deref(), Some("Device name USER3 is already in use"));
+    assert!(!controller.is_connected());
+}
+
+#[test]
+fn invalid_pattern_fails_before_connecting() {
+    let mut controller = AsyncTerminalController::new();
+    controller.set_device_name(Some("QPADEV%X".to_string()));
+    let err = controller.connect_async_with_tls("127.0.0.1".to_string(), 1, Some(false)).unwrap_err();
+    assert!(err.contains("%X"), "{err}");
+    assert!(!controller.is_connecting());
+}
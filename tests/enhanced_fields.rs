@@ -155,4 +155,294 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result, Err(FieldError::FieldFull));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_overstrike_replaces_at_cursor() {
+        let mut manager = FieldManager::new();
+        manager.add_field_for_test(Field::new(1, FieldType::Input, 1, 1, 5));
+        manager.set_active_field_for_test(Some(0));
+
+        for ch in "ABCD".chars() {
+            manager.type_char(ch).unwrap();
+        }
+
+        // Move back to the second position and overwrite
+        assert!(manager.click_at_position(1, 2));
+        assert!(!manager.is_insert_mode());
+        manager.type_char('x').unwrap();
+        assert_eq!(manager.get_fields()[0].content, "AxCD");
+        assert_eq!(manager.get_cursor_position(), (1, 3));
+    }
+
+    #[test]
+    fn test_insert_mode_shifts_right() {
+        let mut manager = FieldManager::new();
+        manager.add_field_for_test(Field::new(1, FieldType::Input, 1, 1, 5));
+        manager.set_active_field_for_test(Some(0));
+
+        for ch in "ABC".chars() {
+            manager.type_char(ch).unwrap();
+        }
+
+        assert!(manager.click_at_position(1, 2));
+        assert!(manager.toggle_insert_mode());
+        manager.type_char('x').unwrap();
+        assert_eq!(manager.get_fields()[0].content, "AxBC");
+
+        // Trailing blanks are pushed off the end
+        let mut manager = FieldManager::new();
+        let mut field = Field::new(1, FieldType::Input, 1, 1, 4);
+        field.content = "AB  ".to_string();
+        manager.add_field_for_test(field);
+        manager.set_insert_mode(true);
+        assert!(manager.click_at_position(1, 1));
+        manager.type_char('x').unwrap();
+        assert_eq!(manager.get_fields()[0].content, "xAB ");
+    }
+
+    #[test]
+    fn test_insert_mode_rejects_when_last_position_occupied() {
+        let mut manager = FieldManager::new();
+        let mut field = Field::new(1, FieldType::Input, 1, 1, 4);
+        field.content = "ABCD".to_string();
+        manager.add_field_for_test(field);
+        manager.set_insert_mode(true);
+        assert!(manager.click_at_position(1, 2));
+
+        assert!(manager.type_char('x').is_err());
+        assert_eq!(manager.get_fields()[0].content, "ABCD");
+        assert_eq!(manager.get_error(), Some(&FieldError::NoRoomForInsert));
+
+        let error = manager.get_error().unwrap();
+        assert_eq!(error.operator_error_code(), Some(0x12));
+        assert_eq!(error.operator_message(), "0012 No room to insert data.");
+
+        // Reset clears the operator error
+        manager.clear_error();
+        assert!(manager.get_error().is_none());
+    }
+
+    #[test]
+    fn test_erase_eof_clears_from_cursor() {
+        let mut manager = FieldManager::new();
+        let mut field = Field::new(1, FieldType::Input, 3, 10, 8);
+        field.content = "ABCDEFGH".to_string();
+        manager.add_field_for_test(field);
+        assert!(manager.click_at_position(3, 13));
+
+        manager.erase_eof().unwrap();
+        assert_eq!(manager.get_fields()[0].content, "ABC");
+        assert!(manager.get_fields()[0].modified);
+        assert_eq!(manager.get_cursor_position(), (3, 13));
+    }
+
+    #[test]
+    fn test_erase_input_clears_unprotected_fields_and_homes() {
+        let mut manager = FieldManager::new();
+        let mut label = Field::new(1, FieldType::Protected, 1, 1, 10);
+        label.content = "User . . .".to_string();
+        let mut user = Field::new(2, FieldType::Input, 5, 20, 10);
+        user.content = "QSECOFR".to_string();
+        user.modified = true;
+        let mut password = Field::new(3, FieldType::Password, 6, 20, 10);
+        password.content = "secret".to_string();
+        manager.add_field_for_test(label);
+        manager.add_field_for_test(password);
+        manager.add_field_for_test(user);
+        assert!(manager.click_at_position(6, 22));
+
+        manager.erase_input().unwrap();
+
+        let fields = manager.get_fields();
+        assert_eq!(fields[0].content, "User . . .");
+        assert!(fields[1].content.is_empty());
+        assert!(fields[2].content.is_empty());
+        assert!(!manager.has_modified_fields());
+
+        // Cursor lands on the first input field in screen order
+        assert_eq!(manager.get_active_field_index(), Some(2));
+        assert_eq!(manager.get_cursor_position(), (5, 20));
+    }
+
+    #[test]
+    fn test_home_goes_to_first_input_field() {
+        let mut manager = FieldManager::new();
+        manager.add_field_for_test(Field::new(1, FieldType::Protected, 1, 1, 10));
+        manager.add_field_for_test(Field::new(2, FieldType::Input, 8, 5, 10));
+        manager.add_field_for_test(Field::new(3, FieldType::Input, 4, 30, 10));
+        assert!(manager.click_at_position(8, 7));
+
+        manager.home_to_first_input_field().unwrap();
+        assert_eq!(manager.get_active_field_index(), Some(2));
+        assert_eq!(manager.get_cursor_position(), (4, 30));
+        assert_eq!(manager.get_fields()[2].cursor_position, 0);
+    }
+
+    #[test]
+    fn test_backspace_and_delete_at_cursor() {
+        let mut manager = FieldManager::new();
+        let mut field = Field::new(1, FieldType::Input, 1, 1, 6);
+        field.content = "ABCDE".to_string();
+        manager.add_field_for_test(field);
+
+        assert!(manager.click_at_position(1, 3));
+        manager.delete().unwrap();
+        assert_eq!(manager.get_fields()[0].content, "ABDE");
+
+        manager.backspace().unwrap();
+        assert_eq!(manager.get_fields()[0].content, "ADE");
+        assert_eq!(manager.get_cursor_position(), (1, 2));
+    }
+
+    /// Manager with a plain field, a two-segment continued field and a trailing field
+    fn continued_manager(segment_len: usize, word_wrap: bool) -> FieldManager {
+        let mut manager = FieldManager::new();
+        manager.add_field_for_test(Field::new(1, FieldType::Input, 1, 1, 5));
+        for (id, row) in [(2, 2), (3, 3)] {
+            let mut segment = Field::new(id, FieldType::Continued, row, 10, segment_len);
+            segment.behavior.word_wrap = word_wrap;
+            manager.add_field_for_test(segment);
+        }
+        manager.add_field_for_test(Field::new(4, FieldType::Input, 4, 1, 5));
+        manager.add_field_to_continued_group(1, 200);
+        manager.add_field_to_continued_group(2, 200);
+        manager
+    }
+
+    #[test]
+    fn test_continued_field_typing_flows_to_next_segment() {
+        let mut manager = continued_manager(3, false);
+        assert!(manager.click_at_position(2, 10));
+
+        for ch in "ABCDE".chars() {
+            manager.type_char(ch).unwrap();
+        }
+
+        let fields = manager.get_fields();
+        assert_eq!(fields[1].content, "ABC");
+        assert_eq!(fields[2].content, "DE");
+        assert_eq!(manager.get_active_field_index(), Some(2));
+        assert_eq!(manager.get_cursor_position(), (3, 12));
+        assert!(fields[1].modified && fields[2].modified);
+    }
+
+    #[test]
+    fn test_continued_field_insert_and_delete_shift_across_segments() {
+        let mut manager = continued_manager(3, false);
+        assert!(manager.click_at_position(2, 10));
+        for ch in "ABCDE".chars() {
+            manager.type_char(ch).unwrap();
+        }
+
+        assert!(manager.click_at_position(2, 10));
+        manager.set_insert_mode(true);
+        manager.type_char('X').unwrap();
+        assert_eq!(manager.get_fields()[1].content, "XAB");
+        assert_eq!(manager.get_fields()[2].content, "CDE");
+
+        // Last position of the logical field is now occupied
+        assert!(manager.type_char('Y').is_err());
+        assert_eq!(manager.get_error_state(), Some(&FieldError::NoRoomForInsert));
+
+        // Backspace at the start of the second segment pulls data back across
+        assert!(manager.click_at_position(3, 10));
+        manager.backspace().unwrap();
+        assert_eq!(manager.get_fields()[1].content, "XAC");
+        assert_eq!(manager.get_fields()[2].content, "DE");
+        assert_eq!(manager.get_cursor_position(), (2, 12));
+
+        manager.delete().unwrap();
+        assert_eq!(manager.get_fields()[1].content, "XAD");
+        assert_eq!(manager.get_fields()[2].content, "E");
+    }
+
+    #[test]
+    fn test_continued_field_word_wrap() {
+        let mut manager = continued_manager(8, true);
+        assert!(manager.click_at_position(2, 10));
+
+        for ch in "HELLO WORLD".chars() {
+            manager.type_char(ch).unwrap();
+        }
+
+        assert_eq!(manager.get_fields()[1].content, "HELLO");
+        assert_eq!(manager.get_fields()[2].content, "WORLD");
+        assert_eq!(manager.get_cursor_position(), (3, 15));
+    }
+
+    #[test]
+    fn test_continued_field_is_one_navigation_stop() {
+        let mut manager = continued_manager(3, false);
+        assert!(manager.click_at_position(2, 11));
+
+        manager.navigate_to_next_field().unwrap();
+        assert_eq!(manager.get_active_field_index(), Some(3));
+
+        manager.navigate_to_previous_field().unwrap();
+        assert_eq!(manager.get_active_field_index(), Some(1));
+
+        manager.tab_to_previous_field().unwrap();
+        assert_eq!(manager.get_active_field_index(), Some(0));
+        manager.tab_to_next_field().unwrap();
+        assert_eq!(manager.get_active_field_index(), Some(1));
+        manager.tab_to_next_field().unwrap();
+        assert_eq!(manager.get_active_field_index(), Some(3));
+    }
+
+    #[test]
+    fn test_continued_field_from_host_through_controller() {
+        use tn5250r::controller::TerminalController;
+        use tn5250r::lib5250::codes::*;
+
+        // Two 4-character segments of a word-wrapped continued field, a plain field, then
+        // fields with the FFW adjust values 5 (right-adjust zero fill), 6 (right-adjust
+        // blank fill) and 7 (mandatory fill)
+        let mut data = vec![0x04, CMD_CLEAR_UNIT, 0x04, CMD_WRITE_TO_DISPLAY, 0x00, 0x00];
+        data.extend_from_slice(&[SBA, 2, 5, SF, 0x40, 0x00, FCW_CONTINUOUS_FIELD, CONTINUED_FIRST, FCW_WORD_WRAP, 0x01, 0x20, 0x00, 0x04]);
+        data.extend_from_slice(&[SBA, 3, 5, SF, 0x40, 0x00, FCW_CONTINUOUS_FIELD, CONTINUED_LAST, FCW_WORD_WRAP, 0x01, 0x20, 0x00, 0x04]);
+        data.extend_from_slice(&[SBA, 5, 5, SF, 0x40, 0x00, 0x20, 0x00, 0x05]);
+        data.extend_from_slice(&[SBA, 7, 5, SF, 0x40, 0x05, 0x20, 0x00, 0x05]);
+        data.extend_from_slice(&[SBA, 9, 5, SF, 0x40, 0x06, 0x20, 0x00, 0x05]);
+        data.extend_from_slice(&[SBA, 11, 5, SF, 0x40, 0x07, 0x20, 0x00, 0x05]);
+        data.extend_from_slice(&[IC, 2, 6]);
+
+        let mut controller = TerminalController::new();
+        controller.process_host_data(&data);
+        let fields = controller.get_fields_info();
+        assert_eq!(fields.len(), 6);
+        assert!(fields[0].is_active, "the host placed the cursor in the first segment");
+        assert_eq!(controller.ui_cursor_position(), (2, 6));
+
+        for ch in "AB CDE".chars() {
+            controller.type_char(ch).unwrap();
+        }
+        let fields = controller.get_fields_info();
+        assert_eq!(fields[0].content, "AB");
+        assert_eq!(fields[1].content, "CDE");
+        assert!(fields[1].is_active, "typing flowed into the second segment");
+        assert!(controller.get_terminal_content().lines().nth(2).unwrap().contains("CDE"));
+
+        // Field Exit right-adjusts with zeros, then with blanks
+        for (row, index, adjusted) in [(7, 3, "00042"), (9, 4, "   42")] {
+            assert!(controller.activate_field_at_position(row, 6));
+            controller.type_char('4').unwrap();
+            controller.type_char('2').unwrap();
+            controller.field_exit().unwrap();
+            assert_eq!(controller.get_fields_info()[index].content, adjusted);
+            assert!(controller.get_terminal_content().lines().nth(row - 1).unwrap().contains(adjusted));
+        }
+
+        // A mandatory fill field is not adjusted and cannot be left partly filled
+        assert!(controller.activate_field_at_position(11, 6));
+        controller.type_char('4').unwrap();
+        controller.type_char('2').unwrap();
+        assert!(controller.field_exit().is_err());
+        assert!(controller.get_fields_info()[5].is_active, "the cursor stays in the partly filled field");
+        assert_eq!(controller.get_fields_info()[5].content, "42");
+        for ch in "345".chars() {
+            controller.type_char(ch).unwrap();
+        }
+        controller.field_exit().unwrap();
+        assert_eq!(controller.get_fields_info()[5].content, "42345");
+    }
+}
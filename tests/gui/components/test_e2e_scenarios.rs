@@ -19,10 +19,10 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.host = "test.as400.com".to_string();
-            app_lock.port = 23;
-            app_lock.username = "TESTUSER".to_string();
-            app_lock.password = "testpass".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.address = "test.as400.com:23".to_string();
+            session.profile.username = Some("TESTUSER".to_string());
+            session.profile.password = Some("testpass".to_string());
         }
 
         // Click connect
@@ -36,9 +36,10 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.connecting = false;
-            app_lock.connected = true;
-            app_lock.terminal_content = "Sign On\n\nUser  . . . . . . . . . . . . ________\n\nPassword  . . . . . . . . . . . . ________\n\nProgram/procedure . . . . . . . . ________\n\nMenu . . . . . . . . . . . . . . . ________\n\nCurrent library  . . . . . . . . . ________".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.connecting = false;
+            session.connected = true;
+            session.terminal_content = "Sign On\n\nUser  . . . . . . . . . . . . ________\n\nPassword  . . . . . . . . . . . . ________\n\nProgram/procedure . . . . . . . . ________\n\nMenu . . . . . . . . . . . . . . . ________\n\nCurrent library  . . . . . . . . . ________".to_string();
         }
 
         // Should show connected state and signon screen
@@ -59,8 +60,8 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.host = "invalid.host".to_string();
-            app_lock.port = 9999;
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.address = "invalid.host:9999".to_string();
         }
 
         // Attempt connection
@@ -74,8 +75,9 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.connecting = false;
-            app_lock.connected = false;
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.connecting = false;
+            session.connected = false;
             app_lock.error_message = Some("Connection failed: Connection refused".to_string());
         }
 
@@ -96,8 +98,9 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.connected = true;
-            app_lock.terminal_content = "MAIN MENU\n\nSelect one of the following:\n\n1. User tasks\n2. Office tasks\n3. General system tasks\n4. Files\n5. Commands\n6. Communications\n7. Define or change your job\n8. Information assistance\n9. Problem handling\n10. Sign off\n\nSelection or command\n===> ________\n\nF3=Exit   F4=Prompt   F9=Retrieve   F12=Cancel".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.connected = true;
+            session.terminal_content = "MAIN MENU\n\nSelect one of the following:\n\n1. User tasks\n2. Office tasks\n3. General system tasks\n4. Files\n5. Commands\n6. Communications\n7. Define or change your job\n8. Information assistance\n9. Problem handling\n10. Sign off\n\nSelection or command\n===> ________\n\nF3=Exit   F4=Prompt   F9=Retrieve   F12=Cancel".to_string();
         }
 
         harness.step();
@@ -112,7 +115,8 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.terminal_content = "USER TASKS MENU\n\nSelect one of the following:\n\n1. Change password\n2. Work with job\n3. Work with messages\n4. Work with output\n5. Work with spooled files\n\nSelection or command\n===> 1________\n\nF3=Exit   F12=Cancel".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.terminal_content = "USER TASKS MENU\n\nSelect one of the following:\n\n1. Change password\n2. Work with job\n3. Work with messages\n4. Work with output\n5. Work with spooled files\n\nSelection or command\n===> 1________\n\nF3=Exit   F12=Cancel".to_string();
         }
 
         harness.step();
@@ -130,9 +134,10 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.connected = true;
             app_lock.function_keys_visible = true;
-            app_lock.terminal_content = "WORK WITH SPOOLED FILES\n\nNo spooled files to show.\n\nF3=Exit   F5=Refresh   F12=Cancel".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.connected = true;
+            session.terminal_content = "WORK WITH SPOOLED FILES\n\nNo spooled files to show.\n\nF3=Exit   F5=Refresh   F12=Cancel".to_string();
         }
 
         harness.step();
@@ -145,7 +150,8 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.terminal_content = "MAIN MENU\n\nSelect one of the following:\n\n1. User tasks\n...\n\nSelection or command\n===> ________".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.terminal_content = "MAIN MENU\n\nSelect one of the following:\n\n1. User tasks\n...\n\nSelection or command\n===> ________".to_string();
         }
 
         harness.step();
@@ -162,8 +168,9 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.connected = true;
-            app_lock.terminal_content = "Sign On\n\nUser  . . . . . . . . . . . . ________\n\nPassword  . . . . . . . . . . . . ________\n\nProgram/procedure . . . . . . . . ________".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.connected = true;
+            session.terminal_content = "Sign On\n\nUser  . . . . . . . . . . . . ________\n\nPassword  . . . . . . . . . . . . ________\n\nProgram/procedure . . . . . . . . ________".to_string();
         }
 
         harness.step();
@@ -186,7 +193,8 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.terminal_content = "Welcome to AS/400\n\nMAIN MENU\n\nSelect one of the following:\n...".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.terminal_content = "Welcome to AS/400\n\nMAIN MENU\n\nSelect one of the following:\n...".to_string();
         }
 
         harness.step();
@@ -204,8 +212,9 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.connected = true;
-            app_lock.terminal_content = "MAIN MENU\n\nSelection or command\n===> ________".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.connected = true;
+            session.terminal_content = "MAIN MENU\n\nSelection or command\n===> ________".to_string();
         }
 
         harness.step();
@@ -219,8 +228,9 @@ mod e2e_tests {
         {
             let app = harness.app();
             let mut app_lock = app.lock().unwrap();
-            app_lock.connected = false;
-            app_lock.terminal_content = "Disconnected from host.".to_string();
+            let session = app_lock.get_active_session_mut().unwrap();
+            session.connected = false;
+            session.terminal_content = "Disconnected from host.".to_string();
         }
 
         harness.step();
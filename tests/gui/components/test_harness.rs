@@ -64,16 +64,23 @@ impl<'a> TN5250RHarness<'a> {
     /// Check if text is present in the UI by examining the app state
     pub fn has_text(&self, text: &str) -> bool {
         let app = self.app.lock().unwrap();
+        if app.error_message.as_deref().is_some_and(|error| error.contains(text)) {
+            return true;
+        }
+        let Some(session) = app.get_active_session() else {
+            return false;
+        };
 
         // Check various places where text might appear
-        app.terminal_content.contains(text) ||
-        app.connection_string.contains(text) ||
-        app.host.contains(text) ||
-        app.username.contains(text) ||
-        app.input_buffer.contains(text) ||
-        (app.connected && "Connected".contains(text)) ||
-        (!app.connected && "Disconnected".contains(text)) ||
-        (app.connecting && "Connecting".contains(text))
+        session.terminal_content.contains(text) ||
+        session.address.contains(text) ||
+        session.profile.host.contains(text) ||
+        session.profile.username.as_deref().unwrap_or_default().contains(text) ||
+        session.input_buffer.contains(text) ||
+        session.error_message.as_deref().is_some_and(|error| error.contains(text)) ||
+        (session.connected && "Connected".contains(text)) ||
+        (!session.connected && "Disconnected".contains(text)) ||
+        (session.connecting && "Connecting".contains(text))
     }
 
     /// Click on an element containing specific text
@@ -89,17 +96,21 @@ impl<'a> TN5250RHarness<'a> {
                 app.do_disconnect();
             },
             "Cancel" => {
-                app.controller.cancel_connect();
-                app.connecting = false;
-                app.connection_time = None;
+                if let Some(session) = app.get_active_session_mut() {
+                    session.controller.cancel_connect();
+                    session.connecting = false;
+                    session.connection_time = None;
+                }
             },
             "Send" => {
-                if !app.input_buffer.is_empty() {
-                    let input = app.input_buffer.clone();
-                    if let Err(e) = app.controller.send_input(input.as_bytes()) {
-                        app.terminal_content.push_str(&format!("\nError: {}", e));
+                if let Some(session) = app.get_active_session_mut() {
+                    if !session.input_buffer.is_empty() {
+                        let input = session.input_buffer.clone();
+                        if let Err(e) = session.send_input(&input) {
+                            session.terminal_content.push_str(&format!("\nError: {}", e));
+                        }
+                        session.input_buffer.clear();
                     }
-                    app.input_buffer.clear();
                 }
             },
             _ => {
@@ -122,7 +133,9 @@ impl<'a> TN5250RHarness<'a> {
 
         // For simplicity, assume we're typing into the input buffer
         // In a more sophisticated implementation, we'd track which field has focus
-        app.input_buffer.push_str(text);
+        if let Some(session) = app.get_active_session_mut() {
+            session.input_buffer.push_str(text);
+        }
 
         drop(app);
         self.step();
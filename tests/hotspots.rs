@@ -0,0 +1,129 @@
+use tn5250r::hotspots::{HotspotAction, HotspotMatcher, HotspotRule, HotspotSettings};
+use tn5250r::keyboard::FunctionKey;
+use tn5250r::keymap::{KeyAction, KeyChord, Keymap};
+use tn5250r::screen_grid::CellGrid;
+use tn5250r::session::Session;
+use tn5250r::session_profile::SessionProfile;
+
+const WORK_WITH_MEMBERS: &str = "\
+                        Work with Members Using PDM
+ Type options, press Enter.
+  2=Edit         3=Copy         4=Delete
+ Opt  Member      Type
+      QCLSRC      CLP
+                                                                    More...
+ See https://www.ibm.com/docs for help.
+ F3=Exit   F5=Refresh   F12=Cancel F13=Repeat all F24=More keys
+";
+
+fn screen(text: &str) -> CellGrid {
+    CellGrid::from_text(text, (5, 2))
+}
+
+/// (row, column, text, action) of each hotspot found
+fn found(matcher: &HotspotMatcher, grid: &CellGrid) -> Vec<(usize, usize, String, HotspotAction)> {
+    matcher.find(grid).into_iter().map(|h| (h.row, h.col, h.text, h.action)).collect()
+}
+
+fn key(action: KeyAction) -> HotspotAction {
+    HotspotAction::Key(action)
+}
+
+#[test]
+fn builtin_patterns_find_screen_hints() {
+    let matcher = HotspotMatcher::new(&HotspotSettings::default());
+    let mut hotspots = found(&matcher, &screen(WORK_WITH_MEMBERS));
+    hotspots.sort_by_key(|(row, col, ..)| (*row, *col));
+
+    let expected = [
+        (3, 3, "2=Edit", key(KeyAction::Macro("2".to_string()))),
+        (3, 18, "3=Copy", key(KeyAction::Macro("3".to_string()))),
+        (3, 33, "4=Delete", key(KeyAction::Macro("4".to_string()))),
+        (6, 69, "More...", key(KeyAction::Aid(FunctionKey::RollUp))),
+        (7, 6, "https://www.ibm.com/docs", HotspotAction::OpenUrl("https://www.ibm.com/docs".to_string())),
+        (8, 2, "F3=Exit", key(KeyAction::Aid(FunctionKey::F3))),
+        (8, 12, "F5=Refresh", key(KeyAction::Aid(FunctionKey::F5))),
+        (8, 25, "F12=Cancel", key(KeyAction::Aid(FunctionKey::F12))),
+        (8, 36, "F13=Repeat all", key(KeyAction::Aid(FunctionKey::F13))),
+        (8, 51, "F24=More keys", key(KeyAction::Aid(FunctionKey::F24))),
+    ];
+    let expected: Vec<_> = expected.into_iter().map(|(row, col, text, action)| (row, col, text.to_string(), action)).collect();
+    assert_eq!(hotspots, expected);
+}
+
+#[test]
+fn bottom_rolls_back_and_unknown_keys_are_ignored() {
+    let matcher = HotspotMatcher::new(&HotspotSettings::default());
+    let hotspots = found(&matcher, &screen("  F25=Nothing                Bottom"));
+    assert_eq!(hotspots, vec![(1, 30, "Bottom".to_string(), key(KeyAction::Aid(FunctionKey::RollDown)))]);
+}
+
+#[test]
+fn hidden_text_is_never_clickable() {
+    let mut grid = screen(" Password: F3=Exit");
+    for cell in &mut grid.cells[11..18] {
+        cell.style.hidden = true;
+    }
+    assert!(HotspotMatcher::new(&HotspotSettings::default()).find(&grid).is_empty());
+}
+
+#[test]
+fn profile_patterns_come_first_and_use_capture_groups() {
+    let settings = HotspotSettings {
+        rules: vec![
+            HotspotRule::new(r"Menu (\w+)", "GO $1[enter]"),
+            HotspotRule::new(r"F3=\w+", "[pf12]"),
+            HotspotRule::new(r"(unclosed", "[enter]"),
+        ],
+        ..HotspotSettings::default()
+    };
+    let matcher = HotspotMatcher::new(&settings);
+    let mut hotspots = found(&matcher, &screen(" Menu MAIN   F3=Exit   F5=Refresh"));
+    hotspots.sort_by_key(|(row, col, ..)| (*row, *col));
+    assert_eq!(
+        hotspots,
+        vec![
+            (1, 2, "Menu MAIN".to_string(), key(KeyAction::Macro("GO MAIN[enter]".to_string()))),
+            (1, 14, "F3=Exit".to_string(), key(KeyAction::Aid(FunctionKey::F12))),
+            (1, 24, "F5=Refresh".to_string(), key(KeyAction::Aid(FunctionKey::F5))),
+        ]
+    );
+
+    let own_only = HotspotSettings { builtin: false, ..settings.clone() };
+    assert_eq!(HotspotMatcher::new(&own_only).find(&screen(" F5=Refresh")), vec![]);
+    let disabled = HotspotSettings { enabled: false, ..settings };
+    assert_eq!(HotspotMatcher::new(&disabled).find(&screen(" Menu MAIN")), vec![]);
+}
+
+#[test]
+fn rules_are_validated() {
+    assert!(HotspotRule::new(r"(\d+)=Select", "$1[enter]").validate().is_ok());
+    assert!(HotspotRule::new(r"F(\d+)=", "[pf$1]").validate().is_ok());
+    assert!(HotspotRule::new(r"Help", "url:https://example.com/help").validate().is_ok());
+    assert!(HotspotRule::new(r"(unclosed", "[enter]").validate().unwrap_err().contains("pattern"));
+    assert!(HotspotRule::new(r"Help", "").validate().unwrap_err().contains("action"));
+    assert!(HotspotRule::new(r"Help", "[nosuchkey]").validate().is_err());
+}
+
+#[test]
+fn roll_keys_are_bound_to_page_up_and_down() {
+    let keymap = Keymap::acs_default();
+    assert_eq!(keymap.action(&KeyChord::new("PageDown")), Some(&KeyAction::Aid(FunctionKey::RollUp)));
+    assert_eq!(keymap.action(&KeyChord::new("PageUp")), Some(&KeyAction::Aid(FunctionKey::RollDown)));
+    assert_eq!(KeyAction::Aid(FunctionKey::RollUp).mnemonic(), "[pagedn]");
+    assert_eq!(KeyAction::from_mnemonic("[rolldown]"), Some(KeyAction::Aid(FunctionKey::RollDown)));
+}
+
+#[test]
+fn sessions_find_hotspots_on_the_screen_shown() {
+    let mut session = Session::new(SessionProfile::new("PDM".to_string(), "pdm.example.com".to_string(), 23));
+    session.terminal_content = WORK_WITH_MEMBERS.to_string();
+    session.refresh_hotspots();
+    assert_eq!(session.hotspot_at(8, 14).map(|h| h.text.as_str()), Some("F5=Refresh"));
+    assert!(session.hotspot_at(8, 11).is_none());
+
+    // A profile without hotspots
+    session.profile.hotspots.enabled = false;
+    session.load_hotspot_rules();
+    assert!(session.hotspots.is_empty());
+}
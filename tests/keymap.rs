@@ -0,0 +1,172 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tn5250r::controller::AsyncTerminalController;
+use tn5250r::keyboard::{header_flag_record, FunctionKey, RECORD_FLAG_ATN, RECORD_FLAG_SRQ};
+use tn5250r::keymap::{macro_steps, KeyAction, KeyChord, Keymap, MacroStep, PAUSE, RIGHT_CTRL};
+
+#[test]
+fn default_layout_follows_access_client_solutions() {
+    let keymap = Keymap::default();
+    assert_eq!(keymap.action(&KeyChord::new("Enter").with_ctrl()), Some(&KeyAction::Aid(FunctionKey::Enter)));
+    assert_eq!(keymap.action(&KeyChord::new("Enter")), Some(&KeyAction::FieldExit));
+    assert_eq!(keymap.action(&KeyChord::new("Escape")), Some(&KeyAction::Attn));
+    assert_eq!(keymap.action(&KeyChord::new("Escape").with_shift()), Some(&KeyAction::SysReq));
+    assert_eq!(keymap.action(&KeyChord::new("R").with_ctrl()), Some(&KeyAction::Reset));
+    assert!(keymap.unreachable().is_empty(), "every default binding can be pressed");
+    assert_eq!(keymap.action(&KeyChord::new("F3").with_shift()), Some(&KeyAction::Aid(FunctionKey::F15)));
+    assert_eq!(keymap.action(&KeyChord::new("F24")), Some(&KeyAction::Aid(FunctionKey::F24)));
+    assert_eq!(keymap.action(&KeyChord::new("A")), None);
+}
+
+#[test]
+fn chords_parse_and_print_with_modifiers() {
+    let chord = KeyChord::parse("shift+ctrl+f1").unwrap();
+    assert_eq!(chord, KeyChord::new("F1").with_ctrl().with_shift());
+    assert_eq!(chord.to_string(), "Ctrl+Shift+F1");
+    assert_eq!(KeyChord::parse("Shift+Esc").unwrap(), KeyChord::new("Escape").with_shift());
+    assert_eq!(KeyChord::parse("RCtrl").unwrap().key, RIGHT_CTRL);
+    assert!(!KeyChord::new(RIGHT_CTRL).is_reachable());
+    assert!(!KeyChord::new(PAUSE).is_reachable());
+    assert!(KeyChord::new("Escape").is_reachable());
+    assert!(KeyChord::parse("Hyper+F1").is_err());
+    assert!(KeyChord::parse("Ctrl+NoSuchKey").is_err());
+}
+
+#[test]
+fn mnemonics_and_macros() {
+    assert_eq!(KeyAction::parse("[PF12]").unwrap(), KeyAction::Aid(FunctionKey::F12));
+    assert_eq!(KeyAction::parse("[fldext]").unwrap(), KeyAction::FieldExit);
+    assert_eq!(KeyAction::Aid(FunctionKey::F7).mnemonic(), "[pf7]");
+    assert!(KeyAction::parse("[pf25]").is_err());
+    assert!(KeyAction::parse("CALL [nosuchkey]").is_err());
+
+    let action = KeyAction::parse("WRKSPLF[enter]").unwrap();
+    assert_eq!(action, KeyAction::Macro("WRKSPLF[enter]".to_string()));
+    assert_eq!(
+        macro_steps("[[1][tab]X").unwrap(),
+        [
+            MacroStep::Text("[1]".to_string()),
+            MacroStep::Action(KeyAction::NextField),
+            MacroStep::Text("X".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn keymap_file_round_trips() {
+    let mut keymap = Keymap::acs_default();
+    keymap.bind(KeyChord::new("F5").with_ctrl(), KeyAction::Macro("DSPMSG[enter]".to_string()));
+    keymap.unbind(&KeyChord::new(PAUSE));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("keymaps").join("ops.json");
+    keymap.save(&path).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(text.contains("\"Ctrl+F5\""), "{text}");
+    assert!(text.contains("\"DSPMSG[enter]\""), "{text}");
+
+    let loaded = Keymap::for_profile(Some(path.to_str().unwrap())).unwrap();
+    assert_eq!(loaded, keymap);
+    assert_eq!(loaded.action(&KeyChord::new(PAUSE)), None);
+    assert_eq!(Keymap::for_profile(None).unwrap(), Keymap::default());
+    assert!(Keymap::for_profile(Some("/nonexistent/keymap.json")).is_err());
+}
+
+#[test]
+fn acs_keyboard_file_is_imported_over_the_defaults() {
+    let kmp = "\
+[KeyRemap]
+# Java key codes: 10 = Enter, 19 = Pause, 17,0,3 = right Ctrl
+10=[enter]
+17,0,3=[fldext]
+115,2=[pf16]
+Ctrl+Shift+S=SIGNOFF[enter]
+19=
+F9=[nosuchkey]
+garbage line
+";
+    let import = Keymap::import_kmp(kmp);
+    let keymap = &import.keymap;
+    assert_eq!(keymap.action(&KeyChord::new("Enter")), Some(&KeyAction::Aid(FunctionKey::Enter)));
+    assert_eq!(keymap.action(&KeyChord::new(RIGHT_CTRL)), Some(&KeyAction::FieldExit));
+    assert_eq!(keymap.action(&KeyChord::new("F4").with_ctrl()), Some(&KeyAction::Aid(FunctionKey::F16)));
+    assert_eq!(
+        keymap.action(&KeyChord::new("S").with_ctrl().with_shift()),
+        Some(&KeyAction::Macro("SIGNOFF[enter]".to_string()))
+    );
+    assert_eq!(keymap.action(&KeyChord::new(PAUSE)), None, "an empty function unbinds the key");
+    assert_eq!(keymap.action(&KeyChord::new("F9")), Some(&KeyAction::Aid(FunctionKey::F9)), "defaults kept");
+    assert_eq!(import.skipped.len(), 2, "{:?}", import.skipped);
+    assert_eq!(import.unreachable.len(), 1, "{:?}", import.unreachable);
+    assert!(import.unreachable[0].starts_with("17,0,3=[fldext]"));
+    assert_eq!(keymap.unreachable(), vec![KeyChord::new(RIGHT_CTRL)]);
+}
+
+/// Host that completes negotiation with a TN3270E BIND and reports everything the client sends afterwards
+fn recording_host() -> (u16, mpsc::Receiver<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut tcp, _) = listener.accept().unwrap();
+        let mut offer = vec![255, 253, 0, 255, 251, 0, 255, 253, 3, 255, 251, 3, 255, 253, 40];
+        offer.extend_from_slice(&[255, 250, 40, 6, b'L', b'U', b'0', b'1', 0, 255, 240]);
+        tcp.write_all(&offer).unwrap();
+        let mut buf = [0u8; 1024];
+        while let Ok(n) = tcp.read(&mut buf) {
+            if n == 0 || tx.send(buf[..n].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    (port, rx)
+}
+
+fn wait_until(within: Duration, mut done: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + within;
+    while Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    false
+}
+
+#[test]
+fn attn_and_sysreq_send_header_flag_records() {
+    assert_eq!(header_flag_record(RECORD_FLAG_ATN)[7], 0x40);
+
+    let (port, received) = recording_host();
+    let mut controller = AsyncTerminalController::new();
+    controller.connect_async_with_tls("127.0.0.1".to_string(), port, Some(false)).unwrap();
+    assert!(wait_until(Duration::from_secs(10), || controller.is_connected()));
+
+    // Let negotiation replies drain before looking for the records
+    thread::sleep(Duration::from_millis(500));
+    while received.try_recv().is_ok() {}
+
+    let send = |action: KeyAction| {
+        assert!(wait_until(Duration::from_secs(2), || action.perform(&controller).is_ok()), "{action:?}");
+    };
+    let mut sent = Vec::new();
+    let mut wait_for = |record: Vec<u8>| {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !sent.windows(record.len()).any(|w: &[u8]| w == record.as_slice()) && Instant::now() < deadline {
+            if let Ok(bytes) = received.recv_timeout(Duration::from_millis(100)) {
+                sent.extend(bytes);
+            }
+        }
+        assert!(sent.windows(record.len()).any(|w| w == record.as_slice()), "{sent:02x?}");
+    };
+
+    send(KeyAction::Attn);
+    wait_for(header_flag_record(RECORD_FLAG_ATN));
+    send(KeyAction::SysReq);
+    wait_for(header_flag_record(RECORD_FLAG_SRQ));
+    controller.disconnect();
+}
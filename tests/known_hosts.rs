@@ -0,0 +1,121 @@
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::{ServerConfig, ServerConnection};
+use tempfile::TempDir;
+use tn5250r::error::NetworkError;
+use tn5250r::known_hosts::{self, CertificateInfo, CertificatePrompt, KnownHosts};
+use tn5250r::network::AS400Connection;
+
+/// Self-signed localhost certificate served for `connections` handshakes
+fn serve_self_signed(connections: usize) -> (u16, Vec<u8>) {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let der = cert.serialize_der().unwrap();
+    let config = Arc::new(
+        ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(der.clone())],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(cert.serialize_private_key_der())),
+            )
+            .unwrap(),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    thread::spawn(move || {
+        for _ in 0..connections {
+            let Ok((mut tcp, _)) = listener.accept() else { return };
+            let mut conn = ServerConnection::new(Arc::clone(&config)).unwrap();
+            while conn.is_handshaking() {
+                if conn.complete_io(&mut tcp).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    (port, der)
+}
+
+fn connection(port: u16, store: &PathBuf) -> AS400Connection {
+    let mut conn = AS400Connection::new("localhost".to_string(), port);
+    conn.set_tls(true);
+    conn.set_known_hosts_path(store);
+    conn
+}
+
+fn untrusted(result: Result<(), NetworkError>) -> CertificatePrompt {
+    let error = result.expect_err("certificate should not be trusted yet");
+    CertificatePrompt::from_error(&error).unwrap_or_else(|| panic!("expected certificate prompt, got {error:?}"))
+}
+
+#[test]
+fn first_connection_reports_fingerprint_subject_and_expiry() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("known_hosts");
+    let (port, der) = serve_self_signed(1);
+
+    let prompt = untrusted(connection(port, &store).verify_tls());
+
+    assert!(!prompt.is_change());
+    let certificate = prompt.certificate;
+    assert_eq!(certificate.host, format!("localhost:{port}"));
+    assert_eq!(certificate.fingerprint, known_hosts::fingerprint(&der));
+    assert!(certificate.subject.contains("rcgen"));
+    assert!(certificate.not_after.contains("4096"));
+}
+
+#[test]
+fn permanently_accepted_certificate_is_stored_and_trusted() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("tn5250r").join("known_hosts");
+    let (port, _) = serve_self_signed(2);
+
+    let prompt = untrusted(connection(port, &store).verify_tls());
+    KnownHosts::load(&store).unwrap().trust(&prompt.certificate).unwrap();
+
+    let reloaded = KnownHosts::load(&store).unwrap();
+    assert_eq!(reloaded.fingerprint(&prompt.certificate.host), Some(prompt.certificate.fingerprint.as_str()));
+    connection(port, &store).verify_tls().expect("stored certificate is trusted");
+}
+
+#[test]
+fn certificate_accepted_once_is_not_stored() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("known_hosts");
+    let (port, _) = serve_self_signed(2);
+
+    let prompt = untrusted(connection(port, &store).verify_tls());
+    known_hosts::trust_once(&prompt.certificate);
+
+    connection(port, &store).verify_tls().expect("certificate trusted for this run");
+    assert_eq!(KnownHosts::load(&store).unwrap().fingerprint(&prompt.certificate.host), None);
+}
+
+#[test]
+fn changed_certificate_is_flagged() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("known_hosts");
+    let (port, der) = serve_self_signed(1);
+
+    let previous = CertificateInfo {
+        host: format!("localhost:{port}"),
+        fingerprint: known_hosts::fingerprint(b"previous certificate"),
+        subject: "CN=old".to_string(),
+        issuer: "CN=old".to_string(),
+        not_after: String::new(),
+        reason: String::new(),
+    };
+    KnownHosts::load(&store).unwrap().trust(&previous).unwrap();
+
+    match connection(port, &store).verify_tls() {
+        Err(NetworkError::CertificateChanged { known_fingerprint, certificate }) => {
+            assert_eq!(known_fingerprint, previous.fingerprint);
+            assert_eq!(certificate.fingerprint, known_hosts::fingerprint(&der));
+        }
+        other => panic!("expected certificate change, got {other:?}"),
+    }
+}
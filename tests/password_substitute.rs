@@ -0,0 +1,110 @@
+use tn5250r::password_substitute::*;
+use tn5250r::telnet_negotiation::TelnetNegotiator;
+
+// RFC 4777 Section 5 publishes a single worked example: the DES substitute for
+// USER123/ABCDEFG with these seeds. It has no SHA-1 example (the SHA-1 substitute
+// is described in IBM's documentation, not the RFC) and none for 10-character
+// user IDs or passwords, so those expected values are not from the RFC.
+const SERVER_SEED: [u8; 8] = [0x7D, 0x4C, 0x23, 0x19, 0xF2, 0x80, 0x04, 0xB2];
+const CLIENT_SEED: [u8; 8] = [0x08, 0xBE, 0xF6, 0x62, 0xD8, 0x51, 0xF4, 0xB1];
+
+/// Value following `name` VALUE in a NEW-ENVIRON response, with ESC quoting and IAC doubling removed
+fn env_value(response: &[u8], name: &[u8]) -> Vec<u8> {
+    let start = response
+        .windows(name.len() + 1)
+        .position(|w| w[..name.len()] == *name && w[name.len()] == 1)
+        .expect("variable present")
+        + name.len()
+        + 1;
+    let mut value = Vec::new();
+    let mut i = start;
+    while i < response.len() {
+        match response[i] {
+            2 => {
+                value.push(response[i + 1]);
+                i += 2;
+            }
+            0xFF if response.get(i + 1) == Some(&0xFF) => {
+                value.push(0xFF);
+                i += 2;
+            }
+            0 | 3 | 0xFF => break,
+            byte => {
+                value.push(byte);
+                i += 1;
+            }
+        }
+    }
+    value
+}
+
+#[test]
+fn des_substitute_matches_rfc_4777_example() {
+    let substitute = des_substitute("USER123", "ABCDEFG", &SERVER_SEED, &CLIENT_SEED).unwrap();
+    assert_eq!(substitute, [0x5A, 0x58, 0xBD, 0x50, 0xE4, 0xDD, 0x9B, 0x5F]);
+
+    // Case-insensitive at QPWDLVL 0/1
+    assert_eq!(des_substitute("user123", "abcdefg", &SERVER_SEED, &CLIENT_SEED).unwrap(), substitute);
+}
+
+#[test]
+fn des_substitute_handles_ten_character_user_and_password() {
+    // Not in the RFC: regression values of this implementation, whose DES path reproduces the RFC example
+    let long_password = des_substitute("USER123", "ABCDEFGHIJ", &SERVER_SEED, &CLIENT_SEED).unwrap();
+    assert_eq!(long_password, [0x2B, 0xB7, 0xA1, 0x74, 0xF8, 0x08, 0x29, 0xFA]);
+
+    let long_user = des_substitute("LONGUSERID", "PASSWORD", &SERVER_SEED, &CLIENT_SEED).unwrap();
+    assert_eq!(long_user, [0x5B, 0x6A, 0x77, 0x6B, 0x91, 0x5A, 0x4E, 0xC3]);
+
+    assert!(des_substitute("USER123", "ABCDEFGHIJK", &SERVER_SEED, &CLIENT_SEED).is_err());
+    assert!(des_substitute("", "ABCDEFG", &SERVER_SEED, &CLIENT_SEED).is_err());
+}
+
+#[test]
+fn sha1_substitute_is_case_sensitive() {
+    // Not in the RFC: cross-checked with a separate implementation of the algorithm using Python's hashlib
+    let substitute = sha1_substitute("USER123", "ABCDEFG", &SERVER_SEED, &CLIENT_SEED).unwrap();
+    assert_eq!(
+        substitute,
+        [
+            0x1D, 0xC3, 0xFE, 0x2F, 0xD9, 0x99, 0xAA, 0xC3, 0x21, 0xFA, 0x46, 0xE3, 0xDE, 0xF5, 0xED, 0x66, 0xC5,
+            0x43, 0x91, 0x79,
+        ]
+    );
+
+    let mixed = sha1_substitute("user123", "AbCdEfGh123?+", &SERVER_SEED, &CLIENT_SEED).unwrap();
+    assert_eq!(
+        mixed,
+        [
+            0xD4, 0xD3, 0xE9, 0xB6, 0x38, 0x1C, 0xF5, 0x28, 0x90, 0x96, 0xB7, 0xF9, 0xA4, 0x21, 0xA9, 0xB1, 0xD8,
+            0x23, 0x35, 0x97,
+        ]
+    );
+    assert_ne!(sha1_substitute("USER123", "abcdefg", &SERVER_SEED, &CLIENT_SEED).unwrap(), substitute);
+    assert!(sha1_substitute("USER123", &"x".repeat(129), &SERVER_SEED, &CLIENT_SEED).is_err());
+}
+
+#[test]
+fn ibmrseed_request_is_answered_with_encrypted_substitute() {
+    for level in [PasswordLevel::Des, PasswordLevel::Sha1] {
+        let mut negotiator = TelnetNegotiator::new();
+        negotiator.set_credentials("user123", "Secret1");
+        negotiator.set_password_level(level);
+
+        // IAC SB NEW-ENVIRON SEND USERVAR "IBMRSEED" <seed> USERVAR "IBMSUBSPW" IAC SE
+        let mut request = vec![255, 250, 39, 1, 3];
+        request.extend_from_slice(b"IBMRSEED");
+        request.extend_from_slice(&SERVER_SEED);
+        request.push(3);
+        request.extend_from_slice(b"IBMSUBSPW");
+        request.extend_from_slice(&[255, 240]);
+        let response = negotiator.process_incoming_data(&request);
+
+        assert!(!response.windows(7).any(|w| w == b"Secret1"));
+        assert_eq!(env_value(&response, b"USER"), b"USER123");
+
+        let client_seed: [u8; 8] = env_value(&response, b"IBMRSEED").try_into().expect("8-byte client seed");
+        let expected = password_substitute(level, "USER123", "Secret1", &SERVER_SEED, &client_seed).unwrap();
+        assert_eq!(env_value(&response, b"IBMSUBSPW"), expected);
+    }
+}
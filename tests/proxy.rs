@@ -0,0 +1,230 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use base64::Engine;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::{ServerConfig, ServerConnection};
+use tempfile::TempDir;
+use tn5250r::error::NetworkError;
+use tn5250r::known_hosts;
+use tn5250r::network::AS400Connection;
+use tn5250r::network_proxy::{ProxyKind, ProxySettings};
+
+fn listen() -> (TcpListener, u16) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    (listener, port)
+}
+
+/// Copy both directions until each side closes, passing half-closes through
+fn tunnel(client: TcpStream, upstream: TcpStream) {
+    let (mut client_read, mut upstream_write) = (client.try_clone().unwrap(), upstream.try_clone().unwrap());
+    thread::spawn(move || {
+        let _ = std::io::copy(&mut client_read, &mut upstream_write);
+        let _ = upstream_write.shutdown(Shutdown::Write);
+    });
+    let (mut upstream_read, mut client_write) = (upstream, client);
+    let _ = std::io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+}
+
+/// HTTP CONNECT proxy accepting one client with the given basic credentials; reports the request line
+fn http_proxy(credentials: &'static str, target_port: u16) -> (u16, mpsc::Receiver<String>) {
+    let (listener, port) = listen();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (client, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(client.try_clone().unwrap());
+        let mut header = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            header.push(line.trim_end().to_string());
+        }
+        tx.send(header[0].clone()).unwrap();
+
+        let expected = format!(
+            "Proxy-Authorization: Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(credentials)
+        );
+        let mut client = client;
+        if !header.contains(&expected) {
+            let _ = client.write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\nProxy-Authenticate: Basic\r\n\r\n");
+            return;
+        }
+        let upstream = TcpStream::connect(("127.0.0.1", target_port)).unwrap();
+        client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap();
+        tunnel(client, upstream);
+    });
+    (port, rx)
+}
+
+/// SOCKS5 proxy requiring contractor/s3cret; reports the requested domain and port.
+/// Any name is "resolved" to the local target, or refused when there is none.
+fn socks5_proxy(target_port: Option<u16>) -> (u16, mpsc::Receiver<String>) {
+    let (listener, port) = listen();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut client, _) = listener.accept().unwrap();
+        let mut greeting = [0u8; 2];
+        client.read_exact(&mut greeting).unwrap();
+        let mut methods = vec![0u8; greeting[1] as usize];
+        client.read_exact(&mut methods).unwrap();
+        assert!(methods.contains(&0x02), "username/password offered");
+        client.write_all(&[0x05, 0x02]).unwrap();
+
+        let mut auth = [0u8; 2];
+        client.read_exact(&mut auth).unwrap();
+        let mut username = vec![0u8; auth[1] as usize];
+        client.read_exact(&mut username).unwrap();
+        let mut len = [0u8; 1];
+        client.read_exact(&mut len).unwrap();
+        let mut password = vec![0u8; len[0] as usize];
+        client.read_exact(&mut password).unwrap();
+        let accepted = username == b"contractor" && password == b"s3cret";
+        client.write_all(&[0x01, if accepted { 0x00 } else { 0x01 }]).unwrap();
+        if !accepted {
+            return;
+        }
+
+        let mut request = [0u8; 4];
+        client.read_exact(&mut request).unwrap();
+        assert_eq!(request[3], 0x03, "host name sent for remote DNS");
+        client.read_exact(&mut len).unwrap();
+        let mut name = vec![0u8; len[0] as usize + 2];
+        client.read_exact(&mut name).unwrap();
+        let requested_port = u16::from_be_bytes([name[name.len() - 2], name[name.len() - 1]]);
+        tx.send(format!("{}:{requested_port}", String::from_utf8_lossy(&name[..name.len() - 2]))).unwrap();
+
+        match target_port {
+            Some(target_port) => {
+                let upstream = TcpStream::connect(("127.0.0.1", target_port)).unwrap();
+                client.write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0, 0]).unwrap();
+                tunnel(client, upstream);
+            }
+            None => client.write_all(&[0x05, 0x05, 0x00, 0x01, 0, 0, 0, 0, 0, 0]).unwrap(),
+        }
+    });
+    (port, rx)
+}
+
+/// TLS host with a self-signed certificate already trusted in `store` for `name:port`
+fn tls_host(name: &str, store: &Path) -> u16 {
+    let cert = rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap();
+    let der = cert.serialize_der().unwrap();
+    let config = Arc::new(
+        ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(der.clone())],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(cert.serialize_private_key_der())),
+            )
+            .unwrap(),
+    );
+    let (listener, port) = listen();
+    std::fs::write(store, format!("{name}:{port} {}\n", known_hosts::fingerprint(&der))).unwrap();
+    thread::spawn(move || {
+        if let Ok((mut tcp, _)) = listener.accept() {
+            let mut conn = ServerConnection::new(config).unwrap();
+            while conn.is_handshaking() {
+                if conn.complete_io(&mut tcp).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    port
+}
+
+/// Telnet host that offers Binary and SGA, stops sending, and reports what the client sent
+fn telnet_host() -> (u16, mpsc::Receiver<Vec<u8>>) {
+    let (listener, port) = listen();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut tcp, _) = listener.accept().unwrap();
+        tcp.write_all(&[255, 253, 0, 255, 251, 0, 255, 253, 3, 255, 251, 3]).unwrap();
+        tcp.shutdown(Shutdown::Write).unwrap();
+        let mut received = Vec::new();
+        let _ = tcp.read_to_end(&mut received);
+        tx.send(received).unwrap();
+    });
+    (port, rx)
+}
+
+fn proxy(kind: ProxyKind, port: u16, username: &str, password: &str) -> ProxySettings {
+    ProxySettings {
+        kind,
+        host: "127.0.0.1".to_string(),
+        port,
+        username: Some(username.to_string()),
+        password: Some(password.to_string()),
+    }
+}
+
+#[test]
+fn http_connect_proxy_carries_tls_with_basic_auth() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("known_hosts");
+    let host_port = tls_host("localhost", &store);
+    let (proxy_port, requests) = http_proxy("contractor:s3cret", host_port);
+
+    let mut conn = AS400Connection::new("localhost".to_string(), host_port);
+    conn.set_tls(true);
+    conn.set_known_hosts_path(&store);
+    conn.set_proxy(Some(proxy(ProxyKind::Http, proxy_port, "contractor", "s3cret")));
+    conn.verify_tls().expect("TLS through HTTP CONNECT tunnel");
+
+    assert_eq!(requests.recv().unwrap(), format!("CONNECT localhost:{host_port} HTTP/1.1"));
+}
+
+#[test]
+fn http_proxy_rejecting_credentials_is_a_typed_error() {
+    let (proxy_port, _requests) = http_proxy("contractor:s3cret", 1);
+
+    let mut conn = AS400Connection::new("as400.example.com".to_string(), 992);
+    conn.set_proxy(Some(proxy(ProxyKind::Http, proxy_port, "contractor", "wrong")));
+    match conn.verify_tls() {
+        Err(NetworkError::ProxyAuthenticationFailed { proxy }) => assert_eq!(proxy, format!("127.0.0.1:{proxy_port}")),
+        other => panic!("expected proxy authentication failure, got {other:?}"),
+    }
+}
+
+#[test]
+fn socks5_proxy_resolves_host_and_carries_telnet_negotiation() {
+    let (host_port, received) = telnet_host();
+    let (proxy_port, requests) = socks5_proxy(Some(host_port));
+
+    // The name only exists on the proxy's side
+    let mut conn = AS400Connection::new("as400.corp.invalid".to_string(), 23);
+    conn.set_tls(false);
+    conn.set_proxy(Some(proxy(ProxyKind::Socks5, proxy_port, "contractor", "s3cret")));
+    conn.connect().expect("telnet through SOCKS5");
+    conn.disconnect();
+
+    assert_eq!(requests.recv().unwrap(), "as400.corp.invalid:23");
+    let received = received.recv().unwrap();
+    assert!(received.windows(3).any(|w| w == [255, 251, 0]), "WILL BINARY reached the host: {received:?}");
+}
+
+#[test]
+fn socks5_refusal_reports_target() {
+    let (proxy_port, _requests) = socks5_proxy(None);
+
+    let mut conn = AS400Connection::new("as400.corp.invalid".to_string(), 23);
+    conn.set_tls(false);
+    conn.set_proxy(Some(proxy(ProxyKind::Socks5, proxy_port, "contractor", "s3cret")));
+    let err = conn.connect().unwrap_err();
+    match err.get_ref().and_then(|e| e.downcast_ref::<NetworkError>()) {
+        Some(NetworkError::ProxyTargetRefused { target, reason, .. }) => {
+            assert_eq!(target, "as400.corp.invalid:23");
+            assert_eq!(reason, "connection refused");
+        }
+        other => panic!("expected refused target, got {other:?}"),
+    }
+}
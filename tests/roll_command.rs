@@ -0,0 +1,63 @@
+use tn5250r::lib5250::codes::*;
+use tn5250r::lib5250::session::Session;
+
+const ESC: u8 = 0x04;
+
+/// Session with rows 1-6 starting with 'A'..'F'
+fn session_with_rows() -> Session {
+    let mut session = Session::new();
+    session.authenticate("testuser", "testpass").unwrap();
+
+    let mut data = vec![ESC, CMD_CLEAR_UNIT, ESC, CMD_WRITE_TO_DISPLAY, 0x00, 0x00];
+    for (row, ch) in (1..=6).zip(0xC1..=0xC6u8) {
+        data.extend_from_slice(&[SBA, row, 1, ch]);
+    }
+    session.process_stream(&data).expect("initial screen");
+    session
+}
+
+fn first_column(session: &Session, rows: usize) -> String {
+    let lines: Vec<String> = session.display_string().lines().map(str::to_string).collect();
+    lines.iter().take(rows).map(|line| line.chars().next().unwrap_or(' ')).collect()
+}
+
+#[test]
+fn roll_up_moves_rows_within_boundary_and_clears_bottom() {
+    let mut session = session_with_rows();
+
+    // Roll rows 2-5 up by one line
+    session.process_stream(&[ESC, CMD_ROLL, 0x01, 2, 5]).expect("roll up");
+
+    assert_eq!(first_column(&session, 6), "ACDE F");
+}
+
+#[test]
+fn roll_down_moves_rows_within_boundary_and_clears_top() {
+    let mut session = session_with_rows();
+
+    // Roll rows 2-5 down by two lines
+    session.process_stream(&[ESC, CMD_ROLL, 0x82, 2, 5]).expect("roll down");
+
+    assert_eq!(first_column(&session, 6), "A  BCF");
+}
+
+#[test]
+fn roll_without_line_count_uses_host_defined_direction() {
+    let mut session = session_with_rows();
+
+    // Define Roll Direction: down one line
+    session
+        .process_stream(&[ESC, CMD_WRITE_STRUCTURED_FIELD, 0x00, 0x07, 0xD9, SF_DEFINE_ROLL_DIRECTION, 0x81])
+        .expect("define roll direction");
+    session.process_stream(&[ESC, CMD_ROLL, 0x00, 1, 3]).expect("roll");
+
+    assert_eq!(first_column(&session, 6), " ABDEF");
+}
+
+#[test]
+fn roll_rejects_invalid_boundaries() {
+    let mut session = session_with_rows();
+
+    assert!(session.process_stream(&[ESC, CMD_ROLL, 0x01, 0, 5]).is_err());
+    assert!(session.process_stream(&[ESC, CMD_ROLL, 0x01, 5, 2]).is_err());
+}
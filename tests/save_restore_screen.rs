@@ -0,0 +1,103 @@
+use tn5250r::lib5250::codes::*;
+use tn5250r::lib5250::session::Session;
+use tn5250r::terminal::CharAttribute;
+
+const ESC: u8 = 0x04;
+
+fn session_with_screen() -> Session {
+    let mut session = Session::new();
+    session.authenticate("testuser", "testpass").unwrap();
+
+    let mut data = vec![ESC, CMD_CLEAR_UNIT, ESC, CMD_WRITE_TO_DISPLAY, 0x00, 0x02];
+    // "HI" at row 1
+    data.extend_from_slice(&[SBA, 1, 1, 0xC8, 0xC9]);
+    // Mandatory-entry input field at row 5, column 11
+    data.extend_from_slice(&[SBA, 5, 10, SF, 0x40, 0x00, FCW_MANDATORY_ENTRY, 0x01, 0x24, 0x00, 0x0A]);
+    data.extend_from_slice(&[0xC1, 0xC2]);
+    // Output-only field at row 7, column 6
+    data.extend_from_slice(&[SBA, 7, 5, SF, 0x22, 0x00, 0x05]);
+    data.extend_from_slice(&[0xE7, 0xE8, 0xE9]);
+    // High intensity on the "I"
+    data.extend_from_slice(&[SBA, 1, 2, WEA, 0x03, 0x03, 0x01, 0x01]);
+    // Cursor in the input field
+    data.extend_from_slice(&[SBA, 5, 12]);
+    session.process_stream(&data).expect("initial screen");
+
+    session.fields[0].mdt = true;
+    session
+}
+
+fn cells(session: &Session) -> Vec<(char, CharAttribute)> {
+    let screen = session.display().screen_ref();
+    screen.buffer.iter().map(|c| (c.character, c.attribute)).collect()
+}
+
+#[test]
+fn save_and_restore_screen_round_trips_fields_and_attributes() {
+    let mut session = session_with_screen();
+    let fields_before = session.fields.clone();
+    let cells_before = cells(&session);
+    let cursor_before = session.cursor_position();
+
+    let saved = session.process_stream(&[ESC, CMD_SAVE_SCREEN]).expect("save");
+    assert!(!saved.is_empty());
+
+    // Pop a window over the screen with a different format table
+    let mut popup = vec![ESC, CMD_CLEAR_UNIT, ESC, CMD_WRITE_TO_DISPLAY, 0x00, 0x02];
+    popup.extend_from_slice(&[SBA, 3, 3, SF, 0x40, 0x00, 0x20, 0x00, 0x04, 0xD7, 0xD6, 0xD7]);
+    session.process_stream(&popup).expect("popup");
+    assert_ne!(session.fields, fields_before);
+
+    let mut restore = vec![ESC, CMD_RESTORE_SCREEN];
+    restore.extend_from_slice(&saved);
+    session.process_stream(&restore).expect("restore");
+
+    assert_eq!(session.fields, fields_before);
+    assert_eq!(session.fields[0].fcws, vec![(FCW_MANDATORY_ENTRY, 0x01)]);
+    assert!(session.fields[0].mdt);
+    assert_eq!(session.fields[1].ffw, None);
+    assert_eq!(cells(&session), cells_before);
+    assert_eq!(session.display().cell_attribute(0, 1), CharAttribute::HighIntensity);
+    assert_eq!(session.cursor_position(), cursor_before);
+    assert!(!session.display().keyboard_locked());
+}
+
+#[test]
+fn save_and_restore_partial_screen_round_trips_region() {
+    let mut session = session_with_screen();
+    let fields_before = session.fields.clone();
+    let cells_before = cells(&session);
+
+    // Rows 4-8, columns 5-30
+    let saved = session
+        .process_stream(&[ESC, CMD_SAVE_PARTIAL_SCREEN, 0x00, 4, 5, 5, 26])
+        .expect("save partial");
+
+    // Window inside the region with its own field and text
+    let mut popup = vec![ESC, CMD_WRITE_TO_DISPLAY, 0x00, 0x02];
+    popup.extend_from_slice(&[SBA, 5, 8, SF, 0x40, 0x00, 0x20, 0x00, 0x03, 0xE9, 0xE9]);
+    popup.extend_from_slice(&[SBA, 6, 6, WEA, 0x03, 0x04, 0x01, 0x01]);
+    session.process_stream(&popup).expect("popup");
+    assert_eq!(session.fields.len(), 3);
+
+    let mut restore = vec![ESC, CMD_RESTORE_PARTIAL_SCREEN];
+    restore.extend_from_slice(&saved);
+    session.process_stream(&restore).expect("restore partial");
+
+    assert_eq!(session.fields, fields_before);
+    assert_eq!(cells(&session), cells_before);
+}
+
+#[test]
+fn redefining_field_at_same_position_replaces_it() {
+    let mut session = session_with_screen();
+    let count = session.fields.len();
+
+    let data = [ESC, CMD_WRITE_TO_DISPLAY, 0x00, 0x00, SBA, 5, 10, SF, 0x40, 0x00, 0x24, 0x00, 0x08];
+    session.process_stream(&data).expect("redefine field");
+
+    assert_eq!(session.fields.len(), count);
+    let field = session.fields.iter().find(|f| f.row == 4 && f.col == 10).unwrap();
+    assert_eq!(field.length, 8);
+    assert!(field.fcws.is_empty());
+}
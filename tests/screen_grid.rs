@@ -0,0 +1,79 @@
+use tn5250r::lib5250::display::Display;
+use tn5250r::screen_grid::{CellGrid, CellStyle, ScreenColor};
+use tn5250r::terminal::{CharAttribute, TerminalChar, TerminalScreen, TextDirection};
+
+/// "AB" in code page 37
+const AB: [u8; 2] = [0xC1, 0xC2];
+
+#[test]
+fn attribute_bytes_map_to_5250_colors() {
+    assert_eq!(CellStyle::from_attribute_byte(0x20), CellStyle::default());
+    let reverse_white = CellStyle::from_attribute_byte(0x23);
+    assert_eq!((reverse_white.color, reverse_white.reverse), (ScreenColor::White, true));
+    let red_blink = CellStyle::from_attribute_byte(0x2A);
+    assert_eq!((red_blink.color, red_blink.blink), (ScreenColor::Red, true));
+    let separators = CellStyle::from_attribute_byte(0x30);
+    assert_eq!((separators.color, separators.column_separator), (ScreenColor::Turquoise, true));
+    assert_eq!(CellStyle::from_attribute_byte(0x3E).color, ScreenColor::Blue);
+    assert!(CellStyle::from_attribute_byte(0x27).hidden);
+    assert!(CellStyle::from_attribute_byte(0x3F).hidden);
+}
+
+#[test]
+fn attribute_runs_style_the_following_cells_across_rows() {
+    let mut display = Display::new();
+    display.set_cursor(0, 78);
+    display.addch(0x24); // Green underscore
+    display.addch(AB[0]);
+    display.addch(AB[1]);
+    display.addch(0x20); // Back to normal
+    display.addch(AB[0]);
+
+    // The attribute byte itself stays a blank position
+    let text = display.screen_ref().to_visual_string();
+    assert_eq!(&text.lines().next().unwrap()[78..], " A");
+    assert_eq!(display.cell_ebcdic(0, 78), 0x24);
+
+    let grid = CellGrid::from_screen(display.screen_ref(), (1, 1));
+    assert_eq!(grid.text(), text);
+    assert!(grid.cell(0, 78).unwrap().attribute);
+    assert!(grid.cell(0, 79).unwrap().style.underline);
+    assert!(grid.cell(1, 0).unwrap().style.underline, "runs continue on the next row");
+    assert_eq!(grid.cell(1, 2).unwrap().style, CellStyle::default());
+}
+
+#[test]
+fn cell_attributes_apply_on_top_of_the_run() {
+    let mut screen = TerminalScreen::new_with_size(132, 27);
+    screen.set_char_at(0, 0, TerminalChar { character: ' ', attribute: CharAttribute::FieldAttribute(0x3A) });
+    screen.set_char_at(1, 0, TerminalChar { character: 'X', attribute: CharAttribute::ReverseVideo });
+    screen.set_char_at(131, 26, TerminalChar { character: 'Z', attribute: CharAttribute::Normal });
+
+    let grid = CellGrid::from_screen(&screen, (27, 132));
+    assert_eq!((grid.width, grid.height), (132, 27));
+    let x = grid.cell(0, 1).unwrap();
+    assert_eq!((x.ch, x.style.color, x.style.reverse), ('X', ScreenColor::Blue, true));
+    assert_eq!(grid.cell(26, 131).unwrap().ch, 'Z');
+    assert_eq!(grid.cell(26, 131).unwrap().style.color, ScreenColor::Blue);
+    assert!(grid.cell(27, 0).is_none());
+}
+
+#[test]
+fn right_to_left_screens_are_mirrored_like_their_text() {
+    let mut screen = TerminalScreen::new_with_size(10, 2);
+    screen.set_char_at(0, 0, TerminalChar { character: ' ', attribute: CharAttribute::FieldAttribute(0x28) });
+    screen.set_char_at(1, 0, TerminalChar { character: 'A', attribute: CharAttribute::Normal });
+    screen.set_direction(TextDirection::RightToLeft);
+
+    let grid = CellGrid::from_screen(&screen, (1, 1));
+    assert_eq!(grid.text(), screen.to_visual_string());
+    let a = grid.cell(0, 8).unwrap();
+    assert_eq!((a.ch, a.style.color), ('A', ScreenColor::Red));
+}
+
+#[test]
+fn plain_text_fills_a_grid_of_at_least_the_standard_size() {
+    let grid = CellGrid::from_text("Connecting to host:23...\n", (1, 1));
+    assert_eq!((grid.width, grid.height), (80, 24));
+    assert_eq!(grid.text().lines().next().unwrap().trim_end(), "Connecting to host:23...");
+}
@@ -0,0 +1,140 @@
+use tn5250r::clipboard::CopyFormat;
+use tn5250r::screen_history::{HistoryMatch, ScreenHistory};
+use tn5250r::session::Session;
+use tn5250r::session_profile::SessionProfile;
+use tn5250r::terminal::{CharAttribute, TerminalChar, TerminalScreen};
+
+/// A screen with a line of text on each of the first rows
+fn screen(lines: &[&str]) -> TerminalScreen {
+    let mut screen = TerminalScreen::new();
+    for (row, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        screen.write_chars_to_row(row, 0, &chars, CharAttribute::Normal);
+    }
+    screen
+}
+
+fn titles(history: &ScreenHistory) -> Vec<String> {
+    history.iter().map(|snapshot| snapshot.title()).collect()
+}
+
+#[test]
+fn host_updates_fill_one_snapshot_until_the_operator_leaves_the_screen() {
+    let mut history = ScreenHistory::default();
+    history.record(&screen(&["Sign On"]), (1, 1));
+    history.record(&screen(&["Sign On", " User . . ."]), (2, 13));
+    // The operator typed a user name and pressed Enter
+    history.seal(&screen(&["Sign On", " User . . . QSECOFR"]), (2, 20));
+    history.seal(&screen(&["Sign On", " User . . . QSECOFR"]), (2, 20));
+    history.record(&screen(&["Main Menu"]), (20, 7));
+
+    assert_eq!(titles(&history), ["Sign On", "Main Menu"]);
+    let sign_on = history.get(0).unwrap();
+    assert_eq!(sign_on.number, 1);
+    assert_eq!(sign_on.cursor, (2, 20));
+    assert!(sign_on.text().contains("QSECOFR"), "the snapshot keeps what was typed");
+    assert_eq!(history.newest().unwrap().number, 2);
+}
+
+#[test]
+fn blank_screens_are_skipped_and_the_oldest_are_dropped() {
+    let mut history = ScreenHistory::new(3);
+    history.record(&TerminalScreen::new(), (1, 1));
+    assert!(history.is_empty(), "a cleared screen is not worth keeping");
+
+    for title in ["One", "Two", "Three", "Four", "Five"] {
+        history.record(&screen(&[title]), (1, 1));
+        history.seal(&screen(&[title]), (1, 1));
+    }
+    assert_eq!(titles(&history), ["Three", "Four", "Five"]);
+    assert_eq!(history.position(3), Some(0));
+    assert!(history.find(2).is_none(), "numbers stay with their screens");
+}
+
+#[test]
+fn copies_of_a_history_share_its_screens() {
+    let mut history = ScreenHistory::default();
+    history.record(&screen(&["Sign On"]), (1, 1));
+    history.seal(&screen(&["Sign On"]), (1, 1));
+    history.record(&screen(&["Main Menu"]), (20, 7));
+
+    let copy = history.clone();
+    assert!(std::ptr::eq(copy.get(0).unwrap(), history.get(0).unwrap()), "a sealed screen is not copied");
+
+    history.record(&screen(&["Main Menu", " 1. User tasks"]), (20, 7));
+    assert_eq!(copy.newest().unwrap().text().lines().nth(1).unwrap().trim(), "", "a copy keeps the screen it was made with");
+}
+
+#[test]
+fn nondisplay_fields_stay_blank_in_search_and_export() {
+    // A password field: nondisplay attribute 0x27 before "SECRET", normal 0x20 after it
+    let mut sign_on = screen(&["Sign On", " Password . ."]);
+    sign_on.set_char_at(14, 1, TerminalChar { character: ' ', attribute: CharAttribute::FieldAttribute(0x27) });
+    sign_on.write_chars_to_row(1, 15, &"SECRET".chars().collect::<Vec<_>>(), CharAttribute::Normal);
+    sign_on.set_char_at(21, 1, TerminalChar { character: ' ', attribute: CharAttribute::FieldAttribute(0x20) });
+    let mut history = ScreenHistory::default();
+    history.record(&sign_on, (2, 16));
+
+    assert!(history.search("secret").is_empty());
+    assert_eq!(history.search("password").len(), 1);
+    let export = history.newest().unwrap().export(CopyFormat::Text);
+    assert!(!export.contains("SECRET"), "{export}");
+    assert_eq!(export.lines().nth(1), Some(" Password . ."));
+}
+
+#[test]
+fn search_finds_text_on_every_screen_newest_first() {
+    let mut history = ScreenHistory::default();
+    history.record(&screen(&["Work with Spooled Files", " QPJOBLOG   QSYSPRT"]), (1, 1));
+    history.seal(&screen(&["Work with Spooled Files", " QPJOBLOG   QSYSPRT"]), (1, 1));
+    history.record(&screen(&["Display Job Log", " Job QPADEV0001 started"]), (1, 1));
+
+    assert_eq!(
+        history.search("job"),
+        vec![
+            HistoryMatch { snapshot: 2, row: 1, col: 9 },
+            HistoryMatch { snapshot: 2, row: 2, col: 2 },
+            HistoryMatch { snapshot: 1, row: 2, col: 4 },
+        ]
+    );
+    assert!(history.search("  ").is_empty());
+    assert!(history.search("PAYROLL").is_empty());
+}
+
+#[test]
+fn snapshots_export_as_text_and_html() {
+    let mut history = ScreenHistory::default();
+    history.record(&screen(&["Main Menu", " 1. User tasks"]), (20, 7));
+    let snapshot = history.newest().unwrap();
+
+    assert!(snapshot.export(CopyFormat::Text).starts_with("Main Menu\n 1. User tasks"));
+    assert!(snapshot.export(CopyFormat::Html).contains("<td>Main Menu</td>"));
+    assert!(snapshot.file_name(CopyFormat::Html).starts_with("screen-1-"));
+
+    let path = std::env::temp_dir().join(format!("tn5250r-history-{}.txt", std::process::id()));
+    snapshot.save(&path, CopyFormat::Text).unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("User tasks"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn sessions_step_back_and_forward_to_the_live_screen() {
+    let mut session = Session::new(SessionProfile::new("History".to_string(), "history.example.com".to_string(), 23));
+    for title in ["Sign On", "Main Menu", "Work with Objects"] {
+        session.history.record(&screen(&[title]), (1, 1));
+        session.history.seal(&screen(&[title]), (1, 1));
+    }
+    let shown = |session: &Session| session.viewed_snapshot().map(|snapshot| snapshot.title());
+
+    // The newest snapshot is the live screen, so going back starts one before it
+    session.history_back();
+    assert_eq!(shown(&session).as_deref(), Some("Main Menu"));
+    session.history_back();
+    session.history_back();
+    assert_eq!(shown(&session).as_deref(), Some("Sign On"), "the oldest screen is as far as it goes");
+
+    session.history_forward();
+    assert_eq!(shown(&session).as_deref(), Some("Main Menu"));
+    session.history_forward();
+    assert_eq!(session.history_view, None, "forward from the last screen returns to the live one");
+}
@@ -0,0 +1,144 @@
+use eframe::egui;
+use egui_kittest::kittest::Queryable;
+use egui_kittest::Harness;
+use tn5250r::app_state::TN5250RApp;
+use tn5250r::field_manager::FieldDisplayInfo;
+use tn5250r::screen_grid::CellGrid;
+use tn5250r::screen_search::{self, ScreenSearch, SearchMatch};
+
+const WORK_WITH_OBJECTS: &str = "\
+                         Work with Objects Using PDM
+ Library . . . . .   QGPL
+ Opt  Object      Type      Attribute
+      QCLSRC      *FILE     PF-SRC
+      QDDSSRC     *FILE     PF-SRC
+  Position to . . . qddssrc
+";
+
+fn grid() -> CellGrid {
+    CellGrid::from_text(WORK_WITH_OBJECTS, (4, 2))
+}
+
+fn found(row: usize, col: usize, len: usize) -> SearchMatch {
+    SearchMatch { row, col, len }
+}
+
+fn search(query: &str, regex: bool) -> ScreenSearch {
+    let mut search = ScreenSearch { open: true, query: query.to_string(), regex, ..ScreenSearch::default() };
+    search.update(&grid());
+    search
+}
+
+/// An input field as the controller reports it
+fn field(start_row: usize, start_col: usize, length: usize) -> FieldDisplayInfo {
+    FieldDisplayInfo {
+        label: String::new(),
+        content: String::new(),
+        is_active: false,
+        error_state: None,
+        highlighted: false,
+        modified: false,
+        start_row,
+        start_col,
+        length,
+    }
+}
+
+#[test]
+fn text_is_found_ignoring_case() {
+    assert_eq!(search("qddssrc", false).matches, vec![found(5, 7, 7), found(6, 21, 7)]);
+    // Plain text is not a pattern
+    assert_eq!(search("*FILE", false).matches, vec![found(4, 19, 5), found(5, 19, 5)]);
+    assert!(search("", false).matches.is_empty());
+}
+
+#[test]
+fn regular_expressions_and_their_errors() {
+    assert_eq!(search(r"q\w+src", true).matches, vec![found(4, 7, 6), found(5, 7, 7), found(6, 21, 7)]);
+    assert!(search("x*", true).matches.is_empty(), "empty matches are left out");
+
+    let invalid = search("(PF-SRC", true);
+    assert!(invalid.matches.is_empty());
+    assert!(invalid.error.unwrap().contains("Invalid search pattern"));
+}
+
+#[test]
+fn hidden_text_is_never_found() {
+    let mut grid = CellGrid::from_text(" Password: SECRET", (1, 12));
+    for cell in &mut grid.cells[11..17] {
+        cell.style.hidden = true;
+    }
+    let regex = screen_search::compile("secret", false).unwrap();
+    assert!(screen_search::find_matches(&grid, &regex).is_empty());
+}
+
+#[test]
+fn next_and_previous_wrap_around() {
+    let mut search = search("src", false);
+    assert_eq!(search.matches.len(), 5);
+    assert_eq!(search.current, Some(0));
+
+    search.previous();
+    assert_eq!(search.current, Some(4));
+    search.next();
+    search.next();
+    assert_eq!(search.current_match(), Some(found(4, 32, 3)));
+
+    // The picked match stays picked when the screen is searched again
+    search.update(&grid());
+    assert_eq!(search.current, Some(1));
+    search.query = "nowhere".to_string();
+    search.update(&grid());
+    assert_eq!(search.current, None);
+}
+
+#[test]
+fn go_to_field_picks_the_next_match_in_an_input_field() {
+    let mut search = search("src", false);
+    // Option fields on the subfile rows, and the position-to field
+    let fields = [field(4, 2, 2), field(5, 2, 2), field(6, 20, 10)];
+    assert_eq!(search.next_in_field(&fields, (1, 1)), Some(found(6, 25, 3)));
+    assert_eq!(search.current, Some(4));
+    assert_eq!(search.next_in_field(&fields, (6, 25)), Some(found(6, 25, 3)), "wraps to the top");
+
+    let mut library = self::search("library", false);
+    assert_eq!(library.next_in_field(&fields, (1, 1)), None);
+}
+
+#[test]
+fn ctrl_f_opens_the_search_bar_and_escape_closes_it() {
+    let mut harness = Harness::builder().with_size(egui::vec2(1200.0, 800.0)).build_eframe(|cc| TN5250RApp::new(cc));
+    harness.run();
+    assert!(harness.query_by_label("Find:").is_none());
+
+    harness.key_press_modifiers(egui::Modifiers::COMMAND, egui::Key::F);
+    harness.run();
+    assert!(harness.query_by_label("Find:").is_some());
+    let session = harness.state().active_session_id.clone().unwrap();
+    assert!(harness.state().sessions[&session].search.open);
+
+    harness.key_press(egui::Key::Escape);
+    harness.run();
+    assert!(!harness.state().sessions[&session].search.open);
+}
+
+#[test]
+fn the_screen_is_searched_again_only_when_the_query_or_the_screen_changes() {
+    let mut harness = Harness::builder().with_size(egui::vec2(1200.0, 800.0)).build_eframe(|cc| TN5250RApp::new(cc));
+    harness.key_press_modifiers(egui::Modifiers::COMMAND, egui::Key::F);
+    harness.run();
+    let session = harness.state().active_session_id.clone().unwrap();
+
+    harness.state_mut().sessions.get_mut(&session).unwrap().search.query = "Ready".to_string();
+    harness.run();
+    assert_eq!(harness.state().sessions[&session].search.matches.len(), 1);
+
+    // A new screen is only looked at once the session counts it
+    let shown = harness.state_mut().sessions.get_mut(&session).unwrap();
+    shown.terminal_content = "Ready\nReady\n".to_string();
+    harness.run();
+    assert_eq!(harness.state().sessions[&session].search.matches.len(), 1);
+    harness.state_mut().sessions.get_mut(&session).unwrap().screen_revision += 1;
+    harness.run();
+    assert_eq!(harness.state().sessions[&session].search.matches.len(), 2);
+}
@@ -36,11 +36,13 @@ fn test_session_config_custom() {
         auto_reconnect: true,
         max_reconnect_attempts: 5,
         reconnect_backoff_multiplier: 3,
+        reconnect_initial_delay_ms: 1000,
     };
     
     assert_eq!(config.idle_timeout_secs, 300);
     assert_eq!(config.keepalive_interval_secs, 30);
     assert_eq!(config.connection_timeout_secs, 60);
+    assert_eq!(config.connection_timeout(), Duration::from_secs(60), "Connects are bounded by the configured timeout");
     assert!(config.auto_reconnect);
     assert_eq!(config.max_reconnect_attempts, 5);
 }
@@ -59,6 +61,7 @@ fn test_connection_with_session_config() {
         auto_reconnect: false,
         max_reconnect_attempts: 2,
         reconnect_backoff_multiplier: 2,
+        reconnect_initial_delay_ms: 1000,
     };
     
     conn.set_session_config(config);
@@ -84,6 +87,7 @@ fn test_idle_timeout_detection() {
         auto_reconnect: false,
         max_reconnect_attempts: 3,
         reconnect_backoff_multiplier: 2,
+        reconnect_initial_delay_ms: 1000,
     };
     
     conn.set_session_config(config);
@@ -206,6 +210,7 @@ fn test_connection_timeout_configuration() {
         auto_reconnect: false,
         max_reconnect_attempts: 3,
         reconnect_backoff_multiplier: 2,
+        reconnect_initial_delay_ms: 1000,
     };
     
     conn.set_session_config(config);
@@ -362,6 +367,7 @@ mod integration_tests {
             auto_reconnect: true,
             max_reconnect_attempts: 5,
             reconnect_backoff_multiplier: 2,
+            reconnect_initial_delay_ms: 1000,
         };
         
         conn.set_session_config(config.clone());
@@ -464,4 +470,24 @@ fn test_fcw_processing() {
     assert!(!session.current_field_attributes.mandatory_entry, "Mandatory entry should be disabled");
 
     println!("✓ FCW processing test passed");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_continued_field_groups_from_fcws() {
+    use tn5250r::lib5250::codes::*;
+    use tn5250r::lib5250::session::Session;
+
+    let mut session = Session::new();
+    session.authenticate("testuser", "testpass").unwrap();
+
+    let mut data = vec![0x04, CMD_CLEAR_UNIT, 0x04, CMD_WRITE_TO_DISPLAY, 0x00, 0x00];
+    data.extend_from_slice(&[SBA, 2, 5, SF, 0x40, 0x00, FCW_CONTINUOUS_FIELD, CONTINUED_FIRST, FCW_WORD_WRAP, 0x01, 0x20, 0x00, 0x20]);
+    data.extend_from_slice(&[SBA, 3, 5, SF, 0x40, 0x00, FCW_CONTINUOUS_FIELD, CONTINUED_MIDDLE, 0x20, 0x00, 0x20]);
+    data.extend_from_slice(&[SBA, 4, 5, SF, 0x40, 0x00, FCW_CONTINUOUS_FIELD, CONTINUED_LAST, 0x20, 0x00, 0x20]);
+    data.extend_from_slice(&[SBA, 6, 5, SF, 0x40, 0x00, 0x20, 0x00, 0x05]);
+    session.process_stream(&data).expect("continued fields");
+
+    assert_eq!(session.continued_field_groups(), vec![vec![0, 1, 2]]);
+    assert!(session.fields[0].word_wrap());
+    assert!(!session.fields[3].word_wrap());
+}
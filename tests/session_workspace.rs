@@ -0,0 +1,106 @@
+use eframe::egui;
+use egui_kittest::kittest::Queryable;
+use egui_kittest::Harness;
+use tn5250r::app_state::TN5250RApp;
+use tn5250r::session_profile::SessionProfile;
+use tn5250r::ui::session_tabs::session_badges;
+
+fn workspace<'a>() -> Harness<'a, TN5250RApp> {
+    Harness::builder().with_size(egui::vec2(1000.0, 700.0)).build_eframe(|cc| TN5250RApp::new(cc))
+}
+
+/// Open sessions for two more profiles; returns the tab ids in order
+fn open_three(app: &mut TN5250RApp) -> Vec<String> {
+    app.create_session_from_profile(SessionProfile::new("Payroll".to_string(), "payroll.example.com".to_string(), 23));
+    app.create_session_from_profile(SessionProfile::new("Sales".to_string(), "sales.example.com".to_string(), 992));
+    app.session_order.clone()
+}
+
+#[test]
+fn starts_with_a_quick_connect_session() {
+    let mut harness = workspace();
+    harness.run();
+    let app = harness.state();
+    assert_eq!(app.session_order.len(), 1);
+    let session = app.get_active_session().expect("a session is open");
+    assert!(session.is_quick_connect());
+    assert!(session.terminal_content.contains("TN5250R"));
+    assert_eq!(session_badges(session)[0].2, "Disconnected");
+}
+
+#[test]
+fn tabs_reorder_and_close_to_a_neighbour() {
+    let mut harness = workspace();
+    let tabs = open_three(harness.state_mut());
+    let app = harness.state_mut();
+    assert_eq!(app.active_session_id.as_ref(), Some(&tabs[2]));
+
+    app.move_session(2, 0);
+    assert_eq!(app.session_order, vec![tabs[2].clone(), tabs[0].clone(), tabs[1].clone()]);
+
+    // Closing the active tab activates the one that takes its place
+    app.close_session(&tabs[2]);
+    assert_eq!(app.session_order, vec![tabs[0].clone(), tabs[1].clone()]);
+    assert_eq!(app.active_session_id.as_ref(), Some(&tabs[0]));
+    app.switch_to_session(tabs[1].clone());
+    app.close_session(&tabs[1]);
+    assert_eq!(app.active_session_id.as_ref(), Some(&tabs[0]));
+    app.close_session(&tabs[0]);
+    assert!(app.active_session_id.is_none());
+    harness.run();
+}
+
+#[test]
+fn keyboard_shortcuts_switch_tabs() {
+    let mut harness = workspace();
+    let tabs = open_three(harness.state_mut());
+    harness.run();
+
+    harness.key_press_modifiers(egui::Modifiers::CTRL, egui::Key::Tab);
+    harness.run();
+    assert_eq!(harness.state().active_session_id.as_ref(), Some(&tabs[0]), "Ctrl+Tab wraps around");
+
+    harness.key_press_modifiers(egui::Modifiers::CTRL | egui::Modifiers::SHIFT, egui::Key::Tab);
+    harness.run();
+    assert_eq!(harness.state().active_session_id.as_ref(), Some(&tabs[2]));
+
+    harness.key_press_modifiers(egui::Modifiers::CTRL, egui::Key::Num2);
+    harness.run();
+    assert_eq!(harness.state().active_session_id.as_ref(), Some(&tabs[1]));
+}
+
+#[test]
+fn detached_sessions_leave_the_tab_cycle() {
+    let mut harness = workspace();
+    let tabs = open_three(harness.state_mut());
+    let app = harness.state_mut();
+
+    app.detach_session(&tabs[2]);
+    assert!(app.sessions[&tabs[2]].detached);
+    assert_eq!(app.active_session_id.as_ref(), Some(&tabs[1]), "the main window moves to the nearest tab");
+    app.cycle_session(1);
+    assert_eq!(app.active_session_id.as_ref(), Some(&tabs[0]));
+    app.cycle_session(1);
+    assert_eq!(app.active_session_id.as_ref(), Some(&tabs[1]));
+
+    // Without native windows the session is drawn in a window of the main viewport
+    harness.run();
+    assert!(harness.query_all_by_label("TN5250R - Sales").next().is_some());
+
+    let app = harness.state_mut();
+    app.switch_to_session(tabs[2].clone());
+    assert!(!app.sessions[&tabs[2]].detached);
+    assert_eq!(app.active_session_id.as_ref(), Some(&tabs[2]));
+}
+
+#[test]
+fn badges_follow_the_session_state() {
+    let mut harness = workspace();
+    let app = harness.state_mut();
+    let session = app.get_active_session_mut().unwrap();
+    session.connected = true;
+    session.oia.message_waiting = true;
+    session.oia.keyboard_locked = true;
+    let badges: Vec<&str> = session_badges(session).into_iter().map(|(_, _, text)| text).collect();
+    assert_eq!(badges, ["Connected", "Message waiting", "Keyboard locked (X SYSTEM)"]);
+}
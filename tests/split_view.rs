@@ -0,0 +1,80 @@
+use eframe::egui;
+use egui_kittest::kittest::Queryable;
+use egui_kittest::Harness;
+use tn5250r::app_state::TN5250RApp;
+use tn5250r::config::SessionConfig;
+use tn5250r::session_profile::SessionProfile;
+use tn5250r::ui::split_view::SplitLayout;
+
+fn workspace<'a>() -> Harness<'a, TN5250RApp> {
+    Harness::builder().with_size(egui::vec2(1200.0, 800.0)).build_eframe(|cc| TN5250RApp::new(cc))
+}
+
+/// Open sessions for two more profiles; returns the tab ids in order
+fn open_three(app: &mut TN5250RApp) -> Vec<String> {
+    app.create_session_from_profile(SessionProfile::new("Payroll".to_string(), "payroll.example.com".to_string(), 23));
+    app.create_session_from_profile(SessionProfile::new("Sales".to_string(), "sales.example.com".to_string(), 992));
+    app.session_order.clone()
+}
+
+#[test]
+fn layouts_round_trip_through_the_config() {
+    for layout in [
+        SplitLayout::Tabs,
+        SplitLayout::SideBySide,
+        SplitLayout::Quad,
+        SplitLayout::Custom { rows: 2, cols: 3 },
+    ] {
+        let mut cfg = SessionConfig::new("test".to_string(), "test".to_string());
+        layout.save(&mut cfg);
+        assert_eq!(SplitLayout::from_config(&cfg), layout);
+    }
+
+    assert_eq!(SplitLayout::parse("3x2").unwrap().grid(), (2, 3), "columns come first");
+    assert_eq!(SplitLayout::parse("2X2"), Ok(SplitLayout::Quad));
+    assert!(SplitLayout::parse("5x1").is_err());
+    assert!(SplitLayout::parse("wide").is_err());
+
+    let mut cfg = SessionConfig::new("test".to_string(), "test".to_string());
+    cfg.set_property("workspace.splitLayout", "sideways");
+    assert_eq!(SplitLayout::from_config(&cfg), SplitLayout::Tabs);
+}
+
+#[test]
+fn panes_follow_the_tab_order_and_keep_the_active_session_in_sight() {
+    let mut harness = workspace();
+    let tabs = open_three(harness.state_mut());
+    let app = harness.state_mut();
+
+    app.split_layout = SplitLayout::Quad;
+    assert_eq!(app.split_panes(), vec![Some(tabs[0].clone()), Some(tabs[1].clone()), Some(tabs[2].clone()), None]);
+
+    app.split_layout = SplitLayout::SideBySide;
+    assert_eq!(app.split_panes(), vec![Some(tabs[0].clone()), Some(tabs[2].clone())], "the active session takes the last pane");
+
+    // Detached sessions have windows of their own
+    app.detach_session(&tabs[0]);
+    assert_eq!(app.split_panes(), vec![Some(tabs[1].clone()), Some(tabs[2].clone())]);
+}
+
+#[test]
+fn clicking_a_pane_focuses_its_session() {
+    let mut harness = workspace();
+    let tabs = open_three(harness.state_mut());
+    harness.state_mut().split_layout = SplitLayout::Quad;
+    harness.run();
+
+    // Every session is drawn at once, under its own pane heading
+    assert!(harness.query_by_label("Payroll").is_some());
+    assert!(harness.query_by_label("Sales").is_some());
+    assert!(harness.query_by_label("No session").is_some());
+
+    harness.get_by_label("Payroll").click();
+    harness.run();
+    assert_eq!(harness.state().active_session_id.as_ref(), Some(&tabs[1]));
+
+    // The keyboard shortcuts move the focus between panes
+    harness.key_press_modifiers(egui::Modifiers::CTRL, egui::Key::Tab);
+    harness.run();
+    assert_eq!(harness.state().active_session_id.as_ref(), Some(&tabs[2]));
+}
@@ -0,0 +1,123 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::{ServerConfig, ServerConnection, StreamOwned};
+use tempfile::TempDir;
+use tn5250r::error::NetworkError;
+use tn5250r::known_hosts;
+use tn5250r::network::AS400Connection;
+use tn5250r::network_tls::TlsOptions;
+use tn5250r::telnet_negotiation::{StartTlsState, TelnetNegotiator};
+
+const WILL_START_TLS: [u8; 3] = [255, 251, 46];
+const DO_START_TLS: [u8; 3] = [255, 253, 46];
+const WONT_START_TLS: [u8; 3] = [255, 252, 46];
+const FOLLOWS: [u8; 6] = [255, 250, 46, 1, 255, 240];
+
+fn read_exact(tcp: &mut TcpStream, len: usize) -> Vec<u8> {
+    let mut buf = vec![0; len];
+    tcp.read_exact(&mut buf).unwrap();
+    buf
+}
+
+/// Plain telnet server on localhost that upgrades with START_TLS, then reports the
+/// first telnet bytes it receives inside TLS and closes
+fn serve_start_tls(store: &Path) -> (u16, mpsc::Receiver<(Vec<u8>, Vec<u8>)>) {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let der = cert.serialize_der().unwrap();
+    let config = Arc::new(
+        ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(der.clone())],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(cert.serialize_private_key_der())),
+            )
+            .unwrap(),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::fs::write(store, format!("localhost:{port} {}\n", known_hosts::fingerprint(&der))).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut tcp, _) = listener.accept().unwrap();
+        let plain = read_exact(&mut tcp, 3);
+        tcp.write_all(&[DO_START_TLS.as_slice(), &FOLLOWS].concat()).unwrap();
+        let follows = read_exact(&mut tcp, 6);
+
+        let mut tls = StreamOwned::new(ServerConnection::new(config).unwrap(), tcp);
+        let mut buf = [0u8; 256];
+        let n = tls.read(&mut buf).unwrap();
+        tx.send(([plain, follows].concat(), buf[..n].to_vec())).unwrap();
+        // Binary and SGA in both directions, then hang up to end negotiation
+        let _ = tls.write_all(&[255, 253, 0, 255, 251, 0, 255, 253, 3, 255, 251, 3]);
+        tls.conn.send_close_notify();
+        let _ = tls.flush();
+    });
+    (port, rx)
+}
+
+fn start_tls_connection(port: u16, store: &Path) -> AS400Connection {
+    let mut conn = AS400Connection::new("localhost".to_string(), port);
+    conn.set_tls(false);
+    conn.set_known_hosts_path(store);
+    conn.set_tls_options(TlsOptions { start_tls: true, ..Default::default() });
+    conn
+}
+
+#[test]
+fn plain_connection_is_upgraded_before_negotiation() {
+    let dir = TempDir::new().unwrap();
+    let store = dir.path().join("known_hosts");
+    let (port, rx) = serve_start_tls(&store);
+
+    let mut conn = start_tls_connection(port, &store);
+    conn.connect().expect("START_TLS connection");
+    let (plain, encrypted) = rx.recv().unwrap();
+    conn.disconnect();
+
+    // Only the upgrade is visible on the wire; option negotiation happens inside TLS
+    assert_eq!(plain, [WILL_START_TLS.as_slice(), &FOLLOWS].concat());
+    assert!(encrypted.windows(3).any(|w| w == [255, 253, 0]), "DO BINARY sent inside TLS: {encrypted:?}");
+}
+
+#[test]
+fn refused_start_tls_does_not_fall_back_to_plain_text() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    thread::spawn(move || {
+        let (mut tcp, _) = listener.accept().unwrap();
+        read_exact(&mut tcp, 3);
+        tcp.write_all(&[255, 253, 0]).unwrap();
+        tcp.write_all(&WONT_START_TLS).unwrap();
+        let _ = tcp.read(&mut [0u8; 16]);
+    });
+
+    let dir = TempDir::new().unwrap();
+    let err = start_tls_connection(port, &dir.path().join("known_hosts")).connect().unwrap_err();
+    match err.get_ref().and_then(|e| e.downcast_ref::<NetworkError>()) {
+        Some(NetworkError::StartTlsRefused { host }) => assert_eq!(host, "localhost"),
+        other => panic!("expected START_TLS refusal, got {other:?}"),
+    }
+}
+
+#[test]
+fn start_tls_is_declined_unless_enabled() {
+    let mut negotiator = TelnetNegotiator::new();
+    assert_eq!(negotiator.process_incoming_data(&DO_START_TLS), WONT_START_TLS);
+    assert!(negotiator.start_tls_request().is_empty());
+
+    negotiator.set_start_tls(true);
+    assert_eq!(negotiator.process_incoming_data(&DO_START_TLS), WILL_START_TLS);
+    assert_eq!(negotiator.process_incoming_data(&FOLLOWS), FOLLOWS);
+    assert_eq!(negotiator.start_tls_state(), StartTlsState::Ready);
+
+    negotiator.complete_start_tls();
+    assert_eq!(negotiator.start_tls_state(), StartTlsState::Active);
+    assert_eq!(negotiator.process_incoming_data(&DO_START_TLS), WONT_START_TLS);
+}
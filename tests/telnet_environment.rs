@@ -0,0 +1,143 @@
+use tn5250r::telnet_environment::{EnvironmentSettings, EnvironmentVariable, VariableKind};
+use tn5250r::telnet_negotiation::TelnetNegotiator;
+
+/// IAC SB NEW-ENVIRON SEND <kind name>... IAC SE
+fn send_request(vars: &[(u8, &str)]) -> Vec<u8> {
+    let mut request = vec![255, 250, 39, 1];
+    for (kind, name) in vars {
+        request.push(*kind);
+        request.extend_from_slice(name.as_bytes());
+    }
+    request.extend_from_slice(&[255, 240]);
+    request
+}
+
+/// Type byte and value following `name` VALUE in a NEW-ENVIRON response, with ESC quoting and IAC doubling removed
+fn env_entry(response: &[u8], name: &[u8]) -> Option<(u8, Vec<u8>)> {
+    let at = response
+        .windows(name.len() + 2)
+        .position(|w| matches!(w[0], 0 | 3) && w[1..=name.len()] == *name && w[name.len() + 1] == 1)?;
+    let mut value = Vec::new();
+    let mut i = at + name.len() + 2;
+    while i < response.len() {
+        match (response[i], response.get(i + 1)) {
+            (2, Some(&next)) | (0xFF, Some(&next @ 0xFF)) => {
+                value.push(next);
+                i += 2;
+            }
+            (0 | 3 | 0xFF, _) => break,
+            (byte, _) => {
+                value.push(byte);
+                i += 1;
+            }
+        }
+    }
+    Some((response[at], value))
+}
+
+fn sign_on_menu() -> EnvironmentSettings {
+    EnvironmentSettings {
+        program: "ordent".to_string(),
+        current_library: "SALESLIB".to_string(),
+        initial_menu: "MAIN".to_string(),
+        ..EnvironmentSettings::default()
+    }
+}
+
+#[test]
+fn requested_ibm_variables_are_answered_from_the_profile() {
+    let mut negotiator = TelnetNegotiator::new();
+    negotiator.set_environment(sign_on_menu());
+
+    let request = send_request(&[(3, "IBMPROGRAM"), (3, "IBMCURLIB"), (3, "IBMIMENU"), (3, "IBMFONT")]);
+    let response = negotiator.process_incoming_data(&request);
+
+    assert_eq!(env_entry(&response, b"IBMPROGRAM"), Some((3, b"ORDENT".to_vec())));
+    assert_eq!(env_entry(&response, b"IBMCURLIB"), Some((3, b"SALESLIB".to_vec())));
+    assert_eq!(env_entry(&response, b"IBMIMENU"), Some((3, b"MAIN".to_vec())));
+    assert_eq!(env_entry(&response, b"IBMFONT"), None, "unset variables are not answered");
+}
+
+#[test]
+fn typed_fields_override_defaults_and_answer_with_the_requested_type() {
+    let mut negotiator = TelnetNegotiator::new();
+    negotiator.set_environment(EnvironmentSettings { code_page: "500".to_string(), ..EnvironmentSettings::default() });
+
+    let response = negotiator.process_incoming_data(&send_request(&[(3, "CODEPAGE"), (0, "KBDTYPE")]));
+    assert_eq!(env_entry(&response, b"CODEPAGE"), Some((3, b"500".to_vec())));
+    assert_eq!(env_entry(&response, b"KBDTYPE"), Some((0, b"USB".to_vec())));
+}
+
+#[test]
+fn custom_values_are_escaped() {
+    let mut negotiator = TelnetNegotiator::new();
+    let value = "A\u{1}B\u{3}C\u{2}D";
+    negotiator.set_environment(EnvironmentSettings {
+        custom: vec![EnvironmentVariable::new(VariableKind::UserVar, "MYVAR", value)],
+        ..EnvironmentSettings::default()
+    });
+
+    let response = negotiator.process_incoming_data(&send_request(&[(3, "MYVAR")]));
+    let raw = [b"MYVAR".as_slice(), &[1, b'A', 2, 1, b'B', 2, 3, b'C', 2, 2, b'D', 255, 240]].concat();
+    assert!(response.windows(raw.len()).any(|w| w == raw), "{response:?}");
+    assert_eq!(env_entry(&response, b"MYVAR"), Some((3, value.as_bytes().to_vec())));
+}
+
+#[test]
+fn custom_pairs_cannot_replace_device_name_or_user() {
+    let mut negotiator = TelnetNegotiator::new();
+    negotiator.set_credentials("alice", "secret");
+    negotiator.set_device_name(Some("DSP01".to_string()));
+    negotiator.set_environment(EnvironmentSettings {
+        custom: vec![
+            EnvironmentVariable::new(VariableKind::Var, "DEVNAME", "OTHER"),
+            EnvironmentVariable::new(VariableKind::Var, "USER", "MALLORY"),
+            EnvironmentVariable::new(VariableKind::Var, "KBDTYPE", "FRB"),
+        ],
+        ..EnvironmentSettings::default()
+    });
+
+    let response = negotiator.process_incoming_data(&send_request(&[(0, "DEVNAME"), (0, "USER"), (0, "KBDTYPE")]));
+    assert_eq!(env_entry(&response, b"DEVNAME"), Some((0, b"DSP01".to_vec())));
+    assert_eq!(env_entry(&response, b"USER"), Some((0, b"ALICE".to_vec())));
+    assert_eq!(env_entry(&response, b"KBDTYPE"), Some((0, b"FRB".to_vec())));
+}
+
+#[test]
+fn send_all_includes_profile_variables_and_records_them() {
+    let mut negotiator = TelnetNegotiator::new();
+    let mut environment = sign_on_menu();
+    environment.send_config_record = true;
+    environment.custom.push(EnvironmentVariable::new(VariableKind::Var, "SITE", "HQ"));
+    negotiator.set_environment(environment);
+
+    // IAC SB NEW-ENVIRON SEND IAC SE
+    let response = negotiator.process_incoming_data(&[255, 250, 39, 1, 255, 240]);
+    assert_eq!(env_entry(&response, b"DEVNAME"), Some((0, b"TN5250R".to_vec())));
+    assert_eq!(env_entry(&response, b"IBMIMENU"), Some((3, b"MAIN".to_vec())));
+    assert_eq!(env_entry(&response, b"IBMSENDCONFREC"), Some((3, b"YES".to_vec())));
+    assert_eq!(env_entry(&response, b"SITE"), Some((0, b"HQ".to_vec())));
+
+    let sent = negotiator.sent_environment();
+    assert!(sent.contains(&EnvironmentVariable::new(VariableKind::UserVar, "IBMCURLIB", "SALESLIB")));
+    assert!(sent.contains(&EnvironmentVariable::new(VariableKind::Var, "SITE", "HQ")));
+}
+
+#[test]
+fn password_substitute_is_masked_in_the_sent_set() {
+    let mut negotiator = TelnetNegotiator::new();
+    negotiator.set_credentials("user123", "Secret1");
+
+    let mut request = vec![255, 250, 39, 1, 3];
+    request.extend_from_slice(b"IBMRSEED");
+    request.extend_from_slice(&[0x7D, 0x4C, 0x23, 0x19, 0x12, 0x80, 0x04, 0xB2]);
+    request.push(3);
+    request.extend_from_slice(b"IBMSUBSPW");
+    request.extend_from_slice(&[255, 240]);
+    negotiator.process_incoming_data(&request);
+
+    let sent = negotiator.sent_environment();
+    assert!(sent.contains(&EnvironmentVariable::new(VariableKind::Var, "USER", "USER123")));
+    assert!(sent.contains(&EnvironmentVariable::new(VariableKind::UserVar, "IBMSUBSPW", "<8 bytes>")));
+    assert!(sent.iter().all(|var| !var.value.contains("Secret1")));
+}
@@ -0,0 +1,179 @@
+//! Renderer tests: the painted shapes are compared with text snapshots in
+//! `tests/snapshots/terminal_renderer/`. Run with `UPDATE_SNAPSHOTS=1` to rewrite them.
+
+use std::cell::Cell;
+use std::fmt::Write as _;
+
+use eframe::egui;
+use egui_kittest::Harness;
+use tn5250r::color_scheme::ColorScheme;
+use tn5250r::field_manager::FieldDisplayInfo;
+use tn5250r::screen_grid::CellGrid;
+use tn5250r::terminal::{CharAttribute, TerminalChar, TerminalScreen};
+use tn5250r::terminal_display::{draw_terminal_grid, CursorStyle, DisplaySettings, GridLayout};
+
+fn settings() -> DisplaySettings {
+    DisplaySettings { cursor_blink: false, ..DisplaySettings::default() }
+}
+
+/// A small screen exercising each display attribute
+fn sample_grid() -> CellGrid {
+    let mut screen = TerminalScreen::new_with_size(12, 3);
+    let mut put = |x: usize, y: usize, text: &str, attribute: u8| {
+        screen.set_char_at(x, y, TerminalChar { character: ' ', attribute: CharAttribute::FieldAttribute(attribute) });
+        for (i, ch) in text.chars().enumerate() {
+            screen.set_char_at(x + 1 + i, y, TerminalChar { character: ch, attribute: CharAttribute::Normal });
+        }
+    };
+    put(0, 0, "Sign", 0x22); // White
+    put(6, 0, "On", 0x21); // Reverse green
+    put(0, 1, "ABC", 0x30); // Turquoise column separators
+    put(5, 1, "pw", 0x27); // Nondisplay
+    put(0, 2, "Error", 0x2C); // Red underscore
+    CellGrid::from_screen(&screen, (2, 3))
+}
+
+/// Paint `grid` in a window of `size` and return the layout and a description of the shapes
+fn render(grid: &CellGrid, fields: &[FieldDisplayInfo], settings: &DisplaySettings, size: egui::Vec2) -> (GridLayout, String) {
+    render_with(grid, fields, settings, &ColorScheme::default(), size)
+}
+
+fn render_with(
+    grid: &CellGrid,
+    fields: &[FieldDisplayInfo],
+    settings: &DisplaySettings,
+    scheme: &ColorScheme,
+    size: egui::Vec2,
+) -> (GridLayout, String) {
+    let layout = Cell::new(None);
+    let mut harness = Harness::builder().with_size(size).build_ui(|ui| {
+        layout.set(Some(draw_terminal_grid(ui, grid, fields, settings, scheme, "")));
+    });
+    harness.step();
+    let mut out = String::new();
+    for clipped in &harness.output().shapes {
+        describe(&clipped.shape, &mut out);
+    }
+    drop(harness);
+    (layout.get().expect("grid was drawn"), out)
+}
+
+fn describe(shape: &egui::Shape, out: &mut String) {
+    match shape {
+        egui::Shape::Vec(shapes) => shapes.iter().for_each(|shape| describe(shape, out)),
+        egui::Shape::Rect(rect) => {
+            let _ = writeln!(out, "rect {} {:?}", rect_text(rect.rect), rect.fill);
+        }
+        egui::Shape::Text(text) => {
+            let _ = writeln!(out, "text {:.1},{:.1} {:?} {:?}", text.pos.x, text.pos.y, text.galley.text(), text.fallback_color);
+        }
+        egui::Shape::LineSegment { points, stroke } => {
+            let _ = writeln!(
+                out,
+                "line {:.1},{:.1} {:.1},{:.1} {:?}",
+                points[0].x, points[0].y, points[1].x, points[1].y, stroke.color
+            );
+        }
+        egui::Shape::Noop => {}
+        other => {
+            let _ = writeln!(out, "{other:?}");
+        }
+    }
+}
+
+fn rect_text(rect: egui::Rect) -> String {
+    format!("{:.1},{:.1} {:.1}x{:.1}", rect.min.x, rect.min.y, rect.width(), rect.height())
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots/terminal_renderer")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {e}; run with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+    assert_eq!(actual, expected, "snapshot {name} differs; run with UPDATE_SNAPSHOTS=1 to accept");
+}
+
+#[test]
+fn draws_attributes_cell_by_cell() {
+    let (layout, shapes) = render(&sample_grid(), &[], &settings(), egui::vec2(400.0, 120.0));
+    assert_eq!((layout.rows, layout.cols), (3, 12));
+    assert_snapshot("attributes", &shapes);
+
+    // Every character sits at the left edge of its own cell
+    let sign = layout.cell_rect(1, 2);
+    assert!(shapes.contains(&format!("text {:.1},{:.1} \"S\"", sign.min.x, sign.min.y)), "{shapes}");
+    assert!(!shapes.contains("\"p\""), "nondisplay text is not drawn");
+}
+
+fn field(start_row: usize, start_col: usize, length: usize) -> FieldDisplayInfo {
+    FieldDisplayInfo {
+        label: String::new(),
+        content: String::new(),
+        is_active: false,
+        error_state: None,
+        highlighted: false,
+        modified: false,
+        start_row,
+        start_col,
+        length,
+    }
+}
+
+#[test]
+fn draws_cursor_styles_and_field_state() {
+    let grid = sample_grid();
+    let field = FieldDisplayInfo { highlighted: true, ..field(3, 2, 5) };
+    let underline = DisplaySettings { cursor_style: CursorStyle::Underline, ..settings() };
+    let (layout, shapes) = render(&grid, &[field], &underline, egui::vec2(400.0, 120.0));
+    assert_snapshot("cursor_underline", &shapes);
+
+    let cursor = layout.cell_rect(2, 3);
+    assert!(!shapes.contains(&format!("rect {} ", rect_text(cursor))), "no block cursor");
+    let field_cell = rect_text(layout.cell_rect(3, 2));
+    assert!(shapes.contains(&format!("rect {field_cell} {:?}", egui::Color32::YELLOW)), "{shapes}");
+
+    // Without separators or underlines the plain cells have no lines
+    let plain = DisplaySettings { column_separators: false, underlines: false, ..settings() };
+    let (_, shapes) = render(&grid, &[], &plain, egui::vec2(400.0, 120.0));
+    assert!(!shapes.contains("line "), "{shapes}");
+    let block = rect_text(cursor);
+    assert!(shapes.contains(&format!("rect {block} {:?}", egui::Color32::GREEN)), "{shapes}");
+}
+
+#[test]
+fn fits_132_columns_to_the_window() {
+    let grid = CellGrid::from_screen(&TerminalScreen::new_with_size(132, 27), (1, 1));
+    let fitted = DisplaySettings { auto_fit: true, ..settings() };
+    let (layout, _) = render(&grid, &[], &fitted, egui::vec2(900.0, 500.0));
+    assert_eq!((layout.rows, layout.cols), (27, 132));
+    assert!(layout.rect().width() <= 900.0 - 16.0, "{:?}", layout.rect());
+    assert!(layout.rect().height() <= 500.0 - 16.0, "{:?}", layout.rect());
+    assert!(layout.rect().width() > 800.0, "the font grows back to fill the width: {:?}", layout.rect());
+
+    // Pointer positions map back to cells, clamped to the screen
+    let corner = layout.cell_rect(27, 132);
+    assert_eq!(layout.cell_at(corner.center()), (27, 132));
+    assert_eq!(layout.cell_at(corner.max + egui::vec2(50.0, 50.0)), (27, 132));
+    assert_eq!(layout.cell_at(layout.origin - egui::vec2(5.0, 5.0)), (1, 1));
+}
+
+#[test]
+fn draws_with_the_profile_color_scheme() {
+    let scheme = ColorScheme::light();
+    let modified = FieldDisplayInfo { modified: true, ..field(3, 2, 5) };
+    let (layout, shapes) = render_with(&sample_grid(), &[modified], &settings(), &scheme, egui::vec2(400.0, 120.0));
+    assert_snapshot("light_scheme", &shapes);
+
+    let rgb = |rgb: tn5250r::color_scheme::Rgb| egui::Color32::from_rgb(rgb.0[0], rgb.0[1], rgb.0[2]);
+    assert!(shapes.contains(&format!("rect {} {:?}", rect_text(layout.rect()), rgb(scheme.background))), "{shapes}");
+    let field_cell = rect_text(layout.cell_rect(3, 2));
+    assert!(shapes.contains(&format!("rect {field_cell} {:?}", rgb(scheme.modified_field.background))), "{shapes}");
+    // "Sign" is white on a 5250, which the light scheme draws dark
+    assert!(shapes.contains(&format!("\"S\" {:?}", rgb(scheme.white))), "{shapes}");
+}
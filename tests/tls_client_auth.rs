@@ -0,0 +1,206 @@
+use std::io::Write;
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+
+use rcgen::{BasicConstraints, Certificate, CertificateParams, DnType, IsCa};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig, ServerConnection};
+use tempfile::TempDir;
+use tn5250r::error::NetworkError;
+use tn5250r::network::AS400Connection;
+use tn5250r::network_tls::{format_pin, spki_sha256, ClientCertificate, TlsMinVersion, TlsOptions};
+
+static TLS12_ONLY: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS12];
+
+struct Pki {
+    dir: TempDir,
+    ca: Certificate,
+    ca_der: Vec<u8>,
+    server_der: Vec<u8>,
+    server_key: Vec<u8>,
+    client: Certificate,
+    client_der: Vec<u8>,
+}
+
+impl Pki {
+    fn new() -> Self {
+        let mut ca_params = CertificateParams::new(Vec::<String>::new());
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.distinguished_name.push(DnType::CommonName, "Test CA");
+        let ca = Certificate::from_params(ca_params).unwrap();
+        let ca_der = ca.serialize_der().unwrap();
+
+        let server = Certificate::from_params(CertificateParams::new(vec!["localhost".to_string()])).unwrap();
+        let server_der = server.serialize_der_with_signer(&ca).unwrap();
+
+        let mut client_params = CertificateParams::new(Vec::<String>::new());
+        client_params.distinguished_name.push(DnType::CommonName, "QSECOFR");
+        let client = Certificate::from_params(client_params).unwrap();
+        let client_der = client.serialize_der_with_signer(&ca).unwrap();
+
+        let pki = Self {
+            dir: tempfile::tempdir().unwrap(),
+            server_key: server.serialize_private_key_der(),
+            ca,
+            ca_der,
+            server_der,
+            client,
+            client_der,
+        };
+        std::fs::write(pki.path("ca.pem"), pki.ca.serialize_pem().unwrap()).unwrap();
+        pki
+    }
+
+    fn path(&self, name: &str) -> String {
+        self.dir.path().join(name).to_string_lossy().into_owned()
+    }
+
+    fn pem_client_certificate(&self) -> ClientCertificate {
+        let cert_pem = pem_block("CERTIFICATE", &self.client_der);
+        std::fs::write(self.path("client.pem"), cert_pem).unwrap();
+        std::fs::write(self.path("client.key"), self.client.serialize_private_key_pem()).unwrap();
+        ClientCertificate::Pem { cert_path: self.path("client.pem"), key_path: self.path("client.key") }
+    }
+
+    fn pkcs12_client_certificate(&self) -> ClientCertificate {
+        let chain = [p12_keystore::Certificate::from_der(&self.client_der).unwrap()];
+        let key_chain = p12_keystore::PrivateKeyChain::new(self.client.serialize_private_key_der(), [1u8], chain);
+        let mut store = p12_keystore::KeyStore::new();
+        store.add_entry("client", p12_keystore::KeyStoreEntry::PrivateKeyChain(key_chain));
+        std::fs::write(self.path("client.p12"), store.writer("secret").write().unwrap()).unwrap();
+        ClientCertificate::Pkcs12 { path: self.path("client.p12"), password: "secret".to_string() }
+    }
+
+    /// TLS server on localhost that handles one connection, optionally requiring a client certificate
+    fn serve(&self, require_client_cert: bool, versions: &'static [&'static rustls::SupportedProtocolVersion]) -> u16 {
+        let builder = ServerConfig::builder_with_protocol_versions(versions);
+        let builder = if require_client_cert {
+            let mut roots = RootCertStore::empty();
+            roots.add(CertificateDer::from(self.ca_der.clone())).unwrap();
+            builder.with_client_cert_verifier(WebPkiClientVerifier::builder(Arc::new(roots)).build().unwrap())
+        } else {
+            builder.with_no_client_auth()
+        };
+        let config = Arc::new(
+            builder
+                .with_single_cert(
+                    vec![CertificateDer::from(self.server_der.clone())],
+                    PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(self.server_key.clone())),
+                )
+                .unwrap(),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            if let Ok((mut tcp, _)) = listener.accept() {
+                let mut conn = ServerConnection::new(config).unwrap();
+                while conn.is_handshaking() {
+                    if conn.complete_io(&mut tcp).is_err() {
+                        return;
+                    }
+                }
+                let _ = conn.writer().write_all(b"ready");
+                let _ = conn.complete_io(&mut tcp);
+            }
+        });
+        port
+    }
+
+    fn connection(&self, port: u16, options: TlsOptions) -> AS400Connection {
+        let mut conn = AS400Connection::new("localhost".to_string(), port);
+        conn.set_tls(true);
+        conn.set_tls_ca_bundle_path(self.path("ca.pem"));
+        conn.set_tls_options(options);
+        conn
+    }
+}
+
+fn pem_block(label: &str, der: &[u8]) -> String {
+    use base64::Engine;
+    let body = base64::engine::general_purpose::STANDARD.encode(der);
+    let lines: Vec<&str> = body.as_bytes().chunks(64).map(|c| std::str::from_utf8(c).unwrap()).collect();
+    format!("-----BEGIN {label}-----\n{}\n-----END {label}-----\n", lines.join("\n"))
+}
+
+#[test]
+fn client_certificate_from_pem_and_pkcs12_is_accepted() {
+    let pki = Pki::new();
+
+    for certificate in [pki.pem_client_certificate(), pki.pkcs12_client_certificate()] {
+        let port = pki.serve(true, rustls::ALL_VERSIONS);
+        let options = TlsOptions { client_certificate: Some(certificate), ..Default::default() };
+        pki.connection(port, options).verify_tls().expect("handshake with client certificate");
+    }
+}
+
+#[test]
+fn missing_client_key_is_a_typed_error() {
+    let pki = Pki::new();
+    let port = pki.serve(false, rustls::ALL_VERSIONS);
+    let options = TlsOptions {
+        client_certificate: Some(ClientCertificate::Pem {
+            cert_path: pki.path("missing.pem"),
+            key_path: pki.path("missing.key"),
+        }),
+        ..Default::default()
+    };
+
+    match pki.connection(port, options).verify_tls() {
+        Err(NetworkError::TlsClientCertificate { path, .. }) => assert!(path.ends_with("missing.pem")),
+        other => panic!("expected client certificate error, got {other:?}"),
+    }
+
+    let options = TlsOptions {
+        client_certificate: Some(ClientCertificate::Pkcs12 { path: pki.path("ca.pem"), password: "x".to_string() }),
+        ..Default::default()
+    };
+    assert!(matches!(
+        pki.connection(port, options).verify_tls(),
+        Err(NetworkError::TlsClientCertificate { .. })
+    ));
+}
+
+#[test]
+fn minimum_tls_version_is_enforced() {
+    let pki = Pki::new();
+
+    let port = pki.serve(false, TLS12_ONLY);
+    let options = TlsOptions { min_version: TlsMinVersion::Tls13, ..Default::default() };
+    match pki.connection(port, options).verify_tls() {
+        Err(NetworkError::TlsVersionUnsupported { minimum, .. }) => assert_eq!(minimum, "TLS 1.3"),
+        other => panic!("expected version error, got {other:?}"),
+    }
+
+    let port = pki.serve(false, TLS12_ONLY);
+    pki.connection(port, TlsOptions::default()).verify_tls().expect("TLS 1.2 allowed by default");
+}
+
+#[test]
+fn certificate_pin_must_match_server_public_key() {
+    let pki = Pki::new();
+    let pin = format_pin(&spki_sha256(&pki.server_der).unwrap());
+
+    let port = pki.serve(false, rustls::ALL_VERSIONS);
+    let options = TlsOptions { pin_sha256: Some(pin.clone()), ..Default::default() };
+    pki.connection(port, options).verify_tls().expect("matching pin");
+
+    let port = pki.serve(false, rustls::ALL_VERSIONS);
+    let wrong = format_pin(&spki_sha256(&pki.client_der).unwrap());
+    let options = TlsOptions { pin_sha256: Some(wrong.clone()), ..Default::default() };
+    match pki.connection(port, options).verify_tls() {
+        Err(NetworkError::CertificatePinMismatch { expected, actual, .. }) => {
+            assert_eq!(expected, wrong);
+            assert_eq!(actual, pin);
+        }
+        other => panic!("expected pin mismatch, got {other:?}"),
+    }
+
+    let options = TlsOptions { pin_sha256: Some("not-a-pin".to_string()), ..Default::default() };
+    assert!(matches!(
+        pki.connection(port, options).verify_tls(),
+        Err(NetworkError::InvalidCertificatePin { .. })
+    ));
+}